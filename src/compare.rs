@@ -0,0 +1,268 @@
+use std::cmp::Ordering;
+
+use rug::{Complete, Integer, Rational};
+
+use crate::Real;
+
+/// Comparison predicates.
+///
+/// The IEEE 754 standard specifies 22 comparison predicates
+/// (see Section 5.11 of the IEEE 754-2019 standard). Any two values
+/// are related in exactly one of four ways: _less than_, _equal_,
+/// _greater than_, or _unordered_ when at least one value is NaN.
+/// Each predicate is true for some subset of these relations.
+/// Zeros compare equal regardless of sign.
+///
+/// The predicates are split into two groups:
+///
+/// - _quiet_ predicates signal an invalid operation only when
+///   an operand is a signaling NaN;
+/// - _signaling_ predicates signal an invalid operation when
+///   the operands are unordered.
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Predicate {
+    /// `compareQuietEqual`: equal
+    QuietEqual,
+    /// `compareQuietNotEqual`: less than, greater than, or unordered
+    QuietNotEqual,
+    /// `compareSignalingEqual`: equal
+    SignalingEqual,
+    /// `compareSignalingGreater`: greater than
+    SignalingGreater,
+    /// `compareSignalingGreaterEqual`: greater than or equal
+    SignalingGreaterEqual,
+    /// `compareSignalingLess`: less than
+    SignalingLess,
+    /// `compareSignalingLessEqual`: less than or equal
+    SignalingLessEqual,
+    /// `compareSignalingNotEqual`: less than, greater than, or unordered
+    SignalingNotEqual,
+    /// `compareSignalingNotGreater`: less than, equal, or unordered
+    SignalingNotGreater,
+    /// `compareSignalingLessUnordered`: less than or unordered
+    SignalingLessUnordered,
+    /// `compareSignalingNotLess`: greater than, equal, or unordered
+    SignalingNotLess,
+    /// `compareSignalingGreaterUnordered`: greater than or unordered
+    SignalingGreaterUnordered,
+    /// `compareQuietGreater`: greater than
+    QuietGreater,
+    /// `compareQuietGreaterEqual`: greater than or equal
+    QuietGreaterEqual,
+    /// `compareQuietLess`: less than
+    QuietLess,
+    /// `compareQuietLessEqual`: less than or equal
+    QuietLessEqual,
+    /// `compareQuietUnordered`: unordered
+    QuietUnordered,
+    /// `compareQuietNotGreater`: less than, equal, or unordered
+    QuietNotGreater,
+    /// `compareQuietLessUnordered`: less than or unordered
+    QuietLessUnordered,
+    /// `compareQuietNotLess`: greater than, equal, or unordered
+    QuietNotLess,
+    /// `compareQuietGreaterUnordered`: greater than or unordered
+    QuietGreaterUnordered,
+    /// `compareQuietOrdered`: less than, equal, or greater than
+    QuietOrdered,
+}
+
+impl Predicate {
+    /// All comparison predicates in the order listed by the
+    /// IEEE 754 standard.
+    pub const ALL: [Predicate; 22] = [
+        Predicate::QuietEqual,
+        Predicate::QuietNotEqual,
+        Predicate::SignalingEqual,
+        Predicate::SignalingGreater,
+        Predicate::SignalingGreaterEqual,
+        Predicate::SignalingLess,
+        Predicate::SignalingLessEqual,
+        Predicate::SignalingNotEqual,
+        Predicate::SignalingNotGreater,
+        Predicate::SignalingLessUnordered,
+        Predicate::SignalingNotLess,
+        Predicate::SignalingGreaterUnordered,
+        Predicate::QuietGreater,
+        Predicate::QuietGreaterEqual,
+        Predicate::QuietLess,
+        Predicate::QuietLessEqual,
+        Predicate::QuietUnordered,
+        Predicate::QuietNotGreater,
+        Predicate::QuietLessUnordered,
+        Predicate::QuietNotLess,
+        Predicate::QuietGreaterUnordered,
+        Predicate::QuietOrdered,
+    ];
+
+    /// Returns true if this predicate signals an invalid operation
+    /// when its operands are unordered.
+    pub fn is_signaling(&self) -> bool {
+        matches!(
+            self,
+            Predicate::SignalingEqual
+                | Predicate::SignalingGreater
+                | Predicate::SignalingGreaterEqual
+                | Predicate::SignalingLess
+                | Predicate::SignalingLessEqual
+                | Predicate::SignalingNotEqual
+                | Predicate::SignalingNotGreater
+                | Predicate::SignalingLessUnordered
+                | Predicate::SignalingNotLess
+                | Predicate::SignalingGreaterUnordered
+        )
+    }
+
+    /// Evaluates this predicate given the relation between two values
+    /// where `None` indicates the values are unordered.
+    pub fn eval(&self, ord: Option<Ordering>) -> bool {
+        let (lt, eq, gt, un) = match ord {
+            Some(Ordering::Less) => (true, false, false, false),
+            Some(Ordering::Equal) => (false, true, false, false),
+            Some(Ordering::Greater) => (false, false, true, false),
+            None => (false, false, false, true),
+        };
+
+        match self {
+            Predicate::QuietEqual | Predicate::SignalingEqual => eq,
+            Predicate::QuietNotEqual | Predicate::SignalingNotEqual => !eq,
+            Predicate::QuietGreater | Predicate::SignalingGreater => gt,
+            Predicate::QuietGreaterEqual | Predicate::SignalingGreaterEqual => gt || eq,
+            Predicate::QuietLess | Predicate::SignalingLess => lt,
+            Predicate::QuietLessEqual | Predicate::SignalingLessEqual => lt || eq,
+            Predicate::QuietNotGreater | Predicate::SignalingNotGreater => !gt,
+            Predicate::QuietLessUnordered | Predicate::SignalingLessUnordered => lt || un,
+            Predicate::QuietNotLess | Predicate::SignalingNotLess => !lt,
+            Predicate::QuietGreaterUnordered | Predicate::SignalingGreaterUnordered => gt || un,
+            Predicate::QuietUnordered => un,
+            Predicate::QuietOrdered => !un,
+        }
+    }
+}
+
+/// Returns the exact absolute value of a finite number `src`.
+/// Panics if the exponent of `src` does not fit in a `u32`.
+pub(crate) fn exact_magnitude<N: Real>(src: &N) -> Rational {
+    if src.is_zero() {
+        Rational::new()
+    } else {
+        let exp = src.exp().unwrap();
+        let c = src.c().unwrap();
+        let radix = N::radix() as u32;
+        let k = u32::try_from(exp.unsigned_abs()).expect("exponent out of range");
+        if exp >= 0 {
+            Rational::from(c * Integer::u_pow_u(radix, k).complete())
+        } else {
+            Rational::from((c, Integer::u_pow_u(radix, k).complete()))
+        }
+    }
+}
+
+/// Returns bounds `lo < log2(|src|) < hi` for a finite,
+/// non-zero number `src`.
+fn log2_bounds<N: Real>(src: &N) -> (f64, f64) {
+    let e = src.e().unwrap() as f64;
+    let log2_radix = (N::radix() as f64).log2();
+    // margin for floating-point error
+    let margin = 1.0 + e.abs() * 1e-12;
+    (e * log2_radix - margin, (e + 1.0) * log2_radix + margin)
+}
+
+/// Compares the absolute values of two finite, non-zero numbers.
+/// Exact values are only computed when the magnitudes are close.
+fn cmp_nonzero_magnitude<N1: Real, N2: Real>(src1: &N1, src2: &N2) -> Ordering {
+    if N1::radix() == N2::radix() {
+        // `|src| = f * b^e` where `1 <= f < b`
+        let (e1, e2) = (src1.e().unwrap(), src2.e().unwrap());
+        if e1 != e2 {
+            return e1.cmp(&e2);
+        }
+
+        // same normalized exponent: align the significands
+        let (exp1, exp2) = (src1.exp().unwrap(), src2.exp().unwrap());
+        let (c1, c2) = (src1.c().unwrap(), src2.c().unwrap());
+        let radix = N1::radix() as u32;
+        let k = u32::try_from(exp1.abs_diff(exp2)).expect("precision out of range");
+        let scale = Integer::u_pow_u(radix, k).complete();
+        if exp1 >= exp2 {
+            (c1 * scale).cmp(&c2)
+        } else {
+            c1.cmp(&(c2 * scale))
+        }
+    } else {
+        let (lo1, hi1) = log2_bounds(src1);
+        let (lo2, hi2) = log2_bounds(src2);
+        if hi1 <= lo2 {
+            Ordering::Less
+        } else if hi2 <= lo1 {
+            Ordering::Greater
+        } else {
+            exact_magnitude(src1).cmp(&exact_magnitude(src2))
+        }
+    }
+}
+
+/// Compares the absolute values of two numerical values.
+pub(crate) fn cmp_magnitude<N1: Real, N2: Real>(src1: &N1, src2: &N2) -> Ordering {
+    match (src1.is_infinite(), src2.is_infinite()) {
+        (true, true) => Ordering::Equal,
+        (true, false) => Ordering::Greater,
+        (false, true) => Ordering::Less,
+        (false, false) => match (src1.is_zero(), src2.is_zero()) {
+            (true, true) => Ordering::Equal,
+            (true, false) => Ordering::Less,
+            (false, true) => Ordering::Greater,
+            (false, false) => cmp_nonzero_magnitude(src1, src2),
+        },
+    }
+}
+
+/// Compares two values exactly, regardless of radix. The result is
+/// `None` if either value is non-numerical. Zeros compare equal
+/// regardless of sign.
+pub(crate) fn cmp_real<N1: Real, N2: Real>(src1: &N1, src2: &N2) -> Option<Ordering> {
+    if !src1.is_numerical() || !src2.is_numerical() {
+        None
+    } else {
+        // zeros have no sign here
+        let s1 = if src1.is_zero() { None } else { src1.sign() };
+        let s2 = if src2.is_zero() { None } else { src2.sign() };
+        let ord = match (s1, s2) {
+            (None, None) => Ordering::Equal,
+            (None, Some(s)) => {
+                if s {
+                    Ordering::Greater
+                } else {
+                    Ordering::Less
+                }
+            }
+            (Some(s), None) => {
+                if s {
+                    Ordering::Less
+                } else {
+                    Ordering::Greater
+                }
+            }
+            (Some(false), Some(true)) => Ordering::Greater,
+            (Some(true), Some(false)) => Ordering::Less,
+            (Some(false), Some(false)) => cmp_magnitude(src1, src2),
+            (Some(true), Some(true)) => cmp_magnitude(src2, src1),
+        };
+        Some(ord)
+    }
+}
+
+/// Evaluates the comparison predicate `pred` on two values.
+/// Returns the result and whether the comparison signals
+/// an invalid operation.
+pub(crate) fn compare<N1, N2>(src1: &N1, src2: &N2, pred: Predicate) -> (bool, bool)
+where
+    N1: Real,
+    N2: Real,
+{
+    let ord = cmp_real(src1, src2);
+    let invalid =
+        src1.is_signaling() || src2.is_signaling() || (pred.is_signaling() && ord.is_none());
+    (pred.eval(ord), invalid)
+}
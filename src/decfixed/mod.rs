@@ -0,0 +1,13 @@
+//! Decimal fixed-point numbers.
+//!
+//! This module implements decimal fixed-point numbers with
+//! [`DecimalFixedContext`]. The associated storage type is [`DecimalFixed`].
+//! Unlike [`FixedContext`][crate::fixed::FixedContext], the quantum of
+//! these formats is a power of ten, e.g., `0.01` for monetary amounts.
+
+mod number;
+mod ops;
+mod round;
+
+pub use number::DecimalFixed;
+pub use round::DecimalFixedContext;
@@ -0,0 +1,144 @@
+use std::cmp::Ordering;
+use std::fmt;
+
+use rug::{Integer, Rational};
+
+use crate::decfixed::DecimalFixedContext;
+use crate::decimal::DFloat;
+use crate::fixed::Exceptions;
+use crate::ieee754::IEEE754Context;
+use crate::rational::RoundRational;
+use crate::{Real, RoundingMode};
+
+/// The decimal fixed-point format.
+///
+/// The associated [`RoundingContext`][crate::RoundingContext]
+/// implementation is [`DecimalFixedContext`].
+/// See [`DecimalFixedContext`] for more details on numerical properties
+/// of the [`DecimalFixed`] type.
+///
+/// A [`DecimalFixed`] value also has an [`Exceptions`] instance to indicate
+/// exceptional events that occured during its construction.
+#[derive(Clone, Debug)]
+pub struct DecimalFixed {
+    pub(crate) num: DFloat,
+    pub(crate) flags: Exceptions,
+    pub(crate) ctx: DecimalFixedContext,
+}
+
+impl DecimalFixed {
+    /// Returns the flags set during the creation of this number
+    pub fn flags(&self) -> &Exceptions {
+        &self.flags
+    }
+
+    /// Returns the rounding context used to create this number.
+    pub fn ctx(&self) -> &DecimalFixedContext {
+        &self.ctx
+    }
+
+    /// Returns the exact value of this number as a [`Rational`].
+    /// Binary formats may correctly round this value through
+    /// [`RoundRational`].
+    pub fn to_rational(&self) -> Rational {
+        self.num.to_rational().unwrap()
+    }
+
+    /// Converts this [`DecimalFixed`] to the nearest [`f64`] value
+    /// according to the rounding mode `rm`.
+    pub fn to_f64(&self, rm: RoundingMode) -> f64 {
+        let ctx = IEEE754Context::binary64().with_rounding_mode(rm);
+        ctx.round_rational(&self.to_rational()).to_f64(rm)
+    }
+
+    /// Converts this [`DecimalFixed`] to the nearest [`f32`] value
+    /// according to the rounding mode `rm`.
+    pub fn to_f32(&self, rm: RoundingMode) -> f32 {
+        let ctx = IEEE754Context::binary32().with_rounding_mode(rm);
+        ctx.round_rational(&self.to_rational()).to_f32(rm)
+    }
+}
+
+impl Real for DecimalFixed {
+    fn radix() -> usize {
+        10
+    }
+
+    fn sign(&self) -> Option<bool> {
+        self.num.sign()
+    }
+
+    fn exp(&self) -> Option<isize> {
+        self.num.exp()
+    }
+
+    fn e(&self) -> Option<isize> {
+        self.num.e()
+    }
+
+    fn n(&self) -> Option<isize> {
+        self.num.n()
+    }
+
+    fn c(&self) -> Option<Integer> {
+        self.num.c()
+    }
+
+    fn m(&self) -> Option<Integer> {
+        self.num.m()
+    }
+
+    fn prec(&self) -> Option<usize> {
+        self.num.prec()
+    }
+
+    fn is_nar(&self) -> bool {
+        false
+    }
+
+    fn is_finite(&self) -> bool {
+        true
+    }
+
+    fn is_infinite(&self) -> bool {
+        false
+    }
+
+    fn is_zero(&self) -> bool {
+        self.num.is_zero()
+    }
+
+    fn is_negative(&self) -> Option<bool> {
+        self.num.is_negative()
+    }
+
+    fn is_numerical(&self) -> bool {
+        true
+    }
+}
+
+impl From<DecimalFixed> for DFloat {
+    fn from(val: DecimalFixed) -> Self {
+        val.num
+    }
+}
+
+impl PartialEq for DecimalFixed {
+    fn eq(&self, other: &Self) -> bool {
+        self.num == other.num
+    }
+}
+
+impl PartialOrd for DecimalFixed {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.num.partial_cmp(&other.num)
+    }
+}
+
+/// Formats a [`DecimalFixed`] with exactly as many fractional
+/// digits as its scale, e.g., `-12.50`.
+impl fmt::Display for DecimalFixed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.num)
+    }
+}
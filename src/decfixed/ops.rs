@@ -0,0 +1,267 @@
+use rug::{Integer, Rational};
+
+use crate::decfixed::DecimalFixedContext;
+use crate::decimal::dfloat::scale10;
+use crate::decimal::DFloat;
+use crate::ops::*;
+use crate::rfloat::RFloat;
+use crate::{Real, RoundingContext, RoundingMode};
+
+// Decimal values cannot be computed with MPFR, so operations
+// are computed exactly on `DFloat` values and then rounded.
+// Special values are handled as rounding the real result would,
+// e.g., `x / 0` is an infinity that rounds to MAX (or MIN).
+
+/// Computes the sign of an infinite or zero product or quotient.
+fn sign_of(x: &DFloat, y: &DFloat) -> bool {
+    x.sign().unwrap_or(false) != y.sign().unwrap_or(false)
+}
+
+/// Constructs an infinity with a sign.
+fn inf(s: bool) -> DFloat {
+    if s {
+        DFloat::NegInfinity
+    } else {
+        DFloat::PosInfinity
+    }
+}
+
+/// Exact product of two values, at least one of which is non-finite.
+fn mul_nar(x: &DFloat, y: &DFloat) -> DFloat {
+    if !x.is_numerical() || !y.is_numerical() || x.is_zero() || y.is_zero() {
+        DFloat::Nan
+    } else {
+        inf(sign_of(x, y))
+    }
+}
+
+impl RoundedNeg for DecimalFixedContext {
+    fn neg<N: Real>(&self, src: &N) -> Self::Format {
+        match DFloat::from_number(src) {
+            DFloat::Real(s, exp, c) => self.round(&DFloat::Real(!s, exp, c)),
+            DFloat::PosInfinity => self.round(&DFloat::NegInfinity),
+            DFloat::NegInfinity => self.round(&DFloat::PosInfinity),
+            DFloat::Nan => self.round(&DFloat::Nan),
+        }
+    }
+}
+
+impl RoundedAbs for DecimalFixedContext {
+    fn abs<N: Real>(&self, src: &N) -> Self::Format {
+        match DFloat::from_number(src) {
+            DFloat::Real(_, exp, c) => self.round(&DFloat::Real(false, exp, c)),
+            DFloat::Nan => self.round(&DFloat::Nan),
+            _ => self.round(&DFloat::PosInfinity),
+        }
+    }
+}
+
+impl RoundedAdd for DecimalFixedContext {
+    fn add<N1, N2>(&self, src1: &N1, src2: &N2) -> Self::Format
+    where
+        N1: Real,
+        N2: Real,
+    {
+        let x = DFloat::from_number(src1);
+        let y = DFloat::from_number(src2);
+        match (x.to_rational(), y.to_rational()) {
+            (Some(q1), Some(q2)) => self.round_exact(&(q1 + q2)),
+            _ if x.is_infinite() && y.is_infinite() && x.sign() != y.sign() => {
+                self.round(&DFloat::Nan)
+            }
+            _ if !x.is_numerical() || !y.is_numerical() => self.round(&DFloat::Nan),
+            _ if x.is_infinite() => self.round(&x),
+            _ => self.round(&y),
+        }
+    }
+}
+
+impl RoundedSub for DecimalFixedContext {
+    fn sub<N1, N2>(&self, src1: &N1, src2: &N2) -> Self::Format
+    where
+        N1: Real,
+        N2: Real,
+    {
+        let neg = match DFloat::from_number(src2) {
+            DFloat::Real(s, exp, c) => DFloat::Real(!s, exp, c),
+            DFloat::PosInfinity => DFloat::NegInfinity,
+            DFloat::NegInfinity => DFloat::PosInfinity,
+            DFloat::Nan => DFloat::Nan,
+        };
+        self.add(src1, &neg)
+    }
+}
+
+impl RoundedMul for DecimalFixedContext {
+    fn mul<N1, N2>(&self, src1: &N1, src2: &N2) -> Self::Format
+    where
+        N1: Real,
+        N2: Real,
+    {
+        let x = DFloat::from_number(src1);
+        let y = DFloat::from_number(src2);
+        match (x.to_rational(), y.to_rational()) {
+            (Some(q1), Some(q2)) => self.round_exact(&(q1 * q2)),
+            _ if !x.is_numerical() || !y.is_numerical() => self.round(&DFloat::Nan),
+            _ if x.is_zero() || y.is_zero() => self.round(&DFloat::Nan),
+            _ => self.round(&inf(sign_of(&x, &y))),
+        }
+    }
+}
+
+impl RoundedDiv for DecimalFixedContext {
+    fn div<N1, N2>(&self, src1: &N1, src2: &N2) -> Self::Format
+    where
+        N1: Real,
+        N2: Real,
+    {
+        let x = DFloat::from_number(src1);
+        let y = DFloat::from_number(src2);
+        match (x.to_rational(), y.to_rational()) {
+            _ if !x.is_numerical() || !y.is_numerical() => self.round(&DFloat::Nan),
+            _ if x.is_infinite() && y.is_infinite() => self.round(&DFloat::Nan),
+            _ if x.is_zero() && y.is_zero() => self.round(&DFloat::Nan),
+            _ if x.is_infinite() || y.is_zero() => self.round(&inf(sign_of(&x, &y))),
+            _ if y.is_infinite() => self.zero(),
+            (Some(q1), Some(q2)) => self.round_exact(&(q1 / q2)),
+            _ => unreachable!(),
+        }
+    }
+}
+
+impl RoundedSqrt for DecimalFixedContext {
+    fn sqrt<N: Real>(&self, src: &N) -> Self::Format {
+        let x = DFloat::from_number(src);
+        match x.to_rational() {
+            _ if !x.is_numerical() || x.is_negative().unwrap() => self.round(&DFloat::Nan),
+            None => self.round(&x),
+            Some(q) => {
+                // compute two digits below the quantum with a sticky digit
+                let exp = self.scale() - 2;
+                let scaled = q / scale10(Integer::from(1), 2 * exp);
+                let (frac, trunc) = scaled.fract_trunc(Integer::new());
+                let (r, rem) = trunc.sqrt_rem(Integer::new());
+                let r = if frac == 0 && rem == 0 {
+                    scale10(r, exp)
+                } else {
+                    scale10(r * 10 + 1, exp - 1)
+                };
+                self.round_exact(&r)
+            }
+        }
+    }
+}
+
+impl RoundedFMA for DecimalFixedContext {
+    fn fma<N1, N2, N3>(&self, src1: &N1, src2: &N2, src3: &N3) -> Self::Format
+    where
+        N1: Real,
+        N2: Real,
+        N3: Real,
+    {
+        let x = DFloat::from_number(src1);
+        let y = DFloat::from_number(src2);
+        let z = DFloat::from_number(src3);
+        match (x.to_rational(), y.to_rational(), z.to_rational()) {
+            (Some(q1), Some(q2), Some(q3)) => {
+                let r: Rational = q1 * q2 + q3;
+                self.round_exact(&r)
+            }
+            (Some(_), Some(_), None) => self.round(&z),
+            _ => {
+                let prod = mul_nar(&x, &y);
+                self.add(&prod, &z)
+            }
+        }
+    }
+}
+
+macro_rules! rounded_integral_impl {
+    ($tname:ident, $name:ident, $rm:expr) => {
+        impl $tname for DecimalFixedContext {
+            fn $name<N: Real>(&self, src: &N) -> Self::Format {
+                let (integral, _) = round_integral(src, $rm);
+                self.round(&integral)
+            }
+        }
+    };
+}
+
+rounded_integral_impl!(RoundedFloor, floor, RoundingMode::ToNegative);
+rounded_integral_impl!(RoundedCeil, ceil, RoundingMode::ToPositive);
+rounded_integral_impl!(RoundedTrunc, trunc, RoundingMode::ToZero);
+rounded_integral_impl!(
+    RoundedRoundAway,
+    round_away,
+    RoundingMode::NearestTiesAwayZero
+);
+rounded_integral_impl!(
+    RoundedRoundEven,
+    round_even,
+    RoundingMode::NearestTiesToEven
+);
+
+impl RoundedNearbyInt for DecimalFixedContext {
+    fn nearbyint<N: Real>(&self, src: &N) -> Self::Format {
+        let (integral, _) = round_integral(src, self.rm());
+        self.round(&integral)
+    }
+}
+
+impl RoundedRint for DecimalFixedContext {
+    fn rint<N: Real>(&self, src: &N) -> Self::Format {
+        let (integral, inexact) = round_integral(src, self.rm());
+        let mut rounded = self.round(&integral);
+        rounded.flags.inexact |= inexact;
+        rounded
+    }
+}
+
+impl RoundedConvertToInteger for DecimalFixedContext {
+    fn convert_to_integer<N: Real>(&self, src: &N, rm: RoundingMode, exact: bool) -> Self::Format {
+        // NaN is invalid, and the overflow behavior of this context
+        // determines the result of out of range integers
+        let (integral, inexact) = round_integral(src, rm);
+        let mut rounded = self.round(&integral);
+        rounded.flags.inexact = exact && (inexact || rounded.flags.inexact);
+        rounded
+    }
+}
+
+macro_rules! min_max_impl {
+    ($tname:ident, $name:ident, $op:expr) => {
+        impl $tname for DecimalFixedContext {
+            fn $name<N1, N2>(&self, src1: &N1, src2: &N2) -> Self::Format
+            where
+                N1: Real,
+                N2: Real,
+            {
+                let (result, invalid) = min_max(src1, src2, $op);
+                let mut rounded = match result {
+                    MinMaxResult::First => self.round(src1),
+                    MinMaxResult::Second => self.round(src2),
+                    MinMaxResult::Nan => self.round(&RFloat::Nan),
+                };
+                rounded.flags.invalid |= invalid;
+                rounded
+            }
+        }
+    };
+}
+
+min_max_impl!(RoundedMinimum, minimum, MinMax::Minimum);
+min_max_impl!(RoundedMaximum, maximum, MinMax::Maximum);
+min_max_impl!(RoundedMinimumNumber, minimum_number, MinMax::MinimumNumber);
+min_max_impl!(RoundedMaximumNumber, maximum_number, MinMax::MaximumNumber);
+min_max_impl!(
+    RoundedMinimumMagnitude,
+    minimum_magnitude,
+    MinMax::MinimumMagnitude
+);
+min_max_impl!(
+    RoundedMaximumMagnitude,
+    maximum_magnitude,
+    MinMax::MaximumMagnitude
+);
+min_max_impl!(RoundedMinNum, min_num, MinMax::MinNum);
+min_max_impl!(RoundedMaxNum, max_num, MinMax::MaxNum);
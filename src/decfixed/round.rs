@@ -0,0 +1,262 @@
+use num_traits::Zero;
+use rug::ops::Pow;
+use rug::{Integer, Rational};
+
+use crate::decfixed::DecimalFixed;
+use crate::decimal::dfloat::{digits, scale10};
+use crate::decimal::{round_increment, DFloat};
+use crate::fixed::{Exceptions, Overflow};
+use crate::rational::RoundRational;
+use crate::{Real, RoundingContext, RoundingMode};
+
+/// Rounding contexts for decimal fixed-point numbers.
+///
+/// The associated storage type is [`DecimalFixed`].
+///
+/// Values rounded this context are decimal fixed-point numbers:
+/// `(-1)^s * c * 10^scale` where `c` is an unsigned integer with
+/// a fixed number of decimal digits and `scale` is a fixed integer.
+/// For example, monetary amounts with two fractional digits,
+/// i.e., a quantum of `0.01`, have a scale of `-2`.
+/// Signed formats use a sign-magnitude representation,
+/// so their range is symmetric.
+///
+/// A [`DecimalFixedContext`] is parameterized by
+///
+///  - signedness (unsigned vs. signed),
+///  - scale factor (position of least-significant digit),
+///  - total number of decimal digits,
+///  - rounding mode,
+///  - overflow behavior.
+///
+/// By default, the rounding mode is [`RoundingMode::ToZero`], and
+/// the overflow handling is [`Overflow::Wrap`]. Wrapping discards
+/// the high-order digits of the significand; unsigned formats wrap
+/// negative values modulo `10^ndigits`. Since the representation is
+/// sign-magnitude, [`Overflow::WrapSignMagnitude`] is the same as
+/// [`Overflow::Wrap`], and [`Overflow::SaturateSymmetric`] is the same
+/// as [`Overflow::Saturate`]. Under [`Overflow::Trap`], values that
+/// overflow saturate and signal both overflow and an invalid operation.
+///
+/// Values of any radix, e.g., binary floating-point numbers, are
+/// rounded exactly. Conversely, [`DecimalFixed`] values are correctly
+/// rounded by binary formats through
+/// [`RoundRational`][crate::rational::RoundRational]
+/// (see [`DecimalFixed::to_rational`]).
+#[derive(Clone, Debug)]
+pub struct DecimalFixedContext {
+    signed: bool,
+    scale: isize,
+    ndigits: usize,
+    rm: RoundingMode,
+    overflow: Overflow,
+}
+
+impl DecimalFixedContext {
+    /// Constructs new rounding context.
+    /// The default rounding mode is truncation
+    /// (see [`ToZero`][crate::RoundingMode]). The default overflow
+    /// behavior is wrapping (see [`Wrap`][Overflow]).
+    pub fn new(signed: bool, scale: isize, ndigits: usize) -> Self {
+        assert!(ndigits >= 1, "insufficient number of digits");
+        Self {
+            signed,
+            scale,
+            ndigits,
+            rm: RoundingMode::ToZero,
+            overflow: Overflow::Wrap,
+        }
+    }
+
+    /// Sets the rounding mode of this [`DecimalFixedContext`].
+    pub fn with_rounding_mode(mut self, rm: RoundingMode) -> Self {
+        self.rm = rm;
+        self
+    }
+
+    /// Sets the overflow behavior of this [`DecimalFixedContext`].
+    pub fn with_overflow(mut self, overflow: Overflow) -> Self {
+        self.overflow = overflow;
+        self
+    }
+
+    /// Returns true if this format is signed.
+    pub fn signed(&self) -> bool {
+        self.signed
+    }
+
+    /// Returns the scale of this format, i.e., the exponent
+    /// of the least significant digit.
+    pub fn scale(&self) -> isize {
+        self.scale
+    }
+
+    /// Returns the number of decimal digits of this format.
+    pub fn ndigits(&self) -> usize {
+        self.ndigits
+    }
+
+    /// Returns the rounding mode of this context.
+    pub fn rm(&self) -> RoundingMode {
+        self.rm
+    }
+
+    /// Returns the overflow behavior of this context.
+    pub fn overflow(&self) -> Overflow {
+        self.overflow.clone()
+    }
+
+    /// Returns `10^ndigits`, one more than the largest significand.
+    fn limit(&self) -> Integer {
+        Integer::from(10).pow(self.ndigits as u32)
+    }
+
+    /// Constructs a value `(-1)^s * c * 10^scale`.
+    fn make(&self, s: bool, c: Integer, flags: Exceptions) -> DecimalFixed {
+        DecimalFixed {
+            num: DFloat::Real(s && !c.is_zero(), self.scale, c),
+            flags,
+            ctx: self.clone(),
+        }
+    }
+
+    /// The maximum value in format specified by this [`DecimalFixedContext`],
+    /// i.e., `(10^ndigits - 1) * 10^scale`.
+    pub fn maxval(&self) -> DecimalFixed {
+        self.make(false, self.limit() - 1, Exceptions::default())
+    }
+
+    /// The minimum value in format specified by this [`DecimalFixedContext`].
+    /// If the format is unsigned, this is just `0`.
+    /// If the format is signed, this is just `-(10^ndigits - 1) * 10^scale`.
+    pub fn minval(&self) -> DecimalFixed {
+        if self.signed {
+            self.make(true, self.limit() - 1, Exceptions::default())
+        } else {
+            self.zero()
+        }
+    }
+
+    /// Constructs zero in the format described by [`DecimalFixedContext`].
+    pub fn zero(&self) -> DecimalFixed {
+        self.make(false, Integer::zero(), Exceptions::default())
+    }
+
+    /// Constructs the smallest representable difference in
+    /// the format described by [`DecimalFixedContext`], i.e., `10^scale`.
+    pub fn quantum(&self) -> DecimalFixed {
+        self.make(false, Integer::from(1), Exceptions::default())
+    }
+
+    /// Rounds the exact value `q`.
+    pub(crate) fn round_exact(&self, q: &Rational) -> DecimalFixed {
+        // round at the quantum
+        let s = q.is_negative();
+        let scaled = q.clone().abs() / scale10(Integer::from(1), self.scale);
+        let (frac, trunc) = scaled.fract_trunc(Integer::new());
+        let inexact = !frac.is_zero();
+        let c = if inexact && round_increment(self.rm, s, &trunc, &frac) {
+            trunc + 1
+        } else {
+            trunc
+        };
+
+        // check bounds
+        let limit = self.limit();
+        let overflow = c >= limit || (!self.signed && s && !c.is_zero());
+        let flags = Exceptions {
+            invalid: overflow && matches!(self.overflow, Overflow::Trap),
+            inexact,
+            overflow,
+            ..Default::default()
+        };
+
+        if !overflow {
+            self.make(s, c, flags)
+        } else {
+            match (&self.overflow, self.signed, s) {
+                // the representation is sign-magnitude, so both wrapping
+                // modes preserve the sign and the least significant digits
+                (Overflow::Wrap | Overflow::WrapSignMagnitude, true, _) => {
+                    self.make(s, c % limit, flags)
+                }
+                (Overflow::Wrap | Overflow::WrapSignMagnitude, false, false) => {
+                    self.make(false, c % limit, flags)
+                }
+                (Overflow::Wrap | Overflow::WrapSignMagnitude, false, true) => {
+                    let c = (limit.clone() - c % &limit) % limit;
+                    self.make(false, c, flags)
+                }
+                // the range is already symmetric; trapped overflows
+                // are reported by the invalid flag
+                (Overflow::Saturate | Overflow::SaturateSymmetric | Overflow::Trap, _, true) => {
+                    DecimalFixed {
+                        flags,
+                        ..self.minval()
+                    }
+                }
+                (Overflow::Saturate | Overflow::SaturateSymmetric | Overflow::Trap, _, false) => {
+                    DecimalFixed {
+                        flags,
+                        ..self.maxval()
+                    }
+                }
+                (Overflow::SaturateZero, _, _) => self.make(false, Integer::zero(), flags),
+            }
+        }
+    }
+}
+
+impl RoundingContext for DecimalFixedContext {
+    type Format = DecimalFixed;
+
+    fn round<T: Real>(&self, val: &T) -> Self::Format {
+        if T::radix() == 2 && val.is_finite() && !val.is_zero() {
+            // tiny binary values need not be converted exactly:
+            // bound the adjusted exponent by `e * log10(2)`
+            let t = val.e().unwrap() as f64 * std::f64::consts::LOG10_2;
+            let hi = (t + 2.0 + t.abs() * 1e-12).ceil() as isize;
+            if hi < self.scale - 1 {
+                let num = DFloat::Real(val.sign().unwrap(), self.scale - 2, Integer::from(1));
+                return self.round(&num);
+            }
+        }
+
+        match DFloat::from_number(val) {
+            // +Inf goes to MAX
+            DFloat::PosInfinity => self.maxval(),
+            // -Inf goes to MIN
+            DFloat::NegInfinity => self.minval(),
+            // +/- NaN goes to 0
+            DFloat::Nan => self.make(
+                false,
+                Integer::zero(),
+                Exceptions {
+                    invalid: true,
+                    ..Default::default()
+                },
+            ),
+            DFloat::Real(s, exp, c)
+                if !c.is_zero() && exp >= self.scale + self.ndigits as isize =>
+            {
+                // wraps to zero or saturates like any other such value
+                let num = DFloat::Real(s, self.scale + self.ndigits as isize, Integer::from(1));
+                self.round_exact(&num.to_rational().unwrap())
+            }
+            DFloat::Real(s, exp, c)
+                if !c.is_zero() && exp.saturating_add(digits(&c) as isize) < self.scale - 1 =>
+            {
+                // rounds like any other value less than a tenth of the quantum
+                let num = DFloat::Real(s, self.scale - 2, Integer::from(1));
+                self.round_exact(&num.to_rational().unwrap())
+            }
+            num => self.round_exact(&num.to_rational().unwrap()),
+        }
+    }
+}
+
+impl RoundRational for DecimalFixedContext {
+    fn round_rational(&self, q: &Rational) -> Self::Format {
+        self.round_exact(q)
+    }
+}
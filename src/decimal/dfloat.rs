@@ -0,0 +1,285 @@
+use std::cmp::Ordering;
+use std::fmt;
+
+use num_traits::Zero;
+use rug::ops::Pow;
+use rug::{Integer, Rational};
+
+use crate::Real;
+
+/// An arbitrary-precision, decimal floating-point number
+/// with unbounded exponent.
+///
+/// This is the radix-10 analogue of [`RFloat`][crate::RFloat]:
+/// a finite value is `(-1)^s * c * 10^exp`. Unlike [`RFloat`][crate::RFloat],
+/// the representation is not canonical: values like `1.5` and `1.50`
+/// are equal but have different exponents (they are members of the same
+/// _cohort_). Zeros are signed and also carry an exponent.
+#[derive(Debug, Clone)]
+pub enum DFloat {
+    /// A finite (real) number specified by the triple
+    /// of sign, exponent, significand.
+    Real(bool, isize, Integer),
+    /// A positive infinity.
+    PosInfinity,
+    /// A negative infinity.
+    NegInfinity,
+    /// Not a real number.
+    Nan,
+}
+
+/// Number of decimal digits of a positive integer.
+pub(crate) fn digits(c: &Integer) -> usize {
+    if c.is_zero() {
+        0
+    } else {
+        c.to_string_radix(10).len()
+    }
+}
+
+/// Computes `c * 10^exp` exactly.
+/// Panics if `|exp|` does not fit in a `u32`;
+/// see [`checked_scale10`] for a non-panicking alternative.
+pub(crate) fn scale10(c: Integer, exp: isize) -> Rational {
+    checked_scale10(c, exp).expect("exponent out of range")
+}
+
+/// Computes `c * 10^exp` exactly, returning `None`
+/// if `|exp|` does not fit in a `u32`.
+pub(crate) fn checked_scale10(c: Integer, exp: isize) -> Option<Rational> {
+    let scale = Integer::from(10).pow(u32::try_from(exp.unsigned_abs()).ok()?);
+    if exp < 0 {
+        Some(Rational::from((c, scale)))
+    } else {
+        Some(Rational::from(c * scale))
+    }
+}
+
+/// Formats `(-1)^s * c * 10^exp` as a decimal string
+/// preserving the exponent, as described by the
+/// General Decimal Arithmetic specification (_to-scientific-string_).
+pub(crate) fn format_decimal(s: bool, exp: isize, c: &Integer) -> String {
+    let digits = c.to_string_radix(10);
+    let len = digits.len() as isize;
+    let adjusted = exp + (len - 1);
+    let sign = if s { "-" } else { "" };
+
+    if exp <= 0 && adjusted >= -6 {
+        // plain notation
+        if exp == 0 {
+            format!("{}{}", sign, digits)
+        } else if len > -exp {
+            let (int, frac) = digits.split_at((len + exp) as usize);
+            format!("{}{}.{}", sign, int, frac)
+        } else {
+            let zeros = "0".repeat((-exp - len) as usize);
+            format!("{}0.{}{}", sign, zeros, digits)
+        }
+    } else {
+        // scientific notation
+        let (lead, rest) = digits.split_at(1);
+        let point = if rest.is_empty() { "" } else { "." };
+        let esign = if adjusted < 0 { "-" } else { "+" };
+        format!(
+            "{}{}{}{}E{}{}",
+            sign,
+            lead,
+            point,
+            rest,
+            esign,
+            adjusted.abs()
+        )
+    }
+}
+
+impl DFloat {
+    /// Constructs the (unsigned) zero with exponent 0.
+    pub fn zero() -> Self {
+        DFloat::Real(false, 0, Integer::zero())
+    }
+
+    /// Constructs a [`DFloat`] value from a [`Real`] exactly.
+    /// Binary values are always exactly representable since
+    /// `2^-k = 5^k * 10^-k`. Binary values `c * 2^exp` with odd `c`
+    /// and negative `exp` have decimal exponent `exp`; otherwise the
+    /// decimal exponent is 0, e.g., `0.5` has exponent -1.
+    /// Decimal values are copied as is.
+    ///
+    /// Panics if the value is binary and its exponent does not
+    /// fit in a `u32` since the conversion would be too large.
+    pub fn from_number<N: Real>(val: &N) -> Self {
+        if !val.is_numerical() {
+            DFloat::Nan
+        } else if val.is_infinite() {
+            if val.sign().unwrap() {
+                DFloat::NegInfinity
+            } else {
+                DFloat::PosInfinity
+            }
+        } else {
+            let s = val.sign().unwrap_or(false);
+            match N::radix() {
+                10 => {
+                    let exp = val.exp().unwrap_or(0);
+                    let c = val.c().unwrap_or_default();
+                    DFloat::Real(s, exp, c)
+                }
+                2 => {
+                    if val.is_zero() {
+                        DFloat::Real(s, 0, Integer::zero())
+                    } else {
+                        // strip trailing zeros first
+                        let c = val.c().unwrap();
+                        let tz = c.find_one(0).unwrap();
+                        let exp = val.exp().unwrap() + tz as isize;
+                        let c = c >> tz;
+                        let k = u32::try_from(exp.unsigned_abs()).expect("exponent out of range");
+                        if exp < 0 {
+                            DFloat::Real(s, exp, c * Integer::from(5).pow(k))
+                        } else {
+                            DFloat::Real(s, 0, c << k)
+                        }
+                    }
+                }
+                r => panic!("unsupported radix {}", r),
+            }
+        }
+    }
+
+    /// Returns the exact value of this number as a [`Rational`].
+    /// Only defined for finite numbers.
+    pub fn to_rational(&self) -> Option<Rational> {
+        match self {
+            DFloat::Real(s, exp, c) => {
+                let q = scale10(c.clone(), *exp);
+                Some(if *s { -q } else { q })
+            }
+            _ => None,
+        }
+    }
+}
+
+impl Real for DFloat {
+    fn radix() -> usize {
+        10
+    }
+
+    fn sign(&self) -> Option<bool> {
+        match self {
+            DFloat::Real(s, _, _) => Some(*s),
+            DFloat::PosInfinity => Some(false),
+            DFloat::NegInfinity => Some(true),
+            DFloat::Nan => None,
+        }
+    }
+
+    /// Unlike binary numbers, the exponent of a decimal zero
+    /// is well-defined.
+    fn exp(&self) -> Option<isize> {
+        match self {
+            DFloat::Real(_, exp, _) => Some(*exp),
+            _ => None,
+        }
+    }
+
+    fn e(&self) -> Option<isize> {
+        match self {
+            DFloat::Real(_, exp, c) if !c.is_zero() => Some(exp + digits(c) as isize - 1),
+            _ => None,
+        }
+    }
+
+    fn n(&self) -> Option<isize> {
+        match self {
+            DFloat::Real(_, exp, _) => Some(exp - 1),
+            _ => None,
+        }
+    }
+
+    fn c(&self) -> Option<Integer> {
+        match self {
+            DFloat::Real(_, _, c) => Some(c.clone()),
+            _ => None,
+        }
+    }
+
+    fn m(&self) -> Option<Integer> {
+        match self {
+            DFloat::Real(s, _, c) => Some(if *s { -c.clone() } else { c.clone() }),
+            _ => None,
+        }
+    }
+
+    fn prec(&self) -> Option<usize> {
+        match self {
+            DFloat::Real(_, _, c) => Some(digits(c)),
+            _ => None,
+        }
+    }
+
+    fn is_nar(&self) -> bool {
+        !matches!(self, DFloat::Real(_, _, _))
+    }
+
+    fn is_finite(&self) -> bool {
+        matches!(self, DFloat::Real(_, _, _))
+    }
+
+    fn is_infinite(&self) -> bool {
+        matches!(self, DFloat::PosInfinity | DFloat::NegInfinity)
+    }
+
+    fn is_zero(&self) -> bool {
+        matches!(self, DFloat::Real(_, _, c) if c.is_zero())
+    }
+
+    fn is_negative(&self) -> Option<bool> {
+        match self {
+            DFloat::Real(_, _, c) if c.is_zero() => Some(false),
+            DFloat::Real(s, _, _) => Some(*s),
+            DFloat::PosInfinity => Some(false),
+            DFloat::NegInfinity => Some(true),
+            DFloat::Nan => None,
+        }
+    }
+
+    fn is_numerical(&self) -> bool {
+        !matches!(self, DFloat::Nan)
+    }
+}
+
+impl PartialOrd for DFloat {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        match (self, other) {
+            (DFloat::Nan, _) | (_, DFloat::Nan) => None,
+            (DFloat::PosInfinity, DFloat::PosInfinity) => Some(Ordering::Equal),
+            (DFloat::NegInfinity, DFloat::NegInfinity) => Some(Ordering::Equal),
+            (DFloat::PosInfinity, _) | (_, DFloat::NegInfinity) => Some(Ordering::Greater),
+            (DFloat::NegInfinity, _) | (_, DFloat::PosInfinity) => Some(Ordering::Less),
+            (DFloat::Real(_, _, _), DFloat::Real(_, _, _)) => {
+                let q1 = self.to_rational().unwrap();
+                let q2 = other.to_rational().unwrap();
+                q1.partial_cmp(&q2)
+            }
+        }
+    }
+}
+
+impl PartialEq for DFloat {
+    fn eq(&self, other: &Self) -> bool {
+        self.partial_cmp(other) == Some(Ordering::Equal)
+    }
+}
+
+/// Formats a [`DFloat`] preserving its exponent, e.g., `1.50`
+/// or `1.5E+7`. Special values are printed as `Infinity` and `NaN`.
+impl fmt::Display for DFloat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DFloat::Real(s, exp, c) => write!(f, "{}", format_decimal(*s, *exp, c)),
+            DFloat::PosInfinity => write!(f, "Infinity"),
+            DFloat::NegInfinity => write!(f, "-Infinity"),
+            DFloat::Nan => write!(f, "NaN"),
+        }
+    }
+}
@@ -0,0 +1,64 @@
+//! Densely packed decimal (DPD) encoding of three decimal digits.
+//!
+//! A _declet_ is a 10-bit encoding of three decimal digits.
+//! Digits `0-7` are "small" and require three bits while digits
+//! `8-9` are "large" and require one bit; the remaining bits of
+//! a declet indicate which digits are large. Declets with
+//! at most one large digit are canonical. Non-canonical declets,
+//! i.e., the 24 encodings of three large digits that do not have
+//! zeros in the unused bits, still decode to a value.
+
+/// Encodes three decimal digits `0 <= d < 1000` as a declet.
+pub(crate) fn dpd_encode(d: u32) -> u32 {
+    assert!(d < 1000, "must be three decimal digits, given {}", d);
+    let (d1, d2, d3) = (d / 100, (d / 10) % 10, d % 10);
+
+    // large digit indicators and low bits
+    let (a, e, i) = (d1 >> 3, d2 >> 3, d3 >> 3);
+    let (bcd, fgh, jkm) = (d1 & 0b111, d2 & 0b111, d3 & 0b111);
+    let (d, h, m) = (d1 & 1, d2 & 1, d3 & 1);
+    let (fg, jk) = (fgh >> 1, jkm >> 1);
+
+    let (pqr, stu, wxy) = match (a, e, i) {
+        (0, 0, 0) => (bcd, fgh, jkm),
+        (0, 0, 1) => (bcd, fgh, 0b1000 | m),
+        (0, 1, 0) => (bcd, (jk << 1) | h, 0b1010 | m),
+        (1, 0, 0) => ((jk << 1) | d, fgh, 0b1100 | m),
+        (1, 1, 0) => ((jk << 1) | d, h, 0b1110 | m),
+        (1, 0, 1) => ((fg << 1) | d, 0b010 | h, 0b1110 | m),
+        (0, 1, 1) => (bcd, 0b100 | h, 0b1110 | m),
+        _ => (d, 0b110 | h, 0b1110 | m),
+    };
+
+    // `wxy` includes the `v` bit
+    (pqr << 7) | (stu << 4) | wxy
+}
+
+/// Decodes a declet as three decimal digits.
+pub(crate) fn dpd_decode(b: u32) -> u32 {
+    assert!(b < 1024, "must be a 10-bit declet, given {}", b);
+    let pqr = b >> 7;
+    let stu = (b >> 4) & 0b111;
+    let v = (b >> 3) & 1;
+    let wx = (b >> 1) & 0b11;
+    let st = stu >> 1;
+    let (r, u, y) = (pqr & 1, stu & 1, b & 1);
+    let pq = pqr >> 1;
+    let wxy = b & 0b111;
+
+    let (d1, d2, d3) = if v == 0 {
+        (pqr, stu, wxy)
+    } else {
+        match (wx, st) {
+            (0b00, _) => (pqr, stu, 8 + y),
+            (0b01, _) => (pqr, 8 + u, (st << 1) | y),
+            (0b10, _) => (8 + r, stu, (pq << 1) | y),
+            (_, 0b00) => (8 + r, 8 + u, (pq << 1) | y),
+            (_, 0b01) => (8 + r, (pq << 1) | u, 8 + y),
+            (_, 0b10) => (pqr, 8 + u, 8 + y),
+            _ => (8 + r, 8 + u, 8 + y),
+        }
+    };
+
+    100 * d1 + 10 * d2 + d3
+}
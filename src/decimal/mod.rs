@@ -0,0 +1,27 @@
+//! Decimal floating-point numbers as described in the IEEE 754-2019 standard.
+//!
+//! This module implements decimal floating-point numbers with
+//! [`DecimalContext`]. The associated storage type is [`Decimal`]
+//! which represents an IEEE 754 decimal number, e.g., `decimal32`,
+//! `decimal64`, or `decimal128`, with either the binary integer
+//! decimal (BID) or densely packed decimal (DPD) encoding
+//! (see [`Encoding`]).
+//!
+//! Unlike every other number in this library, decimal numbers have
+//! radix 10, i.e., they are of the form `(-1)^s * c * 10^exp`.
+//! Binary contexts round decimal values through their exact value,
+//! but binary operations do not accept decimal values; the unbounded
+//! decimal value type [`DFloat`] converts binary values exactly and is
+//! the intermediate type of decimal operations.
+
+pub(crate) mod dfloat;
+mod dpd;
+mod number;
+pub mod ops;
+mod round;
+
+pub use dfloat::DFloat;
+pub(crate) use number::DecimalVal;
+pub use number::{Decimal, Exceptions};
+pub(crate) use round::{log10_floor, round_increment};
+pub use round::{DecimalContext, Encoding};
@@ -0,0 +1,458 @@
+use std::cmp::Ordering;
+use std::fmt;
+
+use num_traits::Zero;
+use rug::{Integer, Rational};
+
+use crate::decimal::dfloat::{digits, format_decimal, scale10};
+use crate::decimal::{DFloat, DecimalContext, Encoding};
+use crate::ieee754::IEEE754Context;
+use crate::rational::RoundRational;
+use crate::util::bitmask;
+use crate::{Real, RoundingMode};
+
+use super::dpd::{dpd_decode, dpd_encode};
+
+/// Exception flags to signal certain properties of the rounded result.
+///
+/// Besides returning a (possibly) numerical result, any computation with
+/// decimal floating-point numbers may also raise exceptions depending on
+/// certain conditions. The IEEE 754 standard defines five such exceptions:
+///
+/// - _invalid operation_: no useful definable result;
+/// - _division by zero_: an infinite result for finite arguments;
+/// - _overflow_: result exceeded in magnitude what would have been the rounded result
+///   had the exponent range been unbounded;
+/// - _underflow_: non-zero, inexact result that would lie strictly between
+///   `-10^emin` and `+10^emin` had the exponent range and precision
+///   been unbounded (tininess is detected before rounding);
+/// - _inexact_: result would be different had both the exponent range
+///   and precision been unbounded.
+///
+#[derive(Clone, Debug, Default)]
+pub struct Exceptions {
+    pub invalid: bool,
+    pub divzero: bool,
+    pub overflow: bool,
+    pub underflow: bool,
+    pub inexact: bool,
+}
+
+/// IEEE 754 decimal encoding viewed as an enumeration.
+/// Unlike [`Decimal`], [`DecimalVal`] contains only the numerical data
+/// required to encode a decimal floating-point number.
+#[derive(Clone, Debug)]
+pub enum DecimalVal {
+    /// Finite numbers (including zeros): `Finite(s, q, c)` encodes
+    /// `(-1)^s * c * 10^q` where `q` is the quantum exponent and
+    /// `c` has at most `p` digits.
+    Finite(bool, isize, Integer),
+    /// Infinities: `Infinity(s)` encodes `(-1)^s * Inf`.
+    Infinity(bool),
+    /// Not-a-number: `Nan(s, quiet, payload)` where `s` specifies the
+    /// sign bit, `quiet` the signaling bit, and `payload` the payload
+    /// of the NaN value. The payload has at most `p - 1` digits.
+    Nan(bool, bool, Integer),
+}
+
+/// The IEEE 754 decimal floating-point format.
+///
+/// The associated [`RoundingContext`][crate::RoundingContext]
+/// implementation is [`DecimalContext`].
+/// See [`DecimalContext`] for more details on numerical properties
+/// of the [`Decimal`] type.
+///
+/// A finite [`Decimal`] value keeps its quantum exponent, so members
+/// of the same cohort, e.g., `1.5` and `1.50`, are distinguishable
+/// (see [`Decimal::quantum_exp`]) though they compare equal.
+/// A [`Decimal`] value also has an [`Exceptions`] instance to indicate
+/// exceptional events that occured during its construction.
+#[derive(Clone, Debug)]
+pub struct Decimal {
+    pub(crate) num: DecimalVal,
+    pub(crate) flags: Exceptions,
+    pub(crate) ctx: DecimalContext,
+}
+
+impl Decimal {
+    /// Return the flags set when this number was created.
+    pub fn flags(&self) -> &Exceptions {
+        &self.flags
+    }
+
+    /// Returns the rounding context under which this number was created.
+    pub fn ctx(&self) -> &DecimalContext {
+        &self.ctx
+    }
+
+    /// Returns true if this number is NaN.
+    pub fn is_nan(&self) -> bool {
+        matches!(self.num, DecimalVal::Nan(_, _, _))
+    }
+
+    /// Returns the signaling bit of a NaN. Returns `None` if
+    /// this number is not NaN.
+    pub fn nan_quiet(&self) -> Option<bool> {
+        match &self.num {
+            DecimalVal::Nan(_, q, _) => Some(*q),
+            _ => None,
+        }
+    }
+
+    /// Returns the payload of a NaN. Returns `None` if
+    /// this number is not NaN.
+    pub fn nan_payload(&self) -> Option<Integer> {
+        match &self.num {
+            DecimalVal::Nan(_, _, payload) => Some(payload.clone()),
+            _ => None,
+        }
+    }
+
+    /// Returns the quantum exponent `q` of a finite number
+    /// `(-1)^s * c * 10^q`, including zeros.
+    pub fn quantum_exp(&self) -> Option<isize> {
+        match &self.num {
+            DecimalVal::Finite(_, q, _) => Some(*q),
+            _ => None,
+        }
+    }
+
+    /// Returns true if both numbers have the same quantum exponent,
+    /// or are both infinite, or are both NaN (`sameQuantum`).
+    pub fn same_quantum(&self, other: &Self) -> bool {
+        match (&self.num, &other.num) {
+            (DecimalVal::Finite(_, q1, _), DecimalVal::Finite(_, q2, _)) => q1 == q2,
+            (DecimalVal::Infinity(_), DecimalVal::Infinity(_)) => true,
+            (DecimalVal::Nan(_, _, _), DecimalVal::Nan(_, _, _)) => true,
+            _ => false,
+        }
+    }
+
+    /// Converts this [`Decimal`] to the nearest [`f64`] value
+    /// according to the rounding mode `rm`.
+    pub fn to_f64(&self, rm: RoundingMode) -> f64 {
+        let ctx = IEEE754Context::binary64().with_rounding_mode(rm);
+        match &self.num {
+            DecimalVal::Finite(s, _, c) if c.is_zero() => {
+                if *s {
+                    -0.0
+                } else {
+                    0.0
+                }
+            }
+            DecimalVal::Finite(s, q, c) => {
+                let v = scale10(c.clone(), *q);
+                let v = if *s { -v } else { v };
+                ctx.round_rational(&v).to_f64(rm)
+            }
+            DecimalVal::Infinity(s) => {
+                if *s {
+                    f64::NEG_INFINITY
+                } else {
+                    f64::INFINITY
+                }
+            }
+            DecimalVal::Nan(_, _, _) => f64::NAN,
+        }
+    }
+
+    /// Returns the exact value of this number as a [`Rational`].
+    /// Only defined for finite numbers.
+    pub fn to_rational(&self) -> Option<Rational> {
+        DFloat::from(self.clone()).to_rational()
+    }
+
+    /// Converts this [`Decimal`] to an [`Integer`] representing an
+    /// IEEE 754 decimal bitpattern under the encoding of its context
+    /// (see [`Encoding`]).
+    pub fn into_bits(self) -> Integer {
+        let ctx = &self.ctx;
+        let k = ctx.nbits();
+        let w = ctx.w();
+        let t = ctx.t();
+
+        let (s, g, tfield) = match &self.num {
+            DecimalVal::Finite(s, q, c) => {
+                let e = Integer::from(q + ctx.bias());
+                match ctx.encoding() {
+                    Encoding::BID => {
+                        if c.significant_bits() as usize <= t + 3 {
+                            // exponent followed by the significand
+                            let g = (e << 3) | (c.clone() >> t);
+                            (*s, g, c.clone() & bitmask(t))
+                        } else {
+                            // `11`, exponent, and the significand without `100`
+                            let g = (Integer::from(0b11) << (w + 3)) | (e << 1);
+                            let g = g | ((c.clone() >> t) & 1);
+                            (*s, g, c.clone() & bitmask(t))
+                        }
+                    }
+                    Encoding::DPD => {
+                        let (d0, rest) = c.clone().div_rem(ctx.lead_scale());
+                        let d0 = d0.to_u32().unwrap();
+                        let e_top = e.clone() >> w;
+                        let e_low = e & bitmask(w);
+                        let g = if d0 < 8 {
+                            (e_top << 3) | d0
+                        } else {
+                            (Integer::from(0b11000) | (e_top << 1)) | (d0 & 1)
+                        };
+                        (*s, (g << w) | e_low, dpd_encode_digits(&rest, t / 10))
+                    }
+                }
+            }
+            DecimalVal::Infinity(s) => (*s, Integer::from(0b11110) << w, Integer::zero()),
+            DecimalVal::Nan(s, quiet, payload) => {
+                let mut g = Integer::from(0b11111) << w;
+                if !*quiet {
+                    g |= Integer::from(1) << (w - 1);
+                }
+                let tfield = match ctx.encoding() {
+                    Encoding::BID => payload.clone(),
+                    Encoding::DPD => dpd_encode_digits(payload, t / 10),
+                };
+                (*s, g, tfield)
+            }
+        };
+
+        let sfield = if s {
+            Integer::from(1) << (k - 1)
+        } else {
+            Integer::zero()
+        };
+        sfield | (g << t) | tfield
+    }
+}
+
+/// Encodes the lowest `3 * n` digits of `c` as `n` declets.
+pub(crate) fn dpd_encode_digits(c: &Integer, n: usize) -> Integer {
+    let mut c = c.clone();
+    let mut bits = Integer::zero();
+    for i in 0..n {
+        let (q, r) = c.div_rem(Integer::from(1000));
+        bits |= Integer::from(dpd_encode(r.to_u32().unwrap())) << (10 * i);
+        c = q;
+    }
+    bits
+}
+
+/// Decodes `n` declets as a `3 * n` digit integer.
+pub(crate) fn dpd_decode_digits(bits: &Integer, n: usize) -> Integer {
+    (0..n).rev().fold(Integer::zero(), |acc, i| {
+        let declet = (bits.clone() >> (10 * i)) & bitmask(10);
+        acc * 1000 + dpd_decode(declet.to_u32().unwrap())
+    })
+}
+
+impl Real for Decimal {
+    fn radix() -> usize {
+        10
+    }
+
+    fn sign(&self) -> Option<bool> {
+        match &self.num {
+            DecimalVal::Finite(s, _, _) => Some(*s),
+            DecimalVal::Infinity(s) => Some(*s),
+            DecimalVal::Nan(s, _, _) => Some(*s),
+        }
+    }
+
+    /// Unlike binary numbers, the exponent of a decimal zero
+    /// is well-defined.
+    fn exp(&self) -> Option<isize> {
+        self.quantum_exp()
+    }
+
+    fn e(&self) -> Option<isize> {
+        match &self.num {
+            DecimalVal::Finite(_, q, c) if !c.is_zero() => Some(q + digits(c) as isize - 1),
+            _ => None,
+        }
+    }
+
+    fn n(&self) -> Option<isize> {
+        self.quantum_exp().map(|q| q - 1)
+    }
+
+    fn c(&self) -> Option<Integer> {
+        match &self.num {
+            DecimalVal::Finite(_, _, c) => Some(c.clone()),
+            _ => None,
+        }
+    }
+
+    fn m(&self) -> Option<Integer> {
+        match &self.num {
+            DecimalVal::Finite(s, _, c) => Some(if *s { -c.clone() } else { c.clone() }),
+            _ => None,
+        }
+    }
+
+    fn prec(&self) -> Option<usize> {
+        match &self.num {
+            DecimalVal::Finite(_, _, c) => Some(digits(c)),
+            _ => None,
+        }
+    }
+
+    fn is_nar(&self) -> bool {
+        !matches!(self.num, DecimalVal::Finite(_, _, _))
+    }
+
+    fn is_finite(&self) -> bool {
+        matches!(self.num, DecimalVal::Finite(_, _, _))
+    }
+
+    fn is_infinite(&self) -> bool {
+        matches!(self.num, DecimalVal::Infinity(_))
+    }
+
+    fn is_zero(&self) -> bool {
+        matches!(&self.num, DecimalVal::Finite(_, _, c) if c.is_zero())
+    }
+
+    fn is_negative(&self) -> Option<bool> {
+        match &self.num {
+            DecimalVal::Finite(_, _, c) if c.is_zero() => Some(false),
+            DecimalVal::Finite(s, _, _) => Some(*s),
+            DecimalVal::Infinity(s) => Some(*s),
+            DecimalVal::Nan(_, _, _) => None,
+        }
+    }
+
+    fn is_numerical(&self) -> bool {
+        !matches!(self.num, DecimalVal::Nan(_, _, _))
+    }
+
+    fn is_signaling(&self) -> bool {
+        matches!(self.num, DecimalVal::Nan(_, false, _))
+    }
+}
+
+impl PartialOrd for Decimal {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        DFloat::from(self.clone()).partial_cmp(&DFloat::from(other.clone()))
+    }
+}
+
+impl PartialEq for Decimal {
+    fn eq(&self, other: &Self) -> bool {
+        self.partial_cmp(other) == Some(Ordering::Equal)
+    }
+}
+
+impl From<Decimal> for DFloat {
+    fn from(value: Decimal) -> Self {
+        match value.num {
+            DecimalVal::Finite(s, q, c) => DFloat::Real(s, q, c),
+            DecimalVal::Infinity(false) => DFloat::PosInfinity,
+            DecimalVal::Infinity(true) => DFloat::NegInfinity,
+            DecimalVal::Nan(_, _, _) => DFloat::Nan,
+        }
+    }
+}
+
+/// Formats a [`Decimal`] preserving its quantum exponent as described by
+/// the General Decimal Arithmetic specification (_to-scientific-string_),
+/// e.g., `1.50`, `-0.000`, `1.5E+7`, `Infinity`, `NaN`, or `sNaN123`.
+impl fmt::Display for Decimal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.num {
+            DecimalVal::Finite(s, q, c) => write!(f, "{}", format_decimal(*s, *q, c)),
+            DecimalVal::Infinity(s) => write!(f, "{}Infinity", if *s { "-" } else { "" }),
+            DecimalVal::Nan(s, quiet, payload) => {
+                let sign = if *s { "-" } else { "" };
+                let kind = if *quiet { "NaN" } else { "sNaN" };
+                if payload.is_zero() {
+                    write!(f, "{}{}", sign, kind)
+                } else {
+                    write!(f, "{}{}{}", sign, kind, payload)
+                }
+            }
+        }
+    }
+}
+
+impl DecimalContext {
+    /// Converts an [`Integer`] representing an IEEE 754 decimal
+    /// bitpattern into a [`Decimal`] value under the encoding of this
+    /// context (see [`Encoding`]).
+    ///
+    /// Non-canonical encodings are decoded as described by the
+    /// IEEE 754 standard: BID significands exceeding `10^p - 1` and
+    /// NaN payloads exceeding `10^(p-1) - 1` are interpreted as zero;
+    /// non-canonical DPD declets decode to their usual values.
+    pub fn bits_to_number(&self, b: Integer) -> Decimal {
+        let k = self.nbits();
+        let w = self.w();
+        let t = self.t();
+        let limit = Integer::from(1) << k;
+        assert!(b < limit, "must be less than 1 << nbits");
+
+        // decompose into bitfields
+        let s = b.get_bit((k - 1) as u32);
+        let g = (b.clone() >> t) & bitmask(w + 5);
+        let tfield = b & bitmask(t);
+        let top5 = (g.clone() >> w).to_u32().unwrap();
+        let top2 = top5 >> 3;
+
+        let num = if top5 == 0b11111 {
+            // NaN
+            let quiet = !g.get_bit((w - 1) as u32);
+            let payload = match self.encoding() {
+                Encoding::BID => tfield,
+                Encoding::DPD => dpd_decode_digits(&tfield, t / 10),
+            };
+            let payload = if payload >= self.lead_scale() {
+                Integer::zero()
+            } else {
+                payload
+            };
+            DecimalVal::Nan(s, quiet, payload)
+        } else if top5 == 0b11110 {
+            DecimalVal::Infinity(s)
+        } else {
+            let (e, c) = match self.encoding() {
+                Encoding::BID => {
+                    let (e, c) = if top2 != 0b11 {
+                        let e = g.clone() >> 3;
+                        let c = ((g & bitmask(3)) << t) | tfield;
+                        (e, c)
+                    } else {
+                        let e = (g.clone() >> 1) & bitmask(w + 2);
+                        let c = ((Integer::from(0b1000) | (g & 1)) << t) | tfield;
+                        (e, c)
+                    };
+
+                    // non-canonical significands are zero
+                    if c >= self.max_c() {
+                        (e, Integer::zero())
+                    } else {
+                        (e, c)
+                    }
+                }
+                Encoding::DPD => {
+                    let e_low = g.clone() & bitmask(w);
+                    let (e_top, d0) = if top2 != 0b11 {
+                        (top5 >> 3, top5 & 0b111)
+                    } else {
+                        ((top5 >> 1) & 0b11, 8 + (top5 & 1))
+                    };
+                    let e = (Integer::from(e_top) << w) | e_low;
+                    let c =
+                        Integer::from(d0) * self.lead_scale() + dpd_decode_digits(&tfield, t / 10);
+                    (e, c)
+                }
+            };
+
+            let q = e.to_isize().unwrap() - self.bias();
+            DecimalVal::Finite(s, q, c)
+        };
+
+        Decimal {
+            num,
+            flags: Exceptions::default(),
+            ctx: self.clone(),
+        }
+    }
+}
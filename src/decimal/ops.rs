@@ -0,0 +1,460 @@
+//! Rounded operations for IEEE 754 decimal numbers.
+//!
+//! Unlike binary formats, operations are computed exactly on
+//! [`DFloat`] values, so results are correctly rounded without
+//! intermediate precision. Exact results are represented by the member of
+//! their cohort closest to the preferred exponent described by
+//! the IEEE 754 standard, e.g., `min(Q(x), Q(y))` for `x + y`
+//! and `Q(x) + Q(y)` for `x * y` where `Q(x)` is the quantum
+//! exponent of `x`.
+
+use num_traits::Zero;
+use rug::{Integer, Rational};
+
+use crate::decimal::dfloat::{digits, scale10};
+use crate::decimal::{log10_floor, round_increment};
+use crate::decimal::{DFloat, Decimal, DecimalContext, DecimalVal};
+use crate::ops::*;
+use crate::rfloat::RFloat;
+use crate::{Real, RoundingContext, RoundingMode};
+
+/// Signed significand of a finite [`DFloat`] aligned to the exponent `exp`.
+fn aligned(s: bool, q: isize, c: &Integer, exp: isize) -> Integer {
+    let c = scale10(c.clone(), q - exp);
+    let c = c.into_numer_denom().0;
+    if s {
+        -c
+    } else {
+        c
+    }
+}
+
+/// Returns `Some(signaling)` if `src` is NaN.
+fn nan_arg<N: Real>(src: &N) -> Option<bool> {
+    if src.is_numerical() {
+        None
+    } else {
+        Some(src.is_signaling())
+    }
+}
+
+impl DecimalContext {
+    /// Propagates NaN arguments, given by [`nan_arg`], returning `None`
+    /// if no argument is NaN. The result is a quiet NaN; quiet NaN
+    /// arguments propagate silently while signaling NaN arguments
+    /// signal an invalid operation.
+    fn propagate_nan(&self, args: &[Option<bool>]) -> Option<Decimal> {
+        if args.iter().all(Option::is_none) {
+            None
+        } else {
+            let mut result = self.qnan();
+            result.flags.invalid = args.contains(&Some(true));
+            Some(result)
+        }
+    }
+
+    /// Constructs a quiet NaN signaling an invalid operation.
+    fn invalid(&self) -> Decimal {
+        let mut result = self.qnan();
+        result.flags.invalid = true;
+        result
+    }
+
+    /// Constructs an infinity signaling division by zero.
+    fn divzero(&self, sign: bool) -> Decimal {
+        let mut result = self.inf(sign);
+        result.flags.divzero = true;
+        result
+    }
+
+    /// Rounds the exact sum `(-1)^s1 * c1 * 10^q1 + (-1)^s2 * c2 * 10^q2`
+    /// with preferred exponent `min(q1, q2)`.
+    fn round_sum(
+        &self,
+        s1: bool,
+        q1: isize,
+        c1: &Integer,
+        s2: bool,
+        q2: isize,
+        c2: &Integer,
+    ) -> Decimal {
+        let exp = q1.min(q2);
+        let m = aligned(s1, q1, c1, exp) + aligned(s2, q2, c2, exp);
+        if m.is_zero() {
+            // exact zero: sign depends on the rounding mode
+            let s = if s1 == s2 {
+                s1
+            } else {
+                matches!(self.rm(), RoundingMode::ToNegative)
+            };
+            self.round_exact(s, &Rational::new(), exp)
+        } else {
+            let s = m.is_negative();
+            self.round_dfloat(&DFloat::Real(s, exp, m.abs()), exp)
+        }
+    }
+
+    /// Rounds `src` to a value with the same quantum exponent as `q`
+    /// (`quantize`). Rounding uses the rounding mode of this context
+    /// and only raises the inexact exception. If the result does not fit
+    /// in the precision of this format, or the quantum exponent of `q` is
+    /// outside the bounds of this format, the result is NaN, raising the
+    /// invalid exception.
+    pub fn quantize<N: Real>(&self, src: &N, q: &Decimal) -> Decimal {
+        if let Some(nan) = self.propagate_nan(&[nan_arg(src), nan_arg(q)]) {
+            return nan;
+        }
+
+        let x = DFloat::from_number(src);
+        match (&x, &q.num) {
+            (DFloat::Nan, _) | (_, DecimalVal::Nan(_, _, _)) => unreachable!(),
+            (DFloat::PosInfinity, DecimalVal::Infinity(_)) => self.inf(false),
+            (DFloat::NegInfinity, DecimalVal::Infinity(_)) => self.inf(true),
+            (DFloat::PosInfinity | DFloat::NegInfinity, _) => self.invalid(),
+            (_, DecimalVal::Infinity(_)) => self.invalid(),
+            (DFloat::Real(s, _, _), DecimalVal::Finite(_, exp, _)) => {
+                let exp = *exp;
+                if exp < self.expmin() || exp > self.expmax() {
+                    return self.invalid();
+                }
+
+                // round to an integer multiple of `10^exp`
+                let v = x.to_rational().unwrap().abs() / scale10(Integer::from(1), exp);
+                let (frac, trunc) = v.fract_trunc(Integer::new());
+                let inexact = !frac.is_zero();
+                let c = if inexact && round_increment(self.rm(), *s, &trunc, &frac) {
+                    trunc + 1
+                } else {
+                    trunc
+                };
+
+                if digits(&c) > self.max_p() {
+                    self.invalid()
+                } else {
+                    let mut result = self.make(DecimalVal::Finite(*s, exp, c));
+                    result.flags.inexact = inexact;
+                    result
+                }
+            }
+        }
+    }
+
+    /// Returns the unit in the last place of `src` (`quantum`), i.e.,
+    /// `10^q` where `q` is its quantum exponent. The quantum of
+    /// an infinity is positive infinity.
+    pub fn quantum(&self, src: &Decimal) -> Decimal {
+        match &src.num {
+            DecimalVal::Finite(_, q, _) => {
+                self.make(DecimalVal::Finite(false, *q, Integer::from(1)))
+            }
+            DecimalVal::Infinity(_) => self.inf(false),
+            DecimalVal::Nan(_, _, _) => self.propagate_nan(&[nan_arg(src)]).unwrap(),
+        }
+    }
+}
+
+impl RoundedNeg for DecimalContext {
+    fn neg<N: Real>(&self, src: &N) -> Self::Format {
+        match DFloat::from_number(src) {
+            DFloat::Real(s, q, c) => self.round_dfloat(&DFloat::Real(!s, q, c), q),
+            DFloat::PosInfinity => self.inf(true),
+            DFloat::NegInfinity => self.inf(false),
+            DFloat::Nan => self.qnan(),
+        }
+    }
+}
+
+impl RoundedAbs for DecimalContext {
+    fn abs<N: Real>(&self, src: &N) -> Self::Format {
+        match DFloat::from_number(src) {
+            DFloat::Real(_, q, c) => self.round_dfloat(&DFloat::Real(false, q, c), q),
+            DFloat::PosInfinity | DFloat::NegInfinity => self.inf(false),
+            DFloat::Nan => self.qnan(),
+        }
+    }
+}
+
+impl RoundedAdd for DecimalContext {
+    fn add<N1, N2>(&self, src1: &N1, src2: &N2) -> Self::Format
+    where
+        N1: Real,
+        N2: Real,
+    {
+        if let Some(nan) = self.propagate_nan(&[nan_arg(src1), nan_arg(src2)]) {
+            return nan;
+        }
+
+        match (DFloat::from_number(src1), DFloat::from_number(src2)) {
+            (DFloat::Nan, _) | (_, DFloat::Nan) => unreachable!(),
+            (DFloat::PosInfinity, DFloat::NegInfinity) => self.invalid(),
+            (DFloat::NegInfinity, DFloat::PosInfinity) => self.invalid(),
+            (DFloat::PosInfinity, _) | (_, DFloat::PosInfinity) => self.inf(false),
+            (DFloat::NegInfinity, _) | (_, DFloat::NegInfinity) => self.inf(true),
+            (DFloat::Real(s1, q1, c1), DFloat::Real(s2, q2, c2)) => {
+                self.round_sum(s1, q1, &c1, s2, q2, &c2)
+            }
+        }
+    }
+}
+
+impl RoundedSub for DecimalContext {
+    fn sub<N1, N2>(&self, src1: &N1, src2: &N2) -> Self::Format
+    where
+        N1: Real,
+        N2: Real,
+    {
+        if let Some(nan) = self.propagate_nan(&[nan_arg(src1), nan_arg(src2)]) {
+            return nan;
+        }
+
+        let neg = match DFloat::from_number(src2) {
+            DFloat::Real(s, q, c) => DFloat::Real(!s, q, c),
+            DFloat::PosInfinity => DFloat::NegInfinity,
+            DFloat::NegInfinity => DFloat::PosInfinity,
+            DFloat::Nan => DFloat::Nan,
+        };
+        self.add(src1, &neg)
+    }
+}
+
+impl RoundedMul for DecimalContext {
+    fn mul<N1, N2>(&self, src1: &N1, src2: &N2) -> Self::Format
+    where
+        N1: Real,
+        N2: Real,
+    {
+        if let Some(nan) = self.propagate_nan(&[nan_arg(src1), nan_arg(src2)]) {
+            return nan;
+        }
+
+        let x = DFloat::from_number(src1);
+        let y = DFloat::from_number(src2);
+        match (&x, &y) {
+            (DFloat::Nan, _) | (_, DFloat::Nan) => unreachable!(),
+            _ if (x.is_infinite() && y.is_zero()) || (x.is_zero() && y.is_infinite()) => {
+                self.invalid()
+            }
+            _ if x.is_infinite() || y.is_infinite() => {
+                self.inf(x.sign().unwrap() != y.sign().unwrap())
+            }
+            (DFloat::Real(s1, q1, c1), DFloat::Real(s2, q2, c2)) => {
+                let prod = DFloat::Real(s1 != s2, q1 + q2, c1.clone() * c2);
+                self.round_dfloat(&prod, q1 + q2)
+            }
+            _ => unreachable!(),
+        }
+    }
+}
+
+impl RoundedDiv for DecimalContext {
+    fn div<N1, N2>(&self, src1: &N1, src2: &N2) -> Self::Format
+    where
+        N1: Real,
+        N2: Real,
+    {
+        if let Some(nan) = self.propagate_nan(&[nan_arg(src1), nan_arg(src2)]) {
+            return nan;
+        }
+
+        let x = DFloat::from_number(src1);
+        let y = DFloat::from_number(src2);
+        match (&x, &y) {
+            (DFloat::Nan, _) | (_, DFloat::Nan) => unreachable!(),
+            _ if x.is_infinite() && y.is_infinite() => self.invalid(),
+            _ if x.is_zero() && y.is_zero() => self.invalid(),
+            _ => {
+                let s = x.sign().unwrap() != y.sign().unwrap();
+                if x.is_infinite() {
+                    self.inf(s)
+                } else if y.is_infinite() {
+                    self.make(DecimalVal::Finite(s, self.expmin(), Integer::zero()))
+                } else if y.is_zero() {
+                    self.divzero(s)
+                } else {
+                    let pe = x.exp().unwrap() - y.exp().unwrap();
+                    let q = x.to_rational().unwrap() / y.to_rational().unwrap();
+                    self.round_exact(s, &q.abs(), pe)
+                }
+            }
+        }
+    }
+}
+
+impl RoundedSqrt for DecimalContext {
+    fn sqrt<N: Real>(&self, src: &N) -> Self::Format {
+        if let Some(nan) = self.propagate_nan(&[nan_arg(src)]) {
+            return nan;
+        }
+
+        match DFloat::from_number(src) {
+            DFloat::Nan => unreachable!(),
+            DFloat::NegInfinity => self.invalid(),
+            DFloat::PosInfinity => self.inf(false),
+            DFloat::Real(s, q, c) if c.is_zero() => {
+                self.round_exact(s, &Rational::new(), q.div_euclid(2))
+            }
+            DFloat::Real(true, _, _) => self.invalid(),
+            x @ DFloat::Real(false, q, _) => {
+                // compute `p + 2` digits with a sticky digit
+                let p = self.max_p() as isize;
+                let v = x.to_rational().unwrap();
+                let qr = log10_floor(&v).div_euclid(2) - p - 2;
+                let scaled = v / scale10(Integer::from(1), 2 * qr);
+                let (frac, trunc) = scaled.fract_trunc(Integer::new());
+                let (r, rem) = trunc.sqrt_rem(Integer::new());
+                let result = if frac.is_zero() && rem.is_zero() {
+                    DFloat::Real(false, qr, r)
+                } else {
+                    DFloat::Real(false, qr - 1, r * 10 + 1)
+                };
+                self.round_dfloat(&result, q.div_euclid(2))
+            }
+        }
+    }
+}
+
+impl RoundedFMA for DecimalContext {
+    fn fma<N1, N2, N3>(&self, src1: &N1, src2: &N2, src3: &N3) -> Self::Format
+    where
+        N1: Real,
+        N2: Real,
+        N3: Real,
+    {
+        let nan = self.propagate_nan(&[nan_arg(src1), nan_arg(src2), nan_arg(src3)]);
+        if let Some(nan) = nan {
+            return nan;
+        }
+
+        let x = DFloat::from_number(src1);
+        let y = DFloat::from_number(src2);
+        let z = DFloat::from_number(src3);
+        match (&x, &y, &z) {
+            (DFloat::Nan, _, _) | (_, DFloat::Nan, _) | (_, _, DFloat::Nan) => unreachable!(),
+            _ if (x.is_infinite() && y.is_zero()) || (x.is_zero() && y.is_infinite()) => {
+                self.invalid()
+            }
+            _ if x.is_infinite() || y.is_infinite() => {
+                let s = x.sign().unwrap() != y.sign().unwrap();
+                if z.is_infinite() && z.sign().unwrap() != s {
+                    self.invalid()
+                } else {
+                    self.inf(s)
+                }
+            }
+            (_, _, DFloat::PosInfinity) => self.inf(false),
+            (_, _, DFloat::NegInfinity) => self.inf(true),
+            (DFloat::Real(s1, q1, c1), DFloat::Real(s2, q2, c2), DFloat::Real(s3, q3, c3)) => {
+                let c = c1.clone() * c2;
+                self.round_sum(s1 != s2, q1 + q2, &c, *s3, *q3, c3)
+            }
+            _ => unreachable!(),
+        }
+    }
+}
+
+macro_rules! rounded_integral_impl {
+    ($tname:ident, $name:ident, $rm:expr) => {
+        impl $tname for DecimalContext {
+            fn $name<N: Real>(&self, src: &N) -> Self::Format {
+                if let Some(nan) = self.propagate_nan(&[nan_arg(src)]) {
+                    return nan;
+                }
+
+                let (integral, _) = round_integral(src, $rm);
+                self.round(&integral)
+            }
+        }
+    };
+}
+
+rounded_integral_impl!(RoundedFloor, floor, RoundingMode::ToNegative);
+rounded_integral_impl!(RoundedCeil, ceil, RoundingMode::ToPositive);
+rounded_integral_impl!(RoundedTrunc, trunc, RoundingMode::ToZero);
+rounded_integral_impl!(
+    RoundedRoundAway,
+    round_away,
+    RoundingMode::NearestTiesAwayZero
+);
+rounded_integral_impl!(
+    RoundedRoundEven,
+    round_even,
+    RoundingMode::NearestTiesToEven
+);
+
+impl RoundedNearbyInt for DecimalContext {
+    fn nearbyint<N: Real>(&self, src: &N) -> Self::Format {
+        if let Some(nan) = self.propagate_nan(&[nan_arg(src)]) {
+            return nan;
+        }
+
+        let (integral, _) = round_integral(src, self.rm());
+        self.round(&integral)
+    }
+}
+
+impl RoundedRint for DecimalContext {
+    fn rint<N: Real>(&self, src: &N) -> Self::Format {
+        if let Some(nan) = self.propagate_nan(&[nan_arg(src)]) {
+            return nan;
+        }
+
+        let (integral, inexact) = round_integral(src, self.rm());
+        let mut rounded = self.round(&integral);
+        rounded.flags.inexact |= inexact;
+        rounded
+    }
+}
+
+impl RoundedConvertToInteger for DecimalContext {
+    fn convert_to_integer<N: Real>(&self, src: &N, rm: RoundingMode, exact: bool) -> Self::Format {
+        let (integral, inexact) = round_integral(src, rm);
+        let mut rounded = self.round(&integral);
+        if !integral.is_finite() || rounded.flags.overflow {
+            // non-real or out of range integers are invalid
+            let mut result = self.round(&RFloat::Nan);
+            result.flags.invalid = true;
+            result
+        } else {
+            rounded.flags.inexact = exact && (inexact || rounded.flags.inexact);
+            rounded
+        }
+    }
+}
+
+macro_rules! min_max_impl {
+    ($tname:ident, $name:ident, $op:expr) => {
+        impl $tname for DecimalContext {
+            fn $name<N1, N2>(&self, src1: &N1, src2: &N2) -> Self::Format
+            where
+                N1: Real,
+                N2: Real,
+            {
+                let (result, invalid) = min_max(src1, src2, $op);
+                let mut rounded = match result {
+                    MinMaxResult::First => self.round(src1),
+                    MinMaxResult::Second => self.round(src2),
+                    MinMaxResult::Nan => self.round(&RFloat::Nan),
+                };
+                rounded.flags.invalid |= invalid;
+                rounded
+            }
+        }
+    };
+}
+
+min_max_impl!(RoundedMinimum, minimum, MinMax::Minimum);
+min_max_impl!(RoundedMaximum, maximum, MinMax::Maximum);
+min_max_impl!(RoundedMinimumNumber, minimum_number, MinMax::MinimumNumber);
+min_max_impl!(RoundedMaximumNumber, maximum_number, MinMax::MaximumNumber);
+min_max_impl!(
+    RoundedMinimumMagnitude,
+    minimum_magnitude,
+    MinMax::MinimumMagnitude
+);
+min_max_impl!(
+    RoundedMaximumMagnitude,
+    maximum_magnitude,
+    MinMax::MaximumMagnitude
+);
+min_max_impl!(RoundedMinNum, min_num, MinMax::MinNum);
+min_max_impl!(RoundedMaxNum, max_num, MinMax::MaxNum);
@@ -0,0 +1,381 @@
+use num_traits::Zero;
+use rug::ops::Pow;
+use rug::{Integer, Rational};
+
+use crate::decimal::dfloat::{digits, scale10};
+use crate::decimal::{DFloat, Decimal, DecimalVal, Exceptions};
+use crate::{Real, RoundingContext, RoundingDirection, RoundingMode};
+
+/// Encodings of the significand of IEEE 754 decimal numbers.
+///
+/// The IEEE 754 standard specifies two encodings for decimal formats
+/// which represent the same set of values.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Encoding {
+    /// Binary integer decimal: the significand is stored
+    /// as a binary integer.
+    BID,
+    /// Densely packed decimal: the significand is stored as
+    /// a leading digit and groups of three digits (declets).
+    DPD,
+}
+
+/// Rounding contexts for IEEE 754 decimal floating-point numbers.
+///
+/// The associated storage type is [`Decimal`].
+///
+/// Values rounded under this context are floating-point numbers
+/// as described in the IEEE 754 standard: base 10 scientific numbers
+/// `(-1)^s * c * 10^q` where `c` is an unsigned integer with at most `p`
+/// decimal digits and `q` is a signed integer with format-specific bounds.
+/// Decimal formats are not normalized: a finite value may have many
+/// representations (its _cohort_). Rounding an exact value chooses the
+/// member of the cohort with exponent closest to the _preferred exponent_
+/// which for a [`Real`] value is its exponent `exp` (see
+/// [`Real::exp`]).
+///
+/// A [`DecimalContext`] is parameterized by
+///
+///  - total bitwidth of the encoding (a multiple of 32),
+///  - rounding mode,
+///  - encoding of the significand (see [`Encoding`]).
+///
+/// By default, the rounding mode is [`RoundingMode::NearestTiesToEven`]
+/// and the encoding is [`Encoding::BID`]. Tininess is detected before
+/// rounding.
+#[derive(Clone, Debug)]
+pub struct DecimalContext {
+    k: usize,
+    rm: RoundingMode,
+    encoding: Encoding,
+}
+
+impl DecimalContext {
+    /// Constructs a new rounding context for IEEE 754 decimal numbers
+    /// with a total bitwidth of `k`. The bitwidth must be a positive
+    /// multiple of 32.
+    pub fn new(k: usize) -> Self {
+        assert!(
+            k >= 32 && k.is_multiple_of(32),
+            "must be a positive multiple of 32, given {}",
+            k
+        );
+        Self {
+            k,
+            rm: RoundingMode::NearestTiesToEven,
+            encoding: Encoding::BID,
+        }
+    }
+
+    /// Constructs a rounding context for the IEEE 754 `decimal32` format.
+    pub fn decimal32() -> Self {
+        Self::new(32)
+    }
+
+    /// Constructs a rounding context for the IEEE 754 `decimal64` format.
+    pub fn decimal64() -> Self {
+        Self::new(64)
+    }
+
+    /// Constructs a rounding context for the IEEE 754 `decimal128` format.
+    pub fn decimal128() -> Self {
+        Self::new(128)
+    }
+
+    /// Sets the rounding mode of this [`DecimalContext`].
+    pub fn with_rounding_mode(mut self, rm: RoundingMode) -> Self {
+        self.rm = rm;
+        self
+    }
+
+    /// Sets the encoding of this [`DecimalContext`].
+    pub fn with_encoding(mut self, encoding: Encoding) -> Self {
+        self.encoding = encoding;
+        self
+    }
+
+    /// Returns the rounding mode of this context.
+    pub fn rm(&self) -> RoundingMode {
+        self.rm
+    }
+
+    /// Returns the encoding of this context.
+    pub fn encoding(&self) -> Encoding {
+        self.encoding
+    }
+
+    /// Returns the total bitwidth of the format produced by this context.
+    pub fn nbits(&self) -> usize {
+        self.k
+    }
+
+    /// Returns the maximum precision (in decimal digits) allowed
+    /// by this format. The result is always `9 * k / 32 - 2`.
+    pub fn max_p(&self) -> usize {
+        9 * self.k / 32 - 2
+    }
+
+    /// Exponent of the largest finite value representable in this format
+    /// when viewed as `(-1)^s * m * 10^e` where `m` is a fraction
+    /// between 1 and 10. The result is just `3 * 2^(k/16 + 3)`.
+    pub fn emax(&self) -> isize {
+        3 << (self.k / 16 + 3)
+    }
+
+    /// Exponent of the smallest normal value representable in this format
+    /// when viewed as `(-1)^s * m * 10^e` where `m` is a fraction
+    /// between 1 and 10. The result is just `1 - self.emax()`.
+    pub fn emin(&self) -> isize {
+        1 - self.emax()
+    }
+
+    /// Largest quantum exponent `q` of a value `(-1)^s * c * 10^q`
+    /// representable in this format. The result is just
+    /// `self.emax() - self.max_p() + 1`.
+    pub fn expmax(&self) -> isize {
+        self.emax() - self.max_p() as isize + 1
+    }
+
+    /// Smallest quantum exponent `q` of a value `(-1)^s * c * 10^q`
+    /// representable in this format. The result is just
+    /// `self.emin() - self.max_p() + 1`.
+    pub fn expmin(&self) -> isize {
+        self.emin() - self.max_p() as isize + 1
+    }
+
+    /// The exponent "bias" used when converting a quantum exponent
+    /// to an unsigned integer for bitpacking, i.e.,
+    /// `self.emax() + self.max_p() - 2`.
+    pub fn bias(&self) -> isize {
+        self.emax() + self.max_p() as isize - 2
+    }
+
+    /// Bitwidth of the exponent continuation field.
+    pub(crate) fn w(&self) -> usize {
+        self.k / 16 + 4
+    }
+
+    /// Bitwidth of the trailing significand field.
+    pub(crate) fn t(&self) -> usize {
+        15 * self.k / 16 - 10
+    }
+
+    /// Returns `10^(p-1)`, the scale of the leading digit.
+    pub(crate) fn lead_scale(&self) -> Integer {
+        Integer::from(10).pow(self.max_p() as u32 - 1)
+    }
+
+    /// Returns `10^p`, one more than the largest significand.
+    pub(crate) fn max_c(&self) -> Integer {
+        Integer::from(10).pow(self.max_p() as u32)
+    }
+
+    /// Constructs a [`Decimal`] value with no flags set.
+    pub(crate) fn make(&self, num: DecimalVal) -> Decimal {
+        Decimal {
+            num,
+            flags: Exceptions::default(),
+            ctx: self.clone(),
+        }
+    }
+
+    /// Returns a signed zero with quantum exponent 0.
+    pub fn zero(&self, sign: bool) -> Decimal {
+        self.make(DecimalVal::Finite(sign, 0, Integer::zero()))
+    }
+
+    /// Returns the maximum representable value with a sign.
+    pub fn max_float(&self, sign: bool) -> Decimal {
+        let c = self.max_c() - 1;
+        self.make(DecimalVal::Finite(sign, self.expmax(), c))
+    }
+
+    /// Returns the minimum (positive) representable value with a sign.
+    pub fn min_float(&self, sign: bool) -> Decimal {
+        self.make(DecimalVal::Finite(sign, self.expmin(), Integer::from(1)))
+    }
+
+    /// Constructs an infinity with a sign.
+    pub fn inf(&self, sign: bool) -> Decimal {
+        self.make(DecimalVal::Infinity(sign))
+    }
+
+    /// Constructs a canonical, quiet NaN (unsigned, empty payload).
+    pub fn qnan(&self) -> Decimal {
+        self.make(DecimalVal::Nan(false, true, Integer::zero()))
+    }
+
+    /// Constructs a canonical, signaling NaN (unsigned, empty payload).
+    pub fn snan(&self) -> Decimal {
+        self.make(DecimalVal::Nan(false, false, Integer::zero()))
+    }
+
+    /// Given a sign, returns true if overflow produces infinity
+    /// rather than the largest finite value.
+    fn overflow_to_infinity(&self, sign: bool) -> bool {
+        // nearest modes only overflow when the value rounds past
+        // the largest finite value, so ties have already been broken
+        match self.rm.to_direction(sign) {
+            (true, _) => true,
+            (_, RoundingDirection::ToZero) => false,
+            (_, RoundingDirection::AwayZero) => true,
+            (_, RoundingDirection::ToEven) => true, // MAX_FLOAT has odd LSD
+            (_, RoundingDirection::ToOdd) => false, // MAX_FLOAT has odd LSD
+        }
+    }
+
+    /// Rounds the exact value `(-1)^s * q` where `q` is non-negative.
+    /// If the result is exact, the member of its cohort with quantum
+    /// exponent closest to `pe` is chosen.
+    pub(crate) fn round_exact(&self, s: bool, q: &Rational, pe: isize) -> Decimal {
+        let p = self.max_p() as isize;
+        let qmin = self.expmin();
+        let qmax = self.expmax();
+
+        // exact zero
+        if q.is_zero() {
+            let exp = pe.clamp(qmin, qmax);
+            return self.make(DecimalVal::Finite(s, exp, Integer::zero()));
+        }
+
+        // truncate at the smallest quantum exponent
+        let e = log10_floor(q);
+        let mut exp = (e - p + 1).max(qmin);
+        let scaled = q.clone() / scale10(Integer::from(1), exp);
+        let (frac, trunc) = scaled.fract_trunc(Integer::new());
+        let inexact = !frac.is_zero();
+
+        // round
+        let mut c = trunc;
+        if inexact && round_increment(self.rm, s, &c, &frac) {
+            c += 1;
+            if c == self.max_c() {
+                c /= 10;
+                exp += 1;
+            }
+        }
+
+        // overflow
+        if exp > qmax {
+            let mut result = if self.overflow_to_infinity(s) {
+                self.inf(s)
+            } else {
+                self.max_float(s)
+            };
+            result.flags.overflow = true;
+            result.flags.inexact = true;
+            return result;
+        }
+
+        if inexact {
+            let mut result = self.make(DecimalVal::Finite(s, exp, c));
+            result.flags.underflow = e < self.emin();
+            result.flags.inexact = true;
+            result
+        } else {
+            // choose the cohort member closest to the preferred exponent
+            if pe > exp {
+                let mut k = (pe - exp).min(qmax - exp);
+                while k > 0 && c.is_divisible_u(10) {
+                    c /= 10;
+                    exp += 1;
+                    k -= 1;
+                }
+            }
+            self.make(DecimalVal::Finite(s, exp, c))
+        }
+    }
+
+    /// Returns a value that rounds the same as a finite, non-zero value
+    /// with sign `s` and adjusted exponent in `[lo, hi]` if that range
+    /// lies entirely outside the range of this context: every value at
+    /// least `10^(emax+1)` in magnitude overflows, and every value less
+    /// than `10^(expmin-1)` in magnitude rounds as if it were zero
+    /// with a non-zero sticky digit.
+    fn saturate(&self, s: bool, lo: isize, hi: isize) -> Option<DFloat> {
+        if lo > self.emax() {
+            Some(DFloat::Real(s, self.emax() + 1, Integer::from(1)))
+        } else if hi < self.expmin() - 1 {
+            Some(DFloat::Real(s, self.expmin() - 2, Integer::from(1)))
+        } else {
+            None
+        }
+    }
+
+    /// Rounds a [`DFloat`] value, preferring the quantum exponent `pe`
+    /// if the result is exact.
+    pub(crate) fn round_dfloat(&self, num: &DFloat, pe: isize) -> Decimal {
+        match num {
+            DFloat::Real(s, _, c) if c.is_zero() => self.round_exact(*s, &Rational::new(), pe),
+            DFloat::Real(s, exp, c) => {
+                // values far outside the range need not be computed exactly
+                let e = exp.saturating_add(digits(c) as isize - 1);
+                let q = match self.saturate(*s, e, e) {
+                    Some(num) => num.to_rational().unwrap().abs(),
+                    None => num.to_rational().unwrap().abs(),
+                };
+                self.round_exact(*s, &q, pe)
+            }
+            DFloat::PosInfinity => self.inf(false),
+            DFloat::NegInfinity => self.inf(true),
+            DFloat::Nan => self.qnan(),
+        }
+    }
+}
+
+/// Returns true if the truncated significand `c` of a value with sign `sign`
+/// should be incremented given the fraction `frac` discarded by truncation
+/// and the rounding mode `rm`. Preparing for shorter precision rounds
+/// away from zero only if the last digit of `c` is 0 or 5 ("05up"),
+/// so that the result may be correctly rounded again.
+pub(crate) fn round_increment(rm: RoundingMode, sign: bool, c: &Integer, frac: &Rational) -> bool {
+    if let RoundingMode::PrepareShorterPrecision = rm {
+        let digit = c.mod_u(10);
+        return digit == 0 || digit == 5;
+    }
+
+    let (is_nearest, dir) = rm.to_direction(sign);
+    let half = Rational::from((1, 2));
+    if is_nearest && *frac != half {
+        *frac > half
+    } else {
+        match dir {
+            RoundingDirection::ToZero => false,
+            RoundingDirection::AwayZero => true,
+            RoundingDirection::ToEven => c.is_odd(),
+            RoundingDirection::ToOdd => c.is_even(),
+        }
+    }
+}
+
+/// Computes `floor(log10(q))` for a positive rational `q`.
+pub(crate) fn log10_floor(q: &Rational) -> isize {
+    let est = digits(q.numer()) as isize - digits(q.denom()) as isize;
+    if *q >= scale10(Integer::from(1), est) {
+        est
+    } else {
+        est - 1
+    }
+}
+
+impl RoundingContext for DecimalContext {
+    type Format = Decimal;
+
+    fn round<T: Real>(&self, num: &T) -> Self::Format {
+        if T::radix() == 2 && num.is_finite() && !num.is_zero() {
+            // binary values far outside the range need not be converted
+            // exactly: bound the adjusted exponent by `e * log10(2)`
+            let t = num.e().unwrap() as f64 * std::f64::consts::LOG10_2;
+            let margin = 2.0 + t.abs() * 1e-12;
+            let lo = (t - margin).floor() as isize;
+            let hi = (t + margin).ceil() as isize;
+            if let Some(num) = self.saturate(num.sign().unwrap(), lo, hi) {
+                return self.round_dfloat(&num, 0);
+            }
+        }
+
+        let num = DFloat::from_number(num);
+        let pe = num.exp().unwrap_or(0);
+        self.round_dfloat(&num, pe)
+    }
+}
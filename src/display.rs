@@ -0,0 +1,416 @@
+//! Formatting numbers as decimal strings.
+//!
+//! Every number format implements [`Display`][std::fmt::Display] by
+//! printing the shortest decimal string that rounds back to the same
+//! value under the number's own rounding context, e.g., `0.1` rather
+//! than `0.1000000000000000055511151231257827` for the closest
+//! double-precision value to one tenth. Since [`RFloat`] values
+//! have no associated rounding context, they are always printed exactly.
+//!
+//! Other modes are available through [`DecimalDisplay`]:
+//!
+//!  - [`Shortest`][DecimalMode] prints the shortest round-trip string;
+//!  - [`Exact`][DecimalMode] prints the exact decimal value
+//!    (every binary number has a finite decimal expansion);
+//!  - [`Digits`][DecimalMode] prints a fixed number of significant
+//!    digits, rounding according to a [`RoundingMode`].
+//!
+//! Numbers are printed in positional notation when the decimal
+//! exponent is small, and scientific notation otherwise, e.g.,
+//! `1.5e-300`. Special values are printed as `inf`, `-inf`, `NaN`,
+//! or `NaR` (for posits).
+//!
+//! Every number can also be printed exactly in hexadecimal
+//! in the style of C99 `%a` through [`HexDisplay`], or the
+//! [`LowerHex`][std::fmt::LowerHex] and [`UpperHex`][std::fmt::UpperHex]
+//! formatting traits, e.g., `format!("{:x}", x)` produces `-0x1.8p-3`.
+//! IEEE 754 NaNs are printed with their payloads, e.g., `nan(0x1234)`
+//! or `snan(0x1)` (see [`crate::parse`] for the inverse).
+//!
+
+use std::cmp::Ordering;
+use std::fmt;
+
+use rug::ops::Pow;
+use rug::{Integer, Rational};
+
+use crate::fixed::Fixed;
+use crate::float::Float;
+use crate::ieee754::{IEEE754Val, IEEE754};
+use crate::posit::Posit;
+use crate::rational::{round_rational, RationalContext};
+use crate::rfloat::RFloat;
+use crate::{Real, RoundingDirection, RoundingMode};
+
+/// Decimal formatting modes.
+#[derive(Clone, Copy, Debug)]
+pub enum DecimalMode {
+    /// Shortest decimal string that rounds back to the same value
+    /// under the number's rounding context.
+    Shortest,
+    /// Exact decimal value.
+    Exact,
+    /// Fixed number of significant digits, rounded according
+    /// to the given rounding mode.
+    Digits(usize, RoundingMode),
+}
+
+/// Decimal formatting of numbers.
+pub trait DecimalDisplay: Real {
+    /// Formats this number as a decimal string according to `mode`.
+    fn to_decimal_string(&self, mode: DecimalMode) -> String;
+}
+
+/// Decimal exponents in `[MIN_POSITIONAL, MAX_POSITIONAL)`
+/// are printed in positional notation.
+const MIN_POSITIONAL: isize = -5;
+const MAX_POSITIONAL: isize = 17;
+
+/// Computes `10^e` for a non-negative `e`.
+fn pow10(e: isize) -> Integer {
+    Integer::from(10).pow(e as u32)
+}
+
+/// Number of decimal digits of a positive integer.
+fn num_digits(c: &Integer) -> isize {
+    c.to_string_radix(10).len() as isize
+}
+
+/// Removes trailing zeros from `c * 10^e`.
+fn strip_zeros(mut c: Integer, mut e: isize) -> (Integer, isize) {
+    while !c.is_zero() && c.is_divisible_u(10) {
+        c /= 10;
+        e += 1;
+    }
+    (c, e)
+}
+
+/// Exact decimal value of a finite, non-zero number `|num| = c * 10^e`
+/// with no trailing zeros in `c`.
+fn exact_digits(num: &RFloat) -> (Integer, isize) {
+    let c = num.c().unwrap();
+    let exp = num.exp().unwrap();
+    if exp >= 0 {
+        strip_zeros(c << (exp as usize), 0)
+    } else {
+        // c * 2^exp = c * 5^-exp * 10^exp
+        let c = c * Integer::from(5).pow((-exp) as u32);
+        strip_zeros(c, exp)
+    }
+}
+
+/// Rounds `c * 10^e` to `k` significant digits.
+/// The result may have fewer than `k` digits, but never more.
+fn round_digits(sign: bool, c: Integer, e: isize, k: usize, rm: RoundingMode) -> (Integer, isize) {
+    let k = k as isize;
+    let digits = num_digits(&c);
+    if digits <= k {
+        return (c, e);
+    }
+
+    let scale = pow10(digits - k);
+    let (mut q, r) = c.div_rem(scale.clone());
+    if !r.is_zero() {
+        let (is_nearest, rd) = rm.to_direction(sign);
+        let increment = match (is_nearest, (r << 1u32).cmp(&scale)) {
+            (true, Ordering::Less) => false,
+            (true, Ordering::Greater) => true,
+            _ => match rd {
+                RoundingDirection::ToZero => false,
+                RoundingDirection::AwayZero => true,
+                RoundingDirection::ToEven => q.is_odd(),
+                RoundingDirection::ToOdd => q.is_even(),
+            },
+        };
+
+        if increment {
+            q += 1;
+        }
+    }
+
+    // carry may produce an extra digit
+    if q == pow10(k) {
+        (Integer::from(1), e + digits)
+    } else {
+        (q, e + digits - k)
+    }
+}
+
+/// Shortest decimal value `|num| = c * 10^e` that rounds back to `num`
+/// under the rounding context `ctx`.
+fn shortest_digits<Ctx: RationalContext>(num: &Ctx::Format, ctx: &Ctx) -> (Integer, isize) {
+    let x = RFloat::from_number(num);
+    let s = x.sign().unwrap();
+    let (c, e) = exact_digits(&x);
+    let digits = num_digits(&c);
+
+    // does `(-1)^s * c * 10^e` round back to `x`?
+    let round_trips = |c: &Integer, e: isize| {
+        let mut q = if e >= 0 {
+            Rational::from(c * pow10(e))
+        } else {
+            Rational::from((c.clone(), pow10(-e)))
+        };
+        if s {
+            q = -q;
+        }
+        RFloat::from_number(&round_rational(ctx, &q)) == x
+    };
+
+    // try every number of significant digits: if any `k` digit
+    // decimal rounds back to `x`, either the one just below or just
+    // above `x` does since the rounding interval is convex.
+    for k in 1..digits {
+        let scale = pow10(digits - k);
+        let ek = e + digits - k;
+        let lo = Integer::from(&c / &scale);
+        let hi = Integer::from(&lo + 1);
+
+        let candidate = match (round_trips(&lo, ek), round_trips(&hi, ek)) {
+            (false, false) => continue,
+            (true, false) => lo,
+            (false, true) => hi,
+            (true, true) => {
+                // choose the closer one, breaking ties to even
+                let lo_dist = Integer::from(&c - &lo * &scale);
+                let hi_dist = Integer::from(&hi * &scale - &c);
+                match lo_dist.cmp(&hi_dist) {
+                    Ordering::Less => lo,
+                    Ordering::Greater => hi,
+                    Ordering::Equal if lo.is_even() => lo,
+                    Ordering::Equal => hi,
+                }
+            }
+        };
+
+        return strip_zeros(candidate, ek);
+    }
+
+    // the exact value always rounds back to itself
+    (c, e)
+}
+
+/// Formats `(-1)^s * c * 10^e` as a string.
+fn format_digits(sign: bool, c: &Integer, e: isize) -> String {
+    let digits = c.to_string_radix(10);
+    let len = digits.len() as isize;
+    let sci_e = e + len - 1;
+
+    let mut s = String::new();
+    if sign {
+        s.push('-');
+    }
+
+    if (MIN_POSITIONAL..MAX_POSITIONAL).contains(&sci_e) {
+        // positional notation
+        if e >= 0 {
+            s.push_str(&digits);
+            s.push_str(&"0".repeat(e as usize));
+        } else if len + e > 0 {
+            let (int, frac) = digits.split_at((len + e) as usize);
+            s.push_str(int);
+            s.push('.');
+            s.push_str(frac);
+        } else {
+            s.push_str("0.");
+            s.push_str(&"0".repeat((-(len + e)) as usize));
+            s.push_str(&digits);
+        }
+    } else {
+        // scientific notation
+        let (lead, rest) = digits.split_at(1);
+        s.push_str(lead);
+        if !rest.is_empty() {
+            s.push('.');
+            s.push_str(rest);
+        }
+        s.push('e');
+        s.push_str(&sci_e.to_string());
+    }
+
+    s
+}
+
+/// Formats a special value, or `None` if `num` is finite and non-zero.
+fn format_special<T: Real>(num: &T, nar: &str) -> Option<String> {
+    if num.is_infinite() {
+        if num.sign().unwrap_or(false) {
+            Some("-inf".to_string())
+        } else {
+            Some("inf".to_string())
+        }
+    } else if num.is_nar() {
+        Some(nar.to_string())
+    } else if num.is_zero() {
+        if num.sign().unwrap_or(false) {
+            Some("-0".to_string())
+        } else {
+            Some("0".to_string())
+        }
+    } else {
+        None
+    }
+}
+
+/// Formats a finite, non-zero number according to `mode`
+/// where [`DecimalMode::Shortest`] is handled by `shortest`.
+fn format_decimal<T: Real>(
+    num: &T,
+    mode: DecimalMode,
+    shortest: impl FnOnce() -> (Integer, isize),
+) -> String {
+    let x = RFloat::from_number(num);
+    let s = x.sign().unwrap();
+    match mode {
+        DecimalMode::Shortest => {
+            let (c, e) = shortest();
+            format_digits(s, &c, e)
+        }
+        DecimalMode::Exact => {
+            let (c, e) = exact_digits(&x);
+            format_digits(s, &c, e)
+        }
+        DecimalMode::Digits(k, rm) => {
+            assert!(k > 0, "must print at least one digit");
+            let (c, e) = exact_digits(&x);
+            let (c, e) = round_digits(s, c, e, k, rm);
+
+            // pad to exactly `k` digits
+            let pad = k as isize - num_digits(&c);
+            let c = c * pow10(pad);
+            format_digits(s, &c, e - pad)
+        }
+    }
+}
+
+impl DecimalDisplay for RFloat {
+    fn to_decimal_string(&self, mode: DecimalMode) -> String {
+        match format_special(self, "NaN") {
+            Some(s) => s,
+            None => format_decimal(self, mode, || exact_digits(self)),
+        }
+    }
+}
+
+impl fmt::Display for RFloat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.to_decimal_string(DecimalMode::Exact))
+    }
+}
+
+macro_rules! decimal_display_impl {
+    ($format:ty, $nar:expr) => {
+        impl DecimalDisplay for $format {
+            fn to_decimal_string(&self, mode: DecimalMode) -> String {
+                match format_special(self, $nar) {
+                    Some(s) => s,
+                    None => format_decimal(self, mode, || shortest_digits(self, self.ctx())),
+                }
+            }
+        }
+
+        impl fmt::Display for $format {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str(&self.to_decimal_string(DecimalMode::Shortest))
+            }
+        }
+    };
+}
+
+decimal_display_impl!(Float, "NaN");
+decimal_display_impl!(IEEE754, "NaN");
+decimal_display_impl!(Fixed, "NaN");
+decimal_display_impl!(Posit, "NaR");
+
+/// Hexadecimal formatting of numbers in the style of C99 `%a`.
+pub trait HexDisplay: Real {
+    /// Formats this number exactly as a hexadecimal string,
+    /// e.g., `-0x1.8p-3`.
+    fn to_hex_string(&self) -> String;
+}
+
+/// Formats a number as a hexadecimal string.
+/// NaN values are formatted as `nan`.
+fn format_hex<T: Real>(num: &T, nan: &str) -> String {
+    let sign = if num.sign().unwrap_or(false) { "-" } else { "" };
+    if num.is_infinite() {
+        format!("{}inf", sign)
+    } else if num.is_nar() {
+        nan.to_string()
+    } else if num.is_zero() {
+        format!("{}0x0p+0", sign)
+    } else {
+        // normalize as `1.f * 2^e`
+        let c = num.c().unwrap();
+        let e = num.e().unwrap();
+        let fbits = c.significant_bits() - 1;
+        let frac = c.keep_bits(fbits);
+
+        // pad the fraction to a multiple of 4 bits
+        let pad = (4 - fbits % 4) % 4;
+        let width = ((fbits + pad) / 4) as usize;
+        let frac = frac << pad;
+        let digits = format!("{:0width$x}", frac, width = width);
+        let digits = digits.trim_end_matches('0');
+
+        if digits.is_empty() {
+            format!("{}0x1p{:+}", sign, e)
+        } else {
+            format!("{}0x1.{}p{:+}", sign, digits, e)
+        }
+    }
+}
+
+impl HexDisplay for IEEE754 {
+    fn to_hex_string(&self) -> String {
+        match &self.num {
+            IEEE754Val::Nan(s, quiet, payload) => {
+                let sign = if *s { "-" } else { "" };
+                let name = if *quiet { "nan" } else { "snan" };
+                if *quiet && payload.is_zero() {
+                    format!("{}{}", sign, name)
+                } else {
+                    format!("{}{}(0x{:x})", sign, name, payload)
+                }
+            }
+            _ => format_hex(self, "nan"),
+        }
+    }
+}
+
+macro_rules! hex_display_impl {
+    ($format:ty) => {
+        impl HexDisplay for $format {
+            fn to_hex_string(&self) -> String {
+                format_hex(self, "nan")
+            }
+        }
+    };
+}
+
+hex_display_impl!(RFloat);
+hex_display_impl!(Float);
+hex_display_impl!(Fixed);
+hex_display_impl!(Posit);
+
+macro_rules! hex_fmt_impl {
+    ($format:ty) => {
+        impl fmt::LowerHex for $format {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str(&self.to_hex_string())
+            }
+        }
+
+        impl fmt::UpperHex for $format {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str(&self.to_hex_string().to_uppercase())
+            }
+        }
+    };
+}
+
+hex_fmt_impl!(RFloat);
+hex_fmt_impl!(Float);
+hex_fmt_impl!(IEEE754);
+hex_fmt_impl!(Fixed);
+hex_fmt_impl!(Posit);
@@ -8,4 +8,4 @@ mod ops;
 mod round;
 
 pub use number::{Exceptions, Fixed};
-pub use round::{FixedContext, Overflow};
+pub use round::{FixedContext, Overflow, OverflowError};
@@ -2,8 +2,9 @@ use std::cmp::Ordering;
 
 use rug::Integer;
 
+use crate::compare::compare;
 use crate::fixed::FixedContext;
-use crate::{rfloat::RFloat, Real};
+use crate::{rfloat::RFloat, Predicate, Real, RoundingMode};
 
 /// Exception flags to signal certain properties of the rounded result.
 ///
@@ -54,6 +55,47 @@ impl Fixed {
     pub fn ctx(&self) -> &FixedContext {
         &self.ctx
     }
+
+    /// Evaluates the comparison predicate `pred` on this [`Fixed`]
+    /// and `other`, returning the result and any exceptions raised.
+    /// Fixed-point values are always ordered, so comparisons never
+    /// signal an invalid operation (see [`Predicate`]).
+    pub fn compare(&self, other: &Fixed, pred: Predicate) -> (bool, Exceptions) {
+        let (result, invalid) = compare(self, other, pred);
+        let flags = Exceptions {
+            invalid,
+            ..Default::default()
+        };
+        (result, flags)
+    }
+
+    /// Converts this [`Fixed`] to the nearest [`f64`] value
+    /// according to the rounding mode `rm`.
+    pub fn to_f64(&self, rm: RoundingMode) -> f64 {
+        RFloat::from(self.clone()).to_f64(rm)
+    }
+
+    /// Converts this [`Fixed`] to the nearest [`f32`] value
+    /// according to the rounding mode `rm`.
+    pub fn to_f32(&self, rm: RoundingMode) -> f32 {
+        RFloat::from(self.clone()).to_f32(rm)
+    }
+
+    /// Converts this [`Fixed`] to an [`Integer`] representing
+    /// a fixed-point bitpattern: an `nbits`-bit two's complement integer
+    /// if the format is signed, and an `nbits`-bit unsigned integer
+    /// otherwise (see [`FixedContext::bits_to_number`]).
+    pub fn into_bits(self) -> Integer {
+        match self.num {
+            RFloat::Real(s, exp, c) => {
+                // scale the significand to units of `2^scale`
+                let c = c << (exp - self.ctx.scale);
+                let m = if s { -c } else { c };
+                m.keep_bits(self.ctx.nbits as u32)
+            }
+            _ => Integer::from(0),
+        }
+    }
 }
 
 impl Real for Fixed {
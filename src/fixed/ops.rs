@@ -1,21 +1,31 @@
-use crate::fixed::FixedContext;
+use crate::fixed::{Fixed, FixedContext, Overflow};
 use crate::mpfr::*;
 use crate::ops::*;
 use crate::rfloat::RFloat;
-use crate::{Real, RoundingContext};
+use crate::stochastic::guard_bits;
+use crate::{Real, RoundingContext, RoundingMode};
+
+impl FixedContext {
+    /// Rounds the result of an MPFR computation, signaling an invalid
+    /// operation if MPFR does or if an overflow is trapped.
+    fn round_mpfr(&self, result: &MPFRResult) -> Fixed {
+        let mut rounded = self.round(result.num());
+        let trapped = rounded.flags.overflow && matches!(self.overflow, Overflow::Trap);
+        rounded.flags.invalid = result.flags().invalid || trapped;
+        rounded
+    }
+}
 
 macro_rules! rounded_1ary_impl {
     ($tname:ident, $name:ident, $mpfr:ident) => {
         impl $tname for FixedContext {
             fn $name<N: Real>(&self, src: &N) -> Self::Format {
                 // compute approximately, rounding-to-odd,
-                // with 2 rounding bits
-                let p = self.nbits + 2;
+                // with additional rounding bits
+                let p = self.nbits + guard_bits(self.stochastic_rounding());
                 let r = RFloat::from_number(src);
                 let result = $mpfr(r, p);
-                let mut rounded = self.round(result.num());
-                rounded.flags.invalid = result.flags().invalid;
-                rounded
+                self.round_mpfr(&result)
             }
         }
     };
@@ -29,6 +39,7 @@ rounded_1ary_impl!(RoundedRecip, recip, mpfr_recip);
 rounded_1ary_impl!(RoundedRecipSqrt, recip_sqrt, mpfr_recip_sqrt);
 rounded_1ary_impl!(RoundedExp, exp, mpfr_exp);
 rounded_1ary_impl!(RoundedExp2, exp2, mpfr_exp2);
+rounded_1ary_impl!(RoundedExp10, exp10, mpfr_exp10);
 rounded_1ary_impl!(RoundedLog, log, mpfr_log);
 rounded_1ary_impl!(RoundedLog2, log2, mpfr_log2);
 rounded_1ary_impl!(RoundedLog10, log10, mpfr_log10);
@@ -47,6 +58,9 @@ rounded_1ary_impl!(RoundedTanPi, tan_pi, mpfr_tan_pi);
 rounded_1ary_impl!(RoundedAsin, asin, mpfr_asin);
 rounded_1ary_impl!(RoundedAcos, acos, mpfr_acos);
 rounded_1ary_impl!(RoundedAtan, atan, mpfr_atan);
+rounded_1ary_impl!(RoundedAsinPi, asin_pi, mpfr_asin_pi);
+rounded_1ary_impl!(RoundedAcosPi, acos_pi, mpfr_acos_pi);
+rounded_1ary_impl!(RoundedAtanPi, atan_pi, mpfr_atan_pi);
 rounded_1ary_impl!(RoundedSinh, sinh, mpfr_sinh);
 rounded_1ary_impl!(RoundedCosh, cosh, mpfr_cosh);
 rounded_1ary_impl!(RoundedTanh, tanh, mpfr_tanh);
@@ -58,6 +72,25 @@ rounded_1ary_impl!(RoundedErfc, erfc, mpfr_erfc);
 rounded_1ary_impl!(RoundedGamma, tgamma, mpfr_tgamma);
 rounded_1ary_impl!(RoundedLgamma, lgamma, mpfr_lgamma);
 
+macro_rules! rounded_1ary_int_impl {
+    ($tname:ident, $name:ident, $mpfr:ident) => {
+        impl $tname for FixedContext {
+            fn $name<N: Real>(&self, src: &N, n: i64) -> Self::Format {
+                // compute approximately, rounding-to-odd,
+                // with additional rounding bits
+                let p = self.nbits + guard_bits(self.stochastic_rounding());
+                let r = RFloat::from_number(src);
+                let result = $mpfr(r, n, p);
+                self.round_mpfr(&result)
+            }
+        }
+    };
+}
+
+rounded_1ary_int_impl!(RoundedCompound, compound, mpfr_compound);
+rounded_1ary_int_impl!(RoundedRootn, rootn, mpfr_rootn);
+rounded_1ary_int_impl!(RoundedPown, pown, mpfr_pown);
+
 macro_rules! rounded_2ary_impl {
     ($tname:ident, $name:ident, $mpfr:ident) => {
         impl $tname for FixedContext {
@@ -67,14 +100,12 @@ macro_rules! rounded_2ary_impl {
                 N2: Real,
             {
                 // compute approximately, rounding-to-odd,
-                // with 2 rounding bits
-                let p = self.nbits + 2;
+                // with additional rounding bits
+                let p = self.nbits + guard_bits(self.stochastic_rounding());
                 let r1 = RFloat::from_number(src1);
                 let r2 = RFloat::from_number(src2);
                 let result = $mpfr(r1, r2, p);
-                let mut rounded = self.round(result.num());
-                rounded.flags.invalid = result.flags().invalid;
-                rounded
+                self.round_mpfr(&result)
             }
         }
     };
@@ -85,10 +116,13 @@ rounded_2ary_impl!(RoundedSub, sub, mpfr_sub);
 rounded_2ary_impl!(RoundedMul, mul, mpfr_mul);
 rounded_2ary_impl!(RoundedDiv, div, mpfr_div);
 rounded_2ary_impl!(RoundedPow, pow, mpfr_pow);
+rounded_2ary_impl!(RoundedPowr, powr, mpfr_powr);
 rounded_2ary_impl!(RoundedHypot, hypot, mpfr_hypot);
 rounded_2ary_impl!(RoundedFmod, fmod, mpfr_fmod);
 rounded_2ary_impl!(RoundedRemainder, remainder, mpfr_remainder);
 rounded_2ary_impl!(RoundedAtan2, atan2, mpfr_atan2);
+rounded_2ary_impl!(RoundedAtan2Pi, atan2_pi, mpfr_atan2_pi);
+rounded_2ary_impl!(RoundedFdim, fdim, mpfr_fdim);
 
 macro_rules! rounded_3ary_impl {
     ($tname:ident, $name:ident, $mpfr:ident) => {
@@ -100,18 +134,149 @@ macro_rules! rounded_3ary_impl {
                 N3: Real,
             {
                 // compute approximately, rounding-to-odd,
-                // with 2 rounding bits
-                let p = self.nbits + 2;
+                // with additional rounding bits
+                let p = self.nbits + guard_bits(self.stochastic_rounding());
                 let r1 = RFloat::from_number(src1);
                 let r2 = RFloat::from_number(src2);
                 let r3 = RFloat::from_number(src3);
                 let result = $mpfr(r1, r2, r3, p);
-                let mut rounded = self.round(result.num());
-                rounded.flags.invalid = result.flags().invalid;
-                rounded
+                self.round_mpfr(&result)
             }
         }
     };
 }
 
 rounded_3ary_impl!(RoundedFMA, fma, mpfr_fma);
+
+impl RoundedSinCos for FixedContext {
+    fn sin_cos<N: Real>(&self, src: &N) -> (Self::Format, Self::Format) {
+        // compute approximately, rounding-to-odd,
+        // with additional rounding bits
+        let p = self.nbits + guard_bits(self.stochastic_rounding());
+        let r = RFloat::from_number(src);
+        let (sin, cos) = mpfr_sin_cos(r, p);
+        (self.round_mpfr(&sin), self.round_mpfr(&cos))
+    }
+}
+
+impl RoundedRemquo for FixedContext {
+    fn remquo<N1, N2>(&self, src1: &N1, src2: &N2) -> (Self::Format, i64)
+    where
+        N1: Real,
+        N2: Real,
+    {
+        // compute approximately, rounding-to-odd,
+        // with additional rounding bits
+        let p = self.nbits + guard_bits(self.stochastic_rounding());
+        let r1 = RFloat::from_number(src1);
+        let r2 = RFloat::from_number(src2);
+        let (result, q) = mpfr_remquo(r1, r2, p);
+        (self.round_mpfr(&result), q)
+    }
+}
+
+impl RoundedFrexp for FixedContext {
+    fn frexp<N: Real>(&self, src: &N) -> (Self::Format, isize) {
+        // splitting is exact
+        let (m, e) = frexp_exact(src);
+        (self.round(&m), e)
+    }
+}
+
+impl RoundedModf for FixedContext {
+    fn modf<N: Real>(&self, src: &N) -> (Self::Format, Self::Format) {
+        // splitting is exact
+        let (frac, int) = modf_exact(src);
+        (self.round(&frac), self.round(&int))
+    }
+}
+
+macro_rules! rounded_integral_impl {
+    ($tname:ident, $name:ident, $rm:expr) => {
+        impl $tname for FixedContext {
+            fn $name<N: Real>(&self, src: &N) -> Self::Format {
+                let (integral, _) = round_integral(src, $rm);
+                self.round(&integral)
+            }
+        }
+    };
+}
+
+rounded_integral_impl!(RoundedFloor, floor, RoundingMode::ToNegative);
+rounded_integral_impl!(RoundedCeil, ceil, RoundingMode::ToPositive);
+rounded_integral_impl!(RoundedTrunc, trunc, RoundingMode::ToZero);
+rounded_integral_impl!(
+    RoundedRoundAway,
+    round_away,
+    RoundingMode::NearestTiesAwayZero
+);
+rounded_integral_impl!(
+    RoundedRoundEven,
+    round_even,
+    RoundingMode::NearestTiesToEven
+);
+
+impl RoundedNearbyInt for FixedContext {
+    fn nearbyint<N: Real>(&self, src: &N) -> Self::Format {
+        let (integral, _) = round_integral(src, self.rm);
+        self.round(&integral)
+    }
+}
+
+impl RoundedRint for FixedContext {
+    fn rint<N: Real>(&self, src: &N) -> Self::Format {
+        let (integral, inexact) = round_integral(src, self.rm);
+        let mut rounded = self.round(&integral);
+        rounded.flags.inexact |= inexact;
+        rounded
+    }
+}
+
+impl RoundedConvertToInteger for FixedContext {
+    fn convert_to_integer<N: Real>(&self, src: &N, rm: RoundingMode, exact: bool) -> Self::Format {
+        // NaN is invalid, and the overflow behavior of this context
+        // determines the result of out of range integers
+        let (integral, inexact) = round_integral(src, rm);
+        let mut rounded = self.round(&integral);
+        rounded.flags.inexact = exact && (inexact || rounded.flags.inexact);
+        rounded
+    }
+}
+
+macro_rules! min_max_impl {
+    ($tname:ident, $name:ident, $op:expr) => {
+        impl $tname for FixedContext {
+            fn $name<N1, N2>(&self, src1: &N1, src2: &N2) -> Self::Format
+            where
+                N1: Real,
+                N2: Real,
+            {
+                let (result, invalid) = min_max(src1, src2, $op);
+                let mut rounded = match result {
+                    MinMaxResult::First => self.round(src1),
+                    MinMaxResult::Second => self.round(src2),
+                    MinMaxResult::Nan => self.round(&RFloat::Nan),
+                };
+                rounded.flags.invalid |= invalid;
+                rounded
+            }
+        }
+    };
+}
+
+min_max_impl!(RoundedMinimum, minimum, MinMax::Minimum);
+min_max_impl!(RoundedMaximum, maximum, MinMax::Maximum);
+min_max_impl!(RoundedMinimumNumber, minimum_number, MinMax::MinimumNumber);
+min_max_impl!(RoundedMaximumNumber, maximum_number, MinMax::MaximumNumber);
+min_max_impl!(
+    RoundedMinimumMagnitude,
+    minimum_magnitude,
+    MinMax::MinimumMagnitude
+);
+min_max_impl!(
+    RoundedMaximumMagnitude,
+    maximum_magnitude,
+    MinMax::MaximumMagnitude
+);
+min_max_impl!(RoundedMinNum, min_num, MinMax::MinNum);
+min_max_impl!(RoundedMaxNum, max_num, MinMax::MaxNum);
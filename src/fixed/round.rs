@@ -1,10 +1,11 @@
-use std::ops::Rem;
-
 use rug::Integer;
 
 use crate::fixed::{Exceptions, Fixed};
+use crate::rational::{number_to_odd, RationalContext};
 use crate::rfloat::{RFloat, RFloatContext};
-use crate::{Real, RoundingContext, RoundingMode, Split};
+use crate::stochastic::guard_bits;
+use crate::util::bitmask;
+use crate::{Real, RoundingContext, RoundingMode, Split, StochasticRounding};
 
 /// Fixed-point overflow behavior.
 ///
@@ -14,6 +15,13 @@ use crate::{Real, RoundingContext, RoundingMode, Split};
 /// preserving only the least significant bits of the implementation.
 /// Alternatively, the value could be clamped to the largest representable
 /// value in the representation, preserving the sign.
+///
+/// These modes correspond to the overflow modes of Vivado HLS `ap_fixed`
+/// and SystemC `sc_fixed`: `AP_WRAP` ([`Overflow::Wrap`]),
+/// `AP_WRAP_SM` ([`Overflow::WrapSignMagnitude`]),
+/// `AP_SAT` ([`Overflow::Saturate`]),
+/// `AP_SAT_SYM` ([`Overflow::SaturateSymmetric`]),
+/// and `AP_SAT_ZERO` ([`Overflow::SaturateZero`]).
 #[derive(Clone, Debug)]
 pub enum Overflow {
     /// Values that overflow the format should be wrapped, the least
@@ -22,8 +30,49 @@ pub enum Overflow {
     /// Clamp the representation to the largest representable value
     /// in the representation, preserving the sign.
     Saturate,
+    /// Clamp the representation to the largest representable value
+    /// in the representation, preserving the sign, but restricting the
+    /// range of signed formats to be symmetric: the minimum value
+    /// of a signed format is the negation of its maximum value.
+    SaturateSymmetric,
+    /// Values that overflow the format are set to zero.
+    SaturateZero,
+    /// Values that overflow the format are wrapped in sign-magnitude
+    /// fashion: the least significant bits are preserved, the sign bit
+    /// is the least significant discarded bit, and the remaining bits are
+    /// inverted if the sign changed. Unsigned formats wrap as with
+    /// [`Overflow::Wrap`].
+    WrapSignMagnitude,
+    /// Values that overflow the format are an error. Only
+    /// [`FixedContext::try_round`] traps, reporting an [`OverflowError`].
+    /// Elsewhere, e.g., [`RoundingContext::round`] or any operation,
+    /// the value saturates as with [`Overflow::Saturate`] and signals
+    /// both overflow and an invalid operation.
+    Trap,
+}
+
+/// An error indicating that a value overflowed a fixed-point format
+/// whose overflow behavior is [`Overflow::Trap`].
+#[derive(Clone, Debug)]
+pub struct OverflowError {
+    num: RFloat,
+}
+
+impl OverflowError {
+    /// Returns the rounded value (with unbounded range) that overflowed.
+    pub fn value(&self) -> &RFloat {
+        &self.num
+    }
+}
+
+impl std::fmt::Display for OverflowError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "fixed-point overflow: {:?}", self.num)
+    }
 }
 
+impl std::error::Error for OverflowError {}
+
 /// Rounding contexts for fixed-point numbers.
 ///
 /// The associated storage type is [`Fixed`].
@@ -38,18 +87,29 @@ pub enum Overflow {
 ///  - scale factor (position of least-significant digit),
 ///  - total bitwidth of the encoding,
 ///  - rounding mode,
+///  - (optional) stochastic rounding (see [`StochasticRounding`]),
 ///  - overflow behavior.
 ///
 /// By default, the rounding mode is [`RoundingMode::ToZero`], and
 /// the overflow handling is [`Overflow::Wrap`].
 /// See [`Overflow`] for supported overflow behavior.
 ///
+/// The quantization modes of Vivado HLS `ap_fixed` correspond to
+/// rounding modes: `AP_RND` is [`RoundingMode::NearestTiesToPositive`],
+/// `AP_RND_ZERO` is [`RoundingMode::NearestTiesToZero`],
+/// `AP_RND_MIN_INF` is [`RoundingMode::NearestTiesToNegative`],
+/// `AP_RND_INF` is [`RoundingMode::NearestTiesAwayZero`],
+/// `AP_RND_CONV` (convergent rounding) is [`RoundingMode::NearestTiesToEven`],
+/// `AP_TRN` is [`RoundingMode::ToNegative`], and `AP_TRN_ZERO`
+/// is [`RoundingMode::ToZero`].
+///
 #[derive(Clone, Debug)]
 pub struct FixedContext {
     pub(crate) signed: bool,
     pub(crate) scale: isize,
     pub(crate) nbits: usize,
     pub(crate) rm: RoundingMode,
+    pub(crate) sr: Option<StochasticRounding>,
     pub(crate) overflow: Overflow,
 }
 
@@ -57,7 +117,7 @@ impl FixedContext {
     /// Constructs new rounding context.
     /// The default rounding mode is truncation
     /// (see [`ToZero`][crate::RoundingMode]). The default overflow
-    /// behavior is saturation (see [`Wrap`][Overflow]).
+    /// behavior is wrapping (see [`Wrap`][Overflow]).
     pub fn new(signed: bool, scale: isize, nbits: usize) -> Self {
         assert!(
             (signed && nbits >= 2) || (!signed && nbits >= 1),
@@ -69,6 +129,7 @@ impl FixedContext {
             scale,
             nbits,
             rm: RoundingMode::ToZero,
+            sr: None,
             overflow: Overflow::Wrap,
         }
     }
@@ -79,6 +140,19 @@ impl FixedContext {
         self
     }
 
+    /// Sets stochastic rounding for this [`FixedContext`],
+    /// overriding the rounding mode.
+    pub fn with_stochastic_rounding(mut self, sr: StochasticRounding) -> Self {
+        self.sr = Some(sr);
+        self
+    }
+
+    /// Returns the stochastic rounding configuration of this
+    /// [`FixedContext`], if any.
+    pub fn stochastic_rounding(&self) -> Option<&StochasticRounding> {
+        self.sr.as_ref()
+    }
+
     /// Sets the overflow behavior of this [`FixedContext`].
     pub fn with_overflow(mut self, overflow: Overflow) -> Self {
         self.overflow = overflow;
@@ -135,6 +209,29 @@ impl FixedContext {
         }
     }
 
+    /// Converts an [`Integer`] representing a fixed-point bitpattern into
+    /// a [`Fixed`] value under this [`FixedContext`]. The bitpattern is
+    /// an `nbits`-bit two's complement integer if the format is signed,
+    /// and an `nbits`-bit unsigned integer otherwise, representing
+    /// a multiple of `2^scale`.
+    pub fn bits_to_number(&self, b: Integer) -> Fixed {
+        let limit = Integer::from(1) << self.nbits;
+        assert!(b >= 0 && b < limit, "must be in [0, 1 << nbits)");
+
+        // decode two's complement
+        let m = if self.signed && b.get_bit((self.nbits - 1) as u32) {
+            b - limit
+        } else {
+            b
+        };
+
+        Fixed {
+            num: RFloat::Real(m.is_negative(), self.scale, m.abs()).canonicalize(),
+            flags: Default::default(),
+            ctx: self.clone(),
+        }
+    }
+
     /// Constructs the smallest representable difference in
     /// the format described by [`FixedContext`].
     pub fn quantum(&self) -> Fixed {
@@ -149,53 +246,157 @@ impl FixedContext {
 impl FixedContext {
     fn round_wrap(&self, val: RFloat) -> RFloat {
         let offset = val.exp().unwrap() - self.scale;
-        let div = Integer::from(1) << self.nbits;
         let c = val.c().unwrap() << offset;
 
         if self.signed {
             // signed wrapping is harder:
-            // `((v + 2^(w - 1)) % 2^w) - 2^(w - 1))`
+            // `((v + 2^(w - 1)) mod 2^w) - 2^(w - 1))`
             let shift = Integer::from(1) << (self.nbits - 1);
             let m = if val.sign().unwrap() { -c } else { c };
-            let wrapped = (m + shift.clone()).rem(div) - shift;
+            let wrapped = (m + &shift).keep_bits(self.nbits as u32) - shift;
             RFloat::Real(wrapped.is_negative(), self.scale, wrapped.abs())
         } else {
-            // unsigned wrapping is just `(v % 2^w)`
-            let wrapped = c.rem(div);
+            // unsigned wrapping is just `(v mod 2^w)`
+            let m = if val.sign().unwrap() { -c } else { c };
+            let wrapped = m.keep_bits(self.nbits as u32);
             RFloat::Real(false, self.scale, wrapped)
         }
     }
-}
 
-impl RoundingContext for FixedContext {
-    type Format = Fixed;
+    fn round_wrap_sm(&self, val: RFloat) -> RFloat {
+        if !self.signed {
+            // unsigned sign-magnitude wrapping is just wrapping
+            return self.round_wrap(val);
+        }
+
+        let offset = val.exp().unwrap() - self.scale;
+        let c = val.c().unwrap() << offset;
+        let s = val.sign().unwrap();
+        let m = if s { -c } else { c };
+
+        // the new sign is the least significant discarded bit
+        // of the two's complement representation
+        let sign = m.get_bit(self.nbits as u32);
+        let mask = bitmask(self.nbits - 1);
+        let mut low = m & &mask;
+        if sign != s {
+            low ^= mask;
+        }
+
+        let wrapped = if sign {
+            low - (Integer::from(1) << (self.nbits - 1))
+        } else {
+            low
+        };
+        RFloat::Real(wrapped.is_negative(), self.scale, wrapped.abs())
+    }
+
+    /// The smallest value that does not overflow
+    /// under this [`FixedContext`].
+    fn lower_bound(&self) -> RFloat {
+        match (&self.overflow, self.signed) {
+            (Overflow::SaturateSymmetric, true) => {
+                let c = (Integer::from(1) << (self.nbits - 1)) - 1;
+                RFloat::Real(true, self.scale, c)
+            }
+            _ => self.minval().num,
+        }
+    }
+
+    /// Computes the result of a value that overflows this format.
+    /// If `trap` is set, trapped overflows are reported as an error.
+    fn round_overflow(
+        &self,
+        rounded: RFloat,
+        mut flags: Exceptions,
+        trap: bool,
+    ) -> Result<Fixed, OverflowError> {
+        let num = match self.overflow {
+            Overflow::Wrap => self.round_wrap(rounded),
+            Overflow::WrapSignMagnitude => self.round_wrap_sm(rounded),
+            Overflow::SaturateZero => RFloat::zero(),
+            Overflow::Trap if trap => return Err(OverflowError { num: rounded }),
+            Overflow::Saturate | Overflow::SaturateSymmetric | Overflow::Trap => {
+                // untrapped overflows are invalid
+                flags.invalid |= matches!(self.overflow, Overflow::Trap);
+                if rounded.is_negative().unwrap() {
+                    self.lower_bound()
+                } else {
+                    self.maxval().num
+                }
+            }
+        };
+
+        Ok(Fixed {
+            num,
+            flags,
+            ctx: self.clone(),
+        })
+    }
+
+    /// Rounds any [`Real`] value to a [`Fixed`] value, returning an
+    /// error if the value overflows and the overflow behavior is
+    /// [`Overflow::Trap`]. Otherwise, this is the same as
+    /// [`RoundingContext::round`].
+    pub fn try_round<T: Real>(&self, val: &T) -> Result<Fixed, OverflowError> {
+        self.round_with(val, true)
+    }
+
+    /// Rounds any [`Real`] value to a [`Fixed`] value.
+    /// If `trap` is set, trapped overflows are reported as an error.
+    fn round_with<T: Real>(&self, val: &T, trap: bool) -> Result<Fixed, OverflowError> {
+        if T::radix() == 10 {
+            // decimal values are rounded through their exact value
+            return self.round_with(&number_to_odd(self, val), trap);
+        }
 
-    fn round<T: Real>(&self, val: &T) -> Self::Format {
         // case split by class
         if val.is_zero() {
             // zero is always representable
-            Fixed {
+            Ok(Fixed {
                 num: RFloat::zero(),
                 flags: Default::default(),
                 ctx: self.clone(),
-            }
+            })
         } else if val.is_infinite() {
             // +Inf goes to MAX
             // -Inf goes to MIN
-            match val.sign() {
-                Some(true) => self.maxval(),
-                _ => self.minval(),
+            let flags = Exceptions {
+                overflow: true,
+                ..Default::default()
+            };
+
+            match self.overflow {
+                Overflow::Trap | Overflow::SaturateZero | Overflow::SaturateSymmetric => {
+                    let num = if val.sign().unwrap() {
+                        RFloat::NegInfinity
+                    } else {
+                        RFloat::PosInfinity
+                    };
+                    self.round_overflow(num, flags, trap)
+                }
+                _ => {
+                    let num = match val.sign() {
+                        Some(true) => self.minval().num,
+                        _ => self.maxval().num,
+                    };
+                    Ok(Fixed {
+                        num,
+                        flags,
+                        ctx: self.clone(),
+                    })
+                }
             }
         } else if val.is_nar() {
             // +/- NaN goes to 0
-            Fixed {
+            Ok(Fixed {
                 num: RFloat::zero(),
                 flags: Exceptions {
                     invalid: true,
                     ..Default::default()
                 },
                 ctx: self.clone(),
-            }
+            })
         } else {
             // step 1: rounding as a unbounded fixed-point number
             // so we need to compute the context parameters; we only set
@@ -212,18 +413,18 @@ impl RoundingContext for FixedContext {
             let inexact = !split.is_exact();
 
             // step 3: finalize (fixed point)
-            let rounded = RFloatContext::round_finalize(split, self.rm);
+            let rounded = RFloatContext::round_finalize_with(split, self.rm, self.sr.as_ref());
 
             // early exit if zero
             if rounded.is_zero() {
-                return Fixed {
+                return Ok(Fixed {
                     num: RFloat::zero(),
                     flags: Exceptions {
                         inexact,
                         ..Default::default()
                     },
                     ctx: self.clone(),
-                };
+                });
             }
 
             // double check rounding invariants
@@ -235,47 +436,55 @@ impl RoundingContext for FixedContext {
                 rounded
             );
 
-            // step 4: may need to round or saturate
-            let maxval = self.maxval();
-            let minval = self.minval();
-            if rounded > maxval.num {
-                // larger than the maxval
-                Fixed {
-                    num: match self.overflow {
-                        Overflow::Wrap => self.round_wrap(rounded),
-                        Overflow::Saturate => maxval.num,
-                    },
-                    flags: Exceptions {
-                        inexact,
-                        overflow: true,
-                        ..Default::default()
-                    },
-                    ctx: self.clone(),
-                }
-            } else if rounded < minval.num {
-                // smaller than the minval
-                Fixed {
-                    num: match self.overflow {
-                        Overflow::Wrap => self.round_wrap(rounded),
-                        Overflow::Saturate => minval.num,
-                    },
-                    flags: Exceptions {
-                        inexact,
-                        underflow: false,
-                        ..Default::default()
-                    },
-                    ctx: self.clone(),
-                }
+            // step 4: may need to wrap or saturate
+            if rounded > self.maxval().num || rounded < self.lower_bound() {
+                let flags = Exceptions {
+                    inexact,
+                    overflow: true,
+                    ..Default::default()
+                };
+                self.round_overflow(rounded, flags, trap)
             } else {
-                Fixed {
+                Ok(Fixed {
                     num: rounded,
                     flags: Exceptions {
                         inexact,
                         ..Default::default()
                     },
                     ctx: self.clone(),
-                }
+                })
             }
         }
     }
 }
+
+impl RoundingContext for FixedContext {
+    type Format = Fixed;
+
+    fn round<T: Real>(&self, val: &T) -> Self::Format {
+        match self.round_with(val, false) {
+            Ok(rounded) => rounded,
+            Err(_) => unreachable!(),
+        }
+    }
+}
+
+impl RationalContext for FixedContext {
+    fn rational_n(&self, _: isize) -> isize {
+        self.scale - 1
+    }
+
+    fn rational_guard_bits(&self) -> usize {
+        guard_bits(self.stochastic_rounding())
+    }
+
+    fn rational_e_bounds(&self) -> (Option<isize>, Option<isize>) {
+        // wrapping depends on the low-order digits of large values
+        let emin = self.scale - 1 - self.rational_guard_bits() as isize;
+        let emax = match self.overflow {
+            Overflow::Wrap | Overflow::WrapSignMagnitude => None,
+            _ => Some(self.scale + self.nbits as isize - 1),
+        };
+        (Some(emin), emax)
+    }
+}
@@ -1,7 +1,8 @@
 use rug::Integer;
 use std::cmp::Ordering;
 
-use crate::{rfloat::RFloat, Real};
+use crate::compare::compare;
+use crate::{rfloat::RFloat, Predicate, Real, RoundingMode};
 
 use super::FloatContext;
 
@@ -65,6 +66,32 @@ impl Float {
     pub fn ctx(&self) -> &FloatContext {
         &self.ctx
     }
+
+    /// Converts this [`Float`] to the nearest [`f64`] value
+    /// according to the rounding mode `rm`.
+    pub fn to_f64(&self, rm: RoundingMode) -> f64 {
+        RFloat::from(self.clone()).to_f64(rm)
+    }
+
+    /// Converts this [`Float`] to the nearest [`f32`] value
+    /// according to the rounding mode `rm`.
+    pub fn to_f32(&self, rm: RoundingMode) -> f32 {
+        RFloat::from(self.clone()).to_f32(rm)
+    }
+
+    /// Evaluates the comparison predicate `pred` on this [`Float`]
+    /// and `other`, returning the result and any exceptions raised.
+    /// There are no signaling NaNs, so only signaling predicates
+    /// signal an invalid operation when an operand is NaN
+    /// (see [`Predicate`]).
+    pub fn compare(&self, other: &Float, pred: Predicate) -> (bool, Exceptions) {
+        let (result, invalid) = compare(self, other, pred);
+        let flags = Exceptions {
+            invalid,
+            ..Default::default()
+        };
+        (result, flags)
+    }
 }
 
 impl Real for Float {
@@ -109,7 +136,7 @@ impl Real for Float {
     }
 
     fn is_infinite(&self) -> bool {
-        self.num.is_finite()
+        self.num.is_infinite()
     }
 
     fn is_zero(&self) -> bool {
@@ -1,6 +1,8 @@
 use crate::{
+    rational::{number_to_odd, RationalContext},
     rfloat::{RFloat, RFloatContext},
-    Real, RoundingContext, RoundingMode, Split,
+    stochastic::guard_bits,
+    Real, RoundingContext, RoundingMode, Split, StochasticRounding,
 };
 
 use super::{Exceptions, Float};
@@ -18,9 +20,12 @@ use super::{Exceptions, Float};
 /// A [`FloatContext`] is parameterized by
 ///
 ///  - maximum precision (see [`Real::prec`]),
-///  - rounding mode.
+///  - rounding mode,
+///  - (optional) stochastic rounding (see [`StochasticRounding`]).
 ///
 /// By default, the rounding mode is [`RoundingMode::NearestTiesToEven`].
+/// If stochastic rounding is specified, it takes precedence over
+/// the rounding mode.
 /// This rounding context is similar to the one implemented by
 /// [Rug](https://docs.rs/rug/latest/rug/) (MPFR).
 ///
@@ -28,6 +33,7 @@ use super::{Exceptions, Float};
 pub struct FloatContext {
     prec: usize,
     rm: RoundingMode,
+    sr: Option<StochasticRounding>,
 }
 
 impl FloatContext {
@@ -36,6 +42,7 @@ impl FloatContext {
         Self {
             prec,
             rm: RoundingMode::NearestTiesToEven,
+            sr: None,
         }
     }
 
@@ -51,6 +58,13 @@ impl FloatContext {
         self
     }
 
+    /// Sets stochastic rounding for this context,
+    /// overriding the rounding mode.
+    pub fn with_stochastic_rounding(mut self, sr: StochasticRounding) -> Self {
+        self.sr = Some(sr);
+        self
+    }
+
     /// Returns the maximum precision allowed by this format.
     pub fn max_p(&self) -> usize {
         self.prec
@@ -60,12 +74,22 @@ impl FloatContext {
     pub fn rm(&self) -> RoundingMode {
         self.rm
     }
+
+    /// Returns the stochastic rounding configuration of this context, if any.
+    pub fn stochastic_rounding(&self) -> Option<&StochasticRounding> {
+        self.sr.as_ref()
+    }
 }
 
 impl RoundingContext for FloatContext {
     type Format = Float;
 
     fn round<T: Real>(&self, val: &T) -> Self::Format {
+        if T::radix() == 10 {
+            // decimal values are rounded through their exact value
+            return self.round(&number_to_odd(self, val));
+        }
+
         // case split by class
         if val.is_zero() {
             Float {
@@ -109,7 +133,7 @@ impl RoundingContext for FloatContext {
             let unrounded_e = split.e();
 
             // step 4: finalize (unbounded exponent)
-            let rounded = RFloatContext::round_finalize(split, self.rm);
+            let rounded = RFloatContext::round_finalize_with(split, self.rm, self.sr.as_ref());
 
             // step 5: carry flag
             let carry = match (unrounded_e, rounded.e()) {
@@ -130,3 +154,13 @@ impl RoundingContext for FloatContext {
         }
     }
 }
+
+impl RationalContext for FloatContext {
+    fn rational_n(&self, e: isize) -> isize {
+        e - (self.prec as isize)
+    }
+
+    fn rational_guard_bits(&self) -> usize {
+        guard_bits(self.stochastic_rounding())
+    }
+}
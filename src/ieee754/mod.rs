@@ -3,11 +3,15 @@
 //! This module implements floating-point numbers with [`IEEE754Context`].
 //! The associated storage type is [`IEEE754`] which represents an
 //! IEEE 754 style floating-point number.
+//!
+//! Formats that reclaim some or all of the encodings for infinities
+//! and NaNs, e.g., the OCP 8-bit, 6-bit, and 4-bit formats used in
+//! machine learning, are also supported (see [`SpecialValues`]).
 
 mod number;
 pub mod ops;
 mod round;
 
 pub(crate) use number::IEEE754Val;
-pub use number::{Exceptions, IEEE754};
-pub use round::IEEE754Context;
+pub use number::{Exceptions, IEEE754Class, IEEE754};
+pub use round::{IEEE754Context, Overflow, SpecialValues};
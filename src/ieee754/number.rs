@@ -4,10 +4,11 @@ use std::ops::{BitAnd, BitOr};
 use num_traits::Zero;
 use rug::Integer;
 
-use crate::ieee754::IEEE754Context;
+use crate::compare::compare;
+use crate::ieee754::{IEEE754Context, SpecialValues};
 use crate::rfloat::RFloat;
 use crate::util::bitmask;
-use crate::Real;
+use crate::{Predicate, Real, RoundingContext, RoundingMode};
 
 /// Exception flags to signal certain properties of the rounded result.
 ///
@@ -111,6 +112,32 @@ pub enum IEEE754Val {
     Nan(bool, bool, Integer),
 }
 
+/// Classification of IEEE 754 floating-point values as described
+/// by the `class` operation in Section 5.7.2 of the IEEE 754 standard.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum IEEE754Class {
+    /// Signaling NaN
+    SignalingNaN,
+    /// Quiet NaN
+    QuietNaN,
+    /// Negative infinity
+    NegativeInfinity,
+    /// Negative normal number
+    NegativeNormal,
+    /// Negative subnormal number
+    NegativeSubnormal,
+    /// Negative zero
+    NegativeZero,
+    /// Positive zero
+    PositiveZero,
+    /// Positive subnormal number
+    PositiveSubnormal,
+    /// Positive normal number
+    PositiveNormal,
+    /// Positive infinity
+    PositiveInfinity,
+}
+
 /// The IEEE 754 floating-point format.
 ///
 /// The associated [`RoundingContext`][crate::RoundingContext]
@@ -171,6 +198,130 @@ impl IEEE754 {
         }
     }
 
+    /// Returns the class of this [`IEEE754`] value.
+    pub fn class(&self) -> IEEE754Class {
+        match &self.num {
+            IEEE754Val::PosZero => IEEE754Class::PositiveZero,
+            IEEE754Val::NegZero => IEEE754Class::NegativeZero,
+            IEEE754Val::Subnormal(false, _) => IEEE754Class::PositiveSubnormal,
+            IEEE754Val::Subnormal(true, _) => IEEE754Class::NegativeSubnormal,
+            IEEE754Val::Normal(false, _, _) => IEEE754Class::PositiveNormal,
+            IEEE754Val::Normal(true, _, _) => IEEE754Class::NegativeNormal,
+            IEEE754Val::PosInfinity => IEEE754Class::PositiveInfinity,
+            IEEE754Val::NegInfinity => IEEE754Class::NegativeInfinity,
+            IEEE754Val::Nan(_, true, _) => IEEE754Class::QuietNaN,
+            IEEE754Val::Nan(_, false, _) => IEEE754Class::SignalingNaN,
+        }
+    }
+
+    /// Returns true if this [`IEEE754`] value is canonical.
+    /// Every encoding of a binary format is canonical.
+    pub fn is_canonical(&self) -> bool {
+        true
+    }
+
+    /// Returns a copy of this [`IEEE754`] value with the sign bit set to `sign`.
+    /// This is a quiet operation: NaN payloads are preserved
+    /// and no exceptions are raised.
+    fn with_sign(&self, sign: bool) -> IEEE754 {
+        let num = match &self.num {
+            IEEE754Val::PosZero | IEEE754Val::NegZero => {
+                if sign {
+                    IEEE754Val::NegZero
+                } else {
+                    IEEE754Val::PosZero
+                }
+            }
+            IEEE754Val::Subnormal(_, c) => IEEE754Val::Subnormal(sign, c.clone()),
+            IEEE754Val::Normal(_, exp, c) => IEEE754Val::Normal(sign, *exp, c.clone()),
+            IEEE754Val::PosInfinity | IEEE754Val::NegInfinity => {
+                if sign {
+                    IEEE754Val::NegInfinity
+                } else {
+                    IEEE754Val::PosInfinity
+                }
+            }
+            IEEE754Val::Nan(_, q, payload) => IEEE754Val::Nan(sign, *q, payload.clone()),
+        };
+
+        IEEE754 {
+            num,
+            flags: Exceptions::default(),
+            ctx: self.ctx.clone(),
+        }
+    }
+
+    /// Implements the `negate` operation: returns this [`IEEE754`] value
+    /// with the opposite sign bit. Unlike [`RoundedNeg`][crate::ops::RoundedNeg],
+    /// NaN payloads are preserved and no exceptions are raised.
+    pub fn negate(&self) -> IEEE754 {
+        self.with_sign(!self.sign().unwrap())
+    }
+
+    /// Implements the `abs` operation: returns this [`IEEE754`] value
+    /// with the sign bit cleared. Unlike [`RoundedAbs`][crate::ops::RoundedAbs],
+    /// NaN payloads are preserved and no exceptions are raised.
+    pub fn abs(&self) -> IEEE754 {
+        self.with_sign(false)
+    }
+
+    /// Implements the `copySign` operation: returns this [`IEEE754`] value
+    /// with the sign bit of `other`. NaN payloads are preserved
+    /// and no exceptions are raised.
+    pub fn copy_sign(&self, other: &IEEE754) -> IEEE754 {
+        self.with_sign(other.sign().unwrap())
+    }
+
+    /// Implements the `totalOrder` predicate: returns true if this
+    /// [`IEEE754`] value is ordered at or before `other` in the
+    /// total ordering of the format.
+    ///
+    /// The ordering agrees with the numerical ordering of the values
+    /// except that `-0 < +0`, negative NaNs are ordered below every
+    /// other value and positive NaNs above, and NaNs are ordered
+    /// by their signaling bit and payload: signaling NaNs are ordered
+    /// closer to zero than quiet NaNs. Panics if the values are not
+    /// of the same format.
+    pub fn total_order(&self, other: &IEEE754) -> bool {
+        assert!(
+            self.ctx.es() == other.ctx.es()
+                && self.ctx.nbits() == other.ctx.nbits()
+                && self.ctx.specials() == other.ctx.specials(),
+            "values must be of the same format"
+        );
+
+        // the magnitude of the encoding is monotonic in the magnitude
+        // of the value, including NaNs with larger payloads
+        let mag1 = self.abs().into_bits();
+        let mag2 = other.abs().into_bits();
+        match (self.sign().unwrap(), other.sign().unwrap()) {
+            (false, false) => mag1 <= mag2,
+            (false, true) => false,
+            (true, false) => true,
+            (true, true) => mag1 >= mag2,
+        }
+    }
+
+    /// Implements the `totalOrderMag` predicate: like
+    /// [`IEEE754::total_order`], but compares the absolute values.
+    pub fn total_order_mag(&self, other: &IEEE754) -> bool {
+        self.abs().total_order(&other.abs())
+    }
+
+    /// Evaluates the comparison predicate `pred` on this [`IEEE754`]
+    /// value and `other`, returning the result and any exceptions raised.
+    /// Quiet predicates signal an invalid operation only for signaling
+    /// NaN operands, while signaling predicates signal an invalid
+    /// operation for any NaN operand (see [`Predicate`]).
+    pub fn compare(&self, other: &IEEE754, pred: Predicate) -> (bool, Exceptions) {
+        let (result, invalid) = compare(self, other, pred);
+        let flags = Exceptions {
+            invalid,
+            ..Default::default()
+        };
+        (result, flags)
+    }
+
     /// Converts this [`IEEE754`] to an [`Integer`] representing an IEEE 754 bitpattern.
     pub fn into_bits(self) -> Integer {
         let nbits = self.ctx.nbits();
@@ -179,9 +330,12 @@ impl IEEE754 {
             IEEE754Val::NegZero => (true, Integer::zero()),
             IEEE754Val::Subnormal(s, c) => (*s, c.clone()),
             IEEE754Val::Normal(s, exp, c) => {
+                // significand may not have full precision
                 let m = self.ctx().max_m();
-                let efield = Integer::from((exp + m as isize) + self.ctx().emax()) << m;
-                let mfield = c.clone().bitand(bitmask(m));
+                let shift = m + 1 - c.significant_bits() as usize;
+                let efield =
+                    Integer::from((exp - shift as isize + m as isize) + self.ctx().bias()) << m;
+                let mfield = (c.clone() << shift).bitand(bitmask(m));
                 (*s, mfield.bitor(efield))
             }
             IEEE754Val::PosInfinity => {
@@ -194,6 +348,10 @@ impl IEEE754 {
                 let efield = bitmask(self.ctx.es()) << m;
                 (true, efield)
             }
+            IEEE754Val::Nan(s, _, _) if self.ctx.specials() == SpecialValues::NanOnly => {
+                // only one NaN encoding: all ones
+                (*s, bitmask(nbits - 1))
+            }
             IEEE754Val::Nan(s, q, payload) => {
                 let m = self.ctx().max_m() as isize;
                 let efield = bitmask(self.ctx.es()) << m;
@@ -213,6 +371,52 @@ impl IEEE754 {
             unsigned
         }
     }
+
+    /// Converts this [`IEEE754`] to the nearest [`f64`] value
+    /// according to the rounding mode `rm`.
+    ///
+    /// Signed zeros are preserved. NaN values are always quieted
+    /// and their payloads are aligned to the most significant bit
+    /// of the [`f64`] payload, truncating if necessary, as is done
+    /// by most hardware implementations.
+    pub fn to_f64(&self, rm: RoundingMode) -> f64 {
+        let ctx = IEEE754Context::binary64().with_rounding_mode(rm);
+        let b = self.convert_native(&ctx).into_bits();
+        f64::from_bits(b.to_u64().unwrap())
+    }
+
+    /// Converts this [`IEEE754`] to the nearest [`f32`] value
+    /// according to the rounding mode `rm`.
+    /// See [`IEEE754::to_f64`] for the treatment of special values.
+    pub fn to_f32(&self, rm: RoundingMode) -> f32 {
+        let ctx = IEEE754Context::binary32().with_rounding_mode(rm);
+        let b = self.convert_native(&ctx).into_bits();
+        f32::from_bits(b.to_u32().unwrap())
+    }
+
+    /// Rounds this [`IEEE754`] value under `ctx`, carrying over
+    /// the sign and payload of NaN values.
+    pub(crate) fn convert_native(&self, ctx: &IEEE754Context) -> IEEE754 {
+        match &self.num {
+            IEEE754Val::Nan(s, _, payload) => {
+                // payload is aligned below the quiet bit
+                let src_m = self.ctx.max_m() as isize;
+                let dst_m = ctx.max_m() as isize;
+                let payload = if dst_m >= src_m {
+                    payload.clone() << (dst_m - src_m)
+                } else {
+                    payload.clone() >> (src_m - dst_m)
+                };
+
+                IEEE754 {
+                    num: IEEE754Val::Nan(*s, true, payload),
+                    flags: Exceptions::default(),
+                    ctx: ctx.clone(),
+                }
+            }
+            _ => ctx.round(self),
+        }
+    }
 }
 
 impl Real for IEEE754 {
@@ -318,12 +522,25 @@ impl Real for IEEE754 {
     fn is_numerical(&self) -> bool {
         !matches!(&self.num, IEEE754Val::Nan(_, _, _))
     }
+
+    fn is_signaling(&self) -> bool {
+        matches!(&self.num, IEEE754Val::Nan(_, false, _))
+    }
+
+    fn nan_payload_field(&self) -> Option<(Integer, usize)> {
+        // the payload lies below the quiet bit
+        match &self.num {
+            IEEE754Val::Nan(_, _, payload) => Some((payload.clone(), self.ctx.max_m() - 1)),
+            _ => None,
+        }
+    }
 }
 
 impl From<IEEE754> for RFloat {
     fn from(val: IEEE754) -> Self {
         match val.num {
-            IEEE754Val::PosZero | IEEE754Val::NegZero => RFloat::zero(),
+            IEEE754Val::PosZero => RFloat::zero(),
+            IEEE754Val::NegZero => RFloat::Real(true, 0, Integer::zero()),
             IEEE754Val::Subnormal(s, c) => RFloat::Real(s, val.ctx.expmin(), c),
             IEEE754Val::Normal(s, exp, c) => RFloat::Real(s, exp, c),
             IEEE754Val::PosInfinity => RFloat::PosInfinity,
@@ -333,6 +550,20 @@ impl From<IEEE754> for RFloat {
     }
 }
 
+impl From<f64> for IEEE754 {
+    fn from(val: f64) -> Self {
+        let ctx = IEEE754Context::binary64();
+        ctx.bits_to_number(Integer::from(val.to_bits()))
+    }
+}
+
+impl From<f32> for IEEE754 {
+    fn from(val: f32) -> Self {
+        let ctx = IEEE754Context::binary32();
+        ctx.bits_to_number(Integer::from(val.to_bits()))
+    }
+}
+
 impl From<IEEE754> for rug::Float {
     fn from(val: IEEE754) -> Self {
         let s = val.sign().unwrap();
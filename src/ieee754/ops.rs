@@ -1,15 +1,18 @@
-use crate::ieee754::IEEE754Context;
+use rug::Integer;
+
+use crate::ieee754::{Exceptions, IEEE754Context, IEEE754Val, SpecialValues, IEEE754};
 use crate::mpfr::*;
 use crate::ops::*;
 use crate::rfloat::RFloat;
-use crate::{Real, RoundingContext};
+use crate::stochastic::guard_bits;
+use crate::{Real, RoundingContext, RoundingMode};
 
 macro_rules! rounded_1ary_impl {
     ($tname:ident, $name:ident, $mpfr:ident) => {
         impl $tname for IEEE754Context {
             fn $name<N: Real>(&self, src: &N) -> Self::Format {
-                // compute with 2 additional bits, rounding-to-odd
-                let p = self.max_p() + 2;
+                // compute with additional rounding bits, rounding-to-odd
+                let p = self.max_p() + guard_bits(self.stochastic_rounding());
                 let r = RFloat::from_number(src);
                 let result = $mpfr(r, p);
                 let mut rounded = self.round(result.num());
@@ -29,6 +32,7 @@ rounded_1ary_impl!(RoundedRecip, recip, mpfr_recip);
 rounded_1ary_impl!(RoundedRecipSqrt, recip_sqrt, mpfr_recip_sqrt);
 rounded_1ary_impl!(RoundedExp, exp, mpfr_exp);
 rounded_1ary_impl!(RoundedExp2, exp2, mpfr_exp2);
+rounded_1ary_impl!(RoundedExp10, exp10, mpfr_exp10);
 rounded_1ary_impl!(RoundedLog, log, mpfr_log);
 rounded_1ary_impl!(RoundedLog2, log2, mpfr_log2);
 rounded_1ary_impl!(RoundedLog10, log10, mpfr_log10);
@@ -47,6 +51,9 @@ rounded_1ary_impl!(RoundedTanPi, tan_pi, mpfr_tan_pi);
 rounded_1ary_impl!(RoundedAsin, asin, mpfr_asin);
 rounded_1ary_impl!(RoundedAcos, acos, mpfr_acos);
 rounded_1ary_impl!(RoundedAtan, atan, mpfr_atan);
+rounded_1ary_impl!(RoundedAsinPi, asin_pi, mpfr_asin_pi);
+rounded_1ary_impl!(RoundedAcosPi, acos_pi, mpfr_acos_pi);
+rounded_1ary_impl!(RoundedAtanPi, atan_pi, mpfr_atan_pi);
 rounded_1ary_impl!(RoundedSinh, sinh, mpfr_sinh);
 rounded_1ary_impl!(RoundedCosh, cosh, mpfr_cosh);
 rounded_1ary_impl!(RoundedTanh, tanh, mpfr_tanh);
@@ -58,6 +65,27 @@ rounded_1ary_impl!(RoundedErfc, erfc, mpfr_erfc);
 rounded_1ary_impl!(RoundedGamma, tgamma, mpfr_tgamma);
 rounded_1ary_impl!(RoundedLgamma, lgamma, mpfr_lgamma);
 
+macro_rules! rounded_1ary_int_impl {
+    ($tname:ident, $name:ident, $mpfr:ident) => {
+        impl $tname for IEEE754Context {
+            fn $name<N: Real>(&self, src: &N, n: i64) -> Self::Format {
+                // compute with additional rounding bits, rounding-to-odd
+                let p = self.max_p() + guard_bits(self.stochastic_rounding());
+                let r = RFloat::from_number(src);
+                let result = $mpfr(r, n, p);
+                let mut rounded = self.round(result.num());
+                rounded.flags.invalid = result.flags().invalid;
+                rounded.flags.divzero = result.flags().divzero;
+                rounded
+            }
+        }
+    };
+}
+
+rounded_1ary_int_impl!(RoundedCompound, compound, mpfr_compound);
+rounded_1ary_int_impl!(RoundedRootn, rootn, mpfr_rootn);
+rounded_1ary_int_impl!(RoundedPown, pown, mpfr_pown);
+
 macro_rules! rounded_2ary_impl {
     ($tname:ident, $name:ident, $mpfr:ident) => {
         impl $tname for IEEE754Context {
@@ -66,8 +94,8 @@ macro_rules! rounded_2ary_impl {
                 N1: Real,
                 N2: Real,
             {
-                // compute with 2 additional bits, rounding-to-odd
-                let p = self.max_p() + 2;
+                // compute with additional rounding bits, rounding-to-odd
+                let p = self.max_p() + guard_bits(self.stochastic_rounding());
                 let r1 = RFloat::from_number(src1);
                 let r2 = RFloat::from_number(src2);
                 let result = $mpfr(r1, r2, p);
@@ -85,10 +113,13 @@ rounded_2ary_impl!(RoundedSub, sub, mpfr_sub);
 rounded_2ary_impl!(RoundedMul, mul, mpfr_mul);
 rounded_2ary_impl!(RoundedDiv, div, mpfr_div);
 rounded_2ary_impl!(RoundedPow, pow, mpfr_pow);
+rounded_2ary_impl!(RoundedPowr, powr, mpfr_powr);
 rounded_2ary_impl!(RoundedHypot, hypot, mpfr_hypot);
 rounded_2ary_impl!(RoundedFmod, fmod, mpfr_fmod);
 rounded_2ary_impl!(RoundedRemainder, remainder, mpfr_remainder);
 rounded_2ary_impl!(RoundedAtan2, atan2, mpfr_atan2);
+rounded_2ary_impl!(RoundedAtan2Pi, atan2_pi, mpfr_atan2_pi);
+rounded_2ary_impl!(RoundedFdim, fdim, mpfr_fdim);
 
 macro_rules! rounded_3ary_impl {
     ($tname:ident, $name:ident, $mpfr:ident) => {
@@ -99,8 +130,8 @@ macro_rules! rounded_3ary_impl {
                 N2: Real,
                 N3: Real,
             {
-                // compute with 2 additional bits, rounding-to-odd
-                let p = self.max_p() + 2;
+                // compute with additional rounding bits, rounding-to-odd
+                let p = self.max_p() + guard_bits(self.stochastic_rounding());
                 let r1 = RFloat::from_number(src1);
                 let r2 = RFloat::from_number(src2);
                 let r3 = RFloat::from_number(src3);
@@ -115,3 +146,252 @@ macro_rules! rounded_3ary_impl {
 }
 
 rounded_3ary_impl!(RoundedFMA, fma, mpfr_fma);
+
+impl RoundedSinCos for IEEE754Context {
+    fn sin_cos<N: Real>(&self, src: &N) -> (Self::Format, Self::Format) {
+        // compute with additional rounding bits, rounding-to-odd
+        let p = self.max_p() + guard_bits(self.stochastic_rounding());
+        let r = RFloat::from_number(src);
+        let (sin, cos) = mpfr_sin_cos(r, p);
+        let mut rounded_sin = self.round(sin.num());
+        rounded_sin.flags.invalid = sin.flags().invalid;
+        rounded_sin.flags.divzero = sin.flags().divzero;
+        let mut rounded_cos = self.round(cos.num());
+        rounded_cos.flags.invalid = cos.flags().invalid;
+        rounded_cos.flags.divzero = cos.flags().divzero;
+        (rounded_sin, rounded_cos)
+    }
+}
+
+impl RoundedRemquo for IEEE754Context {
+    fn remquo<N1, N2>(&self, src1: &N1, src2: &N2) -> (Self::Format, i64)
+    where
+        N1: Real,
+        N2: Real,
+    {
+        // compute with additional rounding bits, rounding-to-odd
+        let p = self.max_p() + guard_bits(self.stochastic_rounding());
+        let r1 = RFloat::from_number(src1);
+        let r2 = RFloat::from_number(src2);
+        let (result, q) = mpfr_remquo(r1, r2, p);
+        let mut rounded = self.round(result.num());
+        rounded.flags.invalid = result.flags().invalid;
+        rounded.flags.divzero = result.flags().divzero;
+        (rounded, q)
+    }
+}
+
+impl RoundedFrexp for IEEE754Context {
+    fn frexp<N: Real>(&self, src: &N) -> (Self::Format, isize) {
+        // splitting is exact
+        let (m, e) = frexp_exact(src);
+        (self.round(&m), e)
+    }
+}
+
+impl RoundedModf for IEEE754Context {
+    fn modf<N: Real>(&self, src: &N) -> (Self::Format, Self::Format) {
+        // splitting is exact
+        let (frac, int) = modf_exact(src);
+        (self.round(&frac), self.round(&int))
+    }
+}
+
+macro_rules! rounded_integral_impl {
+    ($tname:ident, $name:ident, $rm:expr) => {
+        impl $tname for IEEE754Context {
+            fn $name<N: Real>(&self, src: &N) -> Self::Format {
+                if !src.is_numerical() {
+                    return self.propagate_nan(src);
+                }
+
+                let (integral, _) = round_integral(src, $rm);
+                self.round(&integral)
+            }
+        }
+    };
+}
+
+rounded_integral_impl!(RoundedFloor, floor, RoundingMode::ToNegative);
+rounded_integral_impl!(RoundedCeil, ceil, RoundingMode::ToPositive);
+rounded_integral_impl!(RoundedTrunc, trunc, RoundingMode::ToZero);
+rounded_integral_impl!(
+    RoundedRoundAway,
+    round_away,
+    RoundingMode::NearestTiesAwayZero
+);
+rounded_integral_impl!(
+    RoundedRoundEven,
+    round_even,
+    RoundingMode::NearestTiesToEven
+);
+
+impl RoundedNearbyInt for IEEE754Context {
+    fn nearbyint<N: Real>(&self, src: &N) -> Self::Format {
+        if !src.is_numerical() {
+            return self.propagate_nan(src);
+        }
+
+        let (integral, _) = round_integral(src, self.rm());
+        self.round(&integral)
+    }
+}
+
+impl RoundedRint for IEEE754Context {
+    fn rint<N: Real>(&self, src: &N) -> Self::Format {
+        if !src.is_numerical() {
+            return self.propagate_nan(src);
+        }
+
+        let (integral, inexact) = round_integral(src, self.rm());
+        let mut rounded = self.round(&integral);
+        rounded.flags.inexact |= inexact;
+        rounded
+    }
+}
+
+impl RoundedConvertToInteger for IEEE754Context {
+    fn convert_to_integer<N: Real>(&self, src: &N, rm: RoundingMode, exact: bool) -> Self::Format {
+        let (integral, inexact) = round_integral(src, rm);
+        let mut rounded = self.round(&integral);
+        if !integral.is_finite() || rounded.flags.overflow {
+            // non-real or out of range integers are invalid
+            let mut result = self.round(&RFloat::Nan);
+            result.flags.invalid = true;
+            result
+        } else {
+            rounded.flags.inexact = exact && (inexact || rounded.flags.inexact);
+            rounded
+        }
+    }
+}
+
+// Non-computational and quiet-computational operations.
+impl IEEE754Context {
+    /// Propagates a NaN argument, preserving its sign and payload.
+    /// Signaling NaNs are quieted and signal an invalid operation.
+    fn propagate_nan<N: Real>(&self, x: &N) -> IEEE754 {
+        if self.specials() == SpecialValues::Finite {
+            // no NaNs, so rounding signals an invalid operation
+            self.round(x)
+        } else {
+            // payload is aligned below the quiet bit
+            let width = self.max_m() - 1;
+            let payload = match x.nan_payload_field() {
+                Some((payload, w)) if w <= width => payload << (width - w),
+                Some((payload, w)) => payload >> (w - width),
+                None => Integer::new(),
+            };
+
+            IEEE754 {
+                num: IEEE754Val::Nan(x.sign().unwrap_or(false), true, payload),
+                flags: Exceptions {
+                    invalid: x.is_signaling(),
+                    ..Default::default()
+                },
+                ctx: self.clone(),
+            }
+        }
+    }
+
+    /// Implements the `radix` operation: the radix of this format.
+    pub fn radix(&self) -> usize {
+        2
+    }
+
+    /// Implements the `scaleB` operation: computes `x * 2^n`,
+    /// rounded under this context.
+    ///
+    /// Zeros and infinities are returned unchanged. Quiet NaNs are
+    /// propagated with their payloads, while signaling NaNs are
+    /// quieted and signal an invalid operation.
+    pub fn scale_b(&self, x: &IEEE754, n: isize) -> IEEE754 {
+        match &x.num {
+            IEEE754Val::Subnormal(s, c) | IEEE754Val::Normal(s, _, c) => {
+                // results far out of range all round the same way, so the
+                // exponent is bounded to avoid overflowing it
+                let bits = c.significant_bits() as isize;
+                let min_exp = self.expmin() - 2 - bits;
+                let max_exp = self.expmax() + self.max_p() as isize;
+                let exp = x.exp().unwrap().saturating_add(n);
+                let exp = exp.clamp(min_exp, max_exp);
+                self.round(&RFloat::Real(*s, exp, c.clone()))
+            }
+            IEEE754Val::Nan(..) => self.propagate_nan(x),
+            _ => {
+                // zeros and infinities
+                let mut result = self.round(x);
+                result.flags = Exceptions::default();
+                result
+            }
+        }
+    }
+
+    /// Implements the `logB` operation: computes `floor(log2(|x|))`,
+    /// the exponent of `x` as if it were normalized, rounded under
+    /// this context.
+    ///
+    /// As required when the result format is a floating-point format,
+    /// `logB(NaN)` is NaN, `logB(+/-Inf)` is `+Inf`, and `logB(+/-0)`
+    /// is `-Inf` and signals division by zero.
+    pub fn log_b(&self, x: &IEEE754) -> IEEE754 {
+        match &x.num {
+            IEEE754Val::PosZero | IEEE754Val::NegZero => {
+                let mut result = self.round(&RFloat::NegInfinity);
+                result.flags.divzero = true;
+                result
+            }
+            IEEE754Val::PosInfinity | IEEE754Val::NegInfinity => self.round(&RFloat::PosInfinity),
+            IEEE754Val::Nan(..) => self.propagate_nan(x),
+            _ => {
+                let e = x.e().unwrap();
+                self.round(&RFloat::Real(e < 0, 0, Integer::from(e.unsigned_abs())))
+            }
+        }
+    }
+}
+
+macro_rules! min_max_impl {
+    ($tname:ident, $name:ident, $op:expr) => {
+        impl $tname for IEEE754Context {
+            fn $name<N1, N2>(&self, src1: &N1, src2: &N2) -> Self::Format
+            where
+                N1: Real,
+                N2: Real,
+            {
+                let (result, invalid) = min_max(src1, src2, $op);
+                let mut rounded = match result {
+                    MinMaxResult::First => self.round(src1),
+                    MinMaxResult::Second => self.round(src2),
+                    MinMaxResult::Nan
+                        if !src1.is_numerical()
+                            && (src1.is_signaling() || !src2.is_signaling()) =>
+                    {
+                        // propagate the first NaN, preferring a signaling NaN
+                        self.propagate_nan(src1)
+                    }
+                    MinMaxResult::Nan => self.propagate_nan(src2),
+                };
+                rounded.flags.invalid |= invalid;
+                rounded
+            }
+        }
+    };
+}
+
+min_max_impl!(RoundedMinimum, minimum, MinMax::Minimum);
+min_max_impl!(RoundedMaximum, maximum, MinMax::Maximum);
+min_max_impl!(RoundedMinimumNumber, minimum_number, MinMax::MinimumNumber);
+min_max_impl!(RoundedMaximumNumber, maximum_number, MinMax::MaximumNumber);
+min_max_impl!(
+    RoundedMinimumMagnitude,
+    minimum_magnitude,
+    MinMax::MinimumMagnitude
+);
+min_max_impl!(
+    RoundedMaximumMagnitude,
+    maximum_magnitude,
+    MinMax::MaximumMagnitude
+);
+min_max_impl!(RoundedMinNum, min_num, MinMax::MinNum);
+min_max_impl!(RoundedMaxNum, max_num, MinMax::MaxNum);
@@ -1,11 +1,49 @@
 use num_traits::Zero;
+use rug::ops::Pow;
 use rug::Integer;
 use std::ops::{BitAnd, BitOr};
 
 use crate::ieee754::{Exceptions, IEEE754Val, IEEE754};
+use crate::rational::{number_to_odd, RationalContext};
 use crate::rfloat::{RFloat, RFloatContext};
+use crate::stochastic::guard_bits;
 use crate::util::bitmask;
-use crate::{Real, RoundingContext, RoundingDirection, RoundingMode, Split};
+use crate::{Real, RoundingContext, RoundingDirection, RoundingMode, Split, StochasticRounding};
+
+/// Encodings of non-finite values.
+///
+/// IEEE 754 formats reserve the largest exponent for infinities and NaNs.
+/// Many small formats used in machine learning, e.g., the OCP 8-bit
+/// formats, reclaim some or all of these encodings for finite values.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SpecialValues {
+    /// IEEE 754 encoding: the largest exponent encodes infinities
+    /// (zero significand) and NaNs (non-zero significand).
+    InfNan,
+    /// No infinities: the largest exponent encodes finite values
+    /// except when the significand is all ones which encodes NaN,
+    /// e.g., OCP FP8 E4M3.
+    NanOnly,
+    /// No infinities or NaNs: every encoding is a finite value,
+    /// e.g., OCP FP6 and FP4.
+    Finite,
+}
+
+/// Overflow behavior for IEEE 754 style formats.
+///
+/// See Section 5.4 of the OCP 8-bit Floating Point Specification
+/// for a description of saturating and non-saturating conversions.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Overflow {
+    /// Overflow produces infinity (or NaN if the format has no infinities)
+    /// or the largest finite value depending on the rounding mode,
+    /// as described by the IEEE 754 standard. Formats without infinities
+    /// or NaNs always saturate.
+    NonSaturating,
+    /// Overflow always produces the largest finite value, including
+    /// when rounding infinity.
+    Saturating,
+}
 
 /// Rounding contexts for IEEE 754 floating-point numbers.
 ///
@@ -21,18 +59,29 @@ use crate::{Real, RoundingContext, RoundingDirection, RoundingMode, Split};
 ///  - bitwidth of the exponent field,
 ///  - total bitwidth of the encoding,
 ///  - rounding mode,
-///  - optional subnormal flushing
+///  - optional stochastic rounding (see [`StochasticRounding`]),
+///  - optional subnormal flushing,
+///  - encoding of non-finite values (see [`SpecialValues`]),
+///  - overflow behavior (see [`Overflow`]).
 ///
 /// By default, the rounding mode is [`RoundingMode::NearestTiesToEven`],
 /// and subnormals are not flushed during rounding nor interpreted
-/// as zero during an operation.
+/// as zero during an operation. Non-finite values are encoded as
+/// described by the IEEE 754 standard and overflow is non-saturating.
+/// If stochastic rounding is specified, it takes precedence over
+/// the rounding mode: tininess is detected before rounding, and
+/// a result that rounds beyond MAX_FLOAT overflows as if rounded
+/// away from zero.
 ///
 #[derive(Clone, Debug)]
 pub struct IEEE754Context {
     es: usize,
     nbits: usize,
     rm: RoundingMode,
+    sr: Option<StochasticRounding>,
     ftz: bool,
+    specials: SpecialValues,
+    overflow: Overflow,
 }
 
 impl IEEE754Context {
@@ -42,11 +91,25 @@ impl IEEE754Context {
     pub const ES_MIN: usize = 2;
     /// Implementation limit: minimum precision
     pub const PREC_MIN: usize = 3;
+    /// Implementation limit: minimum precision without infinities
+    pub const PREC_MIN_NO_INF: usize = 2;
 
     /// Constructs a new rounding context with the given format parameters.
     /// The default rounding mode is [`NearestTiesToEven`][RoundingMode].
     /// Both fields specifying subnormal behavior are false by default.
     pub fn new(es: usize, nbits: usize) -> Self {
+        Self::new_with_specials(es, nbits, SpecialValues::InfNan)
+    }
+
+    /// Constructs a new rounding context with the given format parameters
+    /// and encoding of non-finite values. Otherwise, the same as
+    /// [`IEEE754Context::new`].
+    pub fn new_with_specials(es: usize, nbits: usize, specials: SpecialValues) -> Self {
+        let prec_min = match specials {
+            SpecialValues::InfNan => Self::PREC_MIN,
+            _ => Self::PREC_MIN_NO_INF,
+        };
+
         assert!(
             es >= Self::ES_MIN,
             "exponent width needs to be at least {} bits, given {} bits",
@@ -60,9 +123,9 @@ impl IEEE754Context {
             es
         );
         assert!(
-            nbits >= es + Self::PREC_MIN,
+            nbits >= es + prec_min,
             "total bitwidth needs to be at least {} bits, given {} bits",
-            es + Self::PREC_MIN,
+            es + prec_min,
             nbits
         );
 
@@ -70,7 +133,149 @@ impl IEEE754Context {
             es,
             nbits,
             rm: RoundingMode::NearestTiesToEven,
+            sr: None,
             ftz: false,
+            specials,
+            overflow: Overflow::NonSaturating,
+        }
+    }
+
+    /// Constructs a rounding context for the IEEE 754 `binary16` format,
+    /// i.e., half precision.
+    pub fn binary16() -> Self {
+        Self::new(5, 16)
+    }
+
+    /// Constructs a rounding context for the `bfloat16` format,
+    /// i.e., `binary32` with a truncated significand.
+    pub fn bfloat16() -> Self {
+        Self::new(8, 16)
+    }
+
+    /// Constructs a rounding context for the NVIDIA TensorFloat-32 format,
+    /// i.e., the exponent of `binary32` and the precision of `binary16`.
+    pub fn tf32() -> Self {
+        Self::new(8, 19)
+    }
+
+    /// Constructs a rounding context for the IEEE 754 `binary32` format,
+    /// i.e., single precision.
+    pub fn binary32() -> Self {
+        Self::new(8, 32)
+    }
+
+    /// Constructs a rounding context for the IEEE 754 `binary64` format,
+    /// i.e., double precision.
+    pub fn binary64() -> Self {
+        Self::new(11, 64)
+    }
+
+    /// Constructs a rounding context for the IEEE 754 `binary128` format,
+    /// i.e., quadruple precision.
+    pub fn binary128() -> Self {
+        Self::new(15, 128)
+    }
+
+    /// Constructs a rounding context for the IEEE 754 `binary256` format,
+    /// i.e., octuple precision.
+    pub fn binary256() -> Self {
+        Self::new(19, 256)
+    }
+
+    /// Constructs a rounding context for the OCP FP8 E5M2 format.
+    /// This format follows IEEE 754 conventions for non-finite values.
+    pub fn ocp_e5m2() -> Self {
+        Self::new(5, 8)
+    }
+
+    /// Constructs a rounding context for the OCP FP8 E4M3 format.
+    /// This format has no infinities and only one NaN (per sign), so the
+    /// largest finite value is `448`.
+    pub fn ocp_e4m3() -> Self {
+        Self::new_with_specials(4, 8, SpecialValues::NanOnly)
+    }
+
+    /// Constructs a rounding context for the OCP FP6 E3M2 format.
+    /// This format has no infinities or NaNs.
+    pub fn ocp_e3m2() -> Self {
+        Self::new_with_specials(3, 6, SpecialValues::Finite)
+    }
+
+    /// Constructs a rounding context for the OCP FP6 E2M3 format.
+    /// This format has no infinities or NaNs.
+    pub fn ocp_e2m3() -> Self {
+        Self::new_with_specials(2, 6, SpecialValues::Finite)
+    }
+
+    /// Constructs a rounding context for the OCP FP4 E2M1 format.
+    /// This format has no infinities or NaNs.
+    pub fn ocp_e2m1() -> Self {
+        Self::new_with_specials(2, 4, SpecialValues::Finite)
+    }
+
+    /// Constructs a rounding context for the IEEE 754 `binary{k}`
+    /// interchange format (see Section 3.6 of IEEE 754-2019).
+    /// The bitwidth `k` must be 16, 32, 64, or a multiple of 32
+    /// that is at least 128. For `k >= 128`, the exponent bitwidth is
+    /// `round(4 * log2(k)) - 13`.
+    pub fn binary(k: usize) -> Self {
+        match k {
+            16 => Self::binary16(),
+            32 => Self::binary32(),
+            64 => Self::binary64(),
+            _ => {
+                assert!(
+                    k >= 128 && k.is_multiple_of(32),
+                    "interchange format bitwidth must be 16, 32, 64, or a multiple of 32 at least 128, given {}",
+                    k
+                );
+                Self::new(Self::interchange_es(k), k)
+            }
+        }
+    }
+
+    /// Exponent bitwidth `round(4 * log2(k)) - 13` of an interchange
+    /// format of bitwidth `k >= 128`.
+    fn interchange_es(k: usize) -> usize {
+        // `round(4 * log2(k)) = round(log2(k^8) / 2)` and `k^8` is never
+        // an odd power of two, so it suffices to know `floor(log2(k^8))`
+        let lg = Integer::from(k).pow(8).significant_bits() as usize - 1;
+        lg.div_ceil(2) - 13
+    }
+
+    /// Returns the standard name of the format produced by this context,
+    /// e.g., `binary64` or `bfloat16`, if it has one.
+    /// The rounding mode and subnormal behavior are ignored.
+    pub fn name(&self) -> Option<String> {
+        match self.specials {
+            SpecialValues::InfNan => (),
+            SpecialValues::NanOnly => {
+                return match (self.es, self.nbits) {
+                    (4, 8) => Some("e4m3".to_string()),
+                    _ => None,
+                };
+            }
+            SpecialValues::Finite => {
+                return match (self.es, self.nbits) {
+                    (3, 6) => Some("e3m2".to_string()),
+                    (2, 6) => Some("e2m3".to_string()),
+                    (2, 4) => Some("e2m1".to_string()),
+                    _ => None,
+                };
+            }
+        }
+
+        match (self.es, self.nbits) {
+            (5, 8) => Some("e5m2".to_string()),
+            (5, 16) => Some("binary16".to_string()),
+            (8, 16) => Some("bfloat16".to_string()),
+            (8, 19) => Some("tf32".to_string()),
+            (8, 32) => Some("binary32".to_string()),
+            (11, 64) => Some("binary64".to_string()),
+            (es, k) if k >= 128 && k.is_multiple_of(32) && es == Self::interchange_es(k) => {
+                Some(format!("binary{}", k))
+            }
+            _ => None,
         }
     }
 
@@ -80,6 +285,12 @@ impl IEEE754Context {
         self
     }
 
+    /// Sets stochastic rounding, overriding the rounding mode.
+    pub fn with_stochastic_rounding(mut self, sr: StochasticRounding) -> Self {
+        self.sr = Some(sr);
+        self
+    }
+
     /// Sets the subnormal result behavior.
     /// If enabled, any subnormal result will be flushed to zero.
     pub fn with_ftz(mut self, enable: bool) -> Self {
@@ -87,6 +298,12 @@ impl IEEE754Context {
         self
     }
 
+    /// Sets the overflow behavior.
+    pub fn with_overflow(mut self, overflow: Overflow) -> Self {
+        self.overflow = overflow;
+        self
+    }
+
     /// Returns the exponent bitwidth of the format produced by
     /// this context (when viewed as a bitvector). This is guaranteed
     /// to satisfy `2 <= self.es() < self.nbits() - 2.
@@ -99,11 +316,26 @@ impl IEEE754Context {
         self.rm
     }
 
+    /// Returns the stochastic rounding configuration, if any.
+    pub fn stochastic_rounding(&self) -> Option<&StochasticRounding> {
+        self.sr.as_ref()
+    }
+
     /// Returns the ftz (flush-to-zero) field.
     pub fn ftz(&self) -> bool {
         self.ftz
     }
 
+    /// Returns the encoding of non-finite values.
+    pub fn specials(&self) -> SpecialValues {
+        self.specials
+    }
+
+    /// Returns the overflow behavior.
+    pub fn overflow(&self) -> Overflow {
+        self.overflow
+    }
+
     /// Returns the total bitwidth of the format produced by this context
     /// (when viewed as a bitvector). This is guaranteed to satisfy
     /// `self.es() + 2 < self.nbits()`.
@@ -125,16 +357,21 @@ impl IEEE754Context {
 
     /// Exponent of the largst finite floating-point value representable
     /// in this format when viewed as `(-1)^s * m * b^e` where `m`
-    /// is a fraction between 1 and 2.
+    /// is a fraction between 1 and 2. The result is just `self.bias()`
+    /// unless the largest exponent does not encode infinity in which
+    /// case it is `self.bias() + 1`.
     pub fn emax(&self) -> isize {
-        (1 << (self.es - 1)) - 1
+        match self.specials {
+            SpecialValues::InfNan => self.bias(),
+            _ => self.bias() + 1,
+        }
     }
 
     /// Exponent of the smallest normal floating-point value representable
     /// in this format when viewed as `(-1)^s * m * b^e` where `m`
-    /// is a fraction between 1 and 2. The result is just `self.emax() - 1`.
+    /// is a fraction between 1 and 2. The result is just `1 - self.bias()`.
     pub fn emin(&self) -> isize {
-        1 - self.emax()
+        1 - self.bias()
     }
 
     /// Exponent of the largst finite floating-point value representable
@@ -153,9 +390,11 @@ impl IEEE754Context {
 
     /// The exponent "bias" used when converting a valid exponent range
     /// `[emin, emax]` to unsigned integers for bitpacking. Specifically,
-    /// the final range is `[1, 2*emax]` The result is just `self.emax()`.
+    /// the range of normal exponents becomes `[1, 2*bias]` (or `[1, 2*bias+1]`
+    /// if the largest exponent does not encode infinity).
+    /// The result is just `2^(es-1) - 1`.
     pub fn bias(&self) -> isize {
-        self.emax()
+        (1 << (self.es - 1)) - 1
     }
 
     /// Returns a signed zero.
@@ -182,15 +421,27 @@ impl IEEE754Context {
 
     /// Returns the maximum representable value with a sign.
     pub fn max_float(&self, sign: bool) -> IEEE754 {
+        // the all ones significand may be NaN
+        let c = match self.specials {
+            SpecialValues::NanOnly => bitmask(self.max_p()) - 1,
+            _ => bitmask(self.max_p()),
+        };
+
         IEEE754 {
-            num: IEEE754Val::Normal(sign, self.expmax(), bitmask(self.max_p())),
+            num: IEEE754Val::Normal(sign, self.expmax(), c),
             flags: Exceptions::default(),
             ctx: self.clone(),
         }
     }
 
     /// Constructs an infinity with a sign.
+    /// Panics if the format has no infinities.
     pub fn inf(&self, sign: bool) -> IEEE754 {
+        assert_eq!(
+            self.specials,
+            SpecialValues::InfNan,
+            "format has no infinities"
+        );
         IEEE754 {
             num: if sign {
                 IEEE754Val::NegInfinity
@@ -203,7 +454,9 @@ impl IEEE754Context {
     }
 
     /// Constructs a canonical, quiet NaN (unsigned, quiet bit, empty payload).
+    /// Panics if the format has no NaNs.
     pub fn qnan(&self) -> IEEE754 {
+        assert_ne!(self.specials, SpecialValues::Finite, "format has no NaNs");
         IEEE754 {
             num: IEEE754Val::Nan(false, true, Integer::from(0)),
             flags: Default::default(),
@@ -212,7 +465,13 @@ impl IEEE754Context {
     }
 
     /// Constructs a canonical, signaling NaN (unsigned, signal bit, 1).
+    /// Panics if the format has no signaling NaNs.
     pub fn snan(&self) -> IEEE754 {
+        assert_eq!(
+            self.specials,
+            SpecialValues::InfNan,
+            "format has no signaling NaNs"
+        );
         IEEE754 {
             num: IEEE754Val::Nan(false, false, Integer::from(1)),
             flags: Default::default(),
@@ -233,8 +492,17 @@ impl IEEE754Context {
         let m = b.bitand(bitmask(p - 1));
 
         // case split by classification
-        let e_norm = e.to_isize().unwrap() - self.emax();
-        let num = if e_norm < self.emin() {
+        let e_norm = e.to_isize().unwrap() - self.bias();
+        let is_nan = match self.specials {
+            SpecialValues::InfNan => false,
+            SpecialValues::NanOnly => e_norm == self.emax() && m == bitmask(p - 1),
+            SpecialValues::Finite => false,
+        };
+
+        let num = if is_nan {
+            // only NaN encoding (no infinities)
+            IEEE754Val::Nan(s, true, Integer::zero())
+        } else if e_norm < self.emin() {
             // subnormal or zero
             if m.is_zero() {
                 // zero
@@ -279,17 +547,47 @@ impl IEEE754Context {
 
 // Rounding utility functions.
 impl IEEE754Context {
-    /// Given a sign and rounding mode, returns true if a overflow
-    /// exception means the result is rounded to infinity rather
-    /// than MAX_FLOAT.
-    fn overflow_to_infinity(sign: bool, rm: RoundingMode) -> bool {
-        // case split on rounding mode
-        match rm.to_direction(sign) {
+    /// Given a sign, returns true if a overflow exception means
+    /// the result is rounded to infinity rather than MAX_FLOAT.
+    fn overflow_to_infinity(&self, sign: bool) -> bool {
+        // parity of MAX_FLOAT for directed rounding to even or odd:
+        // the next value in the direction of infinity has an even LSB
+        let max_odd = self.max_float(false).c().unwrap().is_odd();
+
+        // case split on rounding mode: nearest modes only overflow when
+        // the value rounds past MAX_FLOAT with unbounded exponent,
+        // so ties have already been broken
+        match self.rm.to_direction(sign) {
             (true, _) => true,
             (_, RoundingDirection::ToZero) => false, // always truncate
             (_, RoundingDirection::AwayZero) => true, // always away
-            (_, RoundingDirection::ToEven) => true,  // MAX_FLOAT has odd LSB
-            (_, RoundingDirection::ToOdd) => false,  // MAX_FLOAT has odd LSB
+            (_, RoundingDirection::ToEven) => max_odd,
+            (_, RoundingDirection::ToOdd) => !max_odd,
+        }
+    }
+
+    /// Given a sign, returns true if overflow produces a non-finite
+    /// value rather than MAX_FLOAT.
+    fn overflow_to_special(&self, sign: bool) -> bool {
+        match (self.specials, self.overflow) {
+            (SpecialValues::Finite, _) => false,
+            (_, Overflow::Saturating) => false,
+            (_, Overflow::NonSaturating) => self.sr.is_some() || self.overflow_to_infinity(sign),
+        }
+    }
+
+    /// Returns the result of rounding an infinity with a sign:
+    /// infinity, NaN, or MAX_FLOAT depending on the encoding
+    /// of non-finite values and overflow behavior.
+    fn round_special(&self, sign: bool) -> IEEE754 {
+        match (self.specials, self.overflow) {
+            (SpecialValues::InfNan, Overflow::NonSaturating) => self.inf(sign),
+            (SpecialValues::NanOnly, Overflow::NonSaturating) => IEEE754 {
+                num: IEEE754Val::Nan(sign, true, Integer::zero()),
+                flags: Exceptions::default(),
+                ctx: self.clone(),
+            },
+            (_, _) => self.max_float(sign),
         }
     }
 
@@ -366,21 +664,23 @@ impl IEEE754Context {
 
         // check for overflow
         let e = unbounded.e().unwrap();
-        if e > self.emax() {
-            if IEEE754Context::overflow_to_infinity(sign, self.rm) {
-                return IEEE754 {
-                    num: if sign {
-                        IEEE754Val::NegInfinity
-                    } else {
-                        IEEE754Val::PosInfinity
-                    },
-                    flags: Exceptions {
-                        overflow: true,
-                        inexact: true,
-                        ..Default::default()
-                    },
-                    ctx: self.clone(),
-                };
+        let overflow = match e.cmp(&self.emax()) {
+            std::cmp::Ordering::Less => false,
+            std::cmp::Ordering::Greater => true,
+            std::cmp::Ordering::Equal => {
+                // the largest binade may not be entirely finite
+                let c = unbounded.c().unwrap();
+                let magnitude = RFloat::Real(false, unbounded.exp().unwrap(), c);
+                magnitude > RFloat::from(self.max_float(false))
+            }
+        };
+
+        if overflow {
+            if self.overflow_to_special(sign) {
+                let mut special = self.round_special(sign);
+                special.flags.overflow = true;
+                special.flags.inexact = true;
+                return special;
             } else {
                 let mut maxfloat = self.max_float(sign);
                 maxfloat.flags.overflow = true;
@@ -450,6 +750,11 @@ impl RoundingContext for IEEE754Context {
     type Format = IEEE754;
 
     fn round<T: Real>(&self, num: &T) -> Self::Format {
+        if T::radix() == 10 {
+            // decimal values are rounded through their exact value
+            return self.round(&number_to_odd(self, num));
+        }
+
         // case split by class
         if num.is_zero() {
             IEEE754 {
@@ -461,20 +766,30 @@ impl RoundingContext for IEEE754Context {
                 ctx: self.clone(),
             }
         } else if num.is_infinite() {
-            IEEE754 {
-                num: match num.sign() {
-                    Some(true) => IEEE754Val::NegInfinity,
-                    _ => IEEE754Val::PosInfinity,
-                },
-                flags: Exceptions::default(),
-                ctx: self.clone(),
+            let sign = num.sign().unwrap();
+            match (self.specials, self.overflow) {
+                (SpecialValues::InfNan, Overflow::NonSaturating) => self.inf(sign),
+                (_, _) => {
+                    // saturating or no infinities, so this overflows
+                    let mut special = self.round_special(sign);
+                    special.flags.overflow = true;
+                    special
+                }
             }
         } else if num.is_nar() {
             let sign = num.sign().unwrap_or(false);
-            IEEE754 {
-                num: IEEE754Val::Nan(sign, true, Integer::zero()),
-                flags: Exceptions::default(),
-                ctx: self.clone(),
+            match self.specials {
+                SpecialValues::Finite => {
+                    // no NaNs, so this is invalid
+                    let mut zero = self.zero(false);
+                    zero.flags.invalid = true;
+                    zero
+                }
+                _ => IEEE754 {
+                    num: IEEE754Val::Nan(sign, true, Integer::zero()),
+                    flags: Exceptions::default(),
+                    ctx: self.clone(),
+                },
             }
         } else {
             // step 1: rounding as an unbounded, fixed-precision floating-point,
@@ -497,13 +812,16 @@ impl RoundingContext for IEEE754Context {
                 Some(e) => {
                     // need to actually compute the flags
                     let tiny_pre = e < self.emin();
-                    let tiny_post = self.round_tiny(&split);
+                    let tiny_post = match self.sr {
+                        Some(_) => tiny_pre,
+                        None => self.round_tiny(&split),
+                    };
                     (tiny_pre, tiny_post)
                 }
             };
 
             // step 4: finalize the rounding (unbounded exponent)
-            let unbounded = RFloatContext::round_finalize(split, self.rm);
+            let unbounded = RFloatContext::round_finalize_with(split, self.rm, self.sr.as_ref());
 
             // step 5: carry flag
             let carry = match (unrounded_e, unbounded.e()) {
@@ -516,3 +834,21 @@ impl RoundingContext for IEEE754Context {
         }
     }
 }
+
+impl RationalContext for IEEE754Context {
+    fn rational_n(&self, e: isize) -> isize {
+        // subnormal numbers only lose more digits
+        e - (self.max_p() as isize)
+    }
+
+    fn rational_guard_bits(&self) -> usize {
+        guard_bits(self.stochastic_rounding())
+    }
+
+    fn rational_e_bounds(&self) -> (Option<isize>, Option<isize>) {
+        // tiny values below the guard digits of the smallest subnormal
+        // and values at least `2^(emax+1)` always round the same
+        let emin = self.expmin() - 1 - self.rational_guard_bits() as isize;
+        (Some(emin), Some(self.emax()))
+    }
+}
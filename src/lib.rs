@@ -43,28 +43,66 @@
 //!  - [`IEEE754Context`][crate::ieee754::IEEE754Context]
 //!     rounds a [`Real`] value to a floating-point number as described by
 //!     the IEEE 754 standard,
+//!  - [`DecimalContext`][crate::decimal::DecimalContext]
+//!     rounds a [`Real`] value to a decimal floating-point number as
+//!     described by the IEEE 754 standard,
+//!  - [`MultiDoubleContext`][crate::multidouble::MultiDoubleContext]
+//!     rounds a [`Real`] value to an unevaluated sum of IEEE 754
+//!     double-precision numbers, e.g., double-double,
+//!  - [`X87Context`][crate::x87::X87Context]
+//!     rounds a [`Real`] value to the x87 80-bit extended precision format,
 //!  - [`FixedContext`][crate::fixed::FixedContext]
 //!     rounds a [`Real`] value to a fixed-point numbers,
+//!  - [`DecimalFixedContext`][crate::decfixed::DecimalFixedContext]
+//!     rounds a [`Real`] value to a decimal fixed-point numbers,
 //!  - [`PositContext`][crate::posit::PositContext]
 //!     rounds a [`Real`] value to a posit number as described by
-//!     the Posit standard.
+//!     the Posit standard,
+//!  - [`TakumContext`][crate::takum::TakumContext]
+//!     rounds a [`Real`] value to a takum number,
+//!  - [`LnsContext`][crate::lns::LnsContext]
+//!     rounds a [`Real`] value to a logarithmic number.
+//!
+//! Block formats sharing a common scale, e.g., the OCP microscaling
+//! formats, are built on top of these contexts (see [`crate::mx`]).
+//!
+//! Decimal strings can be parsed and correctly rounded under most of
+//! these contexts through [`RoundedParse`][crate::parse::RoundedParse].
+//! Exact rational values are rounded similarly through
+//! [`RoundRational`][crate::rational::RoundRational].
+//! Conversely, numbers are printed as the shortest decimal string
+//! that rounds back to the same value (see [`crate::display`]).
 //!
 
+pub mod decfixed;
+pub mod decimal;
 pub mod fixed;
 pub mod float;
 pub mod ieee754;
+pub mod lns;
+pub mod multidouble;
+pub mod mx;
 pub mod posit;
 pub mod real;
 pub mod rfloat;
+pub mod takum;
+pub mod x87;
 
+mod compare;
+pub mod display;
 pub mod mpfr;
 mod number;
 pub mod ops;
+pub mod parse;
+pub mod rational;
 mod round;
 mod split;
+mod stochastic;
 mod util;
 
+pub use crate::compare::Predicate;
 pub use crate::number::Real;
 pub use crate::rfloat::RFloat;
 pub use crate::round::{RoundingContext, RoundingDirection, RoundingMode};
 pub use crate::split::Split;
+pub use crate::stochastic::{RandomSource, SplitMix64, StochasticMode, StochasticRounding};
@@ -0,0 +1,19 @@
+//! Logarithmic number systems (LNS).
+//!
+//! This module implements logarithmic numbers with [`LnsContext`].
+//! The associated storage type is [`Lns`] which represents a
+//! number by its sign and a fixed-point, base-2 logarithm of
+//! its magnitude. Multiplication, division, and square root are
+//! (almost) exact in the log domain, while addition and subtraction
+//! require evaluating a transcendental function.
+//!
+//! Most LNS values are irrational, so they cannot be represented
+//! exactly as a [`Real`][crate::Real] value. See [`Lns`] for details.
+
+mod number;
+pub mod ops;
+mod round;
+
+pub use number::Lns;
+pub(crate) use number::LnsVal;
+pub use round::LnsContext;
@@ -0,0 +1,234 @@
+use std::cmp::Ordering;
+
+use rug::float::Round;
+use rug::{Float, Integer, Rational};
+
+use crate::rfloat::RFloat;
+use crate::{Real, RoundingMode};
+
+use super::LnsContext;
+
+/// LNS number encoding viewed as an enumeration.
+#[derive(Clone, Debug)]
+pub enum LnsVal {
+    /// Exact zero
+    Zero,
+    /// Non-zero value `(-1)^s * 2^(l * 2^-fbits)` where `l` is
+    /// the scaled logarithm
+    NonZero(bool, Integer),
+    /// Not a number
+    Nan,
+}
+
+/// Logarithmic number format.
+///
+/// The associated [`RoundingContext`][crate::RoundingContext]
+/// implementation is [`LnsContext`].
+/// See [`LnsContext`] for more details on numerical properties
+/// of the [`Lns`] type.
+///
+/// An [`Lns`] value is exactly `(-1)^s * 2^l` where `l` is a
+/// fixed-point number. When `l` is an integer, the value is
+/// dyadic and the [`Real`] implementation exposes it exactly.
+/// Otherwise, the value is irrational and the [`Real`]
+/// implementation exposes an approximation with
+/// [`LnsContext::APPROX_P`] bits of precision, correctly rounded
+/// to nearest. The exact logarithm is available through [`Lns::log2`].
+#[derive(Clone, Debug)]
+pub struct Lns {
+    pub(crate) num: LnsVal,
+    pub(crate) ctx: LnsContext,
+}
+
+impl Lns {
+    /// Returns the rounding context under which this number was created.
+    pub fn ctx(&self) -> &LnsContext {
+        &self.ctx
+    }
+
+    /// Returns true if this number is NaN.
+    pub fn is_nan(&self) -> bool {
+        matches!(self.num, LnsVal::Nan)
+    }
+
+    /// Returns the exact base-2 logarithm of the magnitude of this number.
+    /// Returns `None` if this number is zero or NaN.
+    pub fn log2(&self) -> Option<Rational> {
+        match &self.num {
+            LnsVal::NonZero(_, l) => Some(Rational::from(l.clone()) >> (self.ctx.fbits() as u32)),
+            _ => None,
+        }
+    }
+
+    /// Returns true if the value of this number is dyadic,
+    /// i.e., exactly representable as a [`Real`] value.
+    pub fn is_exact(&self) -> bool {
+        match &self.num {
+            LnsVal::NonZero(_, l) => l.is_divisible_2pow(self.ctx.fbits() as u32),
+            _ => true,
+        }
+    }
+
+    /// Converts this [`Lns`] to the nearest [`f64`] value
+    /// according to the rounding mode `rm`.
+    pub fn to_f64(&self, rm: RoundingMode) -> f64 {
+        self.to_rfloat().to_f64(rm)
+    }
+
+    /// Converts this [`Lns`] to the nearest [`f32`] value
+    /// according to the rounding mode `rm`.
+    pub fn to_f32(&self, rm: RoundingMode) -> f32 {
+        self.to_rfloat().to_f32(rm)
+    }
+
+    /// Converts this [`Lns`] to an [`Integer`] representing an LNS bitpattern.
+    pub fn into_bits(self) -> Integer {
+        let nbits = self.ctx.nbits();
+        let lbits = nbits - 1;
+        let special = Integer::from(1) << (lbits - 1);
+        let (s, lfield) = match self.num {
+            LnsVal::Zero => (false, special),
+            LnsVal::Nan => (true, special),
+            LnsVal::NonZero(s, l) => (s, l.keep_bits(lbits as u32)),
+        };
+
+        if s {
+            (Integer::from(1) << lbits) | lfield
+        } else {
+            lfield
+        }
+    }
+
+    /// Returns the exact value of this number if it is dyadic,
+    /// or an approximation otherwise.
+    fn to_rfloat(&self) -> RFloat {
+        match &self.num {
+            LnsVal::Zero => RFloat::zero(),
+            LnsVal::Nan => RFloat::Nan,
+            LnsVal::NonZero(s, l) => {
+                let fbits = self.ctx.fbits();
+                if self.is_exact() {
+                    let exp = (l.clone() >> (fbits as u32)).to_isize().unwrap();
+                    RFloat::Real(*s, exp, Integer::from(1))
+                } else {
+                    let mut x = Float::with_val(LnsContext::APPROX_P, l) >> (fbits as u32);
+                    x.exp2_round(Round::Nearest);
+                    match RFloat::from(x) {
+                        RFloat::Real(_, exp, c) => RFloat::Real(*s, exp, c),
+                        _ => unreachable!(),
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl Real for Lns {
+    fn radix() -> usize {
+        2
+    }
+
+    fn sign(&self) -> Option<bool> {
+        match &self.num {
+            LnsVal::NonZero(s, _) => Some(*s),
+            _ => None,
+        }
+    }
+
+    fn exp(&self) -> Option<isize> {
+        self.to_rfloat().exp()
+    }
+
+    fn e(&self) -> Option<isize> {
+        self.to_rfloat().e()
+    }
+
+    fn n(&self) -> Option<isize> {
+        self.to_rfloat().n()
+    }
+
+    fn c(&self) -> Option<Integer> {
+        self.to_rfloat().c()
+    }
+
+    fn m(&self) -> Option<Integer> {
+        self.to_rfloat().m()
+    }
+
+    fn prec(&self) -> Option<usize> {
+        self.to_rfloat().prec()
+    }
+
+    fn is_nar(&self) -> bool {
+        self.is_nan()
+    }
+
+    fn is_finite(&self) -> bool {
+        !self.is_nan()
+    }
+
+    fn is_infinite(&self) -> bool {
+        false
+    }
+
+    fn is_zero(&self) -> bool {
+        matches!(self.num, LnsVal::Zero)
+    }
+
+    fn is_negative(&self) -> Option<bool> {
+        self.sign()
+    }
+
+    fn is_numerical(&self) -> bool {
+        !self.is_nan()
+    }
+
+    fn exact_log2(&self) -> Option<Rational> {
+        self.log2()
+    }
+}
+
+impl From<Lns> for RFloat {
+    fn from(value: Lns) -> Self {
+        value.to_rfloat()
+    }
+}
+
+impl PartialEq for Lns {
+    fn eq(&self, other: &Self) -> bool {
+        self.partial_cmp(other) == Some(Ordering::Equal)
+    }
+}
+
+impl PartialOrd for Lns {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        // compare exactly: logarithms of values in different formats
+        // can be compared as rationals
+        match (&self.num, &other.num) {
+            (LnsVal::Nan, _) | (_, LnsVal::Nan) => None,
+            (LnsVal::Zero, LnsVal::Zero) => Some(Ordering::Equal),
+            (LnsVal::Zero, LnsVal::NonZero(s, _)) => Some(if *s {
+                Ordering::Greater
+            } else {
+                Ordering::Less
+            }),
+            (LnsVal::NonZero(s, _), LnsVal::Zero) => Some(if *s {
+                Ordering::Less
+            } else {
+                Ordering::Greater
+            }),
+            (LnsVal::NonZero(s1, _), LnsVal::NonZero(s2, _)) => {
+                if s1 != s2 {
+                    Some(if *s1 {
+                        Ordering::Less
+                    } else {
+                        Ordering::Greater
+                    })
+                } else {
+                    let ord = self.log2().unwrap().cmp(&other.log2().unwrap());
+                    Some(if *s1 { ord.reverse() } else { ord })
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,386 @@
+use std::cmp::Ordering;
+
+use rug::float::Round;
+use rug::{Float, Rational};
+
+use crate::compare::exact_magnitude;
+use crate::lns::{Lns, LnsContext, LnsVal};
+use crate::ops::*;
+use crate::rfloat::RFloat;
+use crate::{Real, RoundingContext, RoundingMode};
+
+/// A finite, non-zero operand of an LNS operation.
+/// Operands with a rational logarithm are kept in the log domain
+/// so that results with a rational logarithm are rounded exactly.
+enum Operand {
+    /// `(-1)^s * 2^l` where `l` is rational
+    Log(bool, Rational),
+    /// `(-1)^s * q` where `log2(q)` is irrational
+    Value(bool, Rational),
+}
+
+impl Operand {
+    /// Classifies a finite, non-zero value.
+    fn new<N: Real>(src: &N) -> Self {
+        let s = src.sign().unwrap();
+        if let Some(l) = src.exact_log2() {
+            return Operand::Log(s, l);
+        }
+
+        // the logarithm of a rational value is either
+        // an integer or irrational
+        let q = exact_magnitude(src);
+        let (num, den) = (q.numer(), q.denom());
+        if num.is_power_of_two() && den.is_power_of_two() {
+            let l = num.significant_bits() as isize - den.significant_bits() as isize;
+            Operand::Log(s, Rational::from(l))
+        } else {
+            Operand::Value(s, q)
+        }
+    }
+
+    /// The sign of this operand.
+    fn sign(&self) -> bool {
+        match self {
+            Operand::Log(s, _) | Operand::Value(s, _) => *s,
+        }
+    }
+
+    /// Negates this operand.
+    fn neg(self) -> Self {
+        match self {
+            Operand::Log(s, l) => Operand::Log(!s, l),
+            Operand::Value(s, q) => Operand::Value(!s, q),
+        }
+    }
+
+    /// Computes a lower and upper bound of the magnitude
+    /// with `prec` bits of precision.
+    fn bounds(&self, prec: u32) -> (Float, Float) {
+        match self {
+            Operand::Log(_, l) => {
+                let (l_lo, _) = Float::with_val_round(prec, l, Round::Down);
+                let (l_hi, _) = Float::with_val_round(prec, l, Round::Up);
+                let (lo, _) = Float::with_val_round(prec, l_lo.exp2_ref(), Round::Down);
+                let (hi, _) = Float::with_val_round(prec, l_hi.exp2_ref(), Round::Up);
+                (lo, hi)
+            }
+            Operand::Value(_, q) => {
+                let (lo, _) = Float::with_val_round(prec, q, Round::Down);
+                let (hi, _) = Float::with_val_round(prec, q, Round::Up);
+                (lo, hi)
+            }
+        }
+    }
+
+    /// Compares the magnitudes of two operands.
+    fn cmp_magnitude(&self, other: &Operand) -> Ordering {
+        match (self, other) {
+            (Operand::Log(_, la), Operand::Log(_, lb)) => la.cmp(lb),
+            (Operand::Value(_, qa), Operand::Value(_, qb)) => qa.cmp(qb),
+            _ => {
+                // one logarithm is rational and the other is not,
+                // so the magnitudes differ
+                let mut prec = 64;
+                loop {
+                    let (a_lo, a_hi) = self.bounds(prec);
+                    let (b_lo, b_hi) = other.bounds(prec);
+                    if a_hi < b_lo {
+                        return Ordering::Less;
+                    } else if b_hi < a_lo {
+                        return Ordering::Greater;
+                    }
+                    prec *= 2;
+                }
+            }
+        }
+    }
+}
+
+impl LnsContext {
+    /// Rounds a positive value with sign `s` given its bounds
+    /// (see [`LnsContext::round_log`]).
+    fn round_bounds<F>(&self, s: bool, bounds: F) -> Lns
+    where
+        F: Fn(u32) -> (Float, Float),
+    {
+        let l = self.round_log(bounds);
+        self.make(s, l)
+    }
+
+    /// Rounds the exact sum of two finite, non-zero operands.
+    fn round_sum(&self, a: Operand, b: Operand) -> Lns {
+        // order by magnitude, `a` is larger
+        let (a, b) = match a.cmp_magnitude(&b) {
+            Ordering::Less => (b, a),
+            _ => (a, b),
+        };
+
+        let s = a.sign();
+        let add = a.sign() == b.sign();
+        match (&a, &b) {
+            (Operand::Log(_, la), Operand::Log(_, lb)) if la == lb => {
+                if add {
+                    // `2^l + 2^l = 2^(l + 1)`
+                    self.round_exact_log(s, &(la.clone() + 1))
+                } else {
+                    // exact cancellation
+                    self.zero()
+                }
+            }
+            (Operand::Log(_, la), Operand::Log(_, lb)) if !add && la.clone() - lb == 1 => {
+                // `2^(l + 1) - 2^l = 2^l`
+                self.round_exact_log(s, lb)
+            }
+            (Operand::Value(_, qa), Operand::Value(_, qb)) if !add && qa == qb => {
+                // exact cancellation
+                self.zero()
+            }
+            _ => self.round_bounds(s, |prec| {
+                let (a_lo, a_hi) = a.bounds(prec);
+                let (b_lo, b_hi) = b.bounds(prec);
+                if add {
+                    let (lo, _) = Float::with_val_round(prec, &a_lo + &b_lo, Round::Down);
+                    let (hi, _) = Float::with_val_round(prec, &a_hi + &b_hi, Round::Up);
+                    (lo, hi)
+                } else {
+                    let (lo, _) = Float::with_val_round(prec, &a_lo - &b_hi, Round::Down);
+                    let (hi, _) = Float::with_val_round(prec, &a_hi - &b_lo, Round::Up);
+                    (lo, hi)
+                }
+            }),
+        }
+    }
+}
+
+impl RoundedNeg for LnsContext {
+    fn neg<N: Real>(&self, src: &N) -> Self::Format {
+        let mut x = self.round(src);
+        if let LnsVal::NonZero(s, _) = &mut x.num {
+            *s = !*s;
+        }
+        x
+    }
+}
+
+impl RoundedAbs for LnsContext {
+    fn abs<N: Real>(&self, src: &N) -> Self::Format {
+        let mut x = self.round(src);
+        if let LnsVal::NonZero(s, _) = &mut x.num {
+            *s = false;
+        }
+        x
+    }
+}
+
+impl RoundedSqrt for LnsContext {
+    fn sqrt<N: Real>(&self, src: &N) -> Self::Format {
+        if src.is_nar() {
+            self.nan()
+        } else if src.is_zero() {
+            self.zero()
+        } else {
+            match Operand::new(src) {
+                Operand::Log(false, l) => self.round_exact_log(false, &(l / 2)),
+                x @ Operand::Value(false, _) => self.round_bounds(false, |prec| {
+                    let (x_lo, x_hi) = x.bounds(prec);
+                    let (lo, _) = Float::with_val_round(prec, x_lo.sqrt_ref(), Round::Down);
+                    let (hi, _) = Float::with_val_round(prec, x_hi.sqrt_ref(), Round::Up);
+                    (lo, hi)
+                }),
+                _ => self.nan(),
+            }
+        }
+    }
+}
+
+impl RoundedRecip for LnsContext {
+    fn recip<N: Real>(&self, src: &N) -> Self::Format {
+        if src.is_nar() || src.is_zero() {
+            self.nan()
+        } else {
+            match Operand::new(src) {
+                Operand::Log(s, l) => self.round_exact_log(s, &-l),
+                x => self.round_bounds(x.sign(), |prec| {
+                    let (x_lo, x_hi) = x.bounds(prec);
+                    let (lo, _) = Float::with_val_round(prec, x_hi.recip_ref(), Round::Down);
+                    let (hi, _) = Float::with_val_round(prec, x_lo.recip_ref(), Round::Up);
+                    (lo, hi)
+                }),
+            }
+        }
+    }
+}
+
+impl RoundedAdd for LnsContext {
+    fn add<N1, N2>(&self, src1: &N1, src2: &N2) -> Self::Format
+    where
+        N1: Real,
+        N2: Real,
+    {
+        if src1.is_nar() || src2.is_nar() {
+            self.nan()
+        } else if src1.is_zero() {
+            self.round(src2)
+        } else if src2.is_zero() {
+            self.round(src1)
+        } else {
+            self.round_sum(Operand::new(src1), Operand::new(src2))
+        }
+    }
+}
+
+impl RoundedSub for LnsContext {
+    fn sub<N1, N2>(&self, src1: &N1, src2: &N2) -> Self::Format
+    where
+        N1: Real,
+        N2: Real,
+    {
+        if src1.is_nar() || src2.is_nar() {
+            self.nan()
+        } else if src1.is_zero() {
+            self.neg(src2)
+        } else if src2.is_zero() {
+            self.round(src1)
+        } else {
+            self.round_sum(Operand::new(src1), Operand::new(src2).neg())
+        }
+    }
+}
+
+impl RoundedMul for LnsContext {
+    fn mul<N1, N2>(&self, src1: &N1, src2: &N2) -> Self::Format
+    where
+        N1: Real,
+        N2: Real,
+    {
+        if src1.is_nar() || src2.is_nar() {
+            self.nan()
+        } else if src1.is_zero() || src2.is_zero() {
+            self.zero()
+        } else {
+            match (Operand::new(src1), Operand::new(src2)) {
+                (Operand::Log(sa, la), Operand::Log(sb, lb)) => {
+                    self.round_exact_log(sa != sb, &(la + lb))
+                }
+                (a, b) => self.round_bounds(a.sign() != b.sign(), |prec| {
+                    let (a_lo, a_hi) = a.bounds(prec);
+                    let (b_lo, b_hi) = b.bounds(prec);
+                    let (lo, _) = Float::with_val_round(prec, &a_lo * &b_lo, Round::Down);
+                    let (hi, _) = Float::with_val_round(prec, &a_hi * &b_hi, Round::Up);
+                    (lo, hi)
+                }),
+            }
+        }
+    }
+}
+
+impl RoundedDiv for LnsContext {
+    fn div<N1, N2>(&self, src1: &N1, src2: &N2) -> Self::Format
+    where
+        N1: Real,
+        N2: Real,
+    {
+        if src1.is_nar() || src2.is_nar() || src2.is_zero() {
+            self.nan()
+        } else if src1.is_zero() {
+            self.zero()
+        } else {
+            match (Operand::new(src1), Operand::new(src2)) {
+                (Operand::Log(sa, la), Operand::Log(sb, lb)) => {
+                    self.round_exact_log(sa != sb, &(la - lb))
+                }
+                (a, b) => self.round_bounds(a.sign() != b.sign(), |prec| {
+                    let (a_lo, a_hi) = a.bounds(prec);
+                    let (b_lo, b_hi) = b.bounds(prec);
+                    let (lo, _) = Float::with_val_round(prec, &a_lo / &b_hi, Round::Down);
+                    let (hi, _) = Float::with_val_round(prec, &a_hi / &b_lo, Round::Up);
+                    (lo, hi)
+                }),
+            }
+        }
+    }
+}
+
+macro_rules! rounded_integral_impl {
+    ($tname:ident, $name:ident, $rm:expr) => {
+        impl $tname for LnsContext {
+            fn $name<N: Real>(&self, src: &N) -> Self::Format {
+                let (integral, _) = round_integral(src, $rm);
+                self.round(&integral)
+            }
+        }
+    };
+}
+
+rounded_integral_impl!(RoundedFloor, floor, RoundingMode::ToNegative);
+rounded_integral_impl!(RoundedCeil, ceil, RoundingMode::ToPositive);
+rounded_integral_impl!(RoundedTrunc, trunc, RoundingMode::ToZero);
+rounded_integral_impl!(
+    RoundedRoundAway,
+    round_away,
+    RoundingMode::NearestTiesAwayZero
+);
+rounded_integral_impl!(
+    RoundedRoundEven,
+    round_even,
+    RoundingMode::NearestTiesToEven
+);
+
+impl RoundedNearbyInt for LnsContext {
+    fn nearbyint<N: Real>(&self, src: &N) -> Self::Format {
+        let (integral, _) = round_integral(src, RoundingMode::NearestTiesToEven);
+        self.round(&integral)
+    }
+}
+
+impl RoundedRint for LnsContext {
+    fn rint<N: Real>(&self, src: &N) -> Self::Format {
+        // no exception flags, so this is just `nearbyint`
+        self.nearbyint(src)
+    }
+}
+
+impl RoundedConvertToInteger for LnsContext {
+    fn convert_to_integer<N: Real>(&self, src: &N, rm: RoundingMode, _exact: bool) -> Self::Format {
+        // no exception flags, so this is just rounding
+        let (integral, _) = round_integral(src, rm);
+        self.round(&integral)
+    }
+}
+
+macro_rules! min_max_impl {
+    ($tname:ident, $name:ident, $op:expr) => {
+        impl $tname for LnsContext {
+            fn $name<N1, N2>(&self, src1: &N1, src2: &N2) -> Self::Format
+            where
+                N1: Real,
+                N2: Real,
+            {
+                // no exception flags, so this is just selection
+                match min_max(src1, src2, $op).0 {
+                    MinMaxResult::First => self.round(src1),
+                    MinMaxResult::Second => self.round(src2),
+                    MinMaxResult::Nan => self.round(&RFloat::Nan),
+                }
+            }
+        }
+    };
+}
+
+min_max_impl!(RoundedMinimum, minimum, MinMax::Minimum);
+min_max_impl!(RoundedMaximum, maximum, MinMax::Maximum);
+min_max_impl!(RoundedMinimumNumber, minimum_number, MinMax::MinimumNumber);
+min_max_impl!(RoundedMaximumNumber, maximum_number, MinMax::MaximumNumber);
+min_max_impl!(
+    RoundedMinimumMagnitude,
+    minimum_magnitude,
+    MinMax::MinimumMagnitude
+);
+min_max_impl!(
+    RoundedMaximumMagnitude,
+    maximum_magnitude,
+    MinMax::MaximumMagnitude
+);
+min_max_impl!(RoundedMinNum, min_num, MinMax::MinNum);
+min_max_impl!(RoundedMaxNum, max_num, MinMax::MaxNum);
@@ -0,0 +1,267 @@
+use std::cmp::Ordering;
+
+use rug::float::Round;
+use rug::{Float, Integer, Rational};
+
+use crate::decimal::dfloat::scale10;
+use crate::rational::decimal_e_bounds;
+use crate::rfloat::RFloat;
+use crate::util::bitmask;
+use crate::{Real, RoundingContext};
+
+use super::{Lns, LnsVal};
+
+/// Rounding contexts for logarithmic numbers.
+///
+/// The associated storage type is [`Lns`].
+///
+/// Values rounded under this context are logarithmic numbers:
+/// `(-1)^s * 2^l` where `l` is a signed fixed-point number
+/// with `ibits` integer bits (including its sign bit) and `fbits`
+/// fractional bits. An LNS number is encoded in `1 + ibits + fbits`
+/// bits as a sign bit followed by the two's complement encoding
+/// of `l`. The most negative logarithm `-2^(ibits - 1)` is reserved:
+/// it encodes zero when the sign bit is clear and NaN when
+/// the sign bit is set.
+///
+/// A [`LnsContext`] is parameterized by
+///
+///  - number of integer bits of the logarithm,
+///  - number of fractional bits of the logarithm.
+///
+/// Values are rounded to nearest in the log domain, that is,
+/// the logarithm `log2(|x|)` is rounded to the nearest fixed-point
+/// value with ties to even. Values beyond the largest or smallest
+/// magnitude saturate; a non-zero value never rounds to zero.
+/// Infinities and NaN round to NaN.
+#[derive(Clone, Debug)]
+pub struct LnsContext {
+    ibits: usize,
+    fbits: usize,
+}
+
+impl LnsContext {
+    /// Implementation limit: maximum number of fractional bits
+    pub const FBITS_MAX: usize = 64;
+
+    /// Precision of the approximation of a non-dyadic [`Lns`]
+    /// value when viewed as a [`Real`] value.
+    pub const APPROX_P: u32 = 2 * Self::FBITS_MAX as u32;
+
+    /// Constructs a new rounding context.
+    pub fn new(ibits: usize, fbits: usize) -> Self {
+        assert!(ibits >= 1, "must have at least one integer bit");
+        assert!(
+            fbits <= Self::FBITS_MAX,
+            "fractional bitwidth needs to be at most {} bits, given {} bits",
+            Self::FBITS_MAX,
+            fbits
+        );
+
+        Self { ibits, fbits }
+    }
+
+    /// Returns the number of integer bits of the logarithm.
+    pub fn ibits(&self) -> usize {
+        self.ibits
+    }
+
+    /// Returns the number of fractional bits of the logarithm.
+    pub fn fbits(&self) -> usize {
+        self.fbits
+    }
+
+    /// Returns the total bitwidth of the format produced by this context
+    /// (when viewed as a bitvector).
+    pub fn nbits(&self) -> usize {
+        1 + self.ibits + self.fbits
+    }
+
+    /// Largest representable scaled logarithm.
+    fn lmax(&self) -> Integer {
+        bitmask(self.ibits + self.fbits - 1)
+    }
+
+    /// Smallest representable scaled logarithm.
+    fn lmin(&self) -> Integer {
+        -self.lmax()
+    }
+
+    /// Bounds on the normalized exponent of values that round
+    /// to neither the minimum nor the maximum value. Every value whose
+    /// normalized exponent lies below the lower bound (above the upper
+    /// bound) rounds to the minimum (maximum) value.
+    fn e_bounds(&self) -> (isize, isize) {
+        let emin = self.lmin() >> self.fbits;
+        let emax = self.lmax() >> self.fbits;
+        (
+            emin.to_isize().unwrap_or(isize::MIN),
+            emax.to_isize().unwrap_or(isize::MAX),
+        )
+    }
+
+    /// Constructs `(-1)^s * 2^(l * 2^-fbits)`, saturating
+    /// if `l` is out of range.
+    pub(crate) fn make(&self, s: bool, l: Integer) -> Lns {
+        let l = l.clamp(&self.lmin(), &self.lmax());
+        Lns {
+            num: LnsVal::NonZero(s, l),
+            ctx: self.clone(),
+        }
+    }
+
+    /// Maximum representable value.
+    pub fn maxval(&self, sign: bool) -> Lns {
+        self.make(sign, self.lmax())
+    }
+
+    /// Minimum (positive) representable value.
+    pub fn minval(&self, sign: bool) -> Lns {
+        self.make(sign, self.lmin())
+    }
+
+    /// Constructs zero in this format.
+    pub fn zero(&self) -> Lns {
+        Lns {
+            num: LnsVal::Zero,
+            ctx: self.clone(),
+        }
+    }
+
+    /// Constructs NaN in this format.
+    pub fn nan(&self) -> Lns {
+        Lns {
+            num: LnsVal::Nan,
+            ctx: self.clone(),
+        }
+    }
+
+    /// Converts an [`Integer`] representing an LNS bitpattern into
+    /// an [`Lns`] value under this [`LnsContext`].
+    pub fn bits_to_number(&self, b: Integer) -> Lns {
+        let limit = Integer::from(1) << self.nbits();
+        assert!(b < limit, "must be less than 1 << nbits");
+
+        // decompose into sign and logarithm
+        let lbits = self.ibits + self.fbits;
+        let s = b.get_bit(lbits as u32);
+        let lfield = b & bitmask(lbits);
+
+        if lfield == (Integer::from(1) << (lbits - 1)) {
+            // reserved logarithm
+            if s {
+                self.nan()
+            } else {
+                self.zero()
+            }
+        } else if lfield.get_bit((lbits - 1) as u32) {
+            // negative logarithm
+            self.make(s, lfield - (Integer::from(1) << lbits))
+        } else {
+            self.make(s, lfield)
+        }
+    }
+
+    /// Rounds the logarithm of a positive value to the nearest
+    /// scaled logarithm (ties to even). The closure computes a lower
+    /// and upper bound of the value with a given working precision.
+    /// The precision is increased until both bounds round to
+    /// the same logarithm.
+    pub(crate) fn round_log<F>(&self, bounds: F) -> Integer
+    where
+        F: Fn(u32) -> (Float, Float),
+    {
+        // the logarithm of a rational value is either an integer or
+        // irrational, so the loop terminates for exact values
+        let mut prec = (self.ibits + self.fbits + 16) as u32;
+        loop {
+            let (lo, hi) = bounds(prec);
+            if lo.is_sign_positive() && !lo.is_zero() {
+                let (llo, _) = Float::with_val_round(prec, lo.log2_ref(), Round::Down);
+                let (lhi, _) = Float::with_val_round(prec, hi.log2_ref(), Round::Up);
+                let llo = (llo << (self.fbits as u32)).round_even();
+                let lhi = (lhi << (self.fbits as u32)).round_even();
+                if llo == lhi || prec > Self::PREC_MAX {
+                    return llo.to_integer().unwrap();
+                }
+            }
+
+            prec *= 2;
+        }
+    }
+
+    /// Returns the minimum or maximum value if a finite, non-zero
+    /// value lies entirely outside the exponent bounds of this context
+    /// (see [`LnsContext::e_bounds`]).
+    fn saturate<T: Real>(&self, val: &T) -> Option<Lns> {
+        let s = val.sign().unwrap();
+        let (lo, hi) = if T::radix() == 10 {
+            decimal_e_bounds(&val.c().unwrap(), val.exp().unwrap())
+        } else {
+            let e = val.e().unwrap();
+            (e, e)
+        };
+
+        let (emin, emax) = self.e_bounds();
+        if lo > emax {
+            Some(self.maxval(s))
+        } else if hi < emin {
+            Some(self.minval(s))
+        } else {
+            None
+        }
+    }
+
+    /// Rounds `(-1)^s * 2^l` given its exact logarithm `l`,
+    /// i.e., rounds `l` to the nearest scaled logarithm
+    /// (ties to even).
+    pub(crate) fn round_exact_log(&self, s: bool, l: &Rational) -> Lns {
+        let scaled = l.clone() << (self.fbits as u32);
+        let (frac, mut q) = scaled.fract_floor(Integer::new());
+        match frac.cmp(&Rational::from((1, 2))) {
+            Ordering::Greater => q += 1,
+            Ordering::Equal if q.is_odd() => q += 1,
+            _ => {}
+        }
+        self.make(s, q)
+    }
+
+    /// Implementation limit: working precision for [`LnsContext::round_log`]
+    const PREC_MAX: u32 = 1 << 16;
+}
+
+impl RoundingContext for LnsContext {
+    type Format = Lns;
+
+    fn round<T: Real>(&self, val: &T) -> Self::Format {
+        if val.is_nar() {
+            // all NaN and infinities are mapped to NaN
+            self.nan()
+        } else if val.is_zero() {
+            // exact zeros are mapped to zero
+            self.zero()
+        } else if let Some(l) = val.exact_log2() {
+            // logarithmic values are rounded through their exact logarithm
+            self.round_exact_log(val.sign().unwrap(), &l)
+        } else if let Some(num) = self.saturate(val) {
+            // values far outside the range saturate
+            num
+        } else if T::radix() == 10 {
+            // decimal values are bounded by rounding their exact value
+            let s = val.sign().unwrap();
+            let q = scale10(val.c().unwrap(), val.exp().unwrap());
+            let l = self.round_log(|prec| {
+                let (lo, _) = Float::with_val_round(prec, &q, Round::Down);
+                let (hi, _) = Float::with_val_round(prec, &q, Round::Up);
+                (lo, hi)
+            });
+            self.make(s, l)
+        } else {
+            // the value is exact, so its bounds are trivial
+            let s = val.sign().unwrap();
+            let x = Float::from(RFloat::Real(false, val.exp().unwrap(), val.c().unwrap()));
+            let l = self.round_log(|_| (x.clone(), x.clone()));
+            self.make(s, l)
+        }
+    }
+}
@@ -11,6 +11,8 @@ These functions are exported for convenience.
 All computation is done using [`RFloat`] values.
 */
 
+use std::os::raw::c_long;
+
 use gmp_mpfr_sys::mpfr;
 use rug::Float;
 
@@ -171,6 +173,41 @@ macro_rules! mpfr_3ary {
     };
 }
 
+/// Unary RTO operations with an integer argument.
+macro_rules! mpfr_1ary_si {
+    ($name:ident, $mpfr:ident, $cname:expr) => {
+        #[doc = "Computes `"]
+        #[doc = $cname]
+        #[doc = "` for an integer `n` to `p` binary digits of precision,"]
+        #[doc = " rounding to odd."]
+        pub fn $name(src: RFloat, n: i64, p: usize) -> MPFRResult {
+            assert!(
+                p as i64 > mpfr::PREC_MIN && p as i64 <= mpfr::PREC_MAX,
+                "precision must be between {} and {}",
+                mpfr::PREC_MIN + 1,
+                mpfr::PREC_MAX
+            );
+
+            // compute with `p - 1` bits
+            let mut dst = Float::new((p - 1) as u32);
+            let src = Float::from(src);
+            let (t, flags) = unsafe {
+                mpfr::clear_flags();
+                let t = mpfr::$mpfr(
+                    dst.as_raw_mut(),
+                    src.as_raw(),
+                    n as c_long,
+                    mpfr::rnd_t::RNDZ,
+                );
+                (t, mpfr_flags())
+            };
+
+            // compose result
+            MPFRResult::new(dst, t, flags, p)
+        }
+    };
+}
+
 // Unary operators
 mpfr_1ary!(mpfr_neg, neg, "(- x)");
 mpfr_1ary!(mpfr_abs, abs, "|x|");
@@ -198,6 +235,9 @@ mpfr_1ary!(mpfr_tan_pi, tanpi, "tan(pi * x)");
 mpfr_1ary!(mpfr_asin, asin, "arcsin(x)");
 mpfr_1ary!(mpfr_acos, acos, "arccos(x)");
 mpfr_1ary!(mpfr_atan, atan, "arctan(x)");
+mpfr_1ary!(mpfr_asin_pi, asinpi, "arcsin(x) / pi");
+mpfr_1ary!(mpfr_acos_pi, acospi, "arccos(x) / pi");
+mpfr_1ary!(mpfr_atan_pi, atanpi, "arctan(x) / pi");
 mpfr_1ary!(mpfr_sinh, sinh, "sinh(x)");
 mpfr_1ary!(mpfr_cosh, cosh, "cosh(x)");
 mpfr_1ary!(mpfr_tanh, tanh, "tanh(x)");
@@ -209,16 +249,24 @@ mpfr_1ary!(mpfr_erfc, erfc, "erfc(x)");
 mpfr_1ary!(mpfr_tgamma, gamma, "tgamma(x)");
 mpfr_1ary!(mpfr_lgamma, lngamma, "lgamma(x)");
 
+// Unary operators with an integer argument
+mpfr_1ary_si!(mpfr_compound, compound_si, "(1 + x)^n");
+mpfr_1ary_si!(mpfr_rootn, rootn_si, "x^(1/n)");
+mpfr_1ary_si!(mpfr_pown, pow_si, "x^n");
+
 // Binary operators
 mpfr_2ary!(mpfr_add, add, "x + y");
 mpfr_2ary!(mpfr_sub, sub, "x - y");
 mpfr_2ary!(mpfr_mul, mul, "x * y");
 mpfr_2ary!(mpfr_div, div, "x / y");
 mpfr_2ary!(mpfr_pow, pow, "x ^ y");
+mpfr_2ary!(mpfr_powr, powr, "e^(y * ln(x))");
 mpfr_2ary!(mpfr_hypot, hypot, "sqrt(x^2 + y^2)");
 mpfr_2ary!(mpfr_fmod, fmod, "fmod(x, y)");
 mpfr_2ary!(mpfr_remainder, remainder, "remainder(x, y)");
 mpfr_2ary!(mpfr_atan2, atan2, "arctan(y / x)");
+mpfr_2ary!(mpfr_atan2_pi, atan2pi, "arctan(y / x) / pi");
+mpfr_2ary!(mpfr_fdim, dim, "max(x - y, 0)");
 
 // Ternary operators
 mpfr_3ary!(mpfr_fma, fma, "a * b + c");
@@ -250,3 +298,68 @@ pub fn mpfr_recip(src: RFloat, p: usize) -> MPFRResult {
         flags,
     }
 }
+
+/// Computes `sin(x)` and `cos(x)` to `p` binary digits of precision,
+/// rounding to odd. Both results share the same flags.
+pub fn mpfr_sin_cos(src: RFloat, p: usize) -> (MPFRResult, MPFRResult) {
+    assert!(
+        p as i64 > mpfr::PREC_MIN && p as i64 <= mpfr::PREC_MAX,
+        "precision must be between {} and {}",
+        mpfr::PREC_MIN + 1,
+        mpfr::PREC_MAX
+    );
+
+    // compute with `p - 1` bits
+    let mut sin = Float::new((p - 1) as u32);
+    let mut cos = Float::new((p - 1) as u32);
+    let src = Float::from(src);
+    let (t, flags) = unsafe {
+        mpfr::clear_flags();
+        let t = mpfr::sin_cos(
+            sin.as_raw_mut(),
+            cos.as_raw_mut(),
+            src.as_raw(),
+            mpfr::rnd_t::RNDZ,
+        );
+        (t, mpfr_flags())
+    };
+
+    // the ternary value encodes both ternary values:
+    // the lower two bits for `sin(x)`, the next two for `cos(x)`
+    let sin = MPFRResult::new(sin, t & 3, flags.clone(), p);
+    let cos = MPFRResult::new(cos, t >> 2, flags, p);
+    (sin, cos)
+}
+
+/// Computes `remainder(x, y)` to `p` binary digits of precision,
+/// rounding to odd. Also returns the low-order bits of the
+/// quotient `x / y` rounded to the nearest integer (at least 3 bits)
+/// with the sign of `x / y`.
+pub fn mpfr_remquo(src1: RFloat, src2: RFloat, p: usize) -> (MPFRResult, i64) {
+    assert!(
+        p as i64 > mpfr::PREC_MIN && p as i64 <= mpfr::PREC_MAX,
+        "precision must be between {} and {}",
+        mpfr::PREC_MIN + 1,
+        mpfr::PREC_MAX
+    );
+
+    // compute with `p - 1` bits
+    let mut dst = Float::new((p - 1) as u32);
+    let mut q: c_long = 0;
+    let src1 = Float::from(src1);
+    let src2 = Float::from(src2);
+    let (t, flags) = unsafe {
+        mpfr::clear_flags();
+        let t = mpfr::remquo(
+            dst.as_raw_mut(),
+            &mut q,
+            src1.as_raw(),
+            src2.as_raw(),
+            mpfr::rnd_t::RNDZ,
+        );
+        (t, mpfr_flags())
+    };
+
+    // compose result
+    (MPFRResult::new(dst, t, flags, p), q as i64)
+}
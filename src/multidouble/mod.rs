@@ -0,0 +1,14 @@
+//! Double-double and quad-double numbers.
+//!
+//! This module implements multi-double numbers with [`MultiDoubleContext`].
+//! The associated storage type is [`MultiDouble`] which represents
+//! an unevaluated sum of two or more [`IEEE754`][crate::ieee754::IEEE754]
+//! `binary64` values. Double-double numbers are also the format of
+//! `long double` on PowerPC (IBM 128-bit extended precision).
+
+mod number;
+pub mod ops;
+mod round;
+
+pub use number::MultiDouble;
+pub use round::MultiDoubleContext;
@@ -0,0 +1,166 @@
+use std::cmp::Ordering;
+
+use rug::Integer;
+
+use crate::ieee754::{Exceptions, IEEE754};
+use crate::{rfloat::RFloat, Real, RoundingMode};
+
+use super::MultiDoubleContext;
+
+/// The multi-double number format.
+///
+/// The associated [`RoundingContext`][crate::RoundingContext]
+/// implementation is [`MultiDoubleContext`].
+/// See [`MultiDoubleContext`] for more details on numerical properties
+/// of the [`MultiDouble`] type.
+///
+/// A [`MultiDouble`] value is an unevaluated sum of `binary64`
+/// components ordered by decreasing magnitude. If the leading component
+/// is zero, infinite, or NaN, so is the whole number.
+/// A [`MultiDouble`] value also has an [`Exceptions`] instance to indicate
+/// exceptional events that occured during its construction.
+#[derive(Clone, Debug)]
+pub struct MultiDouble {
+    pub(crate) parts: Vec<IEEE754>,
+    pub(crate) flags: Exceptions,
+    pub(crate) ctx: MultiDoubleContext,
+}
+
+impl MultiDouble {
+    /// Return the flags set when this number was created.
+    pub fn flags(&self) -> &Exceptions {
+        &self.flags
+    }
+
+    /// Returns the rounding context under which this number was created.
+    pub fn ctx(&self) -> &MultiDoubleContext {
+        &self.ctx
+    }
+
+    /// Returns the components of this number, ordered by
+    /// decreasing magnitude.
+    pub fn parts(&self) -> &[IEEE754] {
+        &self.parts
+    }
+
+    /// Returns true if this number is NaN.
+    pub fn is_nan(&self) -> bool {
+        self.parts[0].is_nan()
+    }
+
+    /// Converts this [`MultiDouble`] to the nearest [`f64`] value
+    /// according to the rounding mode `rm`.
+    pub fn to_f64(&self, rm: RoundingMode) -> f64 {
+        RFloat::from(self.clone()).to_f64(rm)
+    }
+
+    /// Converts this [`MultiDouble`] to an [`Integer`] by concatenating
+    /// the `binary64` encodings of its components, leading component first.
+    /// For double-double numbers, this is the IBM 128-bit extended
+    /// precision encoding.
+    pub fn into_bits(self) -> Integer {
+        self.parts
+            .into_iter()
+            .fold(Integer::from(0), |acc, x| (acc << 64) | x.into_bits())
+    }
+
+    /// The exact value of this number.
+    fn value(&self) -> RFloat {
+        let lead = &self.parts[0];
+        if lead.is_zero() || !lead.is_finite() {
+            RFloat::from_number(lead)
+        } else {
+            self.parts[1..]
+                .iter()
+                .fold(RFloat::from_number(lead), |acc, x| {
+                    acc + RFloat::from_number(x)
+                })
+        }
+    }
+}
+
+impl Real for MultiDouble {
+    fn radix() -> usize {
+        2
+    }
+
+    fn sign(&self) -> Option<bool> {
+        let lead = &self.parts[0];
+        if lead.is_zero() || lead.is_nar() {
+            lead.sign()
+        } else {
+            self.value().sign()
+        }
+    }
+
+    fn exp(&self) -> Option<isize> {
+        self.value().exp()
+    }
+
+    fn e(&self) -> Option<isize> {
+        self.value().e()
+    }
+
+    fn n(&self) -> Option<isize> {
+        self.value().n()
+    }
+
+    fn c(&self) -> Option<Integer> {
+        self.value().c()
+    }
+
+    fn m(&self) -> Option<Integer> {
+        self.value().m()
+    }
+
+    fn prec(&self) -> Option<usize> {
+        self.value().prec()
+    }
+
+    fn is_nar(&self) -> bool {
+        self.parts[0].is_nar()
+    }
+
+    fn is_finite(&self) -> bool {
+        self.parts[0].is_finite()
+    }
+
+    fn is_infinite(&self) -> bool {
+        self.parts[0].is_infinite()
+    }
+
+    fn is_zero(&self) -> bool {
+        let lead = &self.parts[0];
+        lead.is_zero() || (lead.is_finite() && self.value().is_zero())
+    }
+
+    fn is_negative(&self) -> Option<bool> {
+        self.sign()
+    }
+
+    fn is_numerical(&self) -> bool {
+        self.parts[0].is_numerical()
+    }
+}
+
+impl PartialEq for MultiDouble {
+    fn eq(&self, other: &Self) -> bool {
+        self.partial_cmp(other) == Some(Ordering::Equal)
+    }
+}
+
+impl PartialOrd for MultiDouble {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        if self.is_nan() || other.is_nan() {
+            None
+        } else {
+            self.value().partial_cmp(&other.value())
+        }
+    }
+}
+
+impl From<MultiDouble> for RFloat {
+    fn from(value: MultiDouble) -> Self {
+        value.value()
+    }
+}
@@ -0,0 +1,261 @@
+//! Rounded operations for multi-double numbers.
+//!
+//! Negation, absolute value, addition, subtraction, multiplication,
+//! and fused multiply-add are computed exactly and then rounded
+//! componentwise (see [`MultiDoubleContext`][super::MultiDoubleContext]),
+//! so exact results such as `1 + 2^-200` are preserved.
+//! Unlike the classic double-double and quad-double algorithms,
+//! all other results are computed with MPFR to `max_p() + 2` bits,
+//! rounding to odd, and then rounded componentwise.
+//! The relative error of each such operation is therefore
+//! at most about `2^(1 - 53n)`.
+
+use crate::mpfr::*;
+use crate::multidouble::{MultiDouble, MultiDoubleContext};
+use crate::ops::*;
+use crate::real::RealContext;
+use crate::rfloat::RFloat;
+use crate::{Real, RoundingContext, RoundingMode};
+
+impl MultiDoubleContext {
+    /// Rounds the result of an MPFR computation,
+    /// carrying its exceptions into the flags of the result.
+    fn round_mpfr(&self, result: &MPFRResult) -> MultiDouble {
+        let mut rounded = self.round(result.num());
+        let flags = result.flags();
+        rounded.flags.invalid = flags.invalid;
+        rounded.flags.divzero = flags.divzero;
+        rounded.flags.inexact |= flags.inexact;
+        rounded.flags.underflow_pre = rounded.flags.tiny_pre && rounded.flags.inexact;
+        rounded.flags.underflow_post = rounded.flags.tiny_post && rounded.flags.inexact;
+        rounded
+    }
+
+    /// Rounds an exact result, signaling an invalid operation
+    /// if it is NaN but none of the arguments are.
+    fn round_exact(&self, result: &RFloat, args_numerical: bool) -> MultiDouble {
+        let mut rounded = self.round(result);
+        rounded.flags.invalid = args_numerical && matches!(result, RFloat::Nan);
+        rounded
+    }
+}
+
+macro_rules! exact_1ary_impl {
+    ($tname:ident, $name:ident) => {
+        impl $tname for MultiDoubleContext {
+            fn $name<N: Real>(&self, src: &N) -> Self::Format {
+                // compute exactly, then round componentwise
+                let result = RealContext::new().$name(src);
+                self.round_exact(&result, src.is_numerical())
+            }
+        }
+    };
+}
+
+exact_1ary_impl!(RoundedNeg, neg);
+exact_1ary_impl!(RoundedAbs, abs);
+
+macro_rules! exact_2ary_impl {
+    ($tname:ident, $name:ident) => {
+        impl $tname for MultiDoubleContext {
+            fn $name<N1, N2>(&self, src1: &N1, src2: &N2) -> Self::Format
+            where
+                N1: Real,
+                N2: Real,
+            {
+                // compute exactly, then round componentwise
+                let result = RealContext::new().$name(src1, src2);
+                self.round_exact(&result, src1.is_numerical() && src2.is_numerical())
+            }
+        }
+    };
+}
+
+exact_2ary_impl!(RoundedAdd, add);
+exact_2ary_impl!(RoundedSub, sub);
+exact_2ary_impl!(RoundedMul, mul);
+
+impl RoundedFMA for MultiDoubleContext {
+    fn fma<N1, N2, N3>(&self, src1: &N1, src2: &N2, src3: &N3) -> Self::Format
+    where
+        N1: Real,
+        N2: Real,
+        N3: Real,
+    {
+        // compute exactly, then round componentwise
+        let ctx = RealContext::new();
+        let result = ctx.add(&ctx.mul(src1, src2), src3);
+        let numerical = src1.is_numerical() && src2.is_numerical() && src3.is_numerical();
+        self.round_exact(&result, numerical)
+    }
+}
+
+macro_rules! rounded_1ary_impl {
+    ($tname:ident, $name:ident, $mpfr:ident) => {
+        impl $tname for MultiDoubleContext {
+            fn $name<N: Real>(&self, src: &N) -> Self::Format {
+                // compute with 2 additional bits, rounding-to-odd
+                let p = self.max_p() + 2;
+                let r = RFloat::from_number(src);
+                let result = $mpfr(r, p);
+                self.round_mpfr(&result)
+            }
+        }
+    };
+}
+
+rounded_1ary_impl!(RoundedSqrt, sqrt, mpfr_sqrt);
+rounded_1ary_impl!(RoundedCbrt, cbrt, mpfr_cbrt);
+rounded_1ary_impl!(RoundedRecip, recip, mpfr_recip);
+rounded_1ary_impl!(RoundedRecipSqrt, recip_sqrt, mpfr_recip_sqrt);
+rounded_1ary_impl!(RoundedExp, exp, mpfr_exp);
+rounded_1ary_impl!(RoundedExp2, exp2, mpfr_exp2);
+rounded_1ary_impl!(RoundedLog, log, mpfr_log);
+rounded_1ary_impl!(RoundedLog2, log2, mpfr_log2);
+rounded_1ary_impl!(RoundedLog10, log10, mpfr_log10);
+rounded_1ary_impl!(RoundedExpm1, expm1, mpfr_expm1);
+rounded_1ary_impl!(RoundedExp2m1, exp2m1, mpfr_exp2m1);
+rounded_1ary_impl!(RoundedExp10m1, exp10m1, mpfr_exp10m1);
+rounded_1ary_impl!(RoundedLog1p, log1p, mpfr_log1p);
+rounded_1ary_impl!(RoundedLog2p1, log2p1, mpfr_log2p1);
+rounded_1ary_impl!(RoundedLog10p1, log10p1, mpfr_log10p1);
+rounded_1ary_impl!(RoundedSin, sin, mpfr_sin);
+rounded_1ary_impl!(RoundedCos, cos, mpfr_cos);
+rounded_1ary_impl!(RoundedTan, tan, mpfr_tan);
+rounded_1ary_impl!(RoundedSinPi, sin_pi, mpfr_sin_pi);
+rounded_1ary_impl!(RoundedCosPi, cos_pi, mpfr_cos_pi);
+rounded_1ary_impl!(RoundedTanPi, tan_pi, mpfr_tan_pi);
+rounded_1ary_impl!(RoundedAsin, asin, mpfr_asin);
+rounded_1ary_impl!(RoundedAcos, acos, mpfr_acos);
+rounded_1ary_impl!(RoundedAtan, atan, mpfr_atan);
+rounded_1ary_impl!(RoundedSinh, sinh, mpfr_sinh);
+rounded_1ary_impl!(RoundedCosh, cosh, mpfr_cosh);
+rounded_1ary_impl!(RoundedTanh, tanh, mpfr_tanh);
+rounded_1ary_impl!(RoundedAsinh, asinh, mpfr_asinh);
+rounded_1ary_impl!(RoundedAcosh, acosh, mpfr_acosh);
+rounded_1ary_impl!(RoundedAtanh, atanh, mpfr_atanh);
+rounded_1ary_impl!(RoundedErf, erf, mpfr_erf);
+rounded_1ary_impl!(RoundedErfc, erfc, mpfr_erfc);
+rounded_1ary_impl!(RoundedGamma, tgamma, mpfr_tgamma);
+rounded_1ary_impl!(RoundedLgamma, lgamma, mpfr_lgamma);
+
+macro_rules! rounded_2ary_impl {
+    ($tname:ident, $name:ident, $mpfr:ident) => {
+        impl $tname for MultiDoubleContext {
+            fn $name<N1, N2>(&self, src1: &N1, src2: &N2) -> Self::Format
+            where
+                N1: Real,
+                N2: Real,
+            {
+                // compute with 2 additional bits, rounding-to-odd
+                let p = self.max_p() + 2;
+                let r1 = RFloat::from_number(src1);
+                let r2 = RFloat::from_number(src2);
+                let result = $mpfr(r1, r2, p);
+                self.round_mpfr(&result)
+            }
+        }
+    };
+}
+
+rounded_2ary_impl!(RoundedDiv, div, mpfr_div);
+rounded_2ary_impl!(RoundedPow, pow, mpfr_pow);
+rounded_2ary_impl!(RoundedHypot, hypot, mpfr_hypot);
+rounded_2ary_impl!(RoundedFmod, fmod, mpfr_fmod);
+rounded_2ary_impl!(RoundedRemainder, remainder, mpfr_remainder);
+rounded_2ary_impl!(RoundedAtan2, atan2, mpfr_atan2);
+
+macro_rules! rounded_integral_impl {
+    ($tname:ident, $name:ident, $rm:expr) => {
+        impl $tname for MultiDoubleContext {
+            fn $name<N: Real>(&self, src: &N) -> Self::Format {
+                let (integral, _) = round_integral(src, $rm);
+                self.round(&integral)
+            }
+        }
+    };
+}
+
+rounded_integral_impl!(RoundedFloor, floor, RoundingMode::ToNegative);
+rounded_integral_impl!(RoundedCeil, ceil, RoundingMode::ToPositive);
+rounded_integral_impl!(RoundedTrunc, trunc, RoundingMode::ToZero);
+rounded_integral_impl!(
+    RoundedRoundAway,
+    round_away,
+    RoundingMode::NearestTiesAwayZero
+);
+rounded_integral_impl!(
+    RoundedRoundEven,
+    round_even,
+    RoundingMode::NearestTiesToEven
+);
+
+impl RoundedNearbyInt for MultiDoubleContext {
+    fn nearbyint<N: Real>(&self, src: &N) -> Self::Format {
+        let (integral, _) = round_integral(src, RoundingMode::NearestTiesToEven);
+        self.round(&integral)
+    }
+}
+
+impl RoundedRint for MultiDoubleContext {
+    fn rint<N: Real>(&self, src: &N) -> Self::Format {
+        let (integral, inexact) = round_integral(src, RoundingMode::NearestTiesToEven);
+        let mut rounded = self.round(&integral);
+        rounded.flags.inexact |= inexact;
+        rounded
+    }
+}
+
+impl RoundedConvertToInteger for MultiDoubleContext {
+    fn convert_to_integer<N: Real>(&self, src: &N, rm: RoundingMode, exact: bool) -> Self::Format {
+        let (integral, inexact) = round_integral(src, rm);
+        let mut rounded = self.round(&integral);
+        if !integral.is_finite() || rounded.flags.overflow {
+            // non-real or out of range integers are invalid
+            let mut result = self.round(&RFloat::Nan);
+            result.flags.invalid = true;
+            result
+        } else {
+            rounded.flags.inexact = exact && (inexact || rounded.flags.inexact);
+            rounded
+        }
+    }
+}
+
+macro_rules! min_max_impl {
+    ($tname:ident, $name:ident, $op:expr) => {
+        impl $tname for MultiDoubleContext {
+            fn $name<N1, N2>(&self, src1: &N1, src2: &N2) -> Self::Format
+            where
+                N1: Real,
+                N2: Real,
+            {
+                let (result, invalid) = min_max(src1, src2, $op);
+                let mut rounded = match result {
+                    MinMaxResult::First => self.round(src1),
+                    MinMaxResult::Second => self.round(src2),
+                    MinMaxResult::Nan => self.round(&RFloat::Nan),
+                };
+                rounded.flags.invalid |= invalid;
+                rounded
+            }
+        }
+    };
+}
+
+min_max_impl!(RoundedMinimum, minimum, MinMax::Minimum);
+min_max_impl!(RoundedMaximum, maximum, MinMax::Maximum);
+min_max_impl!(RoundedMinimumNumber, minimum_number, MinMax::MinimumNumber);
+min_max_impl!(RoundedMaximumNumber, maximum_number, MinMax::MaximumNumber);
+min_max_impl!(
+    RoundedMinimumMagnitude,
+    minimum_magnitude,
+    MinMax::MinimumMagnitude
+);
+min_max_impl!(
+    RoundedMaximumMagnitude,
+    maximum_magnitude,
+    MinMax::MaximumMagnitude
+);
+min_max_impl!(RoundedMinNum, min_num, MinMax::MinNum);
+min_max_impl!(RoundedMaxNum, max_num, MinMax::MaxNum);
@@ -0,0 +1,168 @@
+use rug::{Integer, Rational};
+
+use crate::compare::exact_magnitude;
+use crate::ieee754::{Exceptions, IEEE754Context, IEEE754};
+use crate::rational::round_rational;
+use crate::rfloat::RFloat;
+use crate::util::bitmask;
+use crate::{Real, RoundingContext};
+
+use super::MultiDouble;
+
+/// Rounding contexts for multi-double numbers.
+///
+/// The associated storage type is [`MultiDouble`].
+///
+/// Values rounded under this context are unevaluated sums
+/// `x_0 + x_1 + ... + x_{n-1}` of `n` IEEE 754 `binary64` numbers.
+/// Rounding produces the canonical, non-overlapping representation:
+/// `x_0` is the `binary64` number nearest to the value and each
+/// subsequent `x_i` is the `binary64` number nearest to the remaining
+/// error, so `|x_{i+1}| <= ulp(x_i) / 2`. Such numbers have at least
+/// `53n` bits of precision, but the set of representable values
+/// is not that of any fixed-precision format: rounding is componentwise,
+/// not correctly rounded.
+///
+/// A [`MultiDoubleContext`] is parameterized by the number of components.
+/// Double-double (`n = 2`) and quad-double (`n = 4`) are the common
+/// instances (see [`MultiDoubleContext::double_double`] and
+/// [`MultiDoubleContext::quad_double`]). All components are rounded with
+/// [`NearestTiesToEven`][crate::RoundingMode::NearestTiesToEven].
+/// If the leading component overflows, the number is infinite.
+#[derive(Clone, Debug)]
+pub struct MultiDoubleContext {
+    n: usize,
+}
+
+impl MultiDoubleContext {
+    /// Precision of a single component.
+    pub const COMPONENT_P: usize = 53;
+
+    /// Constructs a new rounding context for multi-double numbers
+    /// with `n` components.
+    pub fn new(n: usize) -> Self {
+        assert!(n >= 2, "must have at least 2 components, given {}", n);
+        Self { n }
+    }
+
+    /// Constructs a rounding context for double-double numbers.
+    pub fn double_double() -> Self {
+        Self::new(2)
+    }
+
+    /// Constructs a rounding context for quad-double numbers.
+    pub fn quad_double() -> Self {
+        Self::new(4)
+    }
+
+    /// Returns the number of components of this format.
+    pub fn n(&self) -> usize {
+        self.n
+    }
+
+    /// Returns the guaranteed precision of this format (away from
+    /// the subnormal range), i.e., `53n`.
+    pub fn max_p(&self) -> usize {
+        Self::COMPONENT_P * self.n
+    }
+
+    /// Returns the context of a single component.
+    pub fn component_ctx(&self) -> IEEE754Context {
+        IEEE754Context::binary64()
+    }
+
+    /// Composes a number from its components. The components are
+    /// not checked to be canonical.
+    fn compose(&self, parts: Vec<IEEE754>, flags: Exceptions) -> MultiDouble {
+        MultiDouble {
+            parts,
+            flags,
+            ctx: self.clone(),
+        }
+    }
+
+    /// Constructs a number whose leading component is `lead`
+    /// and remaining components are zero.
+    fn with_lead(&self, lead: IEEE754) -> MultiDouble {
+        let ctx = self.component_ctx();
+        let flags = lead.flags().clone();
+        let mut parts = vec![lead];
+        parts.extend((1..self.n).map(|_| ctx.zero(false)));
+        self.compose(parts, flags)
+    }
+
+    /// Converts an [`Integer`] representing the concatenation of
+    /// `n` `binary64` encodings, leading component first, into
+    /// a [`MultiDouble`] value. For double-double numbers, this is the
+    /// IBM 128-bit extended precision encoding.
+    ///
+    /// Components are decoded as is. If the leading component is
+    /// zero, infinite, or NaN, the remaining components are ignored.
+    pub fn bits_to_number(&self, b: Integer) -> MultiDouble {
+        let limit = Integer::from(1) << (64 * self.n);
+        assert!(b < limit, "must be less than 1 << 64n");
+
+        let ctx = self.component_ctx();
+        let parts = (0..self.n)
+            .rev()
+            .map(|i| ctx.bits_to_number((b.clone() >> (64 * i)) & bitmask(64)))
+            .collect();
+        self.compose(parts, Exceptions::default())
+    }
+}
+
+impl RoundingContext for MultiDoubleContext {
+    type Format = MultiDouble;
+
+    fn round<T: Real>(&self, num: &T) -> Self::Format {
+        // round the leading component
+        let ctx = self.component_ctx();
+        let lead = ctx.round(num);
+        if lead.is_zero() || !lead.is_finite() {
+            return self.with_lead(lead);
+        }
+
+        // round the remaining error
+        let mut flags = lead.flags().clone();
+        let mut parts = vec![];
+        if T::radix() == 10 {
+            // decimal errors are not binary, so round them exactly
+            let mut err = exact_value(num) - exact_value(&lead);
+            parts.push(lead);
+            for _ in 1..self.n {
+                let x = round_rational(&ctx, &err);
+                err -= exact_value(&x);
+                parts.push(x);
+            }
+
+            flags.inexact = !err.is_zero();
+        } else {
+            let mut err = RFloat::from_number(num) - RFloat::from_number(&lead);
+            parts.push(lead);
+            for _ in 1..self.n {
+                let x = ctx.round(&err);
+                err = err - RFloat::from_number(&x);
+                parts.push(x);
+            }
+
+            flags.inexact = !err.is_zero();
+        }
+
+        // the value is tiny only if its leading component is,
+        // regardless of the components rounding the error
+        flags.underflow_pre = flags.tiny_pre && flags.inexact;
+        flags.underflow_post = flags.tiny_post && flags.inexact;
+        flags.carry = false;
+        self.compose(parts, flags)
+    }
+}
+
+/// Computes the exact value of a finite number.
+fn exact_value<N: Real>(x: &N) -> Rational {
+    let q = exact_magnitude(x);
+    if x.sign().unwrap_or(false) {
+        -q
+    } else {
+        q
+    }
+}
@@ -0,0 +1,230 @@
+use crate::fixed::{self, FixedContext};
+use crate::ieee754::{self, IEEE754Context};
+use crate::rfloat::RFloat;
+use crate::{Real, RoundingContext, RoundingMode};
+
+use super::{E8M0Context, E8M0};
+
+/// Rounding contexts that may be used as the element format
+/// of an MX block.
+pub trait MXElementContext: RoundingContext {
+    /// Exponent of the largest normal value of the element format,
+    /// i.e., `emax_elem` in the MX specification.
+    fn emax_elem(&self) -> isize;
+}
+
+impl MXElementContext for IEEE754Context {
+    fn emax_elem(&self) -> isize {
+        self.emax()
+    }
+}
+
+impl MXElementContext for FixedContext {
+    fn emax_elem(&self) -> isize {
+        self.maxval().e().unwrap()
+    }
+}
+
+/// A single MX block: a shared [`E8M0`] scale and a number
+/// of elements rounded under the element context.
+///
+/// The value of each element is `X * P_i` where `X` is the
+/// shared scale and `P_i` is the element. If the scale is NaN,
+/// every element of the block is NaN.
+#[derive(Clone, Debug)]
+pub struct MXBlock<F: Real> {
+    pub(crate) scale: E8M0,
+    pub(crate) elems: Vec<F>,
+}
+
+impl<F: Real> MXBlock<F> {
+    /// Returns the shared scale of this block.
+    pub fn scale(&self) -> &E8M0 {
+        &self.scale
+    }
+
+    /// Returns the (unscaled) elements of this block.
+    pub fn elems(&self) -> &[F] {
+        &self.elems
+    }
+
+    /// Returns the number of elements in this block.
+    pub fn len(&self) -> usize {
+        self.elems.len()
+    }
+
+    /// Returns true if this block has no elements.
+    pub fn is_empty(&self) -> bool {
+        self.elems.is_empty()
+    }
+
+    /// Computes the (exact) value of each element of this block.
+    pub fn dequantize(&self) -> Vec<RFloat> {
+        match self.scale.exp {
+            None => vec![RFloat::Nan; self.elems.len()],
+            Some(x) => self.elems.iter().map(|p| scale_by(p, x)).collect(),
+        }
+    }
+}
+
+/// Rounding contexts for MX blocks.
+///
+/// An [`MXContext`] is parameterized by
+///
+///  - the rounding context of its elements,
+///  - the number of elements per block (32 by default).
+///
+/// Quantizing a block follows the OCP specification: the shared
+/// scale is `X = 2^(floor(log2(max_i |V_i|)) - emax_elem)`
+/// where `emax_elem` is the exponent of the largest normal value
+/// of the element format; each element is then `V_i / X` rounded
+/// under the element context. Shared exponents below the smallest
+/// E8M0 exponent are clamped, while shared exponents above the
+/// largest E8M0 exponent, or any NaN or infinite input,
+/// result in a NaN scale. Blocks of zeros have the smallest scale.
+#[derive(Clone, Debug)]
+pub struct MXContext<Ctx: MXElementContext> {
+    elem: Ctx,
+    block_size: usize,
+}
+
+impl<Ctx: MXElementContext> MXContext<Ctx> {
+    /// Default number of elements per block.
+    pub const BLOCK_SIZE: usize = 32;
+
+    /// Constructs a new [`MXContext`] with element context `elem`
+    /// and the default block size.
+    pub fn new(elem: Ctx) -> Self {
+        Self {
+            elem,
+            block_size: Self::BLOCK_SIZE,
+        }
+    }
+
+    /// Sets the number of elements per block of this [`MXContext`].
+    pub fn with_block_size(mut self, block_size: usize) -> Self {
+        assert!(block_size > 0, "block size must be positive");
+        self.block_size = block_size;
+        self
+    }
+
+    /// Returns the element context of this [`MXContext`].
+    pub fn elem(&self) -> &Ctx {
+        &self.elem
+    }
+
+    /// Returns the number of elements per block of this [`MXContext`].
+    pub fn block_size(&self) -> usize {
+        self.block_size
+    }
+
+    /// Quantizes at most `self.block_size()` values into a single block.
+    pub fn quantize_block<T: Real>(&self, vals: &[T]) -> MXBlock<Ctx::Format> {
+        assert!(
+            vals.len() <= self.block_size,
+            "block must have at most {} elements, given {}",
+            self.block_size,
+            vals.len()
+        );
+
+        let scale_ctx = E8M0Context::new();
+        if vals.iter().any(|v| !v.is_finite()) {
+            // NaN or infinite inputs
+            return self.nan_block(vals.len());
+        }
+
+        // shared exponent from the largest magnitude
+        let shared_exp = match vals.iter().filter_map(|v| v.e()).max() {
+            None => E8M0Context::EMIN,
+            Some(e) => e - self.elem.emax_elem(),
+        };
+
+        if shared_exp > E8M0Context::EMAX {
+            return self.nan_block(vals.len());
+        }
+
+        let shared_exp = shared_exp.max(E8M0Context::EMIN);
+        MXBlock {
+            scale: scale_ctx.pow2(shared_exp),
+            elems: vals
+                .iter()
+                .map(|v| self.elem.round(&scale_by(v, -shared_exp)))
+                .collect(),
+        }
+    }
+
+    /// Quantizes a slice of values into consecutive blocks.
+    /// The last block may have fewer than `self.block_size()` elements.
+    pub fn quantize<T: Real>(&self, vals: &[T]) -> Vec<MXBlock<Ctx::Format>> {
+        vals.chunks(self.block_size)
+            .map(|block| self.quantize_block(block))
+            .collect()
+    }
+
+    /// Computes the (exact) value of each element of consecutive blocks.
+    pub fn dequantize(&self, blocks: &[MXBlock<Ctx::Format>]) -> Vec<RFloat> {
+        blocks.iter().flat_map(|b| b.dequantize()).collect()
+    }
+
+    fn nan_block(&self, len: usize) -> MXBlock<Ctx::Format> {
+        MXBlock {
+            scale: E8M0Context::new().nan(),
+            elems: (0..len).map(|_| self.elem.round(&RFloat::Nan)).collect(),
+        }
+    }
+}
+
+impl MXContext<IEEE754Context> {
+    fn ocp_elem(elem: IEEE754Context) -> Self {
+        Self::new(
+            elem.with_rounding_mode(RoundingMode::NearestTiesToEven)
+                .with_overflow(ieee754::Overflow::Saturating),
+        )
+    }
+
+    /// MXFP8 with E4M3 elements.
+    pub fn mxfp8_e4m3() -> Self {
+        Self::ocp_elem(IEEE754Context::ocp_e4m3())
+    }
+
+    /// MXFP8 with E5M2 elements.
+    pub fn mxfp8_e5m2() -> Self {
+        Self::ocp_elem(IEEE754Context::ocp_e5m2())
+    }
+
+    /// MXFP6 with E3M2 elements.
+    pub fn mxfp6_e3m2() -> Self {
+        Self::ocp_elem(IEEE754Context::ocp_e3m2())
+    }
+
+    /// MXFP6 with E2M3 elements.
+    pub fn mxfp6_e2m3() -> Self {
+        Self::ocp_elem(IEEE754Context::ocp_e2m3())
+    }
+
+    /// MXFP4 with E2M1 elements.
+    pub fn mxfp4_e2m1() -> Self {
+        Self::ocp_elem(IEEE754Context::ocp_e2m1())
+    }
+}
+
+impl MXContext<FixedContext> {
+    /// MXINT8: 8-bit, two's complement elements with an
+    /// implicit scale of `2^-6`.
+    pub fn mxint8() -> Self {
+        Self::new(
+            FixedContext::new(true, -6, 8)
+                .with_rounding_mode(RoundingMode::NearestTiesToEven)
+                .with_overflow(fixed::Overflow::Saturate),
+        )
+    }
+}
+
+/// Computes `v * 2^k` exactly.
+fn scale_by<T: Real>(v: &T, k: isize) -> RFloat {
+    if v.is_zero() || !v.is_finite() {
+        RFloat::from_number(v)
+    } else {
+        RFloat::Real(v.sign().unwrap(), v.exp().unwrap() + k, v.c().unwrap())
+    }
+}
@@ -0,0 +1,233 @@
+use std::cmp::Ordering;
+
+use rug::Integer;
+
+use crate::rfloat::{RFloat, RFloatContext};
+use crate::{Real, RoundingContext, RoundingMode};
+
+/// The E8M0 scale format.
+///
+/// An [`E8M0`] value is either an unsigned power of two `2^e`
+/// where `-127 <= e <= 127`, or NaN. The format has no sign,
+/// no zero, and no infinities.
+///
+/// The associated [`RoundingContext`] implementation is [`E8M0Context`].
+#[derive(Clone, Debug)]
+pub struct E8M0 {
+    pub(crate) exp: Option<isize>,
+    pub(crate) ctx: E8M0Context,
+}
+
+impl E8M0 {
+    /// Returns the rounding context under which this number was created.
+    pub fn ctx(&self) -> &E8M0Context {
+        &self.ctx
+    }
+
+    /// Returns true if this number is NaN.
+    pub fn is_nan(&self) -> bool {
+        self.exp.is_none()
+    }
+
+    /// Converts this [`E8M0`] to an [`Integer`] representing
+    /// an E8M0 bitpattern: the biased exponent or `0xFF` for NaN.
+    pub fn into_bits(self) -> Integer {
+        match self.exp {
+            Some(e) => Integer::from(e + E8M0Context::BIAS),
+            None => Integer::from(0xFF),
+        }
+    }
+}
+
+impl Real for E8M0 {
+    fn radix() -> usize {
+        2
+    }
+
+    fn sign(&self) -> Option<bool> {
+        self.exp.map(|_| false)
+    }
+
+    fn exp(&self) -> Option<isize> {
+        self.exp
+    }
+
+    fn e(&self) -> Option<isize> {
+        self.exp
+    }
+
+    fn n(&self) -> Option<isize> {
+        self.exp.map(|e| e - 1)
+    }
+
+    fn c(&self) -> Option<Integer> {
+        self.exp.map(|_| Integer::from(1))
+    }
+
+    fn m(&self) -> Option<Integer> {
+        self.c()
+    }
+
+    fn prec(&self) -> Option<usize> {
+        self.exp.map(|_| 1)
+    }
+
+    fn is_nar(&self) -> bool {
+        self.exp.is_none()
+    }
+
+    fn is_finite(&self) -> bool {
+        self.exp.is_some()
+    }
+
+    fn is_infinite(&self) -> bool {
+        false
+    }
+
+    fn is_zero(&self) -> bool {
+        false
+    }
+
+    fn is_negative(&self) -> Option<bool> {
+        self.sign()
+    }
+
+    fn is_numerical(&self) -> bool {
+        self.exp.is_some()
+    }
+}
+
+impl PartialEq for E8M0 {
+    fn eq(&self, other: &Self) -> bool {
+        self.partial_cmp(other) == Some(Ordering::Equal)
+    }
+}
+
+impl PartialOrd for E8M0 {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        match (self.exp, other.exp) {
+            (Some(e1), Some(e2)) => Some(e1.cmp(&e2)),
+            _ => None,
+        }
+    }
+}
+
+impl From<E8M0> for RFloat {
+    fn from(value: E8M0) -> Self {
+        match value.exp {
+            Some(e) => RFloat::Real(false, e, Integer::from(1)),
+            None => RFloat::Nan,
+        }
+    }
+}
+
+/// Rounding contexts for the E8M0 scale format.
+///
+/// The associated storage type is [`E8M0`].
+///
+/// Positive, finite values are rounded to a power of two according
+/// to the rounding mode. Values larger than `2^127` after rounding
+/// become NaN, while values smaller than `2^-127` (including zero)
+/// are clamped to `2^-127`. Negative values, infinities, and NaN
+/// become NaN.
+#[derive(Clone, Debug)]
+pub struct E8M0Context {
+    rm: RoundingMode,
+}
+
+impl E8M0Context {
+    /// Exponent bias of the encoding.
+    pub const BIAS: isize = 127;
+    /// Largest representable exponent.
+    pub const EMAX: isize = 127;
+    /// Smallest representable exponent.
+    pub const EMIN: isize = -127;
+
+    /// Constructs a new [`E8M0Context`] with rounding mode
+    /// [`ToZero`][RoundingMode::ToZero], i.e., the largest
+    /// power of two not exceeding the value.
+    pub fn new() -> Self {
+        Self {
+            rm: RoundingMode::ToZero,
+        }
+    }
+
+    /// Sets the rounding mode of this [`E8M0Context`].
+    pub fn with_rounding_mode(mut self, rm: RoundingMode) -> Self {
+        self.rm = rm;
+        self
+    }
+
+    /// Returns the rounding mode of this [`E8M0Context`].
+    pub fn rm(&self) -> RoundingMode {
+        self.rm
+    }
+
+    /// Constructs the power of two `2^e` in this format.
+    /// Panics if `e` is out of range.
+    pub fn pow2(&self, e: isize) -> E8M0 {
+        assert!(
+            (Self::EMIN..=Self::EMAX).contains(&e),
+            "exponent must be between {} and {}, given {}",
+            Self::EMIN,
+            Self::EMAX,
+            e
+        );
+
+        E8M0 {
+            exp: Some(e),
+            ctx: self.clone(),
+        }
+    }
+
+    /// Constructs NaN in this format.
+    pub fn nan(&self) -> E8M0 {
+        E8M0 {
+            exp: None,
+            ctx: self.clone(),
+        }
+    }
+
+    /// Converts an [`Integer`] representing an E8M0 bitpattern
+    /// into an [`E8M0`] value.
+    pub fn bits_to_number(&self, b: Integer) -> E8M0 {
+        assert!(b < 0x100, "must be less than 1 << 8");
+        let biased = b.to_isize().unwrap();
+        if biased == 0xFF {
+            self.nan()
+        } else {
+            self.pow2(biased - Self::BIAS)
+        }
+    }
+}
+
+impl Default for E8M0Context {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RoundingContext for E8M0Context {
+    type Format = E8M0;
+
+    fn round<T: Real>(&self, num: &T) -> Self::Format {
+        if num.is_zero() {
+            // no zero: clamp to the smallest value
+            self.pow2(Self::EMIN)
+        } else if !num.is_finite() || num.is_negative().unwrap() {
+            // no sign or infinity
+            self.nan()
+        } else {
+            // round to a power of two with unbounded exponent
+            let ctx = RFloatContext::new()
+                .with_max_p(1)
+                .with_rounding_mode(self.rm);
+            let e = ctx.round(num).e().unwrap();
+            if e > Self::EMAX {
+                self.nan()
+            } else {
+                self.pow2(e.max(Self::EMIN))
+            }
+        }
+    }
+}
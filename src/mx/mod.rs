@@ -0,0 +1,21 @@
+//! Microscaling (MX) block formats as described in the
+//! OCP Microscaling Formats (MX) v1.0 Specification.
+//!
+//! An MX block consists of a fixed number of elements that share
+//! a common power-of-two scale. Elements are stored in a narrow
+//! element format, e.g., FP8, FP6, FP4, or INT8, while the shared
+//! scale is stored in the [`E8M0`] format, an unsigned, 8-bit,
+//! exponent-only format.
+//!
+//! This module implements MX blocks with [`MXContext`], which is
+//! parameterized by a rounding context for its elements
+//! (see [`MXElementContext`]). Quantizing a slice of [`Real`][crate::Real]
+//! values produces one [`MXBlock`] per block of values;
+//! [`MXContext::mxfp8_e4m3`] and friends construct the
+//! concrete formats of the specification.
+
+mod block;
+mod e8m0;
+
+pub use block::{MXBlock, MXContext, MXElementContext};
+pub use e8m0::{E8M0Context, E8M0};
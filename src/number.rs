@@ -1,5 +1,5 @@
 use num_traits::Zero;
-use rug::Integer;
+use rug::{Integer, Rational};
 use std::fmt::Debug;
 
 use crate::rfloat::RFloat;
@@ -91,6 +91,28 @@ pub trait Real: Debug {
     /// either a finite number, interval, or some limiting value.
     fn is_numerical(&self) -> bool;
 
+    /// Returns `true` if this number is a signaling NaN.
+    /// Most number systems have no signaling NaNs.
+    fn is_signaling(&self) -> bool {
+        false
+    }
+
+    /// Returns the payload of this number if it is a NaN with a payload,
+    /// along with the width of the field storing it. Payloads are
+    /// carried between formats by aligning the most significant bits
+    /// of their fields. Most number systems have no NaN payloads.
+    fn nan_payload_field(&self) -> Option<(Integer, usize)> {
+        None
+    }
+
+    /// Returns the exact base-2 logarithm of the magnitude of this
+    /// number if it is represented by its logarithm, e.g.,
+    /// a logarithmic number. Only well-defined for finite,
+    /// non-zero numbers. Most number systems return `None`.
+    fn exact_log2(&self) -> Option<Rational> {
+        None
+    }
+
     /// Splits this value at the `n`th binary digit,
     /// returning two [`RFloat`] values.
     ///
@@ -4,7 +4,14 @@
 //! rounding the result according to a given [`RoundingContext`].
 //!
 
-use crate::{Real, RoundingContext};
+use std::cmp::Ordering;
+
+use rug::{Integer, Rational};
+
+use crate::compare::{cmp_magnitude, exact_magnitude};
+use crate::decimal::round_increment;
+use crate::rfloat::RFloat;
+use crate::{Real, RoundingContext, RoundingMode};
 
 macro_rules! rounded_1ary {
     ($trait:ident, $impl:ident, $descr:expr) => {
@@ -40,6 +47,7 @@ rounded_1ary!(RoundedRecip, recip, "1/x");
 rounded_1ary!(RoundedRecipSqrt, recip_sqrt, "1/sqrt(x)");
 rounded_1ary!(RoundedExp, exp, "exp(x)");
 rounded_1ary!(RoundedExp2, exp2, "2^x");
+rounded_1ary!(RoundedExp10, exp10, "10^x");
 rounded_1ary!(RoundedLog, log, "ln(x)");
 rounded_1ary!(RoundedLog2, log2, "log2(x)");
 rounded_1ary!(RoundedLog10, log10, "log10(x)");
@@ -58,6 +66,9 @@ rounded_1ary!(RoundedTanPi, tan_pi, "tan(pi * x)");
 rounded_1ary!(RoundedAsin, asin, "arcsin(x)");
 rounded_1ary!(RoundedAcos, acos, "arccos(x)");
 rounded_1ary!(RoundedAtan, atan, "arctan(x)");
+rounded_1ary!(RoundedAsinPi, asin_pi, "arcsin(x) / pi");
+rounded_1ary!(RoundedAcosPi, acos_pi, "arccos(x) / pi");
+rounded_1ary!(RoundedAtanPi, atan_pi, "arctan(x) / pi");
 rounded_1ary!(RoundedSinh, sinh, "sinh(x)");
 rounded_1ary!(RoundedCosh, cosh, "cosh(x)");
 rounded_1ary!(RoundedTanh, tanh, "tanh(x)");
@@ -69,6 +80,236 @@ rounded_1ary!(RoundedErfc, erfc, "erfc(x)");
 rounded_1ary!(RoundedGamma, tgamma, "tgamma(x)");
 rounded_1ary!(RoundedLgamma, lgamma, "lgamma(x)");
 
+/// Rounded `sin(x)` and `cos(x)` for rounding contexts.
+pub trait RoundedSinCos: RoundingContext {
+    /// Performs rounded `sin(x)` and `cos(x)`, returning both results.
+    fn sin_cos<N: Real>(&self, src: &N) -> (Self::Format, Self::Format);
+}
+
+/// Computes `sin(x)` and `cos(x)` and rounds both
+/// according to the [`RoundingContext`] ctx.
+pub fn sin_cos<Ctx, N>(ctx: &Ctx, src: &N) -> (Ctx::Format, Ctx::Format)
+where
+    Ctx: RoundedSinCos,
+    N: Real,
+{
+    ctx.sin_cos(src)
+}
+
+/// Rounded `remquo(x, y)` for rounding contexts.
+pub trait RoundedRemquo: RoundingContext {
+    /// Performs rounded `remainder(x, y)`, also returning the low-order
+    /// bits of the quotient `x / y` rounded to the nearest integer
+    /// (at least 3 bits) with the sign of `x / y`.
+    fn remquo<N1, N2>(&self, src1: &N1, src2: &N2) -> (Self::Format, i64)
+    where
+        N1: Real,
+        N2: Real;
+}
+
+/// Computes `remquo(x, y)` and rounds the remainder
+/// according to the [`RoundingContext`] ctx.
+pub fn remquo<Ctx, N1, N2>(ctx: &Ctx, src1: &N1, src2: &N2) -> (Ctx::Format, i64)
+where
+    Ctx: RoundedRemquo,
+    N1: Real,
+    N2: Real,
+{
+    ctx.remquo(src1, src2)
+}
+
+/// Rounded `frexp(x)` for rounding contexts.
+pub trait RoundedFrexp: RoundingContext {
+    /// Splits `x` into a normalized fraction `m` and an integral
+    /// exponent `e` such that `x = m * 2^e` and `1/2 <= |m| < 1`,
+    /// rounding the fraction. If `x` is zero, infinite, or NaN,
+    /// the fraction is `x` rounded and the exponent is zero.
+    fn frexp<N: Real>(&self, src: &N) -> (Self::Format, isize);
+}
+
+/// Computes `frexp(x)` and rounds the fraction
+/// according to the [`RoundingContext`] ctx.
+pub fn frexp<Ctx, N>(ctx: &Ctx, src: &N) -> (Ctx::Format, isize)
+where
+    Ctx: RoundedFrexp,
+    N: Real,
+{
+    ctx.frexp(src)
+}
+
+/// Rounded `modf(x)` for rounding contexts.
+pub trait RoundedModf: RoundingContext {
+    /// Splits `x` into its fractional and integral parts, both with
+    /// the sign of `x`, returning the rounded fractional part first.
+    /// If `x` is infinite, the fractional part is zero.
+    fn modf<N: Real>(&self, src: &N) -> (Self::Format, Self::Format);
+}
+
+/// Computes `modf(x)` and rounds both parts
+/// according to the [`RoundingContext`] ctx.
+pub fn modf<Ctx, N>(ctx: &Ctx, src: &N) -> (Ctx::Format, Ctx::Format)
+where
+    Ctx: RoundedModf,
+    N: Real,
+{
+    ctx.modf(src)
+}
+
+// Traits for rounding to integral values:
+//  - `floor`, `ceil`, `trunc`, `round`, and `roundeven` round to
+//    an integer in a fixed direction;
+//  - `nearbyint` rounds to an integer according to the rounding mode
+//    of the context, never signaling inexact;
+//  - `rint` rounds to an integer according to the rounding mode
+//    of the context, signaling inexact if the result differs.
+rounded_1ary!(RoundedFloor, floor, "floor(x)");
+rounded_1ary!(RoundedCeil, ceil, "ceil(x)");
+rounded_1ary!(RoundedTrunc, trunc, "trunc(x)");
+rounded_1ary!(RoundedRoundAway, round_away, "round(x)");
+rounded_1ary!(RoundedRoundEven, round_even, "roundeven(x)");
+rounded_1ary!(RoundedNearbyInt, nearbyint, "nearbyint(x)");
+rounded_1ary!(RoundedRint, rint, "rint(x)");
+
+/// Conversion to integer for rounding contexts.
+///
+/// Implements the IEEE 754 `convertToInteger` operations:
+/// the argument is rounded to an integer according to a rounding
+/// mode, and the integer is rounded according to the context.
+/// Non-real arguments and integers that overflow the format signal
+/// an invalid operation when the format has exception flags,
+/// unless the context specifies other behavior on overflow,
+/// e.g., saturation or wrapping for fixed-point formats.
+/// Only the `convertToIntegerExact` operations signal inexact.
+pub trait RoundedConvertToInteger: RoundingContext {
+    /// Performs rounded `convertToInteger(x)`, rounding to an integer
+    /// according to `rm`. If `exact` is set, signals inexact when
+    /// the result differs from the argument.
+    fn convert_to_integer<N: Real>(&self, src: &N, rm: RoundingMode, exact: bool) -> Self::Format;
+}
+
+/// Computes `convertToInteger(x)`, rounding to an integer according
+/// to `rm`, and rounds according to the [`RoundingContext`] ctx.
+pub fn convert_to_integer<Ctx, N>(ctx: &Ctx, src: &N, rm: RoundingMode, exact: bool) -> Ctx::Format
+where
+    Ctx: RoundedConvertToInteger,
+    N: Real,
+{
+    ctx.convert_to_integer(src, rm, exact)
+}
+
+/// Splits a binary value `src` into a fraction `m` and an exponent `e`
+/// such that `src = m * 2^e` and `1/2 <= |m| < 1`. Non-real values
+/// and zero are unchanged with exponent zero.
+pub(crate) fn frexp_exact<N: Real>(src: &N) -> (RFloat, isize) {
+    match RFloat::from_number(src) {
+        RFloat::Real(s, exp, c) if !c.is_zero() => {
+            let e = exp + c.significant_bits() as isize;
+            (RFloat::Real(s, exp - e, c), e)
+        }
+        RFloat::Real(..) => (
+            RFloat::Real(src.sign().unwrap_or(false), 0, Integer::new()),
+            0,
+        ),
+        r => (r, 0),
+    }
+}
+
+/// Splits a binary value `src` into its fractional and integral
+/// parts, both with the sign of `src`. The fractional part of
+/// an infinity is zero.
+pub(crate) fn modf_exact<N: Real>(src: &N) -> (RFloat, RFloat) {
+    let s = src.sign().unwrap_or(false);
+    if src.is_infinite() {
+        (RFloat::Real(s, 0, Integer::new()), RFloat::from_number(src))
+    } else if !src.is_numerical() {
+        (RFloat::Nan, RFloat::Nan)
+    } else if src.is_zero() {
+        (
+            RFloat::Real(s, 0, Integer::new()),
+            RFloat::Real(s, 0, Integer::new()),
+        )
+    } else {
+        let (int, frac) = RFloat::from_number(src).split(-1);
+        (frac, int)
+    }
+}
+
+/// Rounds `src` to an integer according to `rm`. Returns the (exact)
+/// integral value and whether it differs from `src`. The sign of
+/// a zero result is the sign of `src`. Infinities are unchanged,
+/// and any non-numerical value becomes NaN.
+pub(crate) fn round_integral<N: Real>(src: &N, rm: RoundingMode) -> (RFloat, bool) {
+    if src.is_infinite() {
+        match src.sign() {
+            Some(true) => (RFloat::NegInfinity, false),
+            _ => (RFloat::PosInfinity, false),
+        }
+    } else if !src.is_numerical() {
+        (RFloat::Nan, false)
+    } else if src.is_zero() {
+        let s = src.sign().unwrap_or(false);
+        (RFloat::Real(s, 0, Integer::new()), false)
+    } else if src.exp().unwrap() >= 0 {
+        // already an integer
+        let s = src.sign().unwrap();
+        if N::radix() == 2 {
+            (RFloat::Real(s, src.exp().unwrap(), src.c().unwrap()), false)
+        } else {
+            let c = exact_magnitude(src).into_numer_denom().0;
+            (RFloat::Real(s, 0, c), false)
+        }
+    } else {
+        // split the exact value `c * radix^exp`
+        let s = src.sign().unwrap();
+        let (frac, trunc) = if src.e().unwrap() < -1 {
+            // `|src| < 1/2`: every such fraction rounds the same
+            (Rational::from((1, 4)), Integer::new())
+        } else {
+            exact_magnitude(src).fract_trunc(Integer::new())
+        };
+
+        // round at the units place
+        let inexact = !frac.is_zero();
+        let c = if inexact && round_increment(rm, s, &trunc, &frac) {
+            trunc + 1
+        } else {
+            trunc
+        };
+
+        (RFloat::Real(s, 0, c), inexact)
+    }
+}
+
+macro_rules! rounded_1ary_int {
+    ($trait:ident, $impl:ident, $descr:expr) => {
+        #[doc = "Rounded `"]
+        #[doc = $descr]
+        #[doc = "` for rounding contexts where `n` is an integer."]
+        pub trait $trait: RoundingContext {
+            #[doc = "Performs rounded `"]
+            #[doc = $descr]
+            #[doc = "`."]
+            fn $impl<N: Real>(&self, src: &N, n: i64) -> Self::Format;
+        }
+
+        #[doc = "Computes `"]
+        #[doc = $descr]
+        #[doc = "` and rounds according to the [`RoundingContext`] ctx."]
+        pub fn $impl<Ctx, N>(ctx: &Ctx, src: &N, n: i64) -> Ctx::Format
+        where
+            Ctx: $trait,
+            N: Real,
+        {
+            ctx.$impl(src, n)
+        }
+    };
+}
+
+// Traits for 1-ary operators with an integer argument
+rounded_1ary_int!(RoundedCompound, compound, "(1 + x)^n");
+rounded_1ary_int!(RoundedRootn, rootn, "x^(1/n)");
+rounded_1ary_int!(RoundedPown, pown, "x^n");
+
 macro_rules! rounded_2ary {
     ($trait:ident, $impl:ident, $descr:expr) => {
         #[doc = "Rounded `"]
@@ -104,10 +345,123 @@ rounded_2ary!(RoundedSub, sub, "x - y");
 rounded_2ary!(RoundedMul, mul, "x * y");
 rounded_2ary!(RoundedDiv, div, "x / y");
 rounded_2ary!(RoundedPow, pow, "x ^ y");
+rounded_2ary!(RoundedPowr, powr, "e^(y * ln(x))");
 rounded_2ary!(RoundedHypot, hypot, "sqrt(x^2 + y^2)");
 rounded_2ary!(RoundedFmod, fmod, "fmod(x, y)");
 rounded_2ary!(RoundedRemainder, remainder, "remainder(x, y)");
 rounded_2ary!(RoundedAtan2, atan2, "arctan(y / x)");
+rounded_2ary!(RoundedAtan2Pi, atan2_pi, "arctan(y / x) / pi");
+rounded_2ary!(RoundedFdim, fdim, "max(x - y, 0)");
+
+// Traits for the IEEE 754 min/max operations:
+//  - `minimum` and `maximum` propagate NaNs;
+//  - `minimumNumber` and `maximumNumber` prefer a number over a NaN;
+//  - `minimumMagnitude` and `maximumMagnitude` compare absolute values,
+//    falling back to `minimum` and `maximum`, propagating NaNs;
+//  - `minNum` and `maxNum` (IEEE 754-2008) prefer a number over
+//    a quiet NaN, but produce NaN given a signaling NaN.
+// In all cases, `-0` is ordered below `+0`, and a signaling NaN
+// argument signals an invalid operation. The result is the selected
+// argument rounded according to the context.
+rounded_2ary!(RoundedMinimum, minimum, "minimum(x, y)");
+rounded_2ary!(RoundedMaximum, maximum, "maximum(x, y)");
+rounded_2ary!(RoundedMinimumNumber, minimum_number, "minimumNumber(x, y)");
+rounded_2ary!(RoundedMaximumNumber, maximum_number, "maximumNumber(x, y)");
+rounded_2ary!(
+    RoundedMinimumMagnitude,
+    minimum_magnitude,
+    "minimumMagnitude(x, y)"
+);
+rounded_2ary!(
+    RoundedMaximumMagnitude,
+    maximum_magnitude,
+    "maximumMagnitude(x, y)"
+);
+rounded_2ary!(RoundedMinNum, min_num, "minNum(x, y)");
+rounded_2ary!(RoundedMaxNum, max_num, "maxNum(x, y)");
+
+/// The IEEE 754 min/max operations.
+#[derive(Clone, Copy, Debug)]
+pub(crate) enum MinMax {
+    Minimum,
+    Maximum,
+    MinimumNumber,
+    MaximumNumber,
+    MinimumMagnitude,
+    MaximumMagnitude,
+    MinNum,
+    MaxNum,
+}
+
+/// Result of a min/max operation: one of the two arguments, or NaN.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum MinMaxResult {
+    First,
+    Second,
+    Nan,
+}
+
+/// Compares two numerical values, ordering `-0` below `+0`.
+fn cmp_signed<N1: Real, N2: Real>(src1: &N1, src2: &N2) -> Ordering {
+    let s1 = src1.sign().unwrap_or(false);
+    let s2 = src2.sign().unwrap_or(false);
+    match (s1, s2) {
+        (false, true) => Ordering::Greater,
+        (true, false) => Ordering::Less,
+        (false, false) => cmp_magnitude(src1, src2),
+        (true, true) => cmp_magnitude(src2, src1),
+    }
+}
+
+/// Selects the result of the min/max operation `op` applied to
+/// `src1` and `src2`. Returns the selected argument (or NaN) and
+/// whether the operation signals an invalid operation.
+pub(crate) fn min_max<N1, N2>(src1: &N1, src2: &N2, op: MinMax) -> (MinMaxResult, bool)
+where
+    N1: Real,
+    N2: Real,
+{
+    let invalid = src1.is_signaling() || src2.is_signaling();
+    let nan1 = !src1.is_numerical();
+    let nan2 = !src2.is_numerical();
+
+    let result = match op {
+        MinMax::MinNum | MinMax::MaxNum if invalid => MinMaxResult::Nan,
+        MinMax::MinimumNumber | MinMax::MaximumNumber | MinMax::MinNum | MinMax::MaxNum
+            if nan1 || nan2 =>
+        {
+            // prefer the number
+            match (nan1, nan2) {
+                (true, true) => MinMaxResult::Nan,
+                (true, false) => MinMaxResult::Second,
+                _ => MinMaxResult::First,
+            }
+        }
+        _ if nan1 || nan2 => MinMaxResult::Nan,
+        _ => {
+            let ord = match op {
+                MinMax::MinimumMagnitude | MinMax::MaximumMagnitude => {
+                    match cmp_magnitude(src1, src2) {
+                        Ordering::Equal => cmp_signed(src1, src2),
+                        ord => ord,
+                    }
+                }
+                _ => cmp_signed(src1, src2),
+            };
+
+            let min = matches!(
+                op,
+                MinMax::Minimum | MinMax::MinimumNumber | MinMax::MinimumMagnitude | MinMax::MinNum
+            );
+            match (ord, min) {
+                (Ordering::Greater, true) | (Ordering::Less, false) => MinMaxResult::Second,
+                _ => MinMaxResult::First,
+            }
+        }
+    };
+
+    (result, invalid)
+}
 
 macro_rules! rounded_3ary {
     ($trait:ident, $impl:ident, $descr:expr) => {
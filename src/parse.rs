@@ -0,0 +1,456 @@
+//! Parsing numbers from strings.
+//!
+//! Decimal strings like `"0.1"` or `"-1.5e-300"` usually denote values
+//! that are not exactly representable by any binary number.
+//! Rounding contexts implementing [`RoundedParse`] correctly round
+//! the exact (rational) value of a decimal string, raising whatever
+//! exceptions the context would normally raise when rounding.
+//! Since the rational value is rounded directly, there is no
+//! intermediate binary value so parsing never double rounds.
+//!
+//! Literals with exponents far outside the range of a format are not
+//! computed exactly since they round like any other value beyond that
+//! range, e.g., `"1e99999999999999999999"` overflows to infinity under an
+//! [`IEEE754Context`]. Formats with an
+//! unbounded exponent fail with [`ParseRealError::OutOfRange`] instead.
+//!
+//! Hexadecimal strings in the style of C99 `%a`, e.g., `"-0x1.8p-3"`,
+//! are also accepted; these always denote binary numbers so they
+//! can be parsed exactly into an [`RFloat`].
+//!
+//! The accepted syntax is
+//!
+//! ```text
+//! [+-]? (digits [. digits?] | . digits) ([eE] [+-]? digits)?
+//! [+-]? 0[xX] (hexdigits [. hexdigits?] | . hexdigits) ([pP] [+-]? digits)?
+//! [+-]? (inf | infinity)
+//! [+-]? s? nan ( '(' payload ')' )?
+//! ```
+//!
+//! where special values are case-insensitive. NaN payloads are
+//! either decimal or hexadecimal (prefixed by `0x`) integers and
+//! are only preserved when parsing under an
+//! [`IEEE754Context`][crate::ieee754::IEEE754Context], where `snan`
+//! denotes a signaling NaN (see [`IEEE754::nan_quiet`] and
+//! [`IEEE754::nan_payload`]), or a [`DecimalContext`].
+//!
+//! Parsing under a [`DecimalContext`] also preserves the exponent
+//! as written, e.g., `"1.50"` and `"1.5"` are distinct members
+//! of the same cohort (see [`crate::decimal`]).
+//!
+
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+use num_traits::Zero;
+use rug::Integer;
+
+use crate::decfixed::DecimalFixedContext;
+use crate::decimal::dfloat::checked_scale10;
+use crate::decimal::{DFloat, DecimalContext, DecimalVal};
+use crate::fixed::FixedContext;
+use crate::float::FloatContext;
+use crate::ieee754::{IEEE754Context, IEEE754Val, SpecialValues, IEEE754};
+use crate::posit::PositContext;
+use crate::rational::{decimal_to_odd, saturate_e, RationalContext};
+use crate::rfloat::{RFloat, RFloatContext};
+use crate::RoundingContext;
+
+/// An error which can be returned when parsing a number.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ParseRealError {
+    /// The string was empty.
+    Empty,
+    /// The string is not a valid number.
+    Invalid,
+    /// The string is a valid number, but it cannot be
+    /// represented exactly by the result type.
+    Inexact,
+    /// The string is a valid number, but its exponent is too large
+    /// to compute its exact value for the result type.
+    OutOfRange,
+}
+
+impl fmt::Display for ParseRealError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseRealError::Empty => write!(f, "cannot parse number from empty string"),
+            ParseRealError::Invalid => write!(f, "invalid number literal"),
+            ParseRealError::Inexact => write!(f, "number literal is not exactly representable"),
+            ParseRealError::OutOfRange => write!(f, "number literal exponent is out of range"),
+        }
+    }
+}
+
+impl Error for ParseRealError {}
+
+/// A parsed number literal: exact, but not necessarily binary.
+#[derive(Clone, Debug)]
+pub(crate) enum Literal {
+    /// A finite binary number `(-1)^s * c * 2^exp`.
+    Binary(bool, Integer, isize),
+    /// A finite decimal number `(-1)^s * c * 10^exp` with
+    /// the exponent as written, e.g., `1.50` has exponent -2.
+    Decimal(bool, Integer, isize),
+    /// An infinity with a sign.
+    Infinity(bool),
+    /// Not-a-number with a sign, quiet bit, and payload.
+    Nan(bool, bool, Integer),
+}
+
+impl Literal {
+    /// Parses a string into a [`Literal`].
+    pub(crate) fn parse(s: &str) -> Result<Self, ParseRealError> {
+        if s.is_empty() {
+            return Err(ParseRealError::Empty);
+        }
+
+        // optional sign
+        let (sign, body) = match s.as_bytes()[0] {
+            b'-' => (true, &s[1..]),
+            b'+' => (false, &s[1..]),
+            _ => (false, s),
+        };
+
+        // special values
+        let lower = body.to_ascii_lowercase();
+        match lower.as_str() {
+            "inf" | "infinity" => return Ok(Literal::Infinity(sign)),
+            "nan" => return Ok(Literal::Nan(sign, true, Integer::zero())),
+            "snan" => return Ok(Literal::Nan(sign, false, Integer::from(1))),
+            _ => (),
+        }
+
+        // NaN with payload
+        if let Some(payload) = lower.strip_suffix(')') {
+            let (quiet, payload) = if let Some(payload) = payload.strip_prefix("nan(") {
+                (true, payload)
+            } else if let Some(payload) = payload.strip_prefix("snan(") {
+                (false, payload)
+            } else {
+                return Err(ParseRealError::Invalid);
+            };
+
+            let payload = Self::parse_integer(payload)?;
+            return Ok(Literal::Nan(sign, quiet, payload));
+        }
+
+        // hexadecimal literals
+        if let Some(hex) = lower.strip_prefix("0x") {
+            return Self::parse_hex(sign, hex);
+        }
+
+        // split off the exponent
+        let (mantissa, exp) = match body.find(['e', 'E']) {
+            Some(i) => (&body[..i], Self::parse_exponent(&body[i + 1..])?),
+            None => (body, 0),
+        };
+
+        // split the mantissa at the decimal point
+        let (int, frac) = match mantissa.find('.') {
+            Some(i) => (&mantissa[..i], &mantissa[i + 1..]),
+            None => (mantissa, ""),
+        };
+
+        if int.is_empty() && frac.is_empty() {
+            return Err(ParseRealError::Invalid);
+        }
+        if !int.bytes().chain(frac.bytes()).all(|b| b.is_ascii_digit()) {
+            return Err(ParseRealError::Invalid);
+        }
+
+        // compose `c * 10^exp` exactly
+        let digits = [int, frac].concat();
+        let c = Integer::from_str(&digits).map_err(|_| ParseRealError::Invalid)?;
+        let exp = Self::shift_exponent(exp, frac.len());
+
+        Ok(Literal::Decimal(sign, c, exp))
+    }
+
+    /// Parses an optionally signed exponent. Exponents that do not
+    /// fit in an `isize` saturate since they are far outside the range
+    /// of any format with a bounded exponent (see [`Literal::round`]).
+    fn parse_exponent(s: &str) -> Result<isize, ParseRealError> {
+        let digits = s.strip_prefix(['+', '-']).unwrap_or(s);
+        if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(ParseRealError::Invalid);
+        }
+
+        match s.parse::<isize>() {
+            Ok(exp) => Ok(exp),
+            Err(_) if s.starts_with('-') => Ok(isize::MIN),
+            Err(_) => Ok(isize::MAX),
+        }
+    }
+
+    /// Subtracts `k` from a parsed exponent.
+    /// Saturated exponents stay saturated.
+    fn shift_exponent(exp: isize, k: usize) -> isize {
+        if Self::is_saturated(exp) {
+            exp
+        } else {
+            exp.saturating_sub(k as isize)
+        }
+    }
+
+    /// Returns true if a parsed exponent may have saturated.
+    fn is_saturated(exp: isize) -> bool {
+        exp == isize::MIN || exp == isize::MAX
+    }
+
+    /// Parses a decimal or hexadecimal (prefixed by `0x`) integer.
+    fn parse_integer(s: &str) -> Result<Integer, ParseRealError> {
+        let (radix, digits) = match s.strip_prefix("0x") {
+            Some(digits) => (16, digits),
+            None => (10, s),
+        };
+
+        if digits.is_empty() || !digits.chars().all(|c| c.is_digit(radix)) {
+            return Err(ParseRealError::Invalid);
+        }
+
+        Integer::from_str_radix(digits, radix as i32).map_err(|_| ParseRealError::Invalid)
+    }
+
+    /// Parses the body of a hexadecimal literal (after `0x`).
+    /// The body must be in lowercase.
+    fn parse_hex(sign: bool, body: &str) -> Result<Self, ParseRealError> {
+        // split off the (binary) exponent
+        let (mantissa, exp) = match body.find('p') {
+            Some(i) => (&body[..i], Self::parse_exponent(&body[i + 1..])?),
+            None => (body, 0),
+        };
+
+        // split the mantissa at the point
+        let (int, frac) = match mantissa.find('.') {
+            Some(i) => (&mantissa[..i], &mantissa[i + 1..]),
+            None => (mantissa, ""),
+        };
+
+        if int.is_empty() && frac.is_empty() {
+            return Err(ParseRealError::Invalid);
+        }
+        if !int
+            .bytes()
+            .chain(frac.bytes())
+            .all(|b| b.is_ascii_hexdigit())
+        {
+            return Err(ParseRealError::Invalid);
+        }
+
+        // compose `c * 2^exp` exactly
+        let digits = [int, frac].concat();
+        let c = Integer::from_str_radix(&digits, 16).map_err(|_| ParseRealError::Invalid)?;
+        let exp = Self::shift_exponent(exp, 4 * frac.len());
+
+        Ok(Literal::Binary(sign, c, exp))
+    }
+
+    /// Converts a non-zero binary literal `c * 2^exp` with sign `s`
+    /// to an [`RFloat`] value exactly. Fails if the exponent may have
+    /// saturated or if the normalized exponent does not fit in an `isize`.
+    fn binary_to_rfloat(s: bool, c: &Integer, exp: isize) -> Result<RFloat, ParseRealError> {
+        if Self::is_saturated(exp) || exp.checked_add(c.significant_bits() as isize).is_none() {
+            Err(ParseRealError::OutOfRange)
+        } else {
+            // strip trailing zeros
+            let tz = c.find_one(0).unwrap();
+            Ok(RFloat::Real(s, exp + tz as isize, c.clone() >> tz))
+        }
+    }
+
+    /// Rounds this literal under a [`RationalContext`].
+    /// Exponents too large to compute the exact value of the literal
+    /// are only allowed if the context bounds the exponent.
+    pub(crate) fn round<Ctx: RationalContext>(
+        &self,
+        ctx: &Ctx,
+    ) -> Result<Ctx::Format, ParseRealError> {
+        match self {
+            Literal::Binary(s, c, _) if c.is_zero() => {
+                // preserve the sign of zero
+                Ok(ctx.round(&RFloat::Real(*s, 0, Integer::zero())))
+            }
+            Literal::Decimal(s, c, exp) => {
+                let num = decimal_to_odd(ctx, *s, c, *exp).ok_or(ParseRealError::OutOfRange)?;
+                Ok(ctx.round(&num))
+            }
+            Literal::Binary(s, c, exp) => {
+                let e = exp.saturating_add(c.significant_bits() as isize - 1);
+                if let Some(num) = saturate_e(ctx, *s, e, e) {
+                    return Ok(ctx.round(&num));
+                }
+
+                Ok(ctx.round(&Self::binary_to_rfloat(*s, c, *exp)?))
+            }
+            Literal::Infinity(s) => {
+                if *s {
+                    Ok(ctx.round(&RFloat::NegInfinity))
+                } else {
+                    Ok(ctx.round(&RFloat::PosInfinity))
+                }
+            }
+            Literal::Nan(_, _, _) => Ok(ctx.round(&RFloat::Nan)),
+        }
+    }
+}
+
+/// Correctly rounded parsing for rounding contexts.
+pub trait RoundedParse: RoundingContext {
+    /// Parses a string as a number, rounding its exact value
+    /// according to this [`RoundingContext`].
+    fn parse(&self, s: &str) -> Result<Self::Format, ParseRealError>;
+}
+
+/// Parses a string as a number, rounding its exact value
+/// according to the [`RoundingContext`] ctx.
+pub fn parse<Ctx: RoundedParse>(ctx: &Ctx, s: &str) -> Result<Ctx::Format, ParseRealError> {
+    ctx.parse(s)
+}
+
+macro_rules! rounded_parse_impl {
+    ($ctx:ty) => {
+        impl RoundedParse for $ctx {
+            fn parse(&self, s: &str) -> Result<Self::Format, ParseRealError> {
+                Literal::parse(s)?.round(self)
+            }
+        }
+    };
+}
+
+rounded_parse_impl!(RFloatContext);
+rounded_parse_impl!(FloatContext);
+rounded_parse_impl!(FixedContext);
+rounded_parse_impl!(PositContext);
+
+impl RoundedParse for IEEE754Context {
+    fn parse(&self, s: &str) -> Result<Self::Format, ParseRealError> {
+        match Literal::parse(s)? {
+            Literal::Nan(s, quiet, payload) if self.specials() == SpecialValues::InfNan => {
+                // payload must fit below the quiet bit and
+                // signaling NaNs must have a non-zero payload
+                let max_bits = self.max_m() - 1;
+                if payload.significant_bits() as usize > max_bits || (!quiet && payload.is_zero()) {
+                    return Err(ParseRealError::Invalid);
+                }
+
+                Ok(IEEE754 {
+                    num: IEEE754Val::Nan(s, quiet, payload),
+                    flags: Default::default(),
+                    ctx: self.clone(),
+                })
+            }
+            lit => lit.round(self),
+        }
+    }
+}
+
+/// Parses an [`RFloat`] from a string. Parsing fails with
+/// [`ParseRealError::Inexact`] if the string does not denote
+/// a binary number, e.g., `"0.1"`. The sign of zero is preserved,
+/// e.g., `"-0"` is `RFloat::Real(true, 0, 0)`.
+impl FromStr for RFloat {
+    type Err = ParseRealError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match Literal::parse(s)? {
+            Literal::Decimal(s, c, _) | Literal::Binary(s, c, _) if c.is_zero() => {
+                // preserve the sign of zero
+                Ok(RFloat::Real(s, 0, Integer::zero()))
+            }
+            Literal::Binary(s, c, exp) => Literal::binary_to_rfloat(s, &c, exp),
+            Literal::Decimal(_, c, exp)
+                if exp < 0 && exp.unsigned_abs() > c.significant_bits() as usize =>
+            {
+                // `5^-exp` cannot divide `c`
+                Err(ParseRealError::Inexact)
+            }
+            Literal::Decimal(s, c, exp) => {
+                // exact only if the denominator is a power of two
+                let q = checked_scale10(c, exp).ok_or(ParseRealError::OutOfRange)?;
+                let (c, den) = q.into_numer_denom();
+                if !den.is_power_of_two() {
+                    return Err(ParseRealError::Inexact);
+                }
+
+                let exp = -(den.significant_bits() as isize - 1);
+                Ok(RFloat::Real(s, exp, c))
+            }
+            Literal::Infinity(s) => Ok(if s {
+                RFloat::NegInfinity
+            } else {
+                RFloat::PosInfinity
+            }),
+            Literal::Nan(_, _, _) => Ok(RFloat::Nan),
+        }
+    }
+}
+
+/// Parses a [`DFloat`] from a string, preserving the exponent
+/// as written, e.g., `"1.50"` has exponent -2. Hexadecimal
+/// strings are converted exactly.
+impl FromStr for DFloat {
+    type Err = ParseRealError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match Literal::parse(s)? {
+            Literal::Decimal(s, c, exp) => Ok(DFloat::Real(s, exp, c)),
+            Literal::Binary(s, c, _) if c.is_zero() => Ok(DFloat::Real(s, 0, Integer::zero())),
+            Literal::Binary(s, c, exp) => {
+                // hexadecimal literals are always binary numbers,
+                // but `2^exp` must be computed exactly
+                if u32::try_from(exp.unsigned_abs()).is_err() {
+                    return Err(ParseRealError::OutOfRange);
+                }
+                Ok(DFloat::from_number(&Literal::binary_to_rfloat(s, &c, exp)?))
+            }
+            Literal::Infinity(s) => Ok(if s {
+                DFloat::NegInfinity
+            } else {
+                DFloat::PosInfinity
+            }),
+            Literal::Nan(_, _, _) => Ok(DFloat::Nan),
+        }
+    }
+}
+
+/// Parsing under a [`DecimalContext`] preserves the exponent as written
+/// whenever the value is exactly representable, e.g., `"1.50"` and `"1.5"`
+/// are distinct members of the same cohort. NaN payloads must have
+/// fewer than `p` digits.
+impl RoundedParse for DecimalContext {
+    fn parse(&self, s: &str) -> Result<Self::Format, ParseRealError> {
+        match Literal::parse(s)? {
+            Literal::Nan(sign, quiet, payload) => {
+                if payload >= self.lead_scale() {
+                    return Err(ParseRealError::Invalid);
+                }
+
+                // `snan` without a payload
+                let body = s.trim_start_matches(['+', '-']);
+                let payload = if body.eq_ignore_ascii_case("snan") {
+                    Integer::zero()
+                } else {
+                    payload
+                };
+
+                Ok(self.make(DecimalVal::Nan(sign, quiet, payload)))
+            }
+            Literal::Binary(s, c, exp) if !c.is_zero() => {
+                // saturated exponents are still far outside the range
+                let exp = exp.clamp(isize::MIN / 2, isize::MAX / 2);
+                Ok(self.round(&RFloat::Real(s, exp, c)))
+            }
+            _ => Ok(self.round(&DFloat::from_str(s)?)),
+        }
+    }
+}
+
+/// Parsing under a [`DecimalFixedContext`] rounds the exact value
+/// of the string directly.
+impl RoundedParse for DecimalFixedContext {
+    fn parse(&self, s: &str) -> Result<Self::Format, ParseRealError> {
+        Ok(self.round(&DFloat::from_str(s)?))
+    }
+}
@@ -0,0 +1,165 @@
+use rug::Integer;
+
+use crate::ops::round_integral;
+use crate::rfloat::RFloat;
+use crate::{Real, RoundingContext, RoundingMode};
+
+use super::{Posit, PositContext};
+
+// Posit functions required by the 2022 Posit Standard that are
+// not covered by the operations in `crate::ops`. Arithmetic,
+// elementary functions, `ceil`, and `floor` are implemented as
+// `crate::ops` traits, conversions between posit formats are just
+// rounding, and comparison is the total order on `Posit`.
+
+/// Rounds `x` to an integer according to `rm`.
+/// Returns `None` if `x` is `NAR`.
+fn to_integer(x: &Posit, rm: RoundingMode) -> Option<Integer> {
+    match round_integral(x, rm).0 {
+        RFloat::Real(s, exp, c) => {
+            let c = c << u32::try_from(exp).expect("exponent out of range");
+            Some(if s { -c } else { c })
+        }
+        RFloat::Nan => None,
+        _ => Some(Integer::new()),
+    }
+}
+
+/// Converts an integer to a real number.
+fn integer_to_rfloat(i: Integer) -> RFloat {
+    if i == 0 {
+        RFloat::zero()
+    } else {
+        RFloat::Real(i < 0, 0, i.abs())
+    }
+}
+
+impl PositContext {
+    /// Asserts that `x` is in the format of this context.
+    pub(crate) fn assert_format(&self, x: &Posit) {
+        assert!(
+            x.ctx().es() == self.es() && x.ctx().nbits() == self.nbits(),
+            "posit must be in the format of this context"
+        );
+    }
+
+    /// Converts a two's complement bitpattern to a [`Posit`]
+    /// regardless of the encoding of this format.
+    fn ordinal_to_number(&self, b: Integer) -> Posit {
+        let b = b.keep_bits(self.nbits() as u32);
+        self.bits_to_number(if self.is_standard() {
+            b
+        } else {
+            self.twos_to_sign_magnitude(b)
+        })
+    }
+
+    /// Rounds `x` to an integer according to `rm` and then
+    /// rounds the result under this context.
+    fn round_integral(&self, x: &Posit, rm: RoundingMode) -> Posit {
+        match to_integer(x, rm) {
+            Some(i) => self.round(&integer_to_rfloat(i)),
+            None => self.nar(),
+        }
+    }
+
+    /// Returns the sign of `x` as a posit: `-1`, `0`, or `1` (`sign`).
+    /// The sign of `NAR` is `NAR`.
+    pub fn signum(&self, x: &Posit) -> Posit {
+        match x.sign() {
+            _ if x.is_nar() => self.nar(),
+            None => self.zero(),
+            Some(s) => self.round(&integer_to_rfloat(Integer::from(if s { -1 } else { 1 }))),
+        }
+    }
+
+    /// Rounds `x` to the nearest integer, with ties to even (`nearestInt`).
+    pub fn nearest_int(&self, x: &Posit) -> Posit {
+        self.round_integral(x, RoundingMode::NearestTiesToEven)
+    }
+
+    /// Returns the integer part of `x`, i.e., rounds `x` to
+    /// the nearest integer towards zero (`pIntPart`).
+    pub fn int_part(&self, x: &Posit) -> Posit {
+        self.round_integral(x, RoundingMode::ToZero)
+    }
+
+    /// Returns the posit following `x` (`next`). As with
+    /// two's complement integers, `next(maxpos)` is `NAR`
+    /// and `next(NAR)` is `-maxpos`.
+    pub fn next(&self, x: &Posit) -> Posit {
+        self.assert_format(x);
+        self.ordinal_to_number(x.to_ordinal() + 1)
+    }
+
+    /// Returns the posit preceding `x` (`prior`). As with
+    /// two's complement integers, `prior(-maxpos)` is `NAR`
+    /// and `prior(NAR)` is `maxpos`.
+    pub fn prior(&self, x: &Posit) -> Posit {
+        self.assert_format(x);
+        let limit = Integer::from(1) << self.nbits();
+        self.ordinal_to_number(x.to_ordinal() + limit - 1)
+    }
+
+    /// Converts `x` to a signed integer of `nbits` bits, rounding
+    /// to the nearest integer with ties to even (`pToInt`).
+    /// Values beyond the range of the integer format saturate to
+    /// `+/-(2^(nbits-1) - 1)`; `NAR` converts to `-2^(nbits-1)`,
+    /// the integer whose bit pattern is the same as `NAR`.
+    pub fn to_int(&self, x: &Posit, nbits: usize) -> Integer {
+        let max: Integer = (Integer::from(1) << (nbits - 1)) - 1;
+        match to_integer(x, RoundingMode::NearestTiesToEven) {
+            None => -max - 1,
+            Some(i) if i > max => max,
+            Some(i) if i < -max.clone() => -max,
+            Some(i) => i,
+        }
+    }
+
+    /// Converts a signed integer `i` of `nbits` bits to the
+    /// nearest posit (`intToP`). The integer `-2^(nbits-1)`
+    /// converts to `NAR`, the inverse of [`PositContext::to_int`].
+    pub fn from_int(&self, i: &Integer, nbits: usize) -> Posit {
+        let min = -(Integer::from(1) << (nbits - 1));
+        assert!(
+            *i >= min && *i < -min.clone(),
+            "must be a {}-bit signed integer",
+            nbits
+        );
+        if *i == min {
+            self.nar()
+        } else {
+            self.round(&integer_to_rfloat(i.clone()))
+        }
+    }
+
+    /// Computes `a * b * c` with a single rounding (`fMM`).
+    pub fn fmm(&self, a: &Posit, b: &Posit, c: &Posit) -> Posit {
+        let prod = RFloat::from(a.clone()) * RFloat::from(b.clone()) * RFloat::from(c.clone());
+        self.round(&prod)
+    }
+
+    /// Computes `a * b - c` with a single rounding (`fMS`).
+    pub fn fms(&self, a: &Posit, b: &Posit, c: &Posit) -> Posit {
+        let mut q = self.quire();
+        q.mul_add(a, b);
+        q.sub_posit(c);
+        q.to_posit()
+    }
+
+    /// Computes `a * b + c * d` with a single rounding (`fMMA`).
+    pub fn fmma(&self, a: &Posit, b: &Posit, c: &Posit, d: &Posit) -> Posit {
+        let mut q = self.quire();
+        q.mul_add(a, b);
+        q.mul_add(c, d);
+        q.to_posit()
+    }
+
+    /// Computes `a * b - c * d` with a single rounding (`fMMS`).
+    pub fn fmms(&self, a: &Posit, b: &Posit, c: &Posit, d: &Posit) -> Posit {
+        let mut q = self.quire();
+        q.mul_add(a, b);
+        q.mul_sub(c, d);
+        q.to_posit()
+    }
+}
@@ -2,12 +2,18 @@
 //!
 //! This module implements posits with [`PositContext`].
 //! The associated storage type is [`Posit`] which represents
-//! a posit number.
+//! a posit number. A context constructed with
+//! [`PositContext::standard`] complies with the 2022 Posit Standard.
+//! The exact accumulator associated with
+//! each posit format is implemented by [`Quire`].
 
+mod functions;
 mod number;
 pub mod ops;
+mod quire;
 mod round;
 
 pub use number::Posit;
 pub(crate) use number::PositVal;
+pub use quire::Quire;
 pub use round::PositContext;
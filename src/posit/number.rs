@@ -3,7 +3,7 @@ use std::cmp::Ordering;
 use num_traits::{One, Zero};
 use rug::Integer;
 
-use crate::{rfloat::RFloat, util::bitmask, Real};
+use crate::{rfloat::RFloat, util::bitmask, Predicate, Real, RoundingMode};
 
 use super::PositContext;
 
@@ -37,8 +37,45 @@ impl Posit {
         &self.ctx
     }
 
+    /// Converts this [`Posit`] to the nearest [`f64`] value
+    /// according to the rounding mode `rm`.
+    pub fn to_f64(&self, rm: RoundingMode) -> f64 {
+        RFloat::from(self.clone()).to_f64(rm)
+    }
+
+    /// Converts this [`Posit`] to the nearest [`f32`] value
+    /// according to the rounding mode `rm`.
+    pub fn to_f32(&self, rm: RoundingMode) -> f32 {
+        RFloat::from(self.clone()).to_f32(rm)
+    }
+
+    /// Evaluates the comparison predicate `pred` on this [`Posit`]
+    /// and `other`. Posits are totally ordered with `NAR` less than
+    /// every other value and equal to itself, so the operands are never
+    /// unordered and comparisons raise no exceptions (see [`Predicate`]).
+    pub fn compare(&self, other: &Posit, pred: Predicate) -> bool {
+        pred.eval(Some(self.cmp(other)))
+    }
+
     /// Converts this [`Posit`] to an [`Integer`] representing a posit bitpattern.
     pub fn into_bits(self) -> Integer {
+        if self.ctx.is_standard() {
+            self.to_ordinal()
+        } else {
+            self.into_sign_magnitude()
+        }
+    }
+
+    /// Converts this [`Posit`] to its two's complement bitpattern
+    /// regardless of the encoding of its format. Bit patterns of this
+    /// form are ordered as two's complement integers.
+    pub(crate) fn to_ordinal(&self) -> Integer {
+        let b = self.clone().into_sign_magnitude();
+        self.ctx.sign_magnitude_to_twos(b)
+    }
+
+    /// Converts this [`Posit`] to its sign-magnitude bitpattern.
+    fn into_sign_magnitude(self) -> Integer {
         let es = self.ctx.es();
         let nbits = self.ctx.nbits();
         match self.num {
@@ -109,7 +146,7 @@ impl Real for Posit {
     fn exp(&self) -> Option<isize> {
         match &self.num {
             PositVal::Zero => None,
-            PositVal::NonZero(_, r, exp, _) => Some((r * self.ctx.useed()) + exp),
+            PositVal::NonZero(_, r, exp, _) => Some((r * self.ctx.rscale()) + exp),
             PositVal::Nar => None,
         }
     }
@@ -118,7 +155,7 @@ impl Real for Posit {
         match &self.num {
             PositVal::Zero => None,
             PositVal::NonZero(_, r, exp, c) => {
-                Some(((r * self.ctx.useed()) + exp - 1) + (c.significant_bits() as isize))
+                Some(((r * self.ctx.rscale()) + exp - 1) + (c.significant_bits() as isize))
             }
             PositVal::Nar => None,
         }
@@ -127,7 +164,7 @@ impl Real for Posit {
     fn n(&self) -> Option<isize> {
         match &self.num {
             PositVal::Zero => None,
-            PositVal::NonZero(_, r, exp, _) => Some((r * self.ctx.useed()) + exp - 1),
+            PositVal::NonZero(_, r, exp, _) => Some((r * self.ctx.rscale()) + exp - 1),
             PositVal::Nar => None,
         }
     }
@@ -182,33 +219,46 @@ impl PartialEq for Posit {
     }
 }
 
+impl Eq for Posit {}
+
 impl PartialOrd for Posit {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Posits are totally ordered: `NAR` is less than every other value
+/// and equal to itself, as if bit patterns were compared
+/// as two's complement integers.
+impl Ord for Posit {
+    fn cmp(&self, other: &Self) -> Ordering {
         match (&self.num, &other.num) {
-            (PositVal::Nar, PositVal::Nar) => Some(Ordering::Equal),
-            (PositVal::Nar, _) => Some(Ordering::Less),
-            (_, PositVal::Nar) => Some(Ordering::Greater),
-            (PositVal::Zero, PositVal::Zero) => Some(Ordering::Equal),
+            (PositVal::Nar, PositVal::Nar) => Ordering::Equal,
+            (PositVal::Nar, _) => Ordering::Less,
+            (_, PositVal::Nar) => Ordering::Greater,
+            (PositVal::Zero, PositVal::Zero) => Ordering::Equal,
             (PositVal::Zero, PositVal::NonZero(s, _, _, _)) => {
                 if *s {
                     // 0 > -x
-                    Some(Ordering::Greater)
+                    Ordering::Greater
                 } else {
                     // 0 < +X
-                    Some(Ordering::Less)
+                    Ordering::Less
                 }
             }
             (PositVal::NonZero(s, _, _, _), PositVal::Zero) => {
                 if *s {
                     // -x < 0
-                    Some(Ordering::Less)
+                    Ordering::Less
                 } else {
                     // +x > 0
-                    Some(Ordering::Greater)
+                    Ordering::Greater
                 }
             }
             (PositVal::NonZero(_, _, _, _), PositVal::NonZero(_, _, _, _)) => {
-                RFloat::from(self.clone()).partial_cmp(&RFloat::from(other.clone()))
+                RFloat::from(self.clone())
+                    .partial_cmp(&RFloat::from(other.clone()))
+                    .unwrap()
             }
         }
     }
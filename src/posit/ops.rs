@@ -2,7 +2,7 @@ use crate::mpfr::*;
 use crate::ops::*;
 use crate::posit::PositContext;
 use crate::rfloat::RFloat;
-use crate::{Real, RoundingContext};
+use crate::{Real, RoundingContext, RoundingMode};
 
 macro_rules! rounded_1ary_impl {
     ($tname:ident, $name:ident, $mpfr:ident) => {
@@ -26,6 +26,7 @@ rounded_1ary_impl!(RoundedRecip, recip, mpfr_recip);
 rounded_1ary_impl!(RoundedRecipSqrt, recip_sqrt, mpfr_recip_sqrt);
 rounded_1ary_impl!(RoundedExp, exp, mpfr_exp);
 rounded_1ary_impl!(RoundedExp2, exp2, mpfr_exp2);
+rounded_1ary_impl!(RoundedExp10, exp10, mpfr_exp10);
 rounded_1ary_impl!(RoundedLog, log, mpfr_log);
 rounded_1ary_impl!(RoundedLog2, log2, mpfr_log2);
 rounded_1ary_impl!(RoundedLog10, log10, mpfr_log10);
@@ -44,6 +45,9 @@ rounded_1ary_impl!(RoundedTanPi, tan_pi, mpfr_tan_pi);
 rounded_1ary_impl!(RoundedAsin, asin, mpfr_asin);
 rounded_1ary_impl!(RoundedAcos, acos, mpfr_acos);
 rounded_1ary_impl!(RoundedAtan, atan, mpfr_atan);
+rounded_1ary_impl!(RoundedAsinPi, asin_pi, mpfr_asin_pi);
+rounded_1ary_impl!(RoundedAcosPi, acos_pi, mpfr_acos_pi);
+rounded_1ary_impl!(RoundedAtanPi, atan_pi, mpfr_atan_pi);
 rounded_1ary_impl!(RoundedSinh, sinh, mpfr_sinh);
 rounded_1ary_impl!(RoundedCosh, cosh, mpfr_cosh);
 rounded_1ary_impl!(RoundedTanh, tanh, mpfr_tanh);
@@ -55,6 +59,24 @@ rounded_1ary_impl!(RoundedErfc, erfc, mpfr_erfc);
 rounded_1ary_impl!(RoundedGamma, tgamma, mpfr_tgamma);
 rounded_1ary_impl!(RoundedLgamma, lgamma, mpfr_lgamma);
 
+macro_rules! rounded_1ary_int_impl {
+    ($tname:ident, $name:ident, $mpfr:ident) => {
+        impl $tname for PositContext {
+            fn $name<N: Real>(&self, src: &N, n: i64) -> Self::Format {
+                // compute with 2 additional bits, rounding-to-odd
+                let p = self.max_p() + 2;
+                let r = RFloat::from_number(src);
+                let result = $mpfr(r, n, p);
+                self.round(result.num())
+            }
+        }
+    };
+}
+
+rounded_1ary_int_impl!(RoundedCompound, compound, mpfr_compound);
+rounded_1ary_int_impl!(RoundedRootn, rootn, mpfr_rootn);
+rounded_1ary_int_impl!(RoundedPown, pown, mpfr_pown);
+
 macro_rules! rounded_2ary_impl {
     ($tname:ident, $name:ident, $mpfr:ident) => {
         impl $tname for PositContext {
@@ -79,10 +101,13 @@ rounded_2ary_impl!(RoundedSub, sub, mpfr_sub);
 rounded_2ary_impl!(RoundedMul, mul, mpfr_mul);
 rounded_2ary_impl!(RoundedDiv, div, mpfr_div);
 rounded_2ary_impl!(RoundedPow, pow, mpfr_pow);
+rounded_2ary_impl!(RoundedPowr, powr, mpfr_powr);
 rounded_2ary_impl!(RoundedHypot, hypot, mpfr_hypot);
 rounded_2ary_impl!(RoundedFmod, fmod, mpfr_fmod);
 rounded_2ary_impl!(RoundedRemainder, remainder, mpfr_remainder);
 rounded_2ary_impl!(RoundedAtan2, atan2, mpfr_atan2);
+rounded_2ary_impl!(RoundedAtan2Pi, atan2_pi, mpfr_atan2_pi);
+rounded_2ary_impl!(RoundedFdim, fdim, mpfr_fdim);
 
 macro_rules! rounded_3ary_impl {
     ($tname:ident, $name:ident, $mpfr:ident) => {
@@ -106,3 +131,127 @@ macro_rules! rounded_3ary_impl {
 }
 
 rounded_3ary_impl!(RoundedFMA, fma, mpfr_fma);
+
+impl RoundedSinCos for PositContext {
+    fn sin_cos<N: Real>(&self, src: &N) -> (Self::Format, Self::Format) {
+        // compute with 2 additional bits, rounding-to-odd
+        let p = self.max_p() + 2;
+        let r = RFloat::from_number(src);
+        let (sin, cos) = mpfr_sin_cos(r, p);
+        (self.round(sin.num()), self.round(cos.num()))
+    }
+}
+
+impl RoundedRemquo for PositContext {
+    fn remquo<N1, N2>(&self, src1: &N1, src2: &N2) -> (Self::Format, i64)
+    where
+        N1: Real,
+        N2: Real,
+    {
+        // compute with 2 additional bits, rounding-to-odd
+        let p = self.max_p() + 2;
+        let r1 = RFloat::from_number(src1);
+        let r2 = RFloat::from_number(src2);
+        let (result, q) = mpfr_remquo(r1, r2, p);
+        (self.round(result.num()), q)
+    }
+}
+
+impl RoundedFrexp for PositContext {
+    fn frexp<N: Real>(&self, src: &N) -> (Self::Format, isize) {
+        // splitting is exact
+        let (m, e) = frexp_exact(src);
+        (self.round(&m), e)
+    }
+}
+
+impl RoundedModf for PositContext {
+    fn modf<N: Real>(&self, src: &N) -> (Self::Format, Self::Format) {
+        // splitting is exact
+        let (frac, int) = modf_exact(src);
+        (self.round(&frac), self.round(&int))
+    }
+}
+
+macro_rules! rounded_integral_impl {
+    ($tname:ident, $name:ident, $rm:expr) => {
+        impl $tname for PositContext {
+            fn $name<N: Real>(&self, src: &N) -> Self::Format {
+                let (integral, _) = round_integral(src, $rm);
+                self.round(&integral)
+            }
+        }
+    };
+}
+
+rounded_integral_impl!(RoundedFloor, floor, RoundingMode::ToNegative);
+rounded_integral_impl!(RoundedCeil, ceil, RoundingMode::ToPositive);
+rounded_integral_impl!(RoundedTrunc, trunc, RoundingMode::ToZero);
+rounded_integral_impl!(
+    RoundedRoundAway,
+    round_away,
+    RoundingMode::NearestTiesAwayZero
+);
+rounded_integral_impl!(
+    RoundedRoundEven,
+    round_even,
+    RoundingMode::NearestTiesToEven
+);
+
+impl RoundedNearbyInt for PositContext {
+    fn nearbyint<N: Real>(&self, src: &N) -> Self::Format {
+        let (integral, _) = round_integral(src, RoundingMode::NearestTiesToEven);
+        self.round(&integral)
+    }
+}
+
+impl RoundedRint for PositContext {
+    fn rint<N: Real>(&self, src: &N) -> Self::Format {
+        // no exception flags, so this is just `nearbyint`
+        self.nearbyint(src)
+    }
+}
+
+impl RoundedConvertToInteger for PositContext {
+    fn convert_to_integer<N: Real>(&self, src: &N, rm: RoundingMode, _exact: bool) -> Self::Format {
+        // no exception flags, so this is just rounding
+        let (integral, _) = round_integral(src, rm);
+        self.round(&integral)
+    }
+}
+
+macro_rules! min_max_impl {
+    ($tname:ident, $name:ident, $op:expr) => {
+        impl $tname for PositContext {
+            fn $name<N1, N2>(&self, src1: &N1, src2: &N2) -> Self::Format
+            where
+                N1: Real,
+                N2: Real,
+            {
+                // no exception flags, so this is just selection
+                match min_max(src1, src2, $op).0 {
+                    MinMaxResult::First => self.round(src1),
+                    MinMaxResult::Second => self.round(src2),
+                    MinMaxResult::Nan => self.round(&RFloat::Nan),
+                }
+            }
+        }
+    };
+}
+
+min_max_impl!(RoundedMinimum, minimum, MinMax::Minimum);
+min_max_impl!(RoundedMaximum, maximum, MinMax::Maximum);
+min_max_impl!(RoundedMinimumNumber, minimum_number, MinMax::MinimumNumber);
+min_max_impl!(RoundedMaximumNumber, maximum_number, MinMax::MaximumNumber);
+min_max_impl!(
+    RoundedMinimumMagnitude,
+    minimum_magnitude,
+    MinMax::MinimumMagnitude
+);
+min_max_impl!(
+    RoundedMaximumMagnitude,
+    maximum_magnitude,
+    MinMax::MaximumMagnitude
+);
+min_max_impl!(RoundedMinNum, min_num, MinMax::MinNum);
+min_max_impl!(RoundedMaxNum, max_num, MinMax::MaxNum);
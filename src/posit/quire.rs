@@ -0,0 +1,201 @@
+use rug::Integer;
+
+use crate::rfloat::RFloat;
+use crate::{Real, RoundingContext};
+
+use super::{Posit, PositContext};
+
+/// The quire, an exact accumulator for posit numbers.
+///
+/// As described by the 2022 Posit Standard, a quire is a
+/// two's complement, fixed-point register associated with
+/// a posit format. Its least significant bit has weight `minpos^2`,
+/// so the product of any two posits is exactly representable,
+/// and it has enough carry bits so that at least `2^31` products
+/// of `maxpos^2` may be accumulated without overflow.
+/// For standard posits (`es = 2`), the quire is `16 * nbits`
+/// bits wide; in general, it is `2 * (emax - emin) + 32` bits wide
+/// (see [`PositContext::quire_bits`]).
+///
+/// The bit pattern `1000...0` is `NAR`. Once a quire is `NAR`,
+/// it remains so until it is cleared.
+/// On overflow, the quire wraps around as a two's complement
+/// integer would.
+///
+/// Operations on a quire are exact; rounding only occurs
+/// when the quire is converted to a posit (see [`Quire::to_posit`]).
+#[derive(Clone, Debug)]
+pub struct Quire {
+    acc: Integer,
+    ctx: PositContext,
+}
+
+impl Quire {
+    /// Returns the rounding context of the associated posit format.
+    pub fn ctx(&self) -> &PositContext {
+        &self.ctx
+    }
+
+    /// Returns true if this quire is `NAR`.
+    pub fn is_nar(&self) -> bool {
+        self.acc == self.ctx.quire_nar()
+    }
+
+    /// Returns true if this quire is zero.
+    pub fn is_zero(&self) -> bool {
+        self.acc == 0
+    }
+
+    /// Sets this quire to zero (`qClear`).
+    pub fn clear(&mut self) {
+        self.acc = Integer::new();
+    }
+
+    /// Negates this quire (`qNegate`).
+    /// The negation of `NAR` is `NAR`.
+    pub fn negate(&mut self) {
+        self.acc = self.ctx.quire_wrap(-self.acc.clone());
+    }
+
+    /// Adds a posit to this quire (`qAddP`).
+    pub fn add_posit(&mut self, a: &Posit) {
+        self.ctx.assert_format(a);
+        if a.is_nar() {
+            self.acc = self.ctx.quire_nar();
+        } else if !self.is_nar() && !a.is_zero() {
+            self.accumulate(a.m().unwrap(), a.exp().unwrap());
+        }
+    }
+
+    /// Subtracts a posit from this quire (`qSubP`).
+    pub fn sub_posit(&mut self, a: &Posit) {
+        self.ctx.assert_format(a);
+        if a.is_nar() {
+            self.acc = self.ctx.quire_nar();
+        } else if !self.is_nar() && !a.is_zero() {
+            self.accumulate(-a.m().unwrap(), a.exp().unwrap());
+        }
+    }
+
+    /// Adds the exact product of two posits to this quire (`qMulAdd`).
+    pub fn mul_add(&mut self, a: &Posit, b: &Posit) {
+        self.ctx.assert_format(a);
+        self.ctx.assert_format(b);
+        if a.is_nar() || b.is_nar() {
+            self.acc = self.ctx.quire_nar();
+        } else if !self.is_nar() && !a.is_zero() && !b.is_zero() {
+            let m = a.m().unwrap() * b.m().unwrap();
+            self.accumulate(m, a.exp().unwrap() + b.exp().unwrap());
+        }
+    }
+
+    /// Subtracts the exact product of two posits from this quire (`qMulSub`).
+    pub fn mul_sub(&mut self, a: &Posit, b: &Posit) {
+        self.ctx.assert_format(a);
+        self.ctx.assert_format(b);
+        if a.is_nar() || b.is_nar() {
+            self.acc = self.ctx.quire_nar();
+        } else if !self.is_nar() && !a.is_zero() && !b.is_zero() {
+            let m = a.m().unwrap() * b.m().unwrap();
+            self.accumulate(-m, a.exp().unwrap() + b.exp().unwrap());
+        }
+    }
+
+    /// Rounds the value of this quire to the associated
+    /// posit format (`qToP`). The result is correctly rounded.
+    pub fn to_posit(&self) -> Posit {
+        self.ctx.round(&RFloat::from(self.clone()))
+    }
+
+    /// Converts this [`Quire`] to an [`Integer`] representing
+    /// a quire bitpattern.
+    pub fn into_bits(self) -> Integer {
+        if self.acc < 0 {
+            self.acc + (Integer::from(1) << self.ctx.quire_bits())
+        } else {
+            self.acc
+        }
+    }
+
+    /// Adds `m * 2^exp` to the accumulator.
+    fn accumulate(&mut self, m: Integer, exp: isize) {
+        let shift = exp - self.ctx.quire_exp();
+        assert!(shift >= 0, "value is below the quire LSB");
+        let acc = self.acc.clone() + (m << (shift as u32));
+        self.acc = self.ctx.quire_wrap(acc);
+    }
+}
+
+// Quire utility functions.
+impl PositContext {
+    /// Returns the bitwidth of the quire associated with this format.
+    pub fn quire_bits(&self) -> usize {
+        (2 * (self.emax() - self.emin()) + 32) as usize
+    }
+
+    /// Returns the weight of the quire LSB, i.e., `2 * emin`.
+    fn quire_exp(&self) -> isize {
+        2 * self.emin()
+    }
+
+    /// The (signed) value of the `NAR` quire.
+    fn quire_nar(&self) -> Integer {
+        -(Integer::from(1) << (self.quire_bits() - 1))
+    }
+
+    /// Wraps an accumulator value to the quire as a two's
+    /// complement integer would.
+    fn quire_wrap(&self, acc: Integer) -> Integer {
+        let qbits = self.quire_bits();
+        let acc = acc.keep_bits(qbits as u32);
+        if acc.get_bit((qbits - 1) as u32) {
+            acc - (Integer::from(1) << qbits)
+        } else {
+            acc
+        }
+    }
+
+    /// Constructs a zero quire for this format (`qClear`).
+    pub fn quire(&self) -> Quire {
+        Quire {
+            acc: Integer::new(),
+            ctx: self.clone(),
+        }
+    }
+
+    /// Converts an [`Integer`] representing a quire bitpattern
+    /// into a [`Quire`] under this [`PositContext`].
+    pub fn bits_to_quire(&self, b: Integer) -> Quire {
+        let limit = Integer::from(1) << self.quire_bits();
+        assert!(b < limit, "must be less than 1 << quire_bits");
+        Quire {
+            acc: self.quire_wrap(b),
+            ctx: self.clone(),
+        }
+    }
+
+    /// Computes the dot product of `xs` and `ys` with a single
+    /// rounding, accumulating the exact products in a quire.
+    pub fn fused_dot(&self, xs: &[Posit], ys: &[Posit]) -> Posit {
+        assert_eq!(xs.len(), ys.len(), "vectors must have the same length");
+        let mut q = self.quire();
+        for (x, y) in xs.iter().zip(ys) {
+            q.mul_add(x, y);
+        }
+        q.to_posit()
+    }
+}
+
+impl From<Quire> for RFloat {
+    fn from(value: Quire) -> Self {
+        if value.is_nar() {
+            RFloat::Nan
+        } else if value.is_zero() {
+            RFloat::zero()
+        } else {
+            let exp = value.ctx.quire_exp();
+            let s = value.acc < 0;
+            RFloat::Real(s, exp, value.acc.abs())
+        }
+    }
+}
@@ -1,8 +1,10 @@
+use num_traits::{One, Zero};
 use rug::Integer;
 
-use crate::rfloat::RFloatContext;
+use crate::rational::{number_to_odd, RationalContext};
+use crate::rfloat::RFloat;
 use crate::util::bitmask;
-use crate::{Real, RoundingContext, RoundingMode, Split};
+use crate::{Real, RoundingContext};
 
 use super::{Posit, PositVal};
 
@@ -25,12 +27,21 @@ use super::{Posit, PositVal};
 ///  - total bitwidth of the encoding
 ///
 /// For values in between the largest and smallest magnitude,
-/// [`NearestTiesToEven`][RoundingMode::NearestTiesToEven].
-/// Otherwise, the values are flushed to `NAR`.
+/// [`NearestTiesToEven`][crate::RoundingMode::NearestTiesToEven].
+/// Otherwise, the values saturate to the largest or smallest magnitude;
+/// posits never round to zero or to `NAR`.
+///
+/// By default, bit patterns are encoded in sign-magnitude form.
+/// A context constructed with [`PositContext::standard`] complies with
+/// the 2022 Posit Standard: the exponent field is fixed at `es = 2` and
+/// negative values are encoded as the two's complement of their
+/// magnitude, so that posit bit patterns are ordered as
+/// two's complement integers.
 #[derive(Clone, Debug)]
 pub struct PositContext {
     es: usize,
     nbits: usize,
+    standard: bool,
 }
 
 impl PositContext {
@@ -53,7 +64,25 @@ impl PositContext {
             nbits
         );
 
-        Self { es, nbits }
+        Self {
+            es,
+            nbits,
+            standard: false,
+        }
+    }
+
+    /// Constructs a rounding context for the standard posit format
+    /// with `nbits` total bits as described by the 2022 Posit Standard.
+    pub fn standard(nbits: usize) -> Self {
+        Self {
+            standard: true,
+            ..Self::new(2, nbits)
+        }
+    }
+
+    /// Returns true if this context complies with the 2022 Posit Standard.
+    pub fn is_standard(&self) -> bool {
+        self.standard
     }
 
     /// Returns the regime bitwidth of the format produced by
@@ -151,7 +180,35 @@ impl PositContext {
     pub fn bits_to_number(&self, b: Integer) -> Posit {
         let limit = Integer::from(1) << self.nbits;
         assert!(b < limit, "must be less than 1 << nbits");
+        if self.standard {
+            self.decode(self.twos_to_sign_magnitude(b))
+        } else {
+            self.decode(b)
+        }
+    }
+
+    /// Converts a two's complement bitpattern to sign-magnitude form.
+    pub(crate) fn twos_to_sign_magnitude(&self, b: Integer) -> Integer {
+        let top = Integer::from(1) << (self.nbits - 1);
+        if b > top {
+            top | ((Integer::from(1) << self.nbits) - b)
+        } else {
+            b
+        }
+    }
+
+    /// Converts a sign-magnitude bitpattern to two's complement form.
+    pub(crate) fn sign_magnitude_to_twos(&self, b: Integer) -> Integer {
+        let top = Integer::from(1) << (self.nbits - 1);
+        if b > top {
+            (Integer::from(1) << self.nbits) - (b - top)
+        } else {
+            b
+        }
+    }
 
+    /// Decodes a sign-magnitude posit bitpattern.
+    fn decode(&self, b: Integer) -> Posit {
         // decompose into sign and magnitude
         let s = b.get_bit((self.nbits - 1) as u32);
         let ns = b & bitmask(self.nbits - 1);
@@ -222,45 +279,68 @@ impl PositContext {
 
 // Rounding utility functions.
 impl PositContext {
-    fn round_params<T: Real>(&self, num: &T) -> (isize, usize) {
-        assert!(
-            !num.is_nar() && !num.is_zero(),
-            "must be a finite, non-zero {:?}",
-            num
-        );
-
-        let useed = self.useed();
-        let r = num.e().unwrap() / useed;
-        let kbits = if r < 0 { -r } else { r + 1 } as usize;
-        let embits = self.nbits - (kbits + 2);
-        let mbits = if embits <= self.es {
-            0
+    /// Rounds a finite, non-zero value within the range of this format.
+    ///
+    /// As described by the Posit standard, rounding is performed on
+    /// the (unbounded) bit string of the value: the encoding is
+    /// truncated to `nbits - 1` bits and rounded to nearest,
+    /// ties to even; the midpoint between two posits is the
+    /// posit with one additional bit, so rounding in the exponent
+    /// field is not rounding to the nearest value.
+    fn round_finite(&self, num: &RFloat) -> Posit {
+        let s = num.sign().unwrap();
+        let e = num.e().unwrap();
+        let c = num.c().unwrap();
+        let p = c.significant_bits() as usize;
+
+        // split the exponent into regime and exponent
+        let r = e.div_euclid(self.rscale());
+        let exp = e.rem_euclid(self.rscale());
+
+        // compute size of regime field and regime LSB
+        let (kbits, r0) = if r < 0 {
+            (-r as usize, false)
         } else {
-            embits - self.es
+            (r as usize + 1, true)
         };
 
-        (useed, mbits)
-    }
-
-    fn round_finite(&self, split: Split, useed: isize) -> Posit {
-        // step 4: extract split parameters
-        let s = split.sign().unwrap();
-
-        // step 5: finalize the rounding
-        let rounded = RFloatContext::round_finalize(split, RoundingMode::NearestTiesToEven);
-
-        // step 6: recompute exponent (in case it has changed)
-        let e = rounded.e().unwrap();
-        let r = e / useed;
-        let e = e % useed;
+        // unbounded encoding of the magnitude
+        let rfield = if r0 {
+            bitmask(kbits) << 1
+        } else {
+            Integer::one()
+        };
+        let mfield = c & bitmask(p - 1);
+        let len = (kbits + 1) + self.es + (p - 1);
+        let encoded = (((rfield << self.es) | Integer::from(exp)) << (p - 1)) | mfield;
+
+        // round to `nbits - 1` bits, nearest, ties to even
+        let width = self.nbits - 1;
+        let mut ns = if len <= width {
+            encoded << (width - len)
+        } else {
+            let shift = len - width;
+            let half = Integer::one() << (shift - 1);
+            let rem = encoded.clone() & bitmask(shift);
+            let trunc = encoded >> shift;
+            if rem > half || (rem == half && trunc.is_odd()) {
+                trunc + 1
+            } else {
+                trunc
+            }
+        };
 
-        // compose result
-        let c = rounded.c().unwrap();
-        let exp = (e + 1) - (c.significant_bits() as isize);
-        Posit {
-            num: PositVal::NonZero(s, r, exp, c),
-            ctx: self.clone(),
+        // posits never round to zero
+        if ns == 0 {
+            ns = Integer::one();
         }
+
+        let sfield = if s {
+            Integer::one() << width
+        } else {
+            Integer::zero()
+        };
+        self.decode(sfield | ns)
     }
 }
 
@@ -268,6 +348,11 @@ impl RoundingContext for PositContext {
     type Format = Posit;
 
     fn round<T: Real>(&self, val: &T) -> Self::Format {
+        if T::radix() == 10 {
+            // decimal values are rounded through their exact value
+            return self.round(&number_to_odd(self, val));
+        }
+
         if val.is_nar() {
             // all NaN and infinities are mapped to Nar
             self.nar()
@@ -284,26 +369,25 @@ impl RoundingContext for PositContext {
             if e >= self.emax() {
                 // |val| >= MAXVAL
                 self.maxval(s)
-            } else if e <= self.emin() {
-                // |val| <= MINVAL
+            } else if e < self.emin() {
+                // |val| < MINVAL
                 self.minval(s)
             } else {
                 // within representable range
-
-                // step 1: compute posit format parameters since it is dynamic
-                let (useed, mbits) = self.round_params(val);
-
-                // step 2: rounding as an unbounded, fixed-precision
-                // floating-point number, so we need to compute the context
-                // parameters: we use precision `mbits + 1` using `NearestTiesToEven`
-                let (p, n) = RFloatContext::new().with_max_p(mbits + 1).round_params(val);
-
-                // step 3: split the significand at binary digit `n`
-                let split = Split::new(val, p, n);
-
-                // step 4...: finalize the rounding using the split
-                self.round_finite(split, useed)
+                self.round_finite(&RFloat::from_number(val))
             }
         }
     }
 }
+
+impl RationalContext for PositContext {
+    fn rational_n(&self, e: isize) -> isize {
+        // posits never have more than `nbits` digits of precision
+        e - (self.nbits as isize)
+    }
+
+    fn rational_e_bounds(&self) -> (Option<isize>, Option<isize>) {
+        // values outside `[MINVAL, MAXVAL]` saturate
+        (Some(self.emin()), Some(self.emax() - 1))
+    }
+}
@@ -0,0 +1,209 @@
+//! Rounding exact rational values.
+//!
+//! Every [`Real`][crate::Real] value is of the form `(-1)^s * c * 2^exp`,
+//! so values like `1/3` or `1/10` cannot be rounded through
+//! [`RoundingContext::round`] without first approximating them
+//! (and rounding twice). Rounding contexts implementing [`RoundRational`]
+//! instead round an exact [`Rational`] value directly,
+//! producing the correctly rounded result with the same exception
+//! flags the context would raise for any other value.
+//!
+
+use num_traits::Zero;
+use rug::{Integer, Rational};
+
+use crate::decimal::dfloat::checked_scale10;
+use crate::fixed::FixedContext;
+use crate::float::FloatContext;
+use crate::ieee754::IEEE754Context;
+use crate::posit::PositContext;
+use crate::rfloat::{RFloat, RFloatContext};
+use crate::{Real, RoundingContext};
+
+/// Rounding contexts that can correctly round exact rational values.
+///
+/// Values like `1/3` or `1/10` cannot be represented by a [`Real`][crate::Real]
+/// value, so they cannot be passed to [`RoundingContext::round`] directly.
+/// Instead, rational values are approximated by an [`RFloat`] value
+/// that is exact above some binary digit `n` and whose digit at position `n`
+/// is a sticky bit (the logical OR of all digits at or below `n`).
+/// This is just round-to-odd at an absolute digit: as long as `n` is
+/// at least two digits below the first digit lost by the context,
+/// rounding the approximation is the same as rounding the rational value.
+pub(crate) trait RationalContext: RoundingContext {
+    /// A lower bound on the first binary digit that will be lost
+    /// when rounding a finite, non-zero value with normalized
+    /// exponent `e` (see [`Real::e`][crate::Real::e]) under this context.
+    fn rational_n(&self, e: isize) -> isize;
+
+    /// Number of digits below the first lost digit at which
+    /// rational values are rounded-to-odd. Stochastic rounding
+    /// needs more digits than deterministic rounding.
+    fn rational_guard_bits(&self) -> usize {
+        2
+    }
+
+    /// Bounds on the normalized exponent of values that round
+    /// differently under this context. Every finite, non-zero value
+    /// whose normalized exponent lies below the lower bound (above the
+    /// upper bound) rounds the same as any other such value of the same
+    /// sign. A bound is `None` if the context does not have one.
+    fn rational_e_bounds(&self) -> (Option<isize>, Option<isize>) {
+        (None, None)
+    }
+}
+
+/// Computes `floor(log2(|q|))` for a non-zero rational `q`.
+pub(crate) fn rational_e(q: &Rational) -> isize {
+    assert!(!q.is_zero(), "must be non-zero {:?}", q);
+    let q = q.clone().abs();
+    let (num, den) = q.into_numer_denom();
+
+    // estimate from bitlengths, off by at most 1
+    let e = (num.significant_bits() as isize) - (den.significant_bits() as isize);
+    let lhs = num << (if e < 0 { -e } else { 0 } as usize);
+    let rhs = den << (if e > 0 { e } else { 0 } as usize);
+    if lhs < rhs {
+        e - 1
+    } else {
+        e
+    }
+}
+
+/// Converts a finite, non-zero rational `q` into an [`RFloat`] value
+/// whose significant digits above position `n` are exact and whose
+/// digit at position `n` is the logical OR of all digits of `q` at or
+/// below position `n`.
+pub(crate) fn rational_to_odd(q: &Rational, n: isize) -> RFloat {
+    let s = q.is_negative();
+    let q = q.clone().abs();
+
+    // scale so that digit `n` is the least significant integer digit
+    let scaled = if n < 0 {
+        q << (-n as usize)
+    } else {
+        q >> (n as usize)
+    };
+
+    // truncate and compute the sticky bit
+    let (frac, trunc) = scaled.fract_trunc(Integer::new());
+    let c = if frac.is_zero() { trunc } else { trunc | 1 };
+    RFloat::Real(s, n, c)
+}
+
+/// Bounds `floor(log2(c * 10^exp))` for a non-zero integer `c`.
+pub(crate) fn decimal_e_bounds(c: &Integer, exp: isize) -> (isize, isize) {
+    // `log2(c)` lies in `[bits - 1, bits)` while the error in computing
+    // `exp * log2(10)` is far smaller than the margin
+    let bits = c.significant_bits() as isize;
+    let t = exp as f64 * std::f64::consts::LOG2_10;
+    let margin = 2.0 + t.abs() * 1e-12;
+    let lo = (bits - 1).saturating_add((t - margin).floor() as isize);
+    let hi = bits.saturating_add((t + margin).ceil() as isize);
+    (lo, hi)
+}
+
+/// Returns a value that rounds the same under a [`RationalContext`] as
+/// any finite, non-zero value with sign `s` whose normalized exponent
+/// lies in `[lo, hi]` if that range lies entirely outside the exponent
+/// bounds of the context (see [`RationalContext::rational_e_bounds`]).
+pub(crate) fn saturate_e<Ctx: RationalContext>(
+    ctx: &Ctx,
+    s: bool,
+    lo: isize,
+    hi: isize,
+) -> Option<RFloat> {
+    match ctx.rational_e_bounds() {
+        (_, Some(emax)) if lo > emax => Some(RFloat::Real(s, emax + 1, Integer::from(1))),
+        (Some(emin), _) if hi < emin => Some(RFloat::Real(s, emin - 1, Integer::from(1))),
+        _ => None,
+    }
+}
+
+/// Returns a binary value that rounds the same under a [`RationalContext`]
+/// as the decimal value `(-1)^s * c * 10^exp`, or `None` if its exact
+/// value is too large to compute. Values far outside the exponent bounds
+/// of the context need not be computed exactly (see [`saturate_e`]).
+pub(crate) fn decimal_to_odd<Ctx: RationalContext>(
+    ctx: &Ctx,
+    s: bool,
+    c: &Integer,
+    exp: isize,
+) -> Option<RFloat> {
+    if c.is_zero() {
+        // preserve the sign of zero
+        return Some(RFloat::Real(s, 0, Integer::zero()));
+    }
+
+    let (lo, hi) = decimal_e_bounds(c, exp);
+    if let Some(num) = saturate_e(ctx, s, lo, hi) {
+        return Some(num);
+    }
+
+    // round-to-odd (at least) two digits below the first lost digit
+    let q = checked_scale10(c.clone(), exp)?;
+    let q = if s { -q } else { q };
+    let n = ctx.rational_n(rational_e(&q)) - ctx.rational_guard_bits() as isize;
+    Some(rational_to_odd(&q, n))
+}
+
+/// Returns a binary value that rounds the same under a [`RationalContext`]
+/// as a decimal [`Real`] value. Panics if the value is not a decimal
+/// number or if its exact value is too large to compute.
+pub(crate) fn number_to_odd<Ctx: RationalContext, T: Real>(ctx: &Ctx, val: &T) -> RFloat {
+    assert_eq!(T::radix(), 10, "must be a decimal number");
+    if !val.is_numerical() {
+        RFloat::Nan
+    } else if val.is_infinite() {
+        if val.sign().unwrap() {
+            RFloat::NegInfinity
+        } else {
+            RFloat::PosInfinity
+        }
+    } else {
+        let s = val.sign().unwrap_or(false);
+        let c = val.c().unwrap_or_default();
+        let exp = val.exp().unwrap_or(0);
+        decimal_to_odd(ctx, s, &c, exp).expect("exponent out of range")
+    }
+}
+
+/// Rounds an exact rational value `q` under a [`RationalContext`].
+pub(crate) fn round_rational<Ctx: RationalContext>(ctx: &Ctx, q: &Rational) -> Ctx::Format {
+    if q.is_zero() {
+        ctx.round(&RFloat::zero())
+    } else {
+        // round-to-odd (at least) two digits below the first lost digit
+        let n = ctx.rational_n(rational_e(q)) - ctx.rational_guard_bits() as isize;
+        ctx.round(&rational_to_odd(q, n))
+    }
+}
+
+/// Correct rounding of exact rational values.
+pub trait RoundRational: RoundingContext {
+    /// Rounds an exact rational value according to this [`RoundingContext`].
+    ///
+    /// The rational value is first truncated to an [`RFloat`] value
+    /// at least two binary digits below the first digit lost by this context,
+    /// with the logical OR of all remaining digits (the sticky bit) in
+    /// the least significant place. That value is then rounded
+    /// as usual, so the result and its flags are the same as if the
+    /// rational value could have been rounded directly.
+    fn round_rational(&self, q: &Rational) -> Self::Format;
+}
+
+macro_rules! round_rational_impl {
+    ($ctx:ty) => {
+        impl RoundRational for $ctx {
+            fn round_rational(&self, q: &Rational) -> Self::Format {
+                round_rational(self, q)
+            }
+        }
+    };
+}
+
+round_rational_impl!(RFloatContext);
+round_rational_impl!(FloatContext);
+round_rational_impl!(IEEE754Context);
+round_rational_impl!(FixedContext);
+round_rational_impl!(PositContext);
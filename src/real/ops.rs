@@ -4,9 +4,15 @@ use std::{
 };
 
 use crate::{
-    ops::{RoundedAbs, RoundedAdd, RoundedMul, RoundedNeg, RoundedSub},
+    ops::{
+        min_max, round_integral, MinMax, MinMaxResult, RoundedAbs, RoundedAdd, RoundedCeil,
+        RoundedConvertToInteger, RoundedFloor, RoundedMaxNum, RoundedMaximum,
+        RoundedMaximumMagnitude, RoundedMaximumNumber, RoundedMinNum, RoundedMinimum,
+        RoundedMinimumMagnitude, RoundedMinimumNumber, RoundedMul, RoundedNearbyInt, RoundedNeg,
+        RoundedRint, RoundedRoundAway, RoundedRoundEven, RoundedSub, RoundedTrunc,
+    },
     rfloat::RFloat,
-    Real, RoundingContext,
+    Real, RoundingContext, RoundingMode,
 };
 
 use super::RealContext;
@@ -187,3 +193,86 @@ impl Mul for RFloat {
         RealContext::new().mul(&self, &rhs)
     }
 }
+
+macro_rules! rounded_integral_impl {
+    ($tname:ident, $name:ident, $rm:expr) => {
+        impl $tname for RealContext {
+            fn $name<N: Real>(&self, src: &N) -> Self::Format {
+                let (integral, _) = round_integral(src, $rm);
+                self.round(&integral)
+            }
+        }
+    };
+}
+
+rounded_integral_impl!(RoundedFloor, floor, RoundingMode::ToNegative);
+rounded_integral_impl!(RoundedCeil, ceil, RoundingMode::ToPositive);
+rounded_integral_impl!(RoundedTrunc, trunc, RoundingMode::ToZero);
+rounded_integral_impl!(
+    RoundedRoundAway,
+    round_away,
+    RoundingMode::NearestTiesAwayZero
+);
+rounded_integral_impl!(
+    RoundedRoundEven,
+    round_even,
+    RoundingMode::NearestTiesToEven
+);
+
+impl RoundedNearbyInt for RealContext {
+    fn nearbyint<N: Real>(&self, src: &N) -> Self::Format {
+        let (integral, _) = round_integral(src, RoundingMode::NearestTiesToEven);
+        self.round(&integral)
+    }
+}
+
+impl RoundedRint for RealContext {
+    fn rint<N: Real>(&self, src: &N) -> Self::Format {
+        // no exception flags, so this is just `nearbyint`
+        self.nearbyint(src)
+    }
+}
+
+impl RoundedConvertToInteger for RealContext {
+    fn convert_to_integer<N: Real>(&self, src: &N, rm: RoundingMode, _exact: bool) -> Self::Format {
+        // no exception flags, so this is just rounding
+        let (integral, _) = round_integral(src, rm);
+        self.round(&integral)
+    }
+}
+
+macro_rules! min_max_impl {
+    ($tname:ident, $name:ident, $op:expr) => {
+        impl $tname for RealContext {
+            fn $name<N1, N2>(&self, src1: &N1, src2: &N2) -> Self::Format
+            where
+                N1: Real,
+                N2: Real,
+            {
+                // no exception flags, so this is just selection
+                match min_max(src1, src2, $op).0 {
+                    MinMaxResult::First => self.round(src1),
+                    MinMaxResult::Second => self.round(src2),
+                    MinMaxResult::Nan => self.round(&RFloat::Nan),
+                }
+            }
+        }
+    };
+}
+
+min_max_impl!(RoundedMinimum, minimum, MinMax::Minimum);
+min_max_impl!(RoundedMaximum, maximum, MinMax::Maximum);
+min_max_impl!(RoundedMinimumNumber, minimum_number, MinMax::MinimumNumber);
+min_max_impl!(RoundedMaximumNumber, maximum_number, MinMax::MaximumNumber);
+min_max_impl!(
+    RoundedMinimumMagnitude,
+    minimum_magnitude,
+    MinMax::MinimumMagnitude
+);
+min_max_impl!(
+    RoundedMaximumMagnitude,
+    maximum_magnitude,
+    MinMax::MaximumMagnitude
+);
+min_max_impl!(RoundedMinNum, min_num, MinMax::MinNum);
+min_max_impl!(RoundedMaxNum, max_num, MinMax::MaxNum);
@@ -10,6 +10,7 @@
 //!
 
 mod number;
+mod ops;
 mod round;
 
 pub use number::RFloat;
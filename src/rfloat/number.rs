@@ -6,7 +6,8 @@ use rug::{Float, Integer};
 
 use gmp_mpfr_sys::mpfr;
 
-use crate::Real;
+use crate::ieee754::{IEEE754Context, IEEE754};
+use crate::{Real, RoundingContext, RoundingMode};
 
 /// An arbitrary-precision, floating-point numbers with unbounded exponent.
 ///
@@ -232,7 +233,11 @@ impl RFloat {
     /// Constructs a [ RFloat`] value from a [`Real`].
     /// This is the default conversion function from
     /// any implementation of the [`Real`] trait.
+    /// Panics if `val` is not a binary number; decimal values
+    /// may be converted exactly through [`DFloat`][crate::decimal::DFloat]
+    /// or rounded by any [`RoundingContext`][crate::RoundingContext].
     pub fn from_number<N: Real>(val: &N) -> Self {
+        assert_eq!(N::radix(), 2, "must be a binary number");
         // case split by class
         if !val.is_numerical() {
             // Any non-numerical type is NaN
@@ -252,6 +257,20 @@ impl RFloat {
             Self::Real(val.sign().unwrap(), val.exp().unwrap(), val.c().unwrap())
         }
     }
+
+    /// Converts this [`RFloat`] to the nearest [`f64`] value
+    /// according to the rounding mode `rm`.
+    pub fn to_f64(&self, rm: RoundingMode) -> f64 {
+        let ctx = IEEE754Context::binary64().with_rounding_mode(rm);
+        ctx.round(self).to_f64(rm)
+    }
+
+    /// Converts this [`RFloat`] to the nearest [`f32`] value
+    /// according to the rounding mode `rm`.
+    pub fn to_f32(&self, rm: RoundingMode) -> f32 {
+        let ctx = IEEE754Context::binary32().with_rounding_mode(rm);
+        ctx.round(self).to_f32(rm)
+    }
 }
 
 impl PartialOrd for RFloat {
@@ -347,6 +366,18 @@ impl PartialEq for RFloat {
     }
 }
 
+impl From<f64> for RFloat {
+    fn from(val: f64) -> Self {
+        RFloat::from(IEEE754::from(val))
+    }
+}
+
+impl From<f32> for RFloat {
+    fn from(val: f32) -> Self {
+        RFloat::from(IEEE754::from(val))
+    }
+}
+
 impl From<RFloat> for Float {
     fn from(val: RFloat) -> Self {
         use rug::float::*;
@@ -0,0 +1,291 @@
+//! Rounded operations for floating-point numbers with
+//! unbounded significand and exponent.
+//!
+//! Results are computed with MPFR, rounding to odd, with enough
+//! additional bits to correctly round under the context. If the
+//! context only limits the least absolute digit (see
+//! [`RFloatContext::with_min_n`]), the required precision depends
+//! on the magnitude of the result, so it is first estimated
+//! at a lower precision.
+
+use crate::mpfr::*;
+use crate::ops::*;
+use crate::rfloat::{RFloat, RFloatContext};
+use crate::stochastic::guard_bits;
+use crate::{Real, RoundingContext, RoundingMode};
+
+impl RFloatContext {
+    /// Precision used to estimate the magnitude of a result when
+    /// this context has no maximum precision.
+    const ESTIMATE_PREC: usize = 64;
+
+    /// Evaluates `f(p)`, computing a result to `p` binary digits of
+    /// precision, rounding to odd, with enough precision so that
+    /// the result is correctly rounded under this context.
+    fn eval<F: FnMut(usize) -> MPFRResult>(&self, mut f: F) -> MPFRResult {
+        let guard = guard_bits(self.stochastic_rounding());
+        match (self.max_p(), self.min_n()) {
+            (Some(max_p), _) => f(max_p + guard),
+            (None, Some(min_n)) => {
+                // the result has digits from `e` down to `min_n + 1`
+                let result = f(Self::ESTIMATE_PREC);
+                match result.num().e() {
+                    Some(e) if e - min_n + guard as isize > Self::ESTIMATE_PREC as isize => {
+                        f((e - min_n) as usize + guard)
+                    }
+                    _ => result,
+                }
+            }
+            (None, None) => panic!("at least one rounding parameter must be specified"),
+        }
+    }
+}
+
+macro_rules! rounded_1ary_impl {
+    ($tname:ident, $name:ident, $mpfr:ident) => {
+        impl $tname for RFloatContext {
+            fn $name<N: Real>(&self, src: &N) -> Self::Format {
+                // compute with enough additional bits, rounding-to-odd
+                let r = RFloat::from_number(src);
+                let result = self.eval(|p| $mpfr(r.clone(), p));
+                self.round(result.num())
+            }
+        }
+    };
+}
+
+rounded_1ary_impl!(RoundedNeg, neg, mpfr_neg);
+rounded_1ary_impl!(RoundedAbs, abs, mpfr_abs);
+rounded_1ary_impl!(RoundedSqrt, sqrt, mpfr_sqrt);
+rounded_1ary_impl!(RoundedCbrt, cbrt, mpfr_cbrt);
+rounded_1ary_impl!(RoundedRecip, recip, mpfr_recip);
+rounded_1ary_impl!(RoundedRecipSqrt, recip_sqrt, mpfr_recip_sqrt);
+rounded_1ary_impl!(RoundedExp, exp, mpfr_exp);
+rounded_1ary_impl!(RoundedExp2, exp2, mpfr_exp2);
+rounded_1ary_impl!(RoundedExp10, exp10, mpfr_exp10);
+rounded_1ary_impl!(RoundedLog, log, mpfr_log);
+rounded_1ary_impl!(RoundedLog2, log2, mpfr_log2);
+rounded_1ary_impl!(RoundedLog10, log10, mpfr_log10);
+rounded_1ary_impl!(RoundedExpm1, expm1, mpfr_expm1);
+rounded_1ary_impl!(RoundedExp2m1, exp2m1, mpfr_exp2m1);
+rounded_1ary_impl!(RoundedExp10m1, exp10m1, mpfr_exp10m1);
+rounded_1ary_impl!(RoundedLog1p, log1p, mpfr_log1p);
+rounded_1ary_impl!(RoundedLog2p1, log2p1, mpfr_log2p1);
+rounded_1ary_impl!(RoundedLog10p1, log10p1, mpfr_log10p1);
+rounded_1ary_impl!(RoundedSin, sin, mpfr_sin);
+rounded_1ary_impl!(RoundedCos, cos, mpfr_cos);
+rounded_1ary_impl!(RoundedTan, tan, mpfr_tan);
+rounded_1ary_impl!(RoundedSinPi, sin_pi, mpfr_sin_pi);
+rounded_1ary_impl!(RoundedCosPi, cos_pi, mpfr_cos_pi);
+rounded_1ary_impl!(RoundedTanPi, tan_pi, mpfr_tan_pi);
+rounded_1ary_impl!(RoundedAsin, asin, mpfr_asin);
+rounded_1ary_impl!(RoundedAcos, acos, mpfr_acos);
+rounded_1ary_impl!(RoundedAtan, atan, mpfr_atan);
+rounded_1ary_impl!(RoundedAsinPi, asin_pi, mpfr_asin_pi);
+rounded_1ary_impl!(RoundedAcosPi, acos_pi, mpfr_acos_pi);
+rounded_1ary_impl!(RoundedAtanPi, atan_pi, mpfr_atan_pi);
+rounded_1ary_impl!(RoundedSinh, sinh, mpfr_sinh);
+rounded_1ary_impl!(RoundedCosh, cosh, mpfr_cosh);
+rounded_1ary_impl!(RoundedTanh, tanh, mpfr_tanh);
+rounded_1ary_impl!(RoundedAsinh, asinh, mpfr_asinh);
+rounded_1ary_impl!(RoundedAcosh, acosh, mpfr_acosh);
+rounded_1ary_impl!(RoundedAtanh, atanh, mpfr_atanh);
+rounded_1ary_impl!(RoundedErf, erf, mpfr_erf);
+rounded_1ary_impl!(RoundedErfc, erfc, mpfr_erfc);
+rounded_1ary_impl!(RoundedGamma, tgamma, mpfr_tgamma);
+rounded_1ary_impl!(RoundedLgamma, lgamma, mpfr_lgamma);
+
+macro_rules! rounded_1ary_int_impl {
+    ($tname:ident, $name:ident, $mpfr:ident) => {
+        impl $tname for RFloatContext {
+            fn $name<N: Real>(&self, src: &N, n: i64) -> Self::Format {
+                // compute with enough additional bits, rounding-to-odd
+                let r = RFloat::from_number(src);
+                let result = self.eval(|p| $mpfr(r.clone(), n, p));
+                self.round(result.num())
+            }
+        }
+    };
+}
+
+rounded_1ary_int_impl!(RoundedCompound, compound, mpfr_compound);
+rounded_1ary_int_impl!(RoundedRootn, rootn, mpfr_rootn);
+rounded_1ary_int_impl!(RoundedPown, pown, mpfr_pown);
+
+macro_rules! rounded_2ary_impl {
+    ($tname:ident, $name:ident, $mpfr:ident) => {
+        impl $tname for RFloatContext {
+            fn $name<N1, N2>(&self, src1: &N1, src2: &N2) -> Self::Format
+            where
+                N1: Real,
+                N2: Real,
+            {
+                // compute with enough additional bits, rounding-to-odd
+                let r1 = RFloat::from_number(src1);
+                let r2 = RFloat::from_number(src2);
+                let result = self.eval(|p| $mpfr(r1.clone(), r2.clone(), p));
+                self.round(result.num())
+            }
+        }
+    };
+}
+
+rounded_2ary_impl!(RoundedAdd, add, mpfr_add);
+rounded_2ary_impl!(RoundedSub, sub, mpfr_sub);
+rounded_2ary_impl!(RoundedMul, mul, mpfr_mul);
+rounded_2ary_impl!(RoundedDiv, div, mpfr_div);
+rounded_2ary_impl!(RoundedPow, pow, mpfr_pow);
+rounded_2ary_impl!(RoundedPowr, powr, mpfr_powr);
+rounded_2ary_impl!(RoundedHypot, hypot, mpfr_hypot);
+rounded_2ary_impl!(RoundedFmod, fmod, mpfr_fmod);
+rounded_2ary_impl!(RoundedRemainder, remainder, mpfr_remainder);
+rounded_2ary_impl!(RoundedAtan2, atan2, mpfr_atan2);
+rounded_2ary_impl!(RoundedAtan2Pi, atan2_pi, mpfr_atan2_pi);
+rounded_2ary_impl!(RoundedFdim, fdim, mpfr_fdim);
+
+macro_rules! rounded_3ary_impl {
+    ($tname:ident, $name:ident, $mpfr:ident) => {
+        impl $tname for RFloatContext {
+            fn $name<N1, N2, N3>(&self, src1: &N1, src2: &N2, src3: &N3) -> Self::Format
+            where
+                N1: Real,
+                N2: Real,
+                N3: Real,
+            {
+                // compute with enough additional bits, rounding-to-odd
+                let r1 = RFloat::from_number(src1);
+                let r2 = RFloat::from_number(src2);
+                let r3 = RFloat::from_number(src3);
+                let result = self.eval(|p| $mpfr(r1.clone(), r2.clone(), r3.clone(), p));
+                self.round(result.num())
+            }
+        }
+    };
+}
+
+rounded_3ary_impl!(RoundedFMA, fma, mpfr_fma);
+
+impl RoundedSinCos for RFloatContext {
+    fn sin_cos<N: Real>(&self, src: &N) -> (Self::Format, Self::Format) {
+        // the results may have different magnitudes
+        (self.sin(src), self.cos(src))
+    }
+}
+
+impl RoundedRemquo for RFloatContext {
+    fn remquo<N1, N2>(&self, src1: &N1, src2: &N2) -> (Self::Format, i64)
+    where
+        N1: Real,
+        N2: Real,
+    {
+        // compute with enough additional bits, rounding-to-odd
+        let r1 = RFloat::from_number(src1);
+        let r2 = RFloat::from_number(src2);
+        let mut q = 0;
+        let result = self.eval(|p| {
+            let (result, quo) = mpfr_remquo(r1.clone(), r2.clone(), p);
+            q = quo;
+            result
+        });
+        (self.round(result.num()), q)
+    }
+}
+
+impl RoundedFrexp for RFloatContext {
+    fn frexp<N: Real>(&self, src: &N) -> (Self::Format, isize) {
+        // splitting is exact
+        let (m, e) = frexp_exact(src);
+        (self.round(&m), e)
+    }
+}
+
+impl RoundedModf for RFloatContext {
+    fn modf<N: Real>(&self, src: &N) -> (Self::Format, Self::Format) {
+        // splitting is exact
+        let (frac, int) = modf_exact(src);
+        (self.round(&frac), self.round(&int))
+    }
+}
+
+macro_rules! rounded_integral_impl {
+    ($tname:ident, $name:ident, $rm:expr) => {
+        impl $tname for RFloatContext {
+            fn $name<N: Real>(&self, src: &N) -> Self::Format {
+                let (integral, _) = round_integral(src, $rm);
+                self.round(&integral)
+            }
+        }
+    };
+}
+
+rounded_integral_impl!(RoundedFloor, floor, RoundingMode::ToNegative);
+rounded_integral_impl!(RoundedCeil, ceil, RoundingMode::ToPositive);
+rounded_integral_impl!(RoundedTrunc, trunc, RoundingMode::ToZero);
+rounded_integral_impl!(
+    RoundedRoundAway,
+    round_away,
+    RoundingMode::NearestTiesAwayZero
+);
+rounded_integral_impl!(
+    RoundedRoundEven,
+    round_even,
+    RoundingMode::NearestTiesToEven
+);
+
+impl RoundedNearbyInt for RFloatContext {
+    fn nearbyint<N: Real>(&self, src: &N) -> Self::Format {
+        let (integral, _) = round_integral(src, self.rm());
+        self.round(&integral)
+    }
+}
+
+impl RoundedRint for RFloatContext {
+    fn rint<N: Real>(&self, src: &N) -> Self::Format {
+        // no exception flags, so this is just `nearbyint`
+        self.nearbyint(src)
+    }
+}
+
+impl RoundedConvertToInteger for RFloatContext {
+    fn convert_to_integer<N: Real>(&self, src: &N, rm: RoundingMode, _exact: bool) -> Self::Format {
+        // no exception flags, so this is just rounding
+        let (integral, _) = round_integral(src, rm);
+        self.round(&integral)
+    }
+}
+
+macro_rules! min_max_impl {
+    ($tname:ident, $name:ident, $op:expr) => {
+        impl $tname for RFloatContext {
+            fn $name<N1, N2>(&self, src1: &N1, src2: &N2) -> Self::Format
+            where
+                N1: Real,
+                N2: Real,
+            {
+                // no exception flags, so this is just selection
+                match min_max(src1, src2, $op).0 {
+                    MinMaxResult::First => self.round(src1),
+                    MinMaxResult::Second => self.round(src2),
+                    MinMaxResult::Nan => self.round(&RFloat::Nan),
+                }
+            }
+        }
+    };
+}
+
+min_max_impl!(RoundedMinimum, minimum, MinMax::Minimum);
+min_max_impl!(RoundedMaximum, maximum, MinMax::Maximum);
+min_max_impl!(RoundedMinimumNumber, minimum_number, MinMax::MinimumNumber);
+min_max_impl!(RoundedMaximumNumber, maximum_number, MinMax::MaximumNumber);
+min_max_impl!(
+    RoundedMinimumMagnitude,
+    minimum_magnitude,
+    MinMax::MinimumMagnitude
+);
+min_max_impl!(
+    RoundedMaximumMagnitude,
+    maximum_magnitude,
+    MinMax::MaximumMagnitude
+);
+min_max_impl!(RoundedMinNum, min_num, MinMax::MinNum);
+min_max_impl!(RoundedMaxNum, max_num, MinMax::MaxNum);
@@ -1,9 +1,11 @@
 use num_traits::Zero;
 use rug::Integer;
 
+use crate::rational::{number_to_odd, RationalContext};
 use crate::rfloat::RFloat;
 use crate::round::RoundingDirection;
-use crate::{Real, RoundingContext, RoundingMode, Split};
+use crate::stochastic::guard_bits;
+use crate::{Real, RoundingContext, RoundingMode, Split, StochasticRounding};
 
 /// Rounding contexts for floating-point numbers with
 /// unbounded significand and unbounded exponent.
@@ -19,7 +21,8 @@ use crate::{Real, RoundingContext, RoundingMode, Split};
 ///
 ///  - (optional) maximum precision (see [`Real::prec`]),
 ///  - (optional) minimum absolute digit,
-///  - and rounding mode [`RoundingMode`].
+///  - rounding mode [`RoundingMode`],
+///  - and (optional) stochastic rounding [`StochasticRounding`].
 ///
 /// The requested precision may be as small as 1 binary digit.
 /// There is no way to restrict the maximum value.
@@ -43,12 +46,14 @@ use crate::{Real, RoundingContext, RoundingMode, Split};
 /// At least one parameter must be given or rounding will panic.
 /// The rounding mode affects how "lost" binary digits are handled.
 /// The possible rounding modes that can be specified
-/// are defined by [`RoundingMode`].
+/// are defined by [`RoundingMode`]. If stochastic rounding is
+/// specified, it takes precedence over the rounding mode.
 #[derive(Clone, Debug)]
 pub struct RFloatContext {
     max_p: Option<usize>,
     min_n: Option<isize>,
     rm: RoundingMode,
+    sr: Option<StochasticRounding>,
 }
 
 impl RFloatContext {
@@ -61,6 +66,7 @@ impl RFloatContext {
             max_p: None,
             min_n: None,
             rm: RoundingMode::NearestTiesToEven,
+            sr: None,
         }
     }
 
@@ -83,6 +89,12 @@ impl RFloatContext {
         self
     }
 
+    /// Sets stochastic rounding, overriding the rounding mode.
+    pub fn with_stochastic_rounding(mut self, sr: StochasticRounding) -> Self {
+        self.sr = Some(sr);
+        self
+    }
+
     /// Clears the maximum allowable precision.
     pub fn without_max_p(mut self) -> Self {
         self.max_p = None;
@@ -95,6 +107,32 @@ impl RFloatContext {
         self
     }
 
+    /// Clears stochastic rounding.
+    pub fn without_stochastic_rounding(mut self) -> Self {
+        self.sr = None;
+        self
+    }
+
+    /// Returns the maximum allowable precision, if any.
+    pub fn max_p(&self) -> Option<usize> {
+        self.max_p
+    }
+
+    /// Returns the minimum least absolute digit, if any.
+    pub fn min_n(&self) -> Option<isize> {
+        self.min_n
+    }
+
+    /// Returns the rounding mode.
+    pub fn rm(&self) -> RoundingMode {
+        self.rm
+    }
+
+    /// Returns the stochastic rounding configuration, if any.
+    pub fn stochastic_rounding(&self) -> Option<&StochasticRounding> {
+        self.sr.as_ref()
+    }
+
     /// Rounding parameters necessary to complete rounding under
     /// this context for a given [`Real`]: the maximum precision `p` allowed
     /// and the minimum absolute digit `n`.
@@ -202,6 +240,33 @@ impl RFloatContext {
     /// by possibly incrementing the mantissa; the rounding decision
     /// is based on rounding mode and rounding bits.
     pub(crate) fn round_finalize(split: Split, rm: RoundingMode) -> RFloat {
+        Self::round_finalize_by(split, |s, c, split| {
+            let (halfway_bit, sticky_bit) = split.rs();
+            Self::round_increment(s, c, halfway_bit, sticky_bit, rm)
+        })
+    }
+
+    /// Rounding utility function: like [`RFloatContext::round_finalize`]
+    /// but uses stochastic rounding if it is specified.
+    pub(crate) fn round_finalize_with(
+        split: Split,
+        rm: RoundingMode,
+        sr: Option<&StochasticRounding>,
+    ) -> RFloat {
+        match sr {
+            Some(sr) => Self::round_finalize_by(split, |_, _, split| sr.round_increment(split)),
+            None => Self::round_finalize(split, rm),
+        }
+    }
+
+    /// Rounding utility function: finishes the rounding procedure
+    /// by possibly incrementing the mantissa; the rounding decision
+    /// is made by `increment` given the sign, truncated mantissa,
+    /// and the split number.
+    fn round_finalize_by<F>(split: Split, increment: F) -> RFloat
+    where
+        F: FnOnce(bool, &Integer, &Split) -> bool,
+    {
         // truncated result
         let s = split.num().sign().unwrap();
         let (mut exp, mut c) = match split.num().exp() {
@@ -209,11 +274,8 @@ impl RFloatContext {
             None => (split.split_pos() + 1, Integer::zero()),
         };
 
-        // rounding bits
-        let (halfway_bit, sticky_bit) = split.rs();
-
         // correct if needed
-        if Self::round_increment(s, &c, halfway_bit, sticky_bit, rm) {
+        if increment(s, &c, &split) {
             c += 1;
             match split.max_p() {
                 None => (),
@@ -248,6 +310,11 @@ impl RoundingContext for RFloatContext {
             "must specify either maximum precision or least absolute digit"
         );
 
+        if T::radix() == 10 {
+            // decimal values are rounded through their exact value
+            return self.round(&number_to_odd(self, num));
+        }
+
         // case split by class
         if num.is_zero() {
             // zero
@@ -272,10 +339,27 @@ impl RoundingContext for RFloatContext {
             let split = Split::new(num, p, n);
 
             // step 3: finalize the rounding
-            let rounded = Self::round_finalize(split, self.rm);
+            let rounded = Self::round_finalize_with(split, self.rm, self.sr.as_ref());
 
             // return the rounded number
             rounded.canonicalize()
         }
     }
 }
+
+impl RationalContext for RFloatContext {
+    fn rational_n(&self, e: isize) -> isize {
+        let (_, n) = self.round_params(&RFloat::Real(false, e, Integer::from(1)));
+        n
+    }
+
+    fn rational_guard_bits(&self) -> usize {
+        guard_bits(self.stochastic_rounding())
+    }
+
+    fn rational_e_bounds(&self) -> (Option<isize>, Option<isize>) {
+        // only the smallest digit bounds rounding
+        let guard = self.rational_guard_bits() as isize;
+        (self.min_n.map(|n| n - 1 - guard), None)
+    }
+}
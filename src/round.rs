@@ -59,13 +59,30 @@ macro_rules! context_alias {
 ///   - `roundTowardZero` rounds to the closest representable value
 ///     in the direction of zero ([`ToZero`][RoundingMode]).
 ///
-/// Three additional rounding modes are provided including:
+/// IEEE 754-2019 additionally specifies `roundTiesToZero` for its
+/// augmented operations: it rounds to the nearest representable value.
+/// In this case there is a tie, rounds to the closest representable value
+/// with smaller magnitude ([`NearestTiesToZero`][RoundingMode]).
+///
+/// Additional rounding modes are provided including:
 /// - [`AwayZero`][RoundingMode] rounds to the closest representable value
 ///    away from zero, towards the nearest infinity.
 /// - [`ToEven`][RoundingMode] rounds to the closest representable value
 ///    whose mantissa has a least significant bit of 0.
 /// - [`ToOdd`][RoundingMode] rounds to the closest representable value
 ///    whose mantissa has a least significant bit of 1.
+/// - [`NearestTiesToOdd`][RoundingMode] rounds to the nearest
+///    representable value, breaking ties like [`ToOdd`][RoundingMode].
+/// - [`NearestTiesToPositive`][RoundingMode] and
+///    [`NearestTiesToNegative`][RoundingMode] round to the nearest
+///    representable value, breaking ties in the direction of
+///    positive or negative infinity, respectively ("round half up"
+///    and "round half down").
+/// - [`PrepareShorterPrecision`][RoundingMode] truncates and sets the
+///    least significant bit of the mantissa if the result is inexact,
+///    so that the result may be correctly rounded again to any
+///    shorter precision. Decimal formats instead increment the last
+///    digit of an inexact result if it is 0 or 5.
 ///
 /// The rounding behavior of zero, infinite values, and non-numerical values
 /// will be unaffected by rounding mode.
@@ -98,6 +115,26 @@ pub enum RoundingMode {
     /// Rounds to the closest representable value whose mantissa has
     /// a least significant bit of 1.
     ToOdd,
+    /// Rounds to the nearest representable value.
+    /// In this case there is a tie, rounds to the closest representable value
+    /// with smaller (or same) magnitude.
+    NearestTiesToZero,
+    /// Rounds to the nearest representable value.
+    /// In this case there is a tie, rounds to the closest representable value
+    /// whose mantissa has a least significant bit of 1.
+    NearestTiesToOdd,
+    /// Rounds to the nearest representable value.
+    /// In this case there is a tie, rounds to the closest representable value
+    /// in the direction of positive infinity.
+    NearestTiesToPositive,
+    /// Rounds to the nearest representable value.
+    /// In this case there is a tie, rounds to the closest representable value
+    /// in the direction of negative infinity.
+    NearestTiesToNegative,
+    /// Round to prepare for shorter precision: truncates and, if the
+    /// result is inexact, sets the least significant bit of the mantissa.
+    /// The result always has the same value as [`RoundingMode::ToOdd`].
+    PrepareShorterPrecision,
 }
 
 impl RoundingMode {
@@ -115,6 +152,13 @@ impl RoundingMode {
             (RoundingMode::AwayZero, _) => (false, RoundingDirection::AwayZero),
             (RoundingMode::ToEven, _) => (false, RoundingDirection::ToEven),
             (RoundingMode::ToOdd, _) => (false, RoundingDirection::ToOdd),
+            (RoundingMode::NearestTiesToZero, _) => (true, RoundingDirection::ToZero),
+            (RoundingMode::NearestTiesToOdd, _) => (true, RoundingDirection::ToOdd),
+            (RoundingMode::NearestTiesToPositive, false) => (true, RoundingDirection::AwayZero),
+            (RoundingMode::NearestTiesToPositive, true) => (true, RoundingDirection::ToZero),
+            (RoundingMode::NearestTiesToNegative, false) => (true, RoundingDirection::ToZero),
+            (RoundingMode::NearestTiesToNegative, true) => (true, RoundingDirection::AwayZero),
+            (RoundingMode::PrepareShorterPrecision, _) => (false, RoundingDirection::ToOdd),
         }
     }
 }
@@ -125,6 +169,9 @@ impl RoundingMode {
 /// into a [`RoundingDirection`] and a boolean indicating if the direction
 /// should only be used for tie-breaking (see [`RoundingMode::to_direction`]).
 /// It is usually easier to implement rounding using the latter pair of values.
+/// Sign-dependent modes, e.g., [`RoundingMode::NearestTiesToPositive`],
+/// translate to either [`ToZero`][RoundingDirection] or
+/// [`AwayZero`][RoundingDirection] depending on the sign.
 #[derive(Clone, Debug)]
 pub enum RoundingDirection {
     /// Rounds to the closest representable value with smaller
@@ -23,6 +23,7 @@ impl Split {
     /// that is, it "splits" a number.
     pub fn new<T: Real>(num: &T, max_p: Option<usize>, n: isize) -> Self {
         assert!(!num.is_nar(), "must be real {:?}", num);
+        assert_eq!(T::radix(), 2, "must be a binary number");
         let (high, low) = num.split(n);
         Self {
             high,
@@ -0,0 +1,191 @@
+use std::fmt::Debug;
+use std::sync::{Arc, Mutex};
+
+use rug::Integer;
+
+use crate::{Real, Split};
+
+/// Sources of random bits for stochastic rounding.
+///
+/// Implementations must be deterministic given their initial state
+/// so that stochastic rounding is reproducible. See [`SplitMix64`]
+/// for a simple, seedable implementation.
+pub trait RandomSource: Debug {
+    /// Returns 64 uniformly random bits.
+    fn next_u64(&mut self) -> u64;
+
+    /// Returns a uniformly random integer in `[0, 2^k)`.
+    fn random_bits(&mut self, k: usize) -> Integer {
+        let mut r = Integer::from(0);
+        let mut remaining = k;
+        while remaining > 0 {
+            let n = std::cmp::min(remaining, 64);
+            let bits = self.next_u64() >> (64 - n);
+            r = (r << n) | bits;
+            remaining -= n;
+        }
+        r
+    }
+}
+
+/// The SplitMix64 pseudo-random number generator.
+///
+/// A small, fast generator with 64 bits of state,
+/// suitable for reproducible stochastic rounding.
+/// It is not cryptographically secure.
+#[derive(Clone, Debug)]
+pub struct SplitMix64 {
+    state: u64,
+}
+
+impl SplitMix64 {
+    /// Constructs a new generator from a seed.
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+}
+
+impl RandomSource for SplitMix64 {
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E3779B97F4A7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+        z ^ (z >> 31)
+    }
+}
+
+/// Variants of stochastic rounding.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StochasticMode {
+    /// Rounds away from zero with probability exactly equal to
+    /// the fraction of the unit in the last place lost.
+    Exact,
+    /// Rounds away from zero by adding `k` random bits to the
+    /// first `k` lost bits and keeping the carry, as in hardware
+    /// implementations. The probability of rounding away from zero
+    /// is the lost fraction truncated to `k` bits.
+    RandomBits(usize),
+}
+
+/// Stochastic rounding.
+///
+/// Rather than rounding deterministically, the magnitude of an inexact
+/// value is rounded to one of its two neighbors at random: it is rounded
+/// away from zero with probability equal to its (possibly truncated)
+/// distance from the neighbor closer to zero in units in the last place.
+/// The result is unbiased in expectation. See [`StochasticMode`] for
+/// supported variants.
+///
+/// Random bits are drawn from an explicit [`RandomSource`].
+/// Clones of a [`StochasticRounding`] share the same source, so
+/// a rounding context and every context cloned from it draw from
+/// one sequence of random bits. Exact values never consume
+/// random bits.
+///
+/// Rounded operations (see [`crate::ops`]) compute intermediate results
+/// with enough additional precision that the `k`-random-bits variant
+/// behaves exactly as if the result were rounded from its infinitely
+/// precise value. For the exact variant, intermediate results have
+/// 64 additional bits so the probability is exact up to `2^-64`.
+#[derive(Clone)]
+pub struct StochasticRounding {
+    mode: StochasticMode,
+    source: Arc<Mutex<dyn RandomSource + Send>>,
+}
+
+impl StochasticRounding {
+    /// Additional bits of intermediate precision for
+    /// rounded operations when using [`StochasticMode::Exact`].
+    const EXACT_GUARD_BITS: usize = 64;
+
+    /// Constructs a stochastic rounding configuration
+    /// from a mode and a random source.
+    pub fn new<R>(mode: StochasticMode, source: R) -> Self
+    where
+        R: RandomSource + Send + 'static,
+    {
+        if let StochasticMode::RandomBits(k) = mode {
+            assert!(k >= 1, "must use at least one random bit");
+        }
+
+        Self {
+            mode,
+            source: Arc::new(Mutex::new(source)),
+        }
+    }
+
+    /// Constructs a stochastic rounding configuration
+    /// using [`StochasticMode::Exact`].
+    pub fn exact<R>(source: R) -> Self
+    where
+        R: RandomSource + Send + 'static,
+    {
+        Self::new(StochasticMode::Exact, source)
+    }
+
+    /// Constructs a stochastic rounding configuration
+    /// using [`StochasticMode::RandomBits`] with `k` random bits.
+    pub fn random_bits<R>(k: usize, source: R) -> Self
+    where
+        R: RandomSource + Send + 'static,
+    {
+        Self::new(StochasticMode::RandomBits(k), source)
+    }
+
+    /// Returns the stochastic rounding variant.
+    pub fn mode(&self) -> StochasticMode {
+        self.mode
+    }
+
+    /// Number of additional bits of precision needed for
+    /// intermediate results of rounded operations.
+    pub(crate) fn guard_bits(&self) -> usize {
+        match self.mode {
+            StochasticMode::Exact => Self::EXACT_GUARD_BITS,
+            StochasticMode::RandomBits(k) => k + 1,
+        }
+    }
+
+    /// Rounding utility function: given a split number, should
+    /// the truncated result be incremented to produce
+    /// the final rounded result?
+    pub(crate) fn round_increment(&self, split: &Split) -> bool {
+        let lost = split.lost();
+        if lost.is_zero() {
+            // exact => truncate
+            return false;
+        }
+
+        // lost fraction of an ulp is `c / 2^m`
+        let c = lost.c().unwrap();
+        let m = ((split.split_pos() + 1) - lost.exp().unwrap()) as usize;
+
+        let mut source = self.source.lock().unwrap();
+        match self.mode {
+            StochasticMode::Exact => source.random_bits(m) < c,
+            StochasticMode::RandomBits(k) => {
+                let frac = if m >= k { c >> (m - k) } else { c << (k - m) };
+                source.random_bits(k) < frac
+            }
+        }
+    }
+}
+
+impl Debug for StochasticRounding {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StochasticRounding")
+            .field("mode", &self.mode)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Number of additional bits of precision needed for intermediate
+/// results of rounded operations, rounding-to-odd, under a context
+/// with optional stochastic rounding.
+pub(crate) fn guard_bits(sr: Option<&StochasticRounding>) -> usize {
+    match sr {
+        None => 2,
+        Some(sr) => sr.guard_bits(),
+    }
+}
@@ -0,0 +1,19 @@
+//! "Takum" numbers, a tapered-precision format similar to posits.
+//!
+//! This module implements (linear) takums with [`TakumContext`].
+//! The associated storage type is [`Takum`] which represents
+//! a takum number. Like posits, takums have a single zero, a single
+//! non-real value `NAR`, and are encoded so that negation is
+//! two's complement negation of the bit pattern. Unlike posits,
+//! the exponent range is fixed regardless of bitwidth,
+//! i.e., `[-255, 254]`.
+//!
+//! Takums are described in "Beating Posits at Their Own Game:
+//! Takum Arithmetic" by L. Hunhold (CoNGA 2024).
+
+mod number;
+pub mod ops;
+mod round;
+
+pub use number::Takum;
+pub use round::TakumContext;
@@ -0,0 +1,161 @@
+use std::cmp::Ordering;
+
+use rug::Integer;
+
+use crate::{rfloat::RFloat, util::bitmask, Real, RoundingMode};
+
+use super::TakumContext;
+
+/// Takum number format.
+///
+/// The associated [`RoundingContext`][crate::RoundingContext]
+/// implementation is [`TakumContext`].
+/// See [`TakumContext`] for more details on numerical properties
+/// of the [`Takum`] type.
+#[derive(Clone, Debug)]
+pub struct Takum {
+    pub(crate) num: RFloat,
+    pub(crate) ctx: TakumContext,
+}
+
+impl Takum {
+    /// Returns the rounding context under which this number was created.
+    pub fn ctx(&self) -> &TakumContext {
+        &self.ctx
+    }
+
+    /// Converts this [`Takum`] to the nearest [`f64`] value
+    /// according to the rounding mode `rm`.
+    pub fn to_f64(&self, rm: RoundingMode) -> f64 {
+        self.num.to_f64(rm)
+    }
+
+    /// Converts this [`Takum`] to the nearest [`f32`] value
+    /// according to the rounding mode `rm`.
+    pub fn to_f32(&self, rm: RoundingMode) -> f32 {
+        self.num.to_f32(rm)
+    }
+
+    /// Converts this [`Takum`] to an [`Integer`] representing a takum bitpattern.
+    pub fn into_bits(self) -> Integer {
+        let nbits = self.ctx.nbits();
+        match self.num {
+            _ if self.num.is_zero() => Integer::from(0),
+            RFloat::Real(s, _, _) => {
+                // encode the magnitude: `0 D R C M`
+                let e = self.num.e().unwrap();
+                let c = self.num.c().unwrap();
+                let (r, dfield, rfield, cfield) = TakumContext::characteristic_fields(e);
+                let mbits = self.ctx.mbits(r);
+
+                // significand bits below the leading one
+                let p = c.significant_bits() as usize;
+                assert!(p - 1 <= mbits, "significand does not fit in the format");
+                let mfield = (c & bitmask(p - 1)) << (mbits - (p - 1));
+
+                let hfield = (Integer::from(dfield) << 3 | rfield) << r | cfield;
+                let b = hfield << mbits | mfield;
+
+                // negative values are encoded by two's complement
+                if s {
+                    (Integer::from(1) << nbits) - b
+                } else {
+                    b
+                }
+            }
+            _ => Integer::from(1) << (nbits - 1),
+        }
+    }
+}
+
+impl Real for Takum {
+    fn radix() -> usize {
+        2
+    }
+
+    fn sign(&self) -> Option<bool> {
+        self.num.sign()
+    }
+
+    fn exp(&self) -> Option<isize> {
+        self.num.exp()
+    }
+
+    fn e(&self) -> Option<isize> {
+        self.num.e()
+    }
+
+    fn n(&self) -> Option<isize> {
+        self.num.n()
+    }
+
+    fn c(&self) -> Option<Integer> {
+        self.num.c()
+    }
+
+    fn m(&self) -> Option<Integer> {
+        self.num.m()
+    }
+
+    fn prec(&self) -> Option<usize> {
+        self.num.prec()
+    }
+
+    fn is_nar(&self) -> bool {
+        self.num.is_nar()
+    }
+
+    fn is_finite(&self) -> bool {
+        !self.num.is_nar()
+    }
+
+    fn is_infinite(&self) -> bool {
+        false
+    }
+
+    fn is_zero(&self) -> bool {
+        self.num.is_zero()
+    }
+
+    fn is_negative(&self) -> Option<bool> {
+        self.num.is_negative()
+    }
+
+    fn is_numerical(&self) -> bool {
+        !self.num.is_nar()
+    }
+}
+
+impl From<Takum> for RFloat {
+    fn from(value: Takum) -> Self {
+        value.num
+    }
+}
+
+impl PartialEq for Takum {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Takum {}
+
+impl PartialOrd for Takum {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Takums are totally ordered: `NAR` is less than every other value
+/// and equal to itself, as if bit patterns were compared
+/// as two's complement integers.
+impl Ord for Takum {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self.is_nar(), other.is_nar()) {
+            (true, true) => Ordering::Equal,
+            (true, false) => Ordering::Less,
+            (false, true) => Ordering::Greater,
+            (false, false) => self.num.partial_cmp(&other.num).unwrap(),
+        }
+    }
+}
@@ -0,0 +1,243 @@
+use rug::Integer;
+
+use crate::rational::{number_to_odd, RationalContext};
+use crate::rfloat::{RFloat, RFloatContext};
+use crate::util::bitmask;
+use crate::{Real, RoundingContext, RoundingMode, Split};
+
+use super::Takum;
+
+/// Rounding contexts for takum numbers.
+///
+/// The associated storage type is [`Takum`].
+///
+/// Values rounded under this context are (linear) takum numbers:
+/// base 2 scientific numbers `(-1)^s * (1 + f) * 2^c` where `c` is
+/// the _characteristic_, an integer in `[-255, 254]`, and `f` is a
+/// fraction in `[0, 1)`. An `nbits` takum is encoded as
+///
+///  - a sign bit `S`,
+///  - a direction bit `D`,
+///  - a 3-bit regime `R`,
+///  - an `r`-bit characteristic field `C`,
+///  - an `(nbits - r - 5)`-bit fraction field `M`,
+///
+/// where `r` is `R` if `D` is set and `7 - R` otherwise.
+/// Like posits, the precision of `f` tapers: it is largest
+/// when `c` is near 0 and smallest when `|c|` is large.
+/// Negative values are encoded as the two's complement
+/// of their magnitude.
+///
+/// A [`TakumContext`] is parameterized only by the total bitwidth
+/// of the encoding. Since the characteristic field is at most 7 bits,
+/// the bitwidth must be at least 12 bits so that every characteristic
+/// is representable.
+///
+/// For values in between the largest and smallest magnitude,
+/// [`NearestTiesToEven`][RoundingMode::NearestTiesToEven].
+/// Otherwise, the values saturate to the largest or smallest magnitude;
+/// takums never round to zero or to `NAR`.
+#[derive(Clone, Debug)]
+pub struct TakumContext {
+    nbits: usize,
+}
+
+impl TakumContext {
+    /// Implementation limit: minimum bitwidth
+    pub const NBITS_MIN: usize = 12;
+
+    /// Constructs a new rounding context.
+    pub fn new(nbits: usize) -> Self {
+        assert!(
+            nbits >= Self::NBITS_MIN,
+            "total bitwidth needs to be at least {} bits, given {} bits",
+            Self::NBITS_MIN,
+            nbits
+        );
+
+        Self { nbits }
+    }
+
+    /// Returns the total bitwidth of the format produced by this context
+    /// (when viewed as a bitvector).
+    pub fn nbits(&self) -> usize {
+        self.nbits
+    }
+
+    /// Returns the maximum precision allowed by this format.
+    pub fn max_p(&self) -> usize {
+        self.nbits - 4
+    }
+
+    /// Largest representable (normalized) exponent
+    pub fn emax(&self) -> isize {
+        254
+    }
+
+    /// Smallest representable (normalized) exponent
+    pub fn emin(&self) -> isize {
+        -255
+    }
+
+    /// Returns the bitwidth of the fraction field for a given
+    /// characteristic bitwidth `r`.
+    pub(crate) fn mbits(&self, r: usize) -> usize {
+        self.nbits - r - 5
+    }
+
+    /// Returns the characteristic bitwidth `r` and the `D`, `R`, and `C`
+    /// fields encoding the characteristic `c`.
+    pub(crate) fn characteristic_fields(c: isize) -> (usize, bool, u32, Integer) {
+        if c >= 0 {
+            let r = (c + 1).ilog2() as usize;
+            let cfield = c - ((1 << r) - 1);
+            (r, true, r as u32, Integer::from(cfield))
+        } else {
+            let r = (-c).ilog2() as usize;
+            let cfield = c + ((1 << (r + 1)) - 1);
+            (r, false, 7 - r as u32, Integer::from(cfield))
+        }
+    }
+
+    /// Maximum representable value.
+    pub fn maxval(&self, sign: bool) -> Takum {
+        let mbits = self.mbits(7);
+        let c = bitmask(mbits + 1);
+        Takum {
+            num: RFloat::Real(sign, self.emax() - mbits as isize, c),
+            ctx: self.clone(),
+        }
+    }
+
+    /// Minimum (positive) representable value.
+    pub fn minval(&self, sign: bool) -> Takum {
+        match self.bits_to_number(Integer::from(1)).num {
+            RFloat::Real(_, exp, c) => Takum {
+                num: RFloat::Real(sign, exp, c),
+                ctx: self.clone(),
+            },
+            _ => unreachable!(),
+        }
+    }
+
+    /// Constructs zero in this format.
+    pub fn zero(&self) -> Takum {
+        Takum {
+            num: RFloat::zero(),
+            ctx: self.clone(),
+        }
+    }
+
+    /// Constructs `NAR` in this format.
+    pub fn nar(&self) -> Takum {
+        Takum {
+            num: RFloat::Nan,
+            ctx: self.clone(),
+        }
+    }
+
+    /// Converts an [`Integer`] representing a takum bitpattern into
+    /// a [`Takum`] value under this [`TakumContext`].
+    pub fn bits_to_number(&self, b: Integer) -> Takum {
+        let limit = Integer::from(1) << self.nbits;
+        assert!(b < limit, "must be less than 1 << nbits");
+
+        let top = Integer::from(1) << (self.nbits - 1);
+        if b == 0 {
+            return self.zero();
+        } else if b == top {
+            return self.nar();
+        }
+
+        // decompose into sign and magnitude
+        let s = b > top;
+        let b = if s { limit - b } else { b };
+
+        // direction and regime
+        let d = b.get_bit((self.nbits - 2) as u32);
+        let rfield = (b.clone() >> (self.nbits - 5)).to_u32().unwrap() & 0x7;
+        let r = if d { rfield } else { 7 - rfield } as usize;
+
+        // characteristic and fraction fields
+        let mbits = self.mbits(r);
+        let cfield = ((b.clone() >> mbits) & bitmask(r)).to_isize().unwrap();
+        let mfield = b & bitmask(mbits);
+
+        // compute the characteristic
+        let c = if d {
+            (1 << r) - 1 + cfield
+        } else {
+            -(1 << (r + 1)) + 1 + cfield
+        };
+
+        Takum {
+            num: RFloat::Real(s, c - mbits as isize, mfield | (Integer::from(1) << mbits)),
+            ctx: self.clone(),
+        }
+    }
+}
+
+impl RoundingContext for TakumContext {
+    type Format = Takum;
+
+    fn round<T: Real>(&self, val: &T) -> Self::Format {
+        if T::radix() == 10 {
+            // decimal values are rounded through their exact value
+            return self.round(&number_to_odd(self, val));
+        }
+
+        if val.is_nar() {
+            // all NaN and infinities are mapped to Nar
+            self.nar()
+        } else if val.is_zero() {
+            // exact zeros are mapped to zero
+            self.zero()
+        } else {
+            // overflow and underflow immediately saturate,
+            // we can simply check the (normalized) exponent.
+            let s = val.sign().unwrap();
+            let e = val.e().unwrap();
+            if e > self.emax() {
+                // |val| >= 2^255
+                return self.maxval(s);
+            } else if e < self.emin() {
+                // |val| < 2^-255
+                return self.minval(s);
+            }
+
+            // the precision is determined by the characteristic
+            let (r, _, _, _) = Self::characteristic_fields(e);
+            let p = self.mbits(r) + 1;
+
+            // split the significand at binary digit `n`
+            // and round to nearest, ties to even
+            let split = Split::new(val, Some(p), e - p as isize);
+            let rounded = RFloatContext::round_finalize(split, RoundingMode::NearestTiesToEven);
+
+            // saturate if the rounded value is out of range
+            let mag = RFloat::Real(false, rounded.exp().unwrap(), rounded.c().unwrap());
+            if rounded.e().unwrap() > self.emax() {
+                self.maxval(s)
+            } else if mag < RFloat::from(self.minval(false)) {
+                self.minval(s)
+            } else {
+                Takum {
+                    num: rounded.canonicalize(),
+                    ctx: self.clone(),
+                }
+            }
+        }
+    }
+}
+
+impl RationalContext for TakumContext {
+    fn rational_n(&self, e: isize) -> isize {
+        // takums never have more than `nbits` digits of precision
+        e - (self.nbits as isize)
+    }
+
+    fn rational_e_bounds(&self) -> (Option<isize>, Option<isize>) {
+        // values outside `[MINVAL, MAXVAL]` saturate
+        (Some(self.emin()), Some(self.emax()))
+    }
+}
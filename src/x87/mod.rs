@@ -0,0 +1,15 @@
+//! The x87 80-bit extended precision format.
+//!
+//! This module implements the extended precision format of the
+//! Intel x87 FPU with [`X87Context`]. The associated storage type
+//! is [`X87`]. Unlike the IEEE 754 interchange formats, the integer
+//! bit of the significand is stored explicitly, so some encodings
+//! are non-canonical or unsupported (see [`X87Class`]).
+//! The precision control setting of the FPU is emulated by
+//! [`PrecisionControl`].
+
+mod number;
+mod round;
+
+pub use number::{X87Class, X87};
+pub use round::{PrecisionControl, X87Context};
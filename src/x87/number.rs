@@ -0,0 +1,193 @@
+use std::cmp::Ordering;
+
+use rug::Integer;
+
+use crate::ieee754::{Exceptions, IEEE754};
+use crate::util::bitmask;
+use crate::{rfloat::RFloat, Real, RoundingMode};
+
+use super::X87Context;
+
+/// Classification of an x87 extended precision encoding.
+///
+/// Since the integer bit of the significand is explicit,
+/// some encodings have no IEEE 754 counterpart. Since the 80387,
+/// the FPU treats pseudo-denormals as denormal operands,
+/// while pseudo-infinities, pseudo-NaNs, and unnormals are
+/// unsupported and raise an invalid operation exception.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum X87Class {
+    /// Zero exponent, integer bit and fraction are zero.
+    Zero,
+    /// Zero exponent, integer bit is zero, non-zero fraction.
+    Denormal,
+    /// Zero exponent, integer bit is set.
+    PseudoDenormal,
+    /// Non-zero, non-maximal exponent, integer bit is set.
+    Normal,
+    /// Non-zero, non-maximal exponent, integer bit is zero.
+    Unnormal,
+    /// Maximal exponent, integer bit is set, zero fraction.
+    Infinity,
+    /// Maximal exponent, integer bit is zero, zero fraction.
+    PseudoInfinity,
+    /// Maximal exponent, integer bit is set, non-zero fraction.
+    Nan,
+    /// Maximal exponent, integer bit is zero, non-zero fraction.
+    PseudoNan,
+}
+
+/// The x87 extended precision format.
+///
+/// The associated [`RoundingContext`][crate::RoundingContext]
+/// implementation is [`X87Context`].
+/// See [`X87Context`] for more details on numerical properties
+/// of the [`X87`] type.
+///
+/// The value is stored as an [`IEEE754`] number with a 15-bit
+/// exponent and 64-bit significand, so an [`X87`] value also has
+/// an [`Exceptions`] instance to indicate exceptional events that
+/// occured during its construction.
+#[derive(Clone, Debug)]
+pub struct X87 {
+    pub(crate) num: IEEE754,
+    pub(crate) ctx: X87Context,
+}
+
+impl X87 {
+    /// Return the flags set when this number was created.
+    pub fn flags(&self) -> &Exceptions {
+        self.num.flags()
+    }
+
+    /// Returns the rounding context under which this number was created.
+    pub fn ctx(&self) -> &X87Context {
+        &self.ctx
+    }
+
+    /// Returns the value of this number as an [`IEEE754`] number
+    /// with a 15-bit exponent and 64-bit significand.
+    pub fn as_ieee754(&self) -> &IEEE754 {
+        &self.num
+    }
+
+    /// Returns true if this number is NaN.
+    pub fn is_nan(&self) -> bool {
+        self.num.is_nan()
+    }
+
+    /// Returns true if this number is denormal.
+    pub fn is_subnormal(&self) -> bool {
+        self.num.is_subnormal()
+    }
+
+    /// Converts this [`X87`] to the nearest [`f64`] value
+    /// according to the rounding mode `rm`.
+    pub fn to_f64(&self, rm: RoundingMode) -> f64 {
+        self.num.to_f64(rm)
+    }
+
+    /// Converts this [`X87`] to an [`Integer`] representing an
+    /// 80-bit extended precision bitpattern. The encoding is always
+    /// canonical, i.e., the integer bit is set if and only if
+    /// the exponent field is non-zero.
+    pub fn into_bits(self) -> Integer {
+        // IEEE 754 style encoding with an implicit integer bit
+        let b = self.num.into_bits();
+        let se = b.clone() >> (X87Context::P - 1);
+        let m = b & bitmask(X87Context::P - 1);
+
+        // insert the integer bit
+        let j = if se.clone() & bitmask(X87Context::ES) == 0 {
+            Integer::from(0)
+        } else {
+            Integer::from(1) << (X87Context::P - 1)
+        };
+
+        (se << X87Context::P) | j | m
+    }
+}
+
+impl Real for X87 {
+    fn radix() -> usize {
+        2
+    }
+
+    fn sign(&self) -> Option<bool> {
+        self.num.sign()
+    }
+
+    fn exp(&self) -> Option<isize> {
+        self.num.exp()
+    }
+
+    fn e(&self) -> Option<isize> {
+        self.num.e()
+    }
+
+    fn n(&self) -> Option<isize> {
+        self.num.n()
+    }
+
+    fn c(&self) -> Option<Integer> {
+        self.num.c()
+    }
+
+    fn m(&self) -> Option<Integer> {
+        self.num.m()
+    }
+
+    fn prec(&self) -> Option<usize> {
+        self.num.prec()
+    }
+
+    fn is_nar(&self) -> bool {
+        self.num.is_nar()
+    }
+
+    fn is_finite(&self) -> bool {
+        self.num.is_finite()
+    }
+
+    fn is_infinite(&self) -> bool {
+        self.num.is_infinite()
+    }
+
+    fn is_zero(&self) -> bool {
+        self.num.is_zero()
+    }
+
+    fn is_negative(&self) -> Option<bool> {
+        self.num.is_negative()
+    }
+
+    fn is_numerical(&self) -> bool {
+        self.num.is_numerical()
+    }
+
+    fn is_signaling(&self) -> bool {
+        self.num.is_signaling()
+    }
+
+    fn nan_payload_field(&self) -> Option<(Integer, usize)> {
+        self.num.nan_payload_field()
+    }
+}
+
+impl PartialEq for X87 {
+    fn eq(&self, other: &Self) -> bool {
+        self.num == other.num
+    }
+}
+
+impl PartialOrd for X87 {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.num.partial_cmp(&other.num)
+    }
+}
+
+impl From<X87> for RFloat {
+    fn from(value: X87) -> Self {
+        RFloat::from(value.num)
+    }
+}
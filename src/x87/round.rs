@@ -0,0 +1,229 @@
+use rug::Integer;
+
+use crate::ieee754::IEEE754Context;
+use crate::util::bitmask;
+use crate::{Real, RoundingContext, RoundingMode};
+
+use super::{X87Class, X87};
+
+/// Precision control setting of the x87 FPU.
+///
+/// The precision control field of the FPU control word specifies
+/// the precision of the significand of rounded results.
+/// The exponent always has the full 15-bit range.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PrecisionControl {
+    /// 24-bit significand
+    Single,
+    /// 53-bit significand
+    Double,
+    /// 64-bit significand
+    Extended,
+}
+
+impl PrecisionControl {
+    /// Returns the precision of the significand.
+    pub fn precision(&self) -> usize {
+        match self {
+            PrecisionControl::Single => 24,
+            PrecisionControl::Double => 53,
+            PrecisionControl::Extended => 64,
+        }
+    }
+}
+
+/// Rounding contexts for the x87 extended precision format.
+///
+/// The associated storage type is [`X87`].
+///
+/// Values rounded under this context are IEEE 754 style floating-point
+/// numbers with a 15-bit exponent and a 64-bit significand whose
+/// leading (integer) bit is stored explicitly. The encoding is
+/// 80 bits wide: a sign bit, a 15-bit biased exponent, the integer bit,
+/// and a 63-bit fraction.
+///
+/// A [`X87Context`] is parameterized by
+///
+///  - precision control (see [`PrecisionControl`]),
+///  - rounding mode.
+///
+/// By default, the precision control is
+/// [`Extended`][PrecisionControl::Extended] and the rounding mode is
+/// [`NearestTiesToEven`][RoundingMode::NearestTiesToEven],
+/// as set by the `FINIT` instruction. Under reduced precision control,
+/// values are rounded as if the format had a 15-bit exponent and
+/// a 24-bit or 53-bit significand; denormals have the same
+/// reduced precision.
+#[derive(Clone, Debug)]
+pub struct X87Context {
+    pc: PrecisionControl,
+    rm: RoundingMode,
+}
+
+impl X87Context {
+    /// Exponent field bitwidth.
+    pub const ES: usize = 15;
+    /// Total bitwidth of the encoding.
+    pub const NBITS: usize = 80;
+    /// Precision of the significand (including the integer bit).
+    pub const P: usize = 64;
+
+    /// Constructs a new rounding context for the x87 extended
+    /// precision format.
+    pub fn new() -> Self {
+        Self {
+            pc: PrecisionControl::Extended,
+            rm: RoundingMode::NearestTiesToEven,
+        }
+    }
+
+    /// Sets the precision control of this [`X87Context`].
+    pub fn with_precision_control(mut self, pc: PrecisionControl) -> Self {
+        self.pc = pc;
+        self
+    }
+
+    /// Sets the rounding mode of this [`X87Context`].
+    pub fn with_rounding_mode(mut self, rm: RoundingMode) -> Self {
+        self.rm = rm;
+        self
+    }
+
+    /// Returns the precision control of this [`X87Context`].
+    pub fn precision_control(&self) -> PrecisionControl {
+        self.pc
+    }
+
+    /// Returns the rounding mode of this [`X87Context`].
+    pub fn rm(&self) -> RoundingMode {
+        self.rm
+    }
+
+    /// Returns the maximum precision allowed by this context,
+    /// i.e., the precision specified by the precision control.
+    pub fn max_p(&self) -> usize {
+        self.pc.precision()
+    }
+
+    /// Returns the exponent bias of the encoding.
+    pub fn bias(&self) -> isize {
+        (1 << (Self::ES - 1)) - 1
+    }
+
+    /// Returns the largest (normalized) exponent.
+    pub fn emax(&self) -> isize {
+        self.bias()
+    }
+
+    /// Returns the smallest (normalized) exponent.
+    pub fn emin(&self) -> isize {
+        1 - self.bias()
+    }
+
+    /// The context for values of the encoding:
+    /// a 15-bit exponent and a 64-bit significand.
+    fn value_ctx(&self) -> IEEE754Context {
+        IEEE754Context::new(Self::ES, Self::NBITS - 1).with_rounding_mode(self.rm)
+    }
+
+    /// The context for rounding under precision control.
+    fn round_ctx(&self) -> IEEE754Context {
+        IEEE754Context::new(Self::ES, Self::ES + self.max_p()).with_rounding_mode(self.rm)
+    }
+
+    /// Classifies an 80-bit extended precision bitpattern.
+    pub fn classify_bits(&self, b: &Integer) -> X87Class {
+        let limit = Integer::from(1) << Self::NBITS;
+        assert!(*b < limit, "must be less than 1 << nbits");
+
+        let e = (b.clone() >> Self::P) & bitmask(Self::ES);
+        let j = b.get_bit((Self::P - 1) as u32);
+        let m = b.clone() & bitmask(Self::P - 1);
+
+        if e == 0 {
+            match (j, m == 0) {
+                (true, _) => X87Class::PseudoDenormal,
+                (false, true) => X87Class::Zero,
+                (false, false) => X87Class::Denormal,
+            }
+        } else if e == bitmask(Self::ES) {
+            match (j, m == 0) {
+                (true, true) => X87Class::Infinity,
+                (true, false) => X87Class::Nan,
+                (false, true) => X87Class::PseudoInfinity,
+                (false, false) => X87Class::PseudoNan,
+            }
+        } else if j {
+            X87Class::Normal
+        } else {
+            X87Class::Unnormal
+        }
+    }
+
+    /// Converts an [`Integer`] representing an 80-bit extended precision
+    /// bitpattern into an [`X87`] value.
+    ///
+    /// Pseudo-denormals are interpreted as the normal number with the
+    /// same significand and the smallest exponent, raising the
+    /// `denorm` flag. Unsupported encodings, i.e., pseudo-infinities,
+    /// pseudo-NaNs, and unnormals, decode to the default (indefinite)
+    /// quiet NaN, raising the `invalid` flag.
+    pub fn bits_to_number(&self, b: Integer) -> X87 {
+        let class = self.classify_bits(&b);
+        let ctx = self.value_ctx();
+
+        // IEEE 754 style encoding with an implicit integer bit
+        let se = b.clone() >> Self::P;
+        let m = b & bitmask(Self::P - 1);
+        let num = match class {
+            X87Class::PseudoDenormal => {
+                let mut num = ctx.bits_to_number(((se + 1) << (Self::P - 1)) | m);
+                num.flags.denorm = true;
+                num
+            }
+            X87Class::Unnormal | X87Class::PseudoInfinity | X87Class::PseudoNan => {
+                // negative, quiet NaN with zero payload
+                let indefinite =
+                    (bitmask(Self::ES + 1) << (Self::P - 1)) | (Integer::from(1) << (Self::P - 2));
+                let mut num = ctx.bits_to_number(indefinite);
+                num.flags.invalid = true;
+                num
+            }
+            _ => ctx.bits_to_number((se << (Self::P - 1)) | m),
+        };
+
+        X87 {
+            num,
+            ctx: self.clone(),
+        }
+    }
+}
+
+impl Default for X87Context {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RoundingContext for X87Context {
+    type Format = X87;
+
+    fn round<T: Real>(&self, num: &T) -> Self::Format {
+        let num = match self.pc {
+            PrecisionControl::Extended => self.value_ctx().round(num),
+            _ => {
+                // round under precision control and then
+                // (exactly) convert to the full precision
+                let rounded = self.round_ctx().round(num);
+                let mut num = self.value_ctx().round(&rounded);
+                num.flags = rounded.flags;
+                num
+            }
+        };
+
+        X87 {
+            num,
+            ctx: self.clone(),
+        }
+    }
+}
@@ -0,0 +1,149 @@
+use std::cmp::Ordering;
+
+use mpmfnum::fixed::FixedContext;
+use mpmfnum::float::FloatContext;
+use mpmfnum::ieee754::{IEEE754Context, IEEE754};
+use mpmfnum::posit::PositContext;
+use mpmfnum::rfloat::RFloat;
+use mpmfnum::{Predicate, RoundingContext};
+use rug::Integer;
+
+/// Expected results for each predicate given the relation
+/// (less, equal, greater, unordered).
+fn expected(pred: Predicate) -> [bool; 4] {
+    match pred {
+        Predicate::QuietEqual | Predicate::SignalingEqual => [false, true, false, false],
+        Predicate::QuietNotEqual | Predicate::SignalingNotEqual => [true, false, true, true],
+        Predicate::QuietGreater | Predicate::SignalingGreater => [false, false, true, false],
+        Predicate::QuietGreaterEqual | Predicate::SignalingGreaterEqual => {
+            [false, true, true, false]
+        }
+        Predicate::QuietLess | Predicate::SignalingLess => [true, false, false, false],
+        Predicate::QuietLessEqual | Predicate::SignalingLessEqual => [true, true, false, false],
+        Predicate::QuietNotGreater | Predicate::SignalingNotGreater => [true, true, false, true],
+        Predicate::QuietLessUnordered | Predicate::SignalingLessUnordered => {
+            [true, false, false, true]
+        }
+        Predicate::QuietNotLess | Predicate::SignalingNotLess => [false, true, true, true],
+        Predicate::QuietGreaterUnordered | Predicate::SignalingGreaterUnordered => {
+            [false, false, true, true]
+        }
+        Predicate::QuietUnordered => [false, false, false, true],
+        Predicate::QuietOrdered => [true, true, true, false],
+    }
+}
+
+#[test]
+fn predicates() {
+    let relations = [
+        Some(Ordering::Less),
+        Some(Ordering::Equal),
+        Some(Ordering::Greater),
+        None,
+    ];
+
+    let signaling = Predicate::ALL.iter().filter(|p| p.is_signaling()).count();
+    assert_eq!(signaling, 10);
+    for pred in Predicate::ALL {
+        for (ord, expect) in relations.iter().zip(expected(pred)) {
+            assert_eq!(pred.eval(*ord), expect, "{:?} on {:?}", pred, ord);
+        }
+    }
+}
+
+#[test]
+fn ieee754() {
+    let ctx = IEEE754Context::binary64();
+    let one = IEEE754::from(1.0);
+    let two = IEEE754::from(2.0);
+    let qnan = ctx.qnan();
+    let snan = ctx.snan();
+
+    for pred in Predicate::ALL {
+        let [lt, eq, gt, un] = expected(pred);
+
+        let (r, flags) = one.compare(&two, pred);
+        assert_eq!(r, lt, "{:?}", pred);
+        assert!(!flags.invalid);
+        let (r, flags) = two.compare(&two, pred);
+        assert_eq!(r, eq, "{:?}", pred);
+        assert!(!flags.invalid);
+        let (r, flags) = two.compare(&one, pred);
+        assert_eq!(r, gt, "{:?}", pred);
+        assert!(!flags.invalid);
+
+        // quiet NaNs only signal for signaling predicates
+        let (r, flags) = one.compare(&qnan, pred);
+        assert_eq!(r, un, "{:?}", pred);
+        assert_eq!(flags.invalid, pred.is_signaling(), "{:?}", pred);
+        let (r, flags) = qnan.compare(&qnan, pred);
+        assert_eq!(r, un, "{:?}", pred);
+        assert_eq!(flags.invalid, pred.is_signaling(), "{:?}", pred);
+
+        // signaling NaNs always signal
+        let (r, flags) = snan.compare(&one, pred);
+        assert_eq!(r, un, "{:?}", pred);
+        assert!(flags.invalid, "{:?}", pred);
+    }
+
+    // zeros are equal regardless of sign
+    let (r, _) = ctx
+        .zero(true)
+        .compare(&ctx.zero(false), Predicate::QuietEqual);
+    assert!(r);
+    let (r, _) = ctx
+        .zero(true)
+        .compare(&ctx.zero(false), Predicate::SignalingLess);
+    assert!(!r);
+
+    // infinities are ordered
+    let (r, flags) = ctx
+        .inf(true)
+        .compare(&ctx.inf(false), Predicate::SignalingLess);
+    assert!(r && !flags.invalid);
+    let (r, _) = ctx
+        .inf(false)
+        .compare(&ctx.max_float(false), Predicate::QuietGreater);
+    assert!(r);
+}
+
+#[test]
+fn other_formats() {
+    // unbounded floating-point
+    let ctx = FloatContext::new(8);
+    let x = ctx.round(&RFloat::from(-0.5));
+    let nan = ctx.round(&RFloat::Nan);
+    let (r, flags) = x.compare(&x, Predicate::SignalingLessEqual);
+    assert!(r && !flags.invalid);
+    let (r, flags) = x.compare(&nan, Predicate::QuietNotEqual);
+    assert!(r && !flags.invalid);
+    let (r, flags) = nan.compare(&x, Predicate::SignalingNotLess);
+    assert!(r && flags.invalid);
+
+    // large exponents are compared without computing `2^(2^32)`
+    let big = ctx.round(&RFloat::Real(false, 1 << 32, Integer::from(1)));
+    let two = ctx.round(&RFloat::from(2.0));
+    let (r, flags) = big.compare(&two, Predicate::QuietGreater);
+    assert!(r && !flags.invalid);
+    let (r, _) = big.compare(&big, Predicate::QuietEqual);
+    assert!(r);
+
+    // fixed-point numbers are always ordered
+    let ctx = FixedContext::new(true, -4, 8);
+    let x = ctx.round(&RFloat::from(-1.25));
+    let y = ctx.round(&RFloat::from(3.0));
+    for pred in Predicate::ALL {
+        let (r, flags) = x.compare(&y, pred);
+        assert_eq!(r, expected(pred)[0], "{:?}", pred);
+        assert!(!flags.invalid);
+    }
+
+    // posits order NaR below every other value
+    let ctx = PositContext::new(2, 16);
+    let x = ctx.round(&RFloat::from(-1e6));
+    let nar = ctx.nar();
+    assert!(nar.compare(&x, Predicate::SignalingLess));
+    assert!(nar.compare(&nar, Predicate::QuietEqual));
+    assert!(!nar.compare(&x, Predicate::QuietUnordered));
+    assert!(x.compare(&nar, Predicate::QuietOrdered));
+}
@@ -0,0 +1,186 @@
+use mpmfnum::decfixed::{DecimalFixed, DecimalFixedContext};
+use mpmfnum::decimal::{DFloat, DecimalContext};
+use mpmfnum::fixed::Overflow;
+use mpmfnum::ieee754::{IEEE754Context, IEEE754};
+use mpmfnum::ops::*;
+use mpmfnum::parse::RoundedParse;
+use mpmfnum::rational::RoundRational;
+use mpmfnum::{Real, RoundingContext, RoundingMode};
+use rug::Integer;
+
+/// Parses a decimal string under a context.
+fn parse(ctx: &DecimalFixedContext, s: &str) -> DecimalFixed {
+    ctx.parse(s).unwrap()
+}
+
+#[test]
+fn bounds() {
+    // DECIMAL(5, 2)
+    let ctx = DecimalFixedContext::new(true, -2, 5);
+    assert_eq!(ctx.maxval().to_string(), "999.99");
+    assert_eq!(ctx.minval().to_string(), "-999.99");
+    assert_eq!(ctx.quantum().to_string(), "0.01");
+    assert_eq!(ctx.zero().to_string(), "0.00");
+
+    // unsigned
+    let ctx = DecimalFixedContext::new(false, -2, 5);
+    assert_eq!(ctx.maxval().to_string(), "999.99");
+    assert!(ctx.minval().is_zero());
+}
+
+#[test]
+fn round() {
+    let ctx = DecimalFixedContext::new(true, -2, 9);
+    let cases = [
+        (RoundingMode::NearestTiesToEven, "2.68", "2.62", "-2.62"),
+        (RoundingMode::NearestTiesAwayZero, "2.68", "2.63", "-2.63"),
+        (RoundingMode::ToZero, "2.67", "2.62", "-2.62"),
+        (RoundingMode::AwayZero, "2.68", "2.63", "-2.63"),
+        (RoundingMode::ToPositive, "2.68", "2.63", "-2.62"),
+        (RoundingMode::ToNegative, "2.67", "2.62", "-2.63"),
+        (RoundingMode::ToEven, "2.68", "2.62", "-2.62"),
+        (RoundingMode::ToOdd, "2.67", "2.63", "-2.63"),
+    ];
+
+    for (rm, x, y, z) in cases {
+        let ctx = ctx.clone().with_rounding_mode(rm);
+        assert_eq!(parse(&ctx, "2.675").to_string(), x, "2.675 under {:?}", rm);
+        assert_eq!(parse(&ctx, "2.625").to_string(), y, "2.625 under {:?}", rm);
+        assert_eq!(
+            parse(&ctx, "-2.625").to_string(),
+            z,
+            "-2.625 under {:?}",
+            rm
+        );
+    }
+
+    let x = parse(&ctx, "2.675");
+    assert!(x.flags().inexact, "2.675 is inexact");
+    let x = parse(&ctx, "2.6");
+    assert!(!x.flags().inexact, "2.6 is exact");
+    assert_eq!(x.to_string(), "2.60");
+}
+
+#[test]
+fn overflow() {
+    // 3-digit, signed, wrapping
+    let ctx = DecimalFixedContext::new(true, 0, 3);
+    let x = ctx.add(&ctx.maxval(), &ctx.quantum());
+    assert!(x.is_zero() && x.flags().overflow, "should have wrapped");
+    let x = parse(&ctx, "-1234");
+    assert_eq!(x.to_string(), "-234", "should have wrapped");
+
+    // 3-digit, unsigned, wrapping
+    let ctx = DecimalFixedContext::new(false, 0, 3);
+    let x = ctx.sub(&ctx.zero(), &ctx.quantum());
+    assert_eq!(x, ctx.maxval(), "should have wrapped");
+    assert!(x.flags().overflow, "should have wrapped");
+
+    // 3-digit, signed, saturating
+    let ctx = DecimalFixedContext::new(true, 0, 3).with_overflow(Overflow::Saturate);
+    let x = ctx.add(&ctx.maxval(), &ctx.quantum());
+    assert_eq!(x, ctx.maxval(), "should have saturated");
+    assert!(x.flags().overflow, "should have saturated");
+    let x = parse(&ctx, "-1234");
+    assert_eq!(x, ctx.minval(), "should have saturated");
+
+    // 3-digit, unsigned, saturating
+    let ctx = DecimalFixedContext::new(false, 0, 3).with_overflow(Overflow::Saturate);
+    let x = parse(&ctx, "-1");
+    assert!(x.is_zero() && x.flags().overflow, "should have saturated");
+
+    // 3-digit, signed, trapping
+    let ctx = DecimalFixedContext::new(true, 0, 3).with_overflow(Overflow::Trap);
+    let x = ctx.add(&ctx.maxval(), &ctx.quantum());
+    assert_eq!(x, ctx.maxval(), "should have trapped");
+    assert!(
+        x.flags().overflow && x.flags().invalid,
+        "should have trapped"
+    );
+    let x = parse(&ctx, "-1234");
+    assert_eq!(x, ctx.minval(), "should have trapped");
+    assert!(
+        x.flags().overflow && x.flags().invalid,
+        "should have trapped"
+    );
+    let ctx = DecimalFixedContext::new(true, 0, 3).with_overflow(Overflow::Saturate);
+    let x = ctx.add(&ctx.maxval(), &ctx.quantum());
+    assert_eq!(x, ctx.maxval(), "should have saturated");
+    assert!(
+        x.flags().overflow && !x.flags().invalid,
+        "should have saturated"
+    );
+    let ctx = DecimalFixedContext::new(false, 0, 3).with_overflow(Overflow::Saturate);
+
+    // large exponents
+    let x = parse(&ctx, "1e4294967296");
+    assert_eq!(x, ctx.maxval(), "should have saturated");
+    assert!(x.flags().overflow, "should have saturated");
+    let ctx = DecimalFixedContext::new(true, 0, 3);
+    let x = parse(&ctx, "-1e99999999999999999999");
+    assert!(x.is_zero() && x.flags().overflow, "should have wrapped");
+    let x = parse(&ctx, "1e-4294967296");
+    assert!(x.is_zero() && x.flags().inexact, "should have truncated");
+    let x = ctx.round(&IEEE754Context::new(11, 64).min_float(false));
+    assert!(x.is_zero() && x.flags().inexact, "should have truncated");
+    let ctx = DecimalFixedContext::new(false, 0, 3).with_overflow(Overflow::Saturate);
+
+    // special values
+    assert_eq!(ctx.round(&DFloat::PosInfinity), ctx.maxval());
+    assert!(ctx.round(&DFloat::Nan).flags().invalid);
+}
+
+#[test]
+fn convert() {
+    let ctx =
+        DecimalFixedContext::new(true, -2, 9).with_rounding_mode(RoundingMode::NearestTiesAwayZero);
+
+    // binary to decimal: 0.125 is exact in binary
+    let x = ctx.round(&IEEE754::from(0.125));
+    assert_eq!(x.to_string(), "0.13", "ties away from zero");
+    // 2.675 is below 2.675 in binary
+    let x = ctx.round(&IEEE754::from(2.675));
+    assert_eq!(x.to_string(), "2.67", "binary 2.675");
+
+    // decimal to binary
+    let x = parse(&ctx, "0.10");
+    assert_eq!(x.to_f64(RoundingMode::NearestTiesToEven), 0.1);
+    let y = IEEE754Context::binary32().round_rational(&x.to_rational());
+    assert_eq!(y.to_f32(RoundingMode::NearestTiesToEven), 0.1);
+
+    // decimal floating-point
+    let y = DecimalContext::decimal64().round(&x);
+    assert_eq!(y.to_string(), "0.10", "preserves the quantum");
+    let z = ctx.round(&DecimalContext::decimal64().parse("1.005").unwrap());
+    assert_eq!(z.to_string(), "1.01", "from decimal");
+}
+
+#[test]
+fn ops() {
+    let ctx =
+        DecimalFixedContext::new(true, -2, 9).with_rounding_mode(RoundingMode::NearestTiesAwayZero);
+    let x = |s: &str| parse(&ctx, s);
+
+    assert_eq!(ctx.add(&x("19.99"), &x("0.01")).to_string(), "20.00");
+    assert_eq!(ctx.sub(&x("1.00"), &x("2.50")).to_string(), "-1.50");
+    assert_eq!(ctx.mul(&x("19.99"), &x("0.07")).to_string(), "1.40");
+    assert_eq!(ctx.div(&x("10.00"), &x("3.00")).to_string(), "3.33");
+    assert_eq!(ctx.div(&x("20.00"), &x("3.00")).to_string(), "6.67");
+    assert_eq!(ctx.sqrt(&x("2.00")).to_string(), "1.41");
+    assert_eq!(ctx.sqrt(&x("0.25")).to_string(), "0.50");
+    assert_eq!(
+        ctx.fma(&x("19.99"), &x("0.07"), &x("0.01")).to_string(),
+        "1.41"
+    );
+    assert_eq!(ctx.neg(&x("1.50")).to_string(), "-1.50");
+    assert_eq!(ctx.abs(&x("-1.50")).to_string(), "1.50");
+
+    let r = ctx.div(&x("1.00"), &x("0.00"));
+    assert_eq!(r, ctx.maxval(), "1 / 0");
+    let r = ctx.sqrt(&x("-1.00"));
+    assert!(r.is_zero() && r.flags().invalid, "sqrt(-1)");
+    assert_eq!(
+        DFloat::from(ctx.mul(&x("0.50"), &x("0.50"))),
+        DFloat::Real(false, -2, Integer::from(25))
+    );
+}
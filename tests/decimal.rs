@@ -0,0 +1,452 @@
+use mpmfnum::decimal::{DFloat, Decimal, DecimalContext, Encoding};
+use mpmfnum::fixed::FixedContext;
+use mpmfnum::ieee754::{IEEE754Context, IEEE754};
+use mpmfnum::lns::LnsContext;
+use mpmfnum::multidouble::MultiDoubleContext;
+use mpmfnum::ops::*;
+use mpmfnum::parse::RoundedParse;
+use mpmfnum::posit::PositContext;
+use mpmfnum::rfloat::{RFloat, RFloatContext};
+use mpmfnum::takum::TakumContext;
+use mpmfnum::{Real, RoundingContext, RoundingMode};
+use rug::Integer;
+
+/// Parses a decimal string under a context.
+fn parse(ctx: &DecimalContext, s: &str) -> Decimal {
+    ctx.parse(s).unwrap()
+}
+
+#[test]
+fn params() {
+    let ctxs = [
+        (DecimalContext::decimal32(), 7, 96, 101),
+        (DecimalContext::decimal64(), 16, 384, 398),
+        (DecimalContext::decimal128(), 34, 6144, 6176),
+    ];
+
+    for (ctx, p, emax, bias) in ctxs {
+        assert_eq!(ctx.max_p(), p, "precision of {}", ctx.nbits());
+        assert_eq!(ctx.emax(), emax, "emax of {}", ctx.nbits());
+        assert_eq!(ctx.emin(), 1 - emax, "emin of {}", ctx.nbits());
+        assert_eq!(ctx.bias(), bias, "bias of {}", ctx.nbits());
+    }
+}
+
+#[test]
+fn encode() {
+    let bid32 = DecimalContext::decimal32();
+    let dpd32 = DecimalContext::decimal32().with_encoding(Encoding::DPD);
+    let bid64 = DecimalContext::decimal64();
+    let dpd64 = DecimalContext::decimal64().with_encoding(Encoding::DPD);
+    let bid128 = DecimalContext::decimal128();
+    let dpd128 = DecimalContext::decimal128().with_encoding(Encoding::DPD);
+
+    let cases: [(&DecimalContext, &str, &str); 14] = [
+        (&bid32, "1", "32800001"),
+        (&dpd32, "1", "22500001"),
+        (&bid32, "-1", "B2800001"),
+        (&bid32, "9.999999E96", "77F8967F"),
+        (&dpd32, "9.999999E96", "77F3FCFF"),
+        (&bid32, "Infinity", "78000000"),
+        (&dpd32, "-Infinity", "F8000000"),
+        (&bid32, "NaN", "7C000000"),
+        (&bid32, "sNaN", "7E000000"),
+        (&bid64, "1", "31C0000000000001"),
+        (&dpd64, "1", "2238000000000001"),
+        (&bid128, "1", "30400000000000000000000000000001"),
+        (&dpd128, "1", "22080000000000000000000000000001"),
+        (&dpd128, "-0", "A2080000000000000000000000000000"),
+    ];
+
+    for (ctx, s, bits) in cases {
+        let x = parse(ctx, s);
+        let expected = Integer::from_str_radix(bits, 16).unwrap();
+        assert_eq!(x.clone().into_bits(), expected, "encoding {}", s);
+
+        let y = ctx.bits_to_number(expected);
+        assert_eq!(y.to_string(), x.to_string(), "decoding {}", s);
+    }
+}
+
+#[test]
+fn decode() {
+    for ctx in [
+        DecimalContext::decimal32(),
+        DecimalContext::decimal32().with_encoding(Encoding::DPD),
+        DecimalContext::decimal64().with_encoding(Encoding::DPD),
+    ] {
+        // every significand of the form `ddd` round trips
+        for d in 0..1000 {
+            for exp in [ctx.expmin(), -3, 0, ctx.expmax()] {
+                let s = format!("{}E{}", d * 1001, exp);
+                let x = parse(&ctx, &s);
+                let y = ctx.bits_to_number(x.clone().into_bits());
+                assert_eq!(y.to_string(), x.to_string(), "round trip {}", s);
+                assert!(y.same_quantum(&x), "round trip {}", s);
+            }
+        }
+    }
+
+    // non-canonical BID significand is zero
+    let ctx = DecimalContext::decimal32();
+    let x = ctx.bits_to_number(Integer::from(0x6CBFFFFF));
+    assert!(x.is_zero(), "non-canonical significand");
+
+    // non-canonical DPD declet
+    let ctx = DecimalContext::decimal32().with_encoding(Encoding::DPD);
+    let x = ctx.bits_to_number(Integer::from(0x225003FF));
+    assert_eq!(x.to_string(), "999", "non-canonical declet");
+}
+
+#[test]
+fn cohorts() {
+    let ctx = DecimalContext::decimal64();
+    let cases = [
+        ("1.50", "1.50", -2),
+        ("1.5", "1.5", -1),
+        ("150E-2", "1.50", -2),
+        ("1.5E+7", "1.5E+7", 6),
+        ("15000000", "15000000", 0),
+        ("-0.000", "-0.000", -3),
+        ("0E+5", "0E+5", 5),
+        ("0.000001", "0.000001", -6),
+        ("0.0000001", "1E-7", -7),
+        ("nan(123)", "NaN123", 0),
+        ("-snan", "-sNaN", 0),
+    ];
+
+    for (s, expected, q) in cases {
+        let x = parse(&ctx, s);
+        assert_eq!(x.to_string(), expected, "display of {}", s);
+        if x.is_finite() {
+            assert_eq!(x.quantum_exp(), Some(q), "quantum of {}", s);
+        }
+    }
+
+    let x = parse(&ctx, "1.50");
+    let y = parse(&ctx, "1.5");
+    assert_eq!(x, y, "same cohort");
+    assert!(!x.same_quantum(&y), "different quantum");
+    assert_eq!(ctx.quantum(&x).to_string(), "0.01", "quantum");
+
+    // payload is too large
+    assert!(DecimalContext::decimal32().parse("nan(1000000)").is_err());
+}
+
+#[test]
+fn round() {
+    let ctx = DecimalContext::decimal32();
+
+    // binary values are rounded exactly
+    let x = ctx.round(&IEEE754::from(0.1));
+    assert_eq!(x.to_string(), "0.1000000", "rounding 0.1");
+    assert!(x.flags().inexact, "rounding 0.1");
+    let x = ctx.round(&IEEE754::from(0.5));
+    assert_eq!(x.to_string(), "0.5", "rounding 0.5");
+    assert!(!x.flags().inexact, "rounding 0.5");
+    assert_eq!(x.to_f64(RoundingMode::NearestTiesToEven), 0.5, "to_f64");
+    assert_eq!(
+        parse(&ctx, "0.1").to_f64(RoundingMode::NearestTiesToEven),
+        0.1,
+        "to_f64"
+    );
+
+    // rounding modes
+    let cases = [
+        (RoundingMode::NearestTiesToEven, "1.234568", "-1.234568"),
+        (RoundingMode::ToZero, "1.234567", "-1.234567"),
+        (RoundingMode::ToPositive, "1.234568", "-1.234567"),
+        (RoundingMode::ToNegative, "1.234567", "-1.234568"),
+    ];
+    for (rm, pos, neg) in cases {
+        let ctx = ctx.clone().with_rounding_mode(rm);
+        assert_eq!(parse(&ctx, "1.2345675").to_string(), pos, "{:?}", rm);
+        assert_eq!(parse(&ctx, "-1.2345675").to_string(), neg, "{:?}", rm);
+    }
+    let x = parse(&ctx, "1.2345665");
+    assert_eq!(x.to_string(), "1.234566", "ties to even");
+    let x = parse(&ctx, "9.9999995");
+    assert_eq!(x.to_string(), "10.00000", "carry");
+
+    // preparing for shorter precision rounds up only if
+    // the last digit is 0 or 5 ("05up")
+    let psp = DecimalContext::decimal64().with_rounding_mode(RoundingMode::PrepareShorterPrecision);
+    let cases = [
+        ("1.2345678901234549", "1.234567890123454"),
+        ("1.2345678901234559", "1.234567890123456"),
+        ("1.2345678901234509", "1.234567890123451"),
+        ("1.2345678901234540", "1.234567890123454"),
+    ];
+    for (x, expect) in cases {
+        assert_eq!(parse(&psp, x).to_string(), expect, "05up({})", x);
+    }
+
+    // so rounding again at a shorter precision is correct
+    let ctx64 = DecimalContext::decimal64();
+    let x = parse(&psp, "1.2345678901234549");
+    let r = ctx64.quantize(&x, &parse(&ctx64, "1E-14"));
+    assert_eq!(r.to_string(), "1.23456789012345", "double rounding");
+
+    // overflow
+    let x = parse(&ctx, "1E97");
+    assert!(x.is_infinite(), "overflow");
+    assert!(x.flags().overflow && x.flags().inexact, "overflow");
+    let x = parse(
+        &ctx.clone().with_rounding_mode(RoundingMode::ToZero),
+        "1E97",
+    );
+    assert_eq!(x.to_string(), "9.999999E+96", "overflow to MAX_FLOAT");
+    let x = parse(&ctx, "1E96");
+    assert_eq!(x.to_string(), "1.000000E+96", "clamped exponent");
+    assert!(!x.flags().overflow, "clamped exponent");
+
+    // subnormals
+    let x = parse(&ctx, "1E-101");
+    assert_eq!(x.to_string(), "1E-101", "MIN_FLOAT");
+    assert!(!x.flags().underflow, "exact subnormal");
+    let x = parse(&ctx, "1.5E-101");
+    assert_eq!(x.to_string(), "2E-101", "rounded subnormal");
+    assert!(
+        x.flags().underflow && x.flags().inexact,
+        "rounded subnormal"
+    );
+    let x = parse(&ctx, "1E-200");
+    assert!(x.is_zero(), "underflow to zero");
+    assert_eq!(x.quantum_exp(), Some(ctx.expmin()), "underflow to zero");
+}
+
+#[test]
+fn round_large_exponent() {
+    let ctx = DecimalContext::decimal64();
+    for s in [
+        "1E4294967296",
+        "1E4294967297",
+        "1E99999999999999999999",
+        "0x1p4294967296",
+    ] {
+        let x = parse(&ctx, s);
+        assert!(x.is_infinite(), "{} overflows", s);
+        assert!(x.flags().overflow && x.flags().inexact, "{} overflows", s);
+    }
+    for s in [
+        "1E-4294967296",
+        "-1E-99999999999999999999",
+        "0x1p-4294967296",
+    ] {
+        let x = parse(&ctx, s);
+        assert!(x.is_zero(), "{} underflows", s);
+        assert!(x.flags().underflow && x.flags().inexact, "{} underflows", s);
+    }
+
+    let ctx = ctx.with_rounding_mode(RoundingMode::ToZero);
+    let x = parse(&ctx, "1E4294967296");
+    assert_eq!(
+        x.to_string(),
+        "9.999999999999999E+384",
+        "overflow to MAX_FLOAT"
+    );
+
+    // binary values are not converted exactly
+    let x = ctx.round(&RFloat::Real(true, 1 << 40, Integer::from(1)));
+    assert_eq!(x.to_string(), "-9.999999999999999E+384", "binary overflow");
+    let x = ctx.round(&RFloat::Real(false, -(1 << 40), Integer::from(3)));
+    assert!(x.is_zero() && x.flags().underflow, "binary underflow");
+}
+
+#[test]
+fn ops() {
+    let ctx = DecimalContext::decimal64();
+    let x = |s: &str| parse(&ctx, s);
+
+    assert_eq!(ctx.add(&x("1.50"), &x("1")).to_string(), "2.50", "add");
+    assert_eq!(ctx.sub(&x("1.50"), &x("1.5")).to_string(), "0.00", "sub");
+    assert_eq!(ctx.mul(&x("1.50"), &x("2.0")).to_string(), "3.000", "mul");
+    assert_eq!(ctx.div(&x("2.400"), &x("2")).to_string(), "1.200", "div");
+    assert_eq!(ctx.div(&x("1"), &x("4")).to_string(), "0.25", "div");
+    assert_eq!(ctx.div(&x("1E+3"), &x("1")).to_string(), "1E+3", "div");
+    assert_eq!(
+        ctx.div(&x("1"), &x("3")).to_string(),
+        "0.3333333333333333",
+        "div"
+    );
+    assert_eq!(ctx.sqrt(&x("1.00")).to_string(), "1.0", "sqrt");
+    assert_eq!(ctx.sqrt(&x("0.25")).to_string(), "0.5", "sqrt");
+    assert_eq!(ctx.sqrt(&x("2")).to_string(), "1.414213562373095", "sqrt");
+    assert_eq!(
+        ctx.fma(&x("1.5"), &x("2"), &x("0.25")).to_string(),
+        "3.25",
+        "fma"
+    );
+    assert_eq!(ctx.neg(&x("1.50")).to_string(), "-1.50", "neg");
+    assert_eq!(ctx.abs(&x("-1.50")).to_string(), "1.50", "abs");
+
+    // sign of exact zeros
+    let z = ctx.add(&x("1"), &x("-1"));
+    assert!(z.is_zero() && z.sign() == Some(false), "1 + -1 = +0");
+    let ctx_rtn = ctx.clone().with_rounding_mode(RoundingMode::ToNegative);
+    let z = ctx_rtn.add(&x("1"), &x("-1"));
+    assert!(z.is_zero() && z.sign() == Some(true), "1 + -1 = -0");
+
+    // exceptions
+    let r = ctx.div(&x("1"), &x("0"));
+    assert!(r.is_infinite() && r.flags().divzero, "1 / 0");
+    let r = ctx.div(&x("0"), &x("0"));
+    assert!(r.is_nan() && r.flags().invalid, "0 / 0");
+    let r = ctx.sqrt(&x("-1"));
+    assert!(r.is_nan() && r.flags().invalid, "sqrt(-1)");
+    let r = ctx.sub(&x("inf"), &x("inf"));
+    assert!(r.is_nan() && r.flags().invalid, "inf - inf");
+    let r = ctx.mul(&x("inf"), &x("0"));
+    assert!(r.is_nan() && r.flags().invalid, "inf * 0");
+
+    // quiet NaNs propagate silently
+    let r = ctx.add(&x("nan"), &x("1"));
+    assert!(r.is_nan() && !r.flags().invalid, "nan + 1");
+    let r = ctx.sub(&x("1"), &x("nan"));
+    assert!(r.is_nan() && !r.flags().invalid, "1 - nan");
+    let r = ctx.div(&x("nan"), &x("0"));
+    assert!(r.is_nan() && !r.flags().invalid, "nan / 0");
+    let r = ctx.sqrt(&x("nan"));
+    assert!(r.is_nan() && !r.flags().invalid, "sqrt(nan)");
+    let r = ctx.fma(&x("inf"), &x("0"), &x("nan"));
+    assert!(r.is_nan() && !r.flags().invalid, "fma(inf, 0, nan)");
+
+    // signaling NaNs are quieted and signal an invalid operation
+    let r = ctx.add(&x("snan"), &x("1"));
+    assert!(
+        r.is_nan() && !r.is_signaling() && r.flags().invalid,
+        "snan + 1"
+    );
+    let r = ctx.sub(&x("1"), &x("snan"));
+    assert!(
+        r.is_nan() && !r.is_signaling() && r.flags().invalid,
+        "1 - snan"
+    );
+    let r = ctx.mul(&x("nan"), &x("snan"));
+    assert!(
+        r.is_nan() && !r.is_signaling() && r.flags().invalid,
+        "nan * snan"
+    );
+    let r = ctx.sqrt(&x("snan"));
+    assert!(
+        r.is_nan() && !r.is_signaling() && r.flags().invalid,
+        "sqrt(snan)"
+    );
+    let r = ctx.floor(&x("snan"));
+    assert!(
+        r.is_nan() && !r.is_signaling() && r.flags().invalid,
+        "floor(snan)"
+    );
+}
+
+#[test]
+fn quantize() {
+    let ctx = DecimalContext::decimal32();
+    let x = |s: &str| parse(&ctx, s);
+
+    let r = ctx.quantize(&x("2.17"), &x("0.001"));
+    assert_eq!(r.to_string(), "2.170", "quantize");
+    assert!(!r.flags().inexact, "quantize");
+    let r = ctx.quantize(&x("2.17"), &x("0.1"));
+    assert_eq!(r.to_string(), "2.2", "quantize");
+    assert!(r.flags().inexact, "quantize");
+    let r = ctx.quantize(&x("-0.1"), &x("1"));
+    assert_eq!(r.to_string(), "-0", "quantize");
+    let r = ctx.quantize(&x("1234567"), &x("0.1"));
+    assert!(r.is_nan() && r.flags().invalid, "quantize");
+    let r = ctx.quantize(&x("inf"), &x("1"));
+    assert!(r.is_nan() && r.flags().invalid, "quantize");
+    let r = ctx.quantize(&x("nan"), &x("1"));
+    assert!(r.is_nan() && !r.flags().invalid, "quantize");
+    let r = ctx.quantize(&x("1"), &x("snan"));
+    assert!(r.is_nan() && r.flags().invalid, "quantize");
+}
+
+#[test]
+fn dfloat() {
+    let x: DFloat = "1.50".parse().unwrap();
+    assert_eq!(x, DFloat::Real(false, -2, Integer::from(150)), "parse");
+    assert_eq!(x.to_string(), "1.50", "display");
+
+    let y = DFloat::from_number(&IEEE754::from(0.375));
+    assert_eq!(
+        y,
+        DFloat::Real(false, -3, Integer::from(375)),
+        "from binary"
+    );
+    let y: DFloat = "0x1.8p-1".parse().unwrap();
+    assert_eq!(y.to_string(), "0.75", "from hex");
+}
+
+#[test]
+fn binary_rounds_decimal() {
+    let ctx = DecimalContext::decimal64();
+    let x = |s: &str| parse(&ctx, s);
+
+    // rounded once, through the exact value
+    for s in ["0.1", "-2.5", "3.4028235E38", "1E-45", "9007199254740993"] {
+        let r = IEEE754Context::binary64().round(&x(s));
+        assert_eq!(
+            r.to_f64(RoundingMode::NearestTiesToEven),
+            s.parse::<f64>().unwrap(),
+            "{}",
+            s
+        );
+        let r = IEEE754Context::binary32().round(&x(s));
+        assert_eq!(
+            r.to_f32(RoundingMode::NearestTiesToEven),
+            s.parse::<f32>().unwrap(),
+            "{}",
+            s
+        );
+    }
+
+    let r = IEEE754Context::binary64().round(&x("0.1"));
+    assert!(r.flags().inexact, "0.1 is inexact");
+    let r = IEEE754Context::binary64().round(&x("0.5"));
+    assert!(!r.flags().inexact, "0.5 is exact");
+    let r = IEEE754Context::binary64().round(&x("-0"));
+    assert!(r.is_zero() && r.sign().unwrap(), "-0 is negative zero");
+    let r = IEEE754Context::binary64().round(&x("-inf"));
+    assert!(r.is_infinite() && r.sign().unwrap(), "-inf is -Inf");
+    let r = IEEE754Context::binary64().round(&x("nan"));
+    assert!(r.is_nar(), "nan is NaN");
+
+    // other binary formats
+    let r = RFloatContext::new().with_max_p(3).round(&x("0.3"));
+    assert_eq!(
+        r,
+        RFloat::Real(false, -4, Integer::from(5)),
+        "0.3 under p = 3"
+    );
+    let r = FixedContext::new(true, -2, 8).round(&x("0.3"));
+    assert_eq!(
+        r.to_f64(RoundingMode::NearestTiesToEven),
+        0.25,
+        "0.3 under scale = -2"
+    );
+    let r = PositContext::new(2, 8).round(&x("1E300"));
+    assert_eq!(r, PositContext::new(2, 8).maxval(false), "posits saturate");
+    let ctx = TakumContext::new(16);
+    let r = ctx.round(&x("0.1"));
+    assert_eq!(
+        r.into_bits(),
+        ctx.round(&IEEE754::from(0.1)).into_bits(),
+        "takum"
+    );
+    let ctx = LnsContext::new(8, 2);
+    let r = ctx.round(&x("0.1"));
+    assert_eq!(
+        r.into_bits(),
+        ctx.round(&IEEE754::from(0.1)).into_bits(),
+        "lns"
+    );
+    let r = MultiDoubleContext::new(2).round(&x("0.1"));
+    let parts: Vec<f64> = r
+        .parts()
+        .iter()
+        .map(|x| x.to_f64(RoundingMode::NearestTiesToEven))
+        .collect();
+    assert_eq!(parts, [0.1, -5.551115123125783e-18], "double-double");
+    assert!(r.flags().inexact, "0.1 is not a double-double");
+}
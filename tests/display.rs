@@ -0,0 +1,227 @@
+use mpmfnum::display::{DecimalDisplay, DecimalMode, HexDisplay};
+use mpmfnum::fixed::FixedContext;
+use mpmfnum::float::FloatContext;
+use mpmfnum::ieee754::{IEEE754Context, IEEE754};
+use mpmfnum::parse::RoundedParse;
+use mpmfnum::posit::PositContext;
+use mpmfnum::rfloat::RFloat;
+use mpmfnum::{RoundingContext, RoundingMode};
+use rug::Integer;
+
+/// Splits a decimal string into its significant digits and
+/// (scientific) decimal exponent. Ties between two shortest strings
+/// may be broken differently than the standard library.
+fn normalize(s: &str) -> (String, isize) {
+    let (m, e) = match s.split_once('e') {
+        Some((m, e)) => (m, e.parse::<isize>().unwrap()),
+        None => (s, 0),
+    };
+
+    let m = m.trim_start_matches('-');
+    let point = m.find('.').unwrap_or(m.len()) as isize;
+    let digits: String = m.chars().filter(|c| *c != '.').collect();
+    let lead = digits.len() - digits.trim_start_matches('0').len();
+    let digits = digits.trim_matches('0').to_string();
+    (digits, e + point - (lead as isize) - 1)
+}
+
+/// Simple linear congruential generator for test values.
+fn lcg(state: &mut u64) -> u64 {
+    *state = state
+        .wrapping_mul(6364136223846793005)
+        .wrapping_add(1442695040888963407);
+    *state
+}
+
+#[test]
+fn shortest_native() {
+    let mut vals = vec![
+        0.1,
+        -1.0 / 3.0,
+        123.456,
+        1e23,
+        1e-7,
+        5e-324,
+        f64::MIN_POSITIVE,
+        f64::MAX,
+        2f64.powi(60),
+    ];
+
+    let mut state = 1;
+    for _ in 0..500 {
+        let x = f64::from_bits(lcg(&mut state));
+        if x.is_finite() && x != 0.0 {
+            vals.push(x);
+        }
+    }
+
+    for x in vals {
+        let y = IEEE754::from(x);
+        let s = y.to_string();
+        assert_eq!(s.parse::<f64>().unwrap(), x, "round trip failed: {}", s);
+        assert_eq!(
+            normalize(&s).0.len(),
+            normalize(&format!("{:e}", x)).0.len(),
+            "not shortest: {}",
+            s
+        );
+    }
+}
+
+#[test]
+fn shortest_native_f32() {
+    let mut state = 7;
+    for _ in 0..500 {
+        let x = f32::from_bits(lcg(&mut state) as u32);
+        if x.is_finite() && x != 0.0 {
+            let y = IEEE754::from(x);
+            let s = y.to_string();
+            assert_eq!(s.parse::<f32>().unwrap(), x, "round trip failed: {}", s);
+            assert_eq!(
+                normalize(&s).0.len(),
+                normalize(&format!("{:e}", x)).0.len(),
+                "not shortest: {}",
+                s
+            );
+        }
+    }
+}
+
+#[test]
+fn shortest_formats() {
+    for (x, expected) in [
+        (0.1, "0.1"),
+        (-2.5, "-2.5"),
+        (100.0, "100"),
+        (1e-5, "0.00001"),
+        (1e-6, "1e-6"),
+        (1.5e-300, "1.5e-300"),
+        (1e17, "1e17"),
+        (0.0, "0"),
+        (-0.0, "-0"),
+        (f64::INFINITY, "inf"),
+        (f64::NEG_INFINITY, "-inf"),
+        (f64::NAN, "NaN"),
+    ] {
+        let y = IEEE754::from(x);
+        assert_eq!(y.to_string(), expected, "display failed: {}", x);
+    }
+
+    // posit<2, 8>: 1.1 rounds to 1.125
+    let ctx = PositContext::new(2, 8);
+    let x = ctx.round(&RFloat::Real(false, -3, Integer::from(9)));
+    assert_eq!(x.to_string(), "1.1", "display failed for Posit<2, 8>");
+    let x = ctx.round(&RFloat::Nan);
+    assert_eq!(x.to_string(), "NaR", "display failed for Posit<2, 8>");
+
+    // Fixed<-4, 8>: shortest string depends on the rounding mode
+    let num = RFloat::Real(false, -4, Integer::from(1));
+    let ctx = FixedContext::new(true, -4, 8);
+    let x = ctx.round(&num);
+    assert_eq!(x.to_string(), "0.07", "display failed for truncation");
+    let ctx = ctx.with_rounding_mode(RoundingMode::NearestTiesToEven);
+    let x = ctx.round(&num);
+    assert_eq!(x.to_string(), "0.06", "display failed for RNE");
+
+    // Float: p = 3
+    let ctx = FloatContext::new(3);
+    let x = ctx.round(&RFloat::Real(false, 0, Integer::from(7)));
+    assert_eq!(x.to_string(), "7", "display failed for p=3");
+    let x = ctx.round(&RFloat::Real(false, 10, Integer::from(5)));
+    assert_eq!(x.to_string(), "5000", "display failed for p=3");
+}
+
+#[test]
+fn exact() {
+    let x = RFloat::from(0.1);
+    assert_eq!(
+        x.to_string(),
+        "0.1000000000000000055511151231257827021181583404541015625",
+        "exact display of 0.1"
+    );
+
+    let x = RFloat::Real(true, 100, Integer::from(1));
+    assert_eq!(
+        x.to_string(),
+        "-1.267650600228229401496703205376e30",
+        "exact display of -2^100"
+    );
+
+    let x = IEEE754::from(0.1f32);
+    assert_eq!(
+        x.to_decimal_string(DecimalMode::Exact),
+        "0.100000001490116119384765625",
+        "exact display of 0.1f32"
+    );
+}
+
+#[test]
+fn digits() {
+    let ctx = IEEE754Context::new(11, 64);
+    let digits = |x: f64, k: usize, rm: RoundingMode| {
+        ctx.round(&RFloat::from(x))
+            .to_decimal_string(DecimalMode::Digits(k, rm))
+    };
+
+    let rne = RoundingMode::NearestTiesToEven;
+    assert_eq!(digits(2.0 / 3.0, 3, rne), "0.667");
+    assert_eq!(digits(2.0 / 3.0, 3, RoundingMode::ToZero), "0.666");
+    assert_eq!(digits(-2.0 / 3.0, 3, RoundingMode::ToPositive), "-0.666");
+    assert_eq!(digits(-2.0 / 3.0, 3, RoundingMode::ToNegative), "-0.667");
+    assert_eq!(digits(1234.5, 3, rne), "1230");
+    assert_eq!(digits(1234.5, 4, rne), "1234");
+    assert_eq!(digits(1235.5, 4, rne), "1236");
+    assert_eq!(digits(9.99, 2, rne), "10");
+    assert_eq!(digits(1.5, 4, rne), "1.500");
+    assert_eq!(digits(1.25, 2, rne), "1.2");
+    assert_eq!(digits(1.25, 2, RoundingMode::NearestTiesAwayZero), "1.3");
+    assert_eq!(digits(0.1, 20, rne), "0.10000000000000000555");
+    assert_eq!(digits(1e300, 3, rne), "1.00e300");
+}
+
+#[test]
+fn hex() {
+    for (x, expected) in [
+        (1.0, "0x1p+0"),
+        (-0.1875, "-0x1.8p-3"),
+        (0.1, "0x1.999999999999ap-4"),
+        (f64::MAX, "0x1.fffffffffffffp+1023"),
+        (5e-324, "0x1p-1074"),
+        (0.0, "0x0p+0"),
+        (-0.0, "-0x0p+0"),
+        (f64::NEG_INFINITY, "-inf"),
+        (f64::NAN, "nan"),
+    ] {
+        let y = IEEE754::from(x);
+        assert_eq!(y.to_hex_string(), expected, "hex failed: {}", x);
+        assert_eq!(format!("{:x}", y), expected, "hex failed: {}", x);
+    }
+
+    let x = RFloat::Real(false, 10, Integer::from(3));
+    assert_eq!(format!("{:X}", x), "0X1.8P+11", "uppercase hex");
+
+    // NaN payloads round trip
+    let ctx = IEEE754Context::new(11, 64);
+    for s in ["nan", "-nan", "nan(0x1234)", "snan(0x1)", "-snan(0x2a)"] {
+        let x = ctx.parse(s).unwrap();
+        assert_eq!(x.to_hex_string(), s, "NaN round trip failed");
+    }
+
+    // hex strings round trip exactly
+    let mut state = 3;
+    for _ in 0..100 {
+        let x = IEEE754::from(f64::from_bits(lcg(&mut state)));
+        let y = ctx.parse(&x.to_hex_string()).unwrap();
+        assert_eq!(
+            x.clone().into_bits(),
+            y.into_bits(),
+            "round trip failed: {:x}",
+            x
+        );
+    }
+
+    // other formats
+    let ctx = PositContext::new(2, 8);
+    let x = ctx.round(&RFloat::Real(false, -3, Integer::from(9)));
+    assert_eq!(format!("{:x}", x), "0x1.2p+0", "hex failed for Posit<2, 8>");
+}
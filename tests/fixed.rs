@@ -1,7 +1,7 @@
 use mpmfnum::fixed::{FixedContext, Overflow};
-use mpmfnum::ops::RoundedAdd;
+use mpmfnum::ops::{RoundedAdd, RoundedMul};
 use mpmfnum::rfloat::RFloat;
-use mpmfnum::{fixed, RoundingContext};
+use mpmfnum::{fixed, Real, RoundingContext, RoundingMode};
 use rug::Integer;
 
 fn assert_round_small(signed: bool, scale: isize, nbits: usize, input: &RFloat, output: &RFloat) {
@@ -117,3 +117,194 @@ fn overflow() {
     let maxval = ctx.maxval();
     assert_eq!(maxval, ctx.add(&maxval, &delta), "should have wrapped");
 }
+
+/// Rounds an integer under a 4-bit integer format,
+/// returning the result and if overflow was signaled.
+fn round_int(signed: bool, overflow: Overflow, x: f64) -> (f64, bool) {
+    let ctx = FixedContext::new(signed, 0, 4).with_overflow(overflow);
+    let rounded = ctx.round(&RFloat::from(x));
+    let flag = rounded.flags().overflow;
+    (rounded.to_f64(RoundingMode::NearestTiesToEven), flag)
+}
+
+#[test]
+fn overflow_modes() {
+    // in range values are unchanged
+    let modes = [
+        Overflow::Wrap,
+        Overflow::Saturate,
+        Overflow::SaturateZero,
+        Overflow::WrapSignMagnitude,
+        Overflow::Trap,
+    ];
+    for mode in modes {
+        for x in [-8.0, -3.0, 0.0, 5.0, 7.0] {
+            assert_eq!(round_int(true, mode.clone(), x), (x, false), "{:?}", mode);
+        }
+        for x in [0.0, 9.0, 15.0] {
+            assert_eq!(round_int(false, mode.clone(), x), (x, false), "{:?}", mode);
+        }
+    }
+
+    // wrapping
+    assert_eq!(round_int(true, Overflow::Wrap, 8.0), (-8.0, true));
+    assert_eq!(round_int(true, Overflow::Wrap, -9.0), (7.0, true));
+    assert_eq!(round_int(true, Overflow::Wrap, 19.0), (3.0, true));
+    assert_eq!(round_int(false, Overflow::Wrap, -1.0), (15.0, true));
+
+    // saturating
+    assert_eq!(round_int(true, Overflow::Saturate, 19.0), (7.0, true));
+    assert_eq!(round_int(true, Overflow::Saturate, -19.0), (-8.0, true));
+    assert_eq!(round_int(false, Overflow::Saturate, -1.0), (0.0, true));
+
+    // symmetric saturation
+    let mode = Overflow::SaturateSymmetric;
+    assert_eq!(round_int(true, mode.clone(), 19.0), (7.0, true));
+    assert_eq!(round_int(true, mode.clone(), -19.0), (-7.0, true));
+    assert_eq!(round_int(true, mode.clone(), -8.0), (-7.0, true));
+    assert_eq!(round_int(true, mode.clone(), -7.0), (-7.0, false));
+    assert_eq!(round_int(false, mode.clone(), 16.0), (15.0, true));
+    assert_eq!(round_int(false, mode, 0.0), (0.0, false));
+
+    // saturation to zero
+    assert_eq!(round_int(true, Overflow::SaturateZero, 19.0), (0.0, true));
+    assert_eq!(round_int(true, Overflow::SaturateZero, -9.0), (0.0, true));
+    assert_eq!(round_int(false, Overflow::SaturateZero, 16.0), (0.0, true));
+
+    // sign-magnitude wrapping
+    let mode = Overflow::WrapSignMagnitude;
+    assert_eq!(round_int(true, mode.clone(), 19.0), (-4.0, true));
+    assert_eq!(round_int(true, mode.clone(), -19.0), (2.0, true));
+    assert_eq!(round_int(false, mode, 19.0), (3.0, true));
+}
+
+#[test]
+fn overflow_rounding() {
+    // convergent rounding and overflow after rounding
+    let ctx = FixedContext::new(true, 0, 4)
+        .with_rounding_mode(RoundingMode::NearestTiesToEven)
+        .with_overflow(Overflow::Saturate);
+    let rounded = ctx.round(&RFloat::from(6.5));
+    assert_eq!(RFloat::from(rounded.clone()), RFloat::from(6.0));
+    assert!(!rounded.flags().overflow && rounded.flags().inexact);
+    let rounded = ctx.round(&RFloat::from(7.5));
+    assert_eq!(RFloat::from(rounded.clone()), RFloat::from(7.0));
+    assert!(rounded.flags().overflow && rounded.flags().inexact);
+    let rounded = ctx.round(&RFloat::from(-8.5));
+    assert_eq!(RFloat::from(rounded.clone()), RFloat::from(-8.0));
+    assert!(!rounded.flags().overflow);
+}
+
+#[test]
+fn overflow_infinity() {
+    let ctx = FixedContext::new(true, 0, 4);
+    let rounded = ctx.round(&RFloat::PosInfinity);
+    assert_eq!(rounded, ctx.maxval());
+    assert!(rounded.flags().overflow);
+    let rounded = ctx.round(&RFloat::NegInfinity);
+    assert_eq!(rounded, ctx.minval());
+    assert!(rounded.flags().overflow);
+
+    let ctx = ctx.with_overflow(Overflow::SaturateSymmetric);
+    let rounded = ctx.round(&RFloat::NegInfinity);
+    assert_eq!(RFloat::from(rounded), RFloat::from(-7.0));
+
+    let ctx = ctx.with_overflow(Overflow::SaturateZero);
+    let rounded = ctx.round(&RFloat::NegInfinity);
+    assert!(rounded.is_zero() && rounded.flags().overflow);
+
+    // NaN is invalid, not an overflow
+    let rounded = ctx.round(&RFloat::Nan);
+    assert!(rounded.is_zero());
+    assert!(rounded.flags().invalid && !rounded.flags().overflow);
+}
+
+#[test]
+fn overflow_trap() {
+    let ctx = FixedContext::new(true, 0, 4).with_overflow(Overflow::Trap);
+    let rounded = ctx.try_round(&RFloat::from(5.0)).unwrap();
+    assert_eq!(RFloat::from(rounded), RFloat::from(5.0));
+
+    let err = ctx.try_round(&RFloat::from(-19.0)).unwrap_err();
+    assert_eq!(*err.value(), RFloat::from(-19.0));
+    assert!(ctx.try_round(&RFloat::from(8.0)).is_err());
+    assert!(ctx.try_round(&RFloat::PosInfinity).is_err());
+    assert!(ctx.try_round(&RFloat::Nan).is_ok());
+}
+
+#[test]
+fn overflow_trap_flag() {
+    // outside of `try_round`, trapped overflows saturate
+    let ctx = FixedContext::new(false, 0, 4).with_overflow(Overflow::Trap);
+    let rounded = ctx.round(&RFloat::from(16.0));
+    assert_eq!(RFloat::from(rounded.clone()), RFloat::from(15.0));
+    assert!(rounded.flags().overflow && rounded.flags().invalid);
+    let rounded = ctx.round(&RFloat::from(-1.0));
+    assert!(rounded.is_zero() && rounded.flags().overflow && rounded.flags().invalid);
+
+    // including the result of operations
+    let ctx = FixedContext::new(true, 0, 4).with_overflow(Overflow::Trap);
+    let rounded = ctx.mul(&RFloat::from(-3.0), &RFloat::from(5.0));
+    assert_eq!(RFloat::from(rounded.clone()), RFloat::from(-8.0));
+    assert!(rounded.flags().overflow && rounded.flags().invalid);
+
+    // unlike saturation, trapped overflows are invalid
+    let (a, b) = (RFloat::from(6.0), RFloat::from(5.0));
+    let trapped = ctx.add(&a, &b);
+    let ctx = FixedContext::new(true, 0, 4).with_overflow(Overflow::Saturate);
+    let saturated = ctx.add(&a, &b);
+    assert_eq!(RFloat::from(trapped.clone()), RFloat::from(7.0));
+    assert_eq!(RFloat::from(saturated.clone()), RFloat::from(7.0));
+    assert!(trapped.flags().overflow && trapped.flags().invalid);
+    assert!(saturated.flags().overflow && !saturated.flags().invalid);
+
+    // values in range are valid
+    let ctx = FixedContext::new(true, 0, 4).with_overflow(Overflow::Trap);
+    let rounded = ctx.add(&RFloat::from(2.0), &b);
+    assert!(!rounded.flags().overflow && !rounded.flags().invalid);
+}
+
+#[test]
+fn bits() {
+    // 4-bit, signed, two fractional bits
+    let ctx = FixedContext::new(true, -2, 4);
+    let cases = [
+        (0b0000, 0.0),
+        (0b0001, 0.25),
+        (0b0110, 1.5),
+        (0b0111, 1.75),
+        (0b1000, -2.0),
+        (0b1111, -0.25),
+        (0b1010, -1.5),
+    ];
+    for (b, x) in cases {
+        let v = ctx.bits_to_number(Integer::from(b));
+        assert_eq!(
+            RFloat::from(v.clone()),
+            RFloat::from(x),
+            "decoding {:#b}",
+            b
+        );
+        assert_eq!(v.into_bits(), b, "round trip {:#b}", b);
+        assert_eq!(ctx.round(&RFloat::from(x)).into_bits(), b, "encoding {}", x);
+    }
+    assert_eq!(ctx.maxval().into_bits(), 0b0111);
+    assert_eq!(ctx.minval().into_bits(), 0b1000);
+
+    // 4-bit, unsigned, scaled by 2
+    let ctx = FixedContext::new(false, 1, 4);
+    for b in 0..16 {
+        let v = ctx.bits_to_number(Integer::from(b));
+        assert_eq!(RFloat::from(v.clone()), RFloat::from(2.0 * b as f64));
+        assert_eq!(v.into_bits(), b);
+    }
+
+    // every bitpattern round trips
+    let ctx = FixedContext::new(true, 3, 6);
+    for b in 0..64 {
+        let v = ctx.bits_to_number(Integer::from(b));
+        let r = ctx.round(&RFloat::from(v));
+        assert!(!r.flags().inexact && !r.flags().overflow);
+        assert_eq!(r.into_bits(), b);
+    }
+}
@@ -465,6 +465,18 @@ fn to_bits_small() {
         let b2 = ctx.bits_to_number(b1.clone()).into_bits();
         assert_eq!(b1, b2, "round trip failed: {} != {}", b1, b2);
     }
+
+    // rounded values need not have full precision
+    let ctx = ieee754::IEEE754Context::binary64();
+    for (num, x) in [
+        (RFloat::Real(false, 0, Integer::from(1)), 1.0),
+        (RFloat::Real(true, 0, Integer::from(3)), -3.0),
+        (RFloat::Real(false, -2, Integer::from(3)), 0.75),
+        (RFloat::Real(false, 10, Integer::from(1)), 1024.0),
+    ] {
+        let b = ctx.round(&num).into_bits();
+        assert_eq!(b, Integer::from(f64::to_bits(x)), "encoding {} failed", x);
+    }
 }
 
 fn convert_round_mode(rm: RoundingMode) -> mpfr::rnd_t {
@@ -671,3 +683,410 @@ fn sandbox() {
 
     println!("{:?}", z);
 }
+
+#[test]
+fn native_round_trip() {
+    let vals = [
+        0.0,
+        -0.0,
+        1.0,
+        -0.1,
+        f64::MIN_POSITIVE,
+        f64::MIN_POSITIVE / 4.0,
+        f64::from_bits(1),
+        f64::MAX,
+        f64::INFINITY,
+        f64::NEG_INFINITY,
+    ];
+    for val in vals {
+        let x = ieee754::IEEE754::from(val);
+        let y = x.to_f64(RoundingMode::NearestTiesToEven);
+        assert_eq!(val.to_bits(), y.to_bits(), "round trip failed: {}", val);
+    }
+
+    // NaN payloads and signs are preserved
+    let nan = f64::from_bits(0xFFF8_0000_0000_1234);
+    let x = ieee754::IEEE754::from(nan);
+    assert!(x.is_nan(), "NaN is NaN");
+    assert!(x.nan_quiet().unwrap(), "NaN is quiet");
+    assert_eq!(x.nan_payload().unwrap(), 0x1234, "NaN payload");
+    let y = x.to_f64(RoundingMode::NearestTiesToEven);
+    assert_eq!(nan.to_bits(), y.to_bits(), "round trip failed: {}", nan);
+
+    // narrowing NaNs are quieted with payloads aligned at the MSB
+    let snan = f32::from_bits(0x7F80_0001);
+    let x = ieee754::IEEE754::from(snan);
+    assert!(!x.nan_quiet().unwrap(), "NaN is signaling");
+    let y = x.to_f64(RoundingMode::NearestTiesToEven);
+    assert_eq!((snan as f64).to_bits(), y.to_bits(), "quieted NaN");
+}
+
+#[test]
+fn native_rounding() {
+    let x = ieee754::IEEE754::from(0.1_f64);
+    let rms = [
+        RoundingMode::NearestTiesToEven,
+        RoundingMode::ToPositive,
+        RoundingMode::ToNegative,
+        RoundingMode::ToZero,
+    ];
+    let expected = [
+        0.1_f32,
+        0.1_f32,
+        f32::from_bits(0x3DCC_CCCC),
+        f32::from_bits(0x3DCC_CCCC),
+    ];
+    for (rm, expected) in rms.iter().zip(expected) {
+        let y = x.to_f32(*rm);
+        assert_eq!(expected.to_bits(), y.to_bits(), "mismatch for {:?}", rm);
+    }
+
+    // overflow and underflow
+    let big = ieee754::IEEE754::from(1e300_f64);
+    assert_eq!(big.to_f32(RoundingMode::NearestTiesToEven), f32::INFINITY);
+    assert_eq!(big.to_f32(RoundingMode::ToZero), f32::MAX);
+    let tiny = ieee754::IEEE754::from(-1e-300_f64);
+    let y = tiny.to_f32(RoundingMode::NearestTiesToEven);
+    assert!(y == 0.0 && y.is_sign_negative(), "should be -0.0");
+}
+
+#[test]
+fn overflow_ties() {
+    // binary16: MAX_FLOAT is 65504, halfway to the next binade is 65520
+    let ctx = ieee754::IEEE754Context::binary16();
+    let max = RFloat::from(ctx.max_float(false));
+    let tie = RFloat::Real(false, 4, Integer::from(4095));
+    let above = RFloat::Real(false, 3, Integer::from(8191));
+
+    // (mode, sign, input, overflows to infinity)
+    let cases = [
+        (RoundingMode::NearestTiesToEven, false, &tie, true),
+        (RoundingMode::NearestTiesToZero, false, &tie, false),
+        (RoundingMode::NearestTiesToZero, true, &tie, false),
+        (RoundingMode::NearestTiesToZero, false, &above, true),
+        (RoundingMode::NearestTiesToOdd, false, &tie, false),
+        (RoundingMode::NearestTiesToOdd, false, &above, true),
+        (RoundingMode::NearestTiesToPositive, false, &tie, true),
+        (RoundingMode::NearestTiesToPositive, true, &tie, false),
+        (RoundingMode::NearestTiesToNegative, false, &tie, false),
+        (RoundingMode::NearestTiesToNegative, true, &tie, true),
+        (RoundingMode::PrepareShorterPrecision, false, &above, false),
+        (RoundingMode::ToEven, false, &above, true),
+        (RoundingMode::ToOdd, false, &above, false),
+    ];
+
+    for (rm, sign, input, to_inf) in cases {
+        let ctx = ctx.clone().with_rounding_mode(rm);
+        let x = RFloat::Real(sign, input.exp().unwrap(), input.c().unwrap());
+        let y = ctx.round(&x);
+        if to_inf {
+            assert!(y.is_infinite(), "{:?} with {:?} should overflow", x, rm);
+            assert_eq!(y.sign(), Some(sign));
+            assert!(y.flags().overflow && y.flags().inexact);
+        } else {
+            assert!(
+                !y.flags().overflow,
+                "{:?} with {:?} should not overflow",
+                x,
+                rm
+            );
+            assert_eq!(
+                RFloat::from(y),
+                RFloat::Real(sign, max.exp().unwrap(), max.c().unwrap()),
+                "{:?} with {:?} should round to MAX_FLOAT",
+                x,
+                rm
+            );
+        }
+    }
+}
+
+#[test]
+fn named_formats() {
+    let formats = [
+        (ieee754::IEEE754Context::binary16(), 5, 16, "binary16"),
+        (ieee754::IEEE754Context::bfloat16(), 8, 16, "bfloat16"),
+        (ieee754::IEEE754Context::tf32(), 8, 19, "tf32"),
+        (ieee754::IEEE754Context::binary32(), 8, 32, "binary32"),
+        (ieee754::IEEE754Context::binary64(), 11, 64, "binary64"),
+        (ieee754::IEEE754Context::binary128(), 15, 128, "binary128"),
+        (ieee754::IEEE754Context::binary256(), 19, 256, "binary256"),
+        (ieee754::IEEE754Context::binary(16), 5, 16, "binary16"),
+        (ieee754::IEEE754Context::binary(64), 11, 64, "binary64"),
+        (ieee754::IEEE754Context::binary(128), 15, 128, "binary128"),
+        (ieee754::IEEE754Context::binary(160), 16, 160, "binary160"),
+        (ieee754::IEEE754Context::binary(192), 17, 192, "binary192"),
+        (ieee754::IEEE754Context::binary(256), 19, 256, "binary256"),
+        (ieee754::IEEE754Context::binary(512), 23, 512, "binary512"),
+        (
+            ieee754::IEEE754Context::binary(1024),
+            27,
+            1024,
+            "binary1024",
+        ),
+    ];
+
+    for (ctx, es, nbits, name) in formats {
+        assert_eq!(ctx.es(), es, "exponent width of {}", name);
+        assert_eq!(ctx.nbits(), nbits, "bitwidth of {}", name);
+        assert_eq!(ctx.name().as_deref(), Some(name), "name of {}", name);
+    }
+
+    // rounding mode does not matter
+    let ctx = ieee754::IEEE754Context::binary64().with_rounding_mode(RoundingMode::ToZero);
+    assert_eq!(ctx.name().as_deref(), Some("binary64"));
+
+    // non-standard formats
+    assert_eq!(ieee754::IEEE754Context::new(4, 8).name(), None);
+    assert_eq!(ieee754::IEEE754Context::new(11, 128).name(), None);
+    assert_eq!(ieee754::IEEE754Context::new(15, 144).name(), None);
+}
+
+fn ocp_value(ctx: &ieee754::IEEE754Context, x: f64) -> ieee754::IEEE754 {
+    ctx.round(&RFloat::from(x))
+}
+
+#[test]
+fn ocp_e4m3() {
+    let ctx = ieee754::IEEE754Context::ocp_e4m3();
+    assert_eq!(ctx.name().as_deref(), Some("e4m3"));
+    assert_eq!(ctx.emax(), 8);
+    assert_eq!(RFloat::from(ctx.max_float(false)), RFloat::from(448.0));
+    assert_eq!(
+        RFloat::from(ctx.min_float(false)),
+        RFloat::from(2f64.powi(-9))
+    );
+
+    // every encoding is finite except S.1111.111
+    for i in 0..256 {
+        let x = ctx.bits_to_number(Integer::from(i));
+        let is_nan = i & 0x7f == 0x7f;
+        assert_eq!(x.is_nan(), is_nan, "NaN encoding: {:#x}", i);
+        assert!(!x.is_infinite(), "no infinities: {:#x}", i);
+        assert_eq!(x.into_bits(), i, "round trip failed: {:#x}", i);
+    }
+
+    // non-saturating overflow produces NaN
+    let x = ocp_value(&ctx, 464.0);
+    assert_eq!(
+        RFloat::from(x.clone()),
+        RFloat::from(448.0),
+        "464 rounds to 448"
+    );
+    assert!(!x.flags().overflow, "464 does not overflow");
+    let x = ocp_value(&ctx, 470.0);
+    assert!(x.is_nan() && x.flags().overflow, "470 overflows to NaN");
+    let x = ocp_value(&ctx, f64::NEG_INFINITY);
+    assert!(x.is_nan() && x.flags().overflow, "-Inf overflows to NaN");
+    assert_eq!(x.into_bits(), 0xff, "-NaN encoding");
+    let ctx_rtz = ctx.clone().with_rounding_mode(RoundingMode::ToZero);
+    let x = ocp_value(&ctx_rtz, 1e6);
+    assert_eq!(
+        RFloat::from(x),
+        RFloat::from(448.0),
+        "RTZ never overflows to NaN"
+    );
+
+    // saturating overflow produces MAX_FLOAT
+    let ctx = ctx.with_overflow(ieee754::Overflow::Saturating);
+    let x = ocp_value(&ctx, 470.0);
+    assert_eq!(
+        RFloat::from(x.clone()),
+        RFloat::from(448.0),
+        "470 saturates"
+    );
+    assert!(x.flags().overflow, "470 overflows");
+    let x = ocp_value(&ctx, f64::NEG_INFINITY);
+    assert_eq!(RFloat::from(x), RFloat::from(-448.0), "-Inf saturates");
+    let x = ocp_value(&ctx, f64::NAN);
+    assert!(x.is_nan(), "NaN is preserved");
+
+    // operations
+    let ctx = ieee754::IEEE754Context::ocp_e4m3();
+    let x = ctx.sqrt(&RFloat::from(2.0));
+    assert_eq!(RFloat::from(x), RFloat::from(1.375), "sqrt(2)");
+    let x = ctx.mul(&RFloat::from(16.0), &RFloat::from(16.0));
+    assert_eq!(RFloat::from(x), RFloat::from(256.0), "16 * 16");
+    let x = ctx.add(&RFloat::from(256.0), &RFloat::from(256.0));
+    assert!(x.is_nan(), "256 + 256 overflows");
+}
+
+#[test]
+fn ocp_e5m2() {
+    let ctx = ieee754::IEEE754Context::ocp_e5m2();
+    assert_eq!(ctx.name().as_deref(), Some("e5m2"));
+    assert_eq!(RFloat::from(ctx.max_float(false)), RFloat::from(57344.0));
+
+    let x = ocp_value(&ctx, 1e6);
+    assert!(
+        x.is_infinite() && x.flags().overflow,
+        "1e6 overflows to Inf"
+    );
+    let ctx = ctx.with_overflow(ieee754::Overflow::Saturating);
+    let x = ocp_value(&ctx, 1e6);
+    assert_eq!(RFloat::from(x), RFloat::from(57344.0), "1e6 saturates");
+    let x = ocp_value(&ctx, f64::INFINITY);
+    assert_eq!(RFloat::from(x), RFloat::from(57344.0), "Inf saturates");
+}
+
+#[test]
+fn ocp_small() {
+    // FP4 E2M1: every encoding is finite
+    let ctx = ieee754::IEEE754Context::ocp_e2m1();
+    assert_eq!(ctx.name().as_deref(), Some("e2m1"));
+    let vals = [0.0, 0.5, 1.0, 1.5, 2.0, 3.0, 4.0, 6.0];
+    for i in 0..16 {
+        let x = ctx.bits_to_number(Integer::from(i));
+        let expected = if i < 8 { vals[i] } else { -vals[i - 8] };
+        assert_eq!(
+            RFloat::from(x.clone()),
+            RFloat::from(expected),
+            "value of {:#x}",
+            i
+        );
+        assert_eq!(x.into_bits(), i, "round trip failed: {:#x}", i);
+    }
+
+    let x = ocp_value(&ctx, 5.0);
+    assert_eq!(RFloat::from(x), RFloat::from(4.0), "5 rounds to 4");
+    let x = ocp_value(&ctx, 7.0);
+    assert_eq!(RFloat::from(x.clone()), RFloat::from(6.0), "7 saturates");
+    assert!(x.flags().overflow, "7 overflows");
+    let x = ocp_value(&ctx, f64::NEG_INFINITY);
+    assert_eq!(
+        RFloat::from(x.clone()),
+        RFloat::from(-6.0),
+        "-Inf saturates"
+    );
+    assert!(x.flags().overflow, "-Inf overflows");
+    let x = ocp_value(&ctx, f64::NAN);
+    assert!(x.is_zero() && x.flags().invalid, "NaN is invalid");
+
+    // FP6 formats
+    let ctx = ieee754::IEEE754Context::ocp_e3m2();
+    assert_eq!(ctx.name().as_deref(), Some("e3m2"));
+    assert_eq!(RFloat::from(ctx.max_float(false)), RFloat::from(28.0));
+    assert_eq!(RFloat::from(ctx.min_float(false)), RFloat::from(0.0625));
+    let ctx = ieee754::IEEE754Context::ocp_e2m3();
+    assert_eq!(ctx.name().as_deref(), Some("e2m3"));
+    assert_eq!(RFloat::from(ctx.max_float(false)), RFloat::from(7.5));
+    assert_eq!(RFloat::from(ctx.min_float(false)), RFloat::from(0.125));
+    for i in 0..64 {
+        let x = ctx.bits_to_number(Integer::from(i));
+        assert!(x.is_finite(), "every encoding is finite: {:#x}", i);
+        assert_eq!(x.into_bits(), i, "round trip failed: {:#x}", i);
+    }
+}
+
+#[test]
+fn sign_bit_ops() {
+    let ctx = ieee754::IEEE754Context::binary32();
+    let x = ieee754::IEEE754::from(-2.5f32);
+    let y = ieee754::IEEE754::from(0.0f32);
+    assert_eq!(x.negate().to_f32(RoundingMode::ToZero), 2.5);
+    assert_eq!(x.abs().to_f32(RoundingMode::ToZero), 2.5);
+    assert_eq!(y.negate().sign(), Some(true));
+    assert_eq!(y.copy_sign(&x).sign(), Some(true));
+    assert_eq!(x.copy_sign(&y).to_f32(RoundingMode::ToZero), 2.5);
+
+    // NaN payloads are preserved and no flags are raised
+    let snan = ctx.bits_to_number(Integer::from(0xFF80_0123u32));
+    for r in [snan.negate(), snan.abs(), snan.copy_sign(&y)] {
+        assert!(r.is_signaling());
+        assert_eq!(r.nan_payload(), Some(Integer::from(0x123)));
+        assert!(!r.flags().invalid);
+    }
+    assert_eq!(snan.abs().into_bits(), Integer::from(0x7F80_0123u32));
+    assert_eq!(snan.negate().sign(), Some(false));
+}
+
+#[test]
+fn classify() {
+    use ieee754::IEEE754Class::*;
+    let ctx = ieee754::IEEE754Context::binary16();
+    let cases = [
+        (0x7E00, QuietNaN),
+        (0xFD00, SignalingNaN),
+        (0xFC00, NegativeInfinity),
+        (0xBC00, NegativeNormal),
+        (0x8001, NegativeSubnormal),
+        (0x8000, NegativeZero),
+        (0x0000, PositiveZero),
+        (0x03FF, PositiveSubnormal),
+        (0x7BFF, PositiveNormal),
+        (0x7C00, PositiveInfinity),
+    ];
+    for (b, class) in cases {
+        let x = ctx.bits_to_number(Integer::from(b));
+        assert_eq!(x.class(), class, "class of {:#06x}", b);
+        assert!(x.is_canonical());
+    }
+    assert_eq!(ctx.radix(), 2);
+}
+
+#[test]
+fn scale_b_log_b() {
+    let ctx = ieee754::IEEE754Context::binary64();
+    let x = ieee754::IEEE754::from(3.0);
+
+    let r = ctx.scale_b(&x, 4);
+    assert_eq!(r.to_f64(RoundingMode::ToZero), 48.0);
+    assert!(!r.flags().inexact);
+    let r = ctx.scale_b(&x, -1076);
+    assert_eq!(r.to_f64(RoundingMode::ToZero), 5e-324);
+    assert!(r.flags().inexact && r.flags().underflow_post);
+    let r = ctx.scale_b(&x, isize::MAX);
+    assert!(r.is_infinite() && r.flags().overflow);
+    let r = ctx.scale_b(&ieee754::IEEE754::from(-0.0), 10);
+    assert!(r.is_zero() && r.sign() == Some(true));
+    let snan = ctx.snan();
+    let r = ctx.scale_b(&snan, 1);
+    assert!(r.is_nan() && !r.is_signaling() && r.flags().invalid);
+
+    assert_eq!(ctx.log_b(&x).to_f64(RoundingMode::ToZero), 1.0);
+    let r = ctx.log_b(&ieee754::IEEE754::from(0.1));
+    assert_eq!(r.to_f64(RoundingMode::ToZero), -4.0);
+    let r = ctx.log_b(&ieee754::IEEE754::from(5e-324));
+    assert_eq!(r.to_f64(RoundingMode::ToZero), -1074.0);
+    let r = ctx.log_b(&ieee754::IEEE754::from(-0.0));
+    assert!(r.is_infinite() && r.sign() == Some(true) && r.flags().divzero);
+    let r = ctx.log_b(&ctx.inf(true));
+    assert!(r.is_infinite() && r.sign() == Some(false));
+    let r = ctx.log_b(&ctx.qnan());
+    assert!(r.is_nan() && !r.flags().invalid);
+}
+
+#[test]
+fn total_order() {
+    let ctx = ieee754::IEEE754Context::binary32();
+    let bits = |b: u32| ctx.bits_to_number(Integer::from(b));
+    let ordered = [
+        bits(0xFFC0_0001), // -qNaN, payload 1
+        bits(0xFFC0_0000), // -qNaN
+        bits(0xFF80_0001), // -sNaN
+        bits(0xFF80_0000), // -Inf
+        ieee754::IEEE754::from(-1.0f32),
+        ieee754::IEEE754::from(-1e-45f32),
+        ieee754::IEEE754::from(-0.0f32),
+        ieee754::IEEE754::from(0.0f32),
+        ieee754::IEEE754::from(1e-45f32),
+        ieee754::IEEE754::from(1.0f32),
+        bits(0x7F80_0000), // +Inf
+        bits(0x7F80_0001), // +sNaN
+        bits(0x7FC0_0000), // +qNaN
+        bits(0x7FC0_0001), // +qNaN, payload 1
+    ];
+
+    for (i, x) in ordered.iter().enumerate() {
+        for (j, y) in ordered.iter().enumerate() {
+            assert_eq!(x.total_order(y), i <= j, "totalOrder({:?}, {:?})", x, y);
+        }
+    }
+
+    let x = ieee754::IEEE754::from(-2.0f32);
+    let y = ieee754::IEEE754::from(1.0f32);
+    assert!(x.total_order(&y) && !y.total_order(&x));
+    assert!(!x.total_order_mag(&y) && y.total_order_mag(&x));
+    assert!(bits(0x8000_0000).total_order_mag(&bits(0)));
+    assert!(bits(0).total_order_mag(&bits(0x8000_0000)));
+}
@@ -0,0 +1,239 @@
+use mpmfnum::decfixed::DecimalFixedContext;
+use mpmfnum::decimal::DecimalContext;
+use mpmfnum::fixed::{FixedContext, Overflow};
+use mpmfnum::float::FloatContext;
+use mpmfnum::ieee754::{IEEE754Context, IEEE754};
+use mpmfnum::ops::*;
+use mpmfnum::posit::PositContext;
+use mpmfnum::real::RealContext;
+use mpmfnum::rfloat::RFloat;
+use mpmfnum::takum::TakumContext;
+use mpmfnum::{Real, RoundingContext, RoundingMode};
+use rug::{Integer, Rational};
+
+fn f64_of<N: Real>(x: &N) -> f64 {
+    RFloat::from_number(x).to_f64(RoundingMode::NearestTiesToEven)
+}
+
+#[test]
+fn directed() {
+    let ctx = IEEE754Context::binary64();
+    let cases = [
+        // (x, floor, ceil, trunc, round, roundeven)
+        (2.5, 2.0, 3.0, 2.0, 3.0, 2.0),
+        (3.5, 3.0, 4.0, 3.0, 4.0, 4.0),
+        (-2.5, -3.0, -2.0, -2.0, -3.0, -2.0),
+        (0.75, 0.0, 1.0, 0.0, 1.0, 1.0),
+        (-1.25, -2.0, -1.0, -1.0, -1.0, -1.0),
+        (7.0, 7.0, 7.0, 7.0, 7.0, 7.0),
+    ];
+
+    for (x, fl, ce, tr, ro, re) in cases {
+        let x = ctx.round(&RFloat::from(x));
+        assert_eq!(f64_of(&ctx.floor(&x)), fl, "floor({:?})", x);
+        assert_eq!(f64_of(&ctx.ceil(&x)), ce, "ceil({:?})", x);
+        assert_eq!(f64_of(&ctx.trunc(&x)), tr, "trunc({:?})", x);
+        assert_eq!(f64_of(&ctx.round_away(&x)), ro, "round({:?})", x);
+        assert_eq!(f64_of(&ctx.round_even(&x)), re, "roundeven({:?})", x);
+
+        // these operations never signal inexact
+        assert!(!ctx.floor(&x).flags().inexact);
+        assert!(!ctx.round_away(&x).flags().inexact);
+    }
+
+    // signed zeros and special values are preserved
+    let x = ctx.round(&RFloat::from(-0.5));
+    assert!(ctx.ceil(&x).is_zero() && ctx.ceil(&x).sign() == Some(true));
+    assert!(ctx.trunc(&x).sign() == Some(true));
+    assert!(ctx.floor(&ctx.inf(true)).is_infinite());
+    assert!(ctx.floor(&ctx.qnan()).is_nar());
+    assert!(!ctx.floor(&ctx.qnan()).flags().invalid);
+}
+
+#[test]
+fn nearbyint_rint() {
+    let x = RFloat::from(2.5);
+    let y = RFloat::from(-7.0);
+    for (rm, expect) in [
+        (RoundingMode::NearestTiesToEven, 2.0),
+        (RoundingMode::NearestTiesAwayZero, 3.0),
+        (RoundingMode::ToPositive, 3.0),
+        (RoundingMode::ToNegative, 2.0),
+        (RoundingMode::ToZero, 2.0),
+    ] {
+        let ctx = IEEE754Context::binary32().with_rounding_mode(rm);
+        let r = ctx.nearbyint(&x);
+        assert_eq!(f64_of(&r), expect, "nearbyint under {:?}", rm);
+        assert!(!r.flags().inexact);
+        let r = ctx.rint(&x);
+        assert_eq!(f64_of(&r), expect, "rint under {:?}", rm);
+        assert!(r.flags().inexact);
+        assert!(!ctx.rint(&y).flags().inexact);
+    }
+}
+
+#[test]
+fn nans() {
+    let ctx = IEEE754Context::binary32();
+    let qnan = ctx.bits_to_number(Integer::from(0xFFC0_0456u32));
+    let snan = ctx.bits_to_number(Integer::from(0xFF80_0123u32));
+    let ops: [fn(&IEEE754Context, &IEEE754) -> IEEE754; 7] = [
+        |ctx, x| ctx.floor(x),
+        |ctx, x| ctx.ceil(x),
+        |ctx, x| ctx.trunc(x),
+        |ctx, x| ctx.round_away(x),
+        |ctx, x| ctx.round_even(x),
+        |ctx, x| ctx.nearbyint(x),
+        |ctx, x| ctx.rint(x),
+    ];
+
+    for op in ops {
+        // quiet NaNs propagate their sign and payload
+        let r = op(&ctx, &qnan);
+        assert_eq!(r.clone().into_bits(), Integer::from(0xFFC0_0456u32));
+        assert!(!r.flags().invalid);
+
+        // signaling NaNs are quieted and signal an invalid operation
+        let r = op(&ctx, &snan);
+        assert_eq!(r.clone().into_bits(), Integer::from(0xFFC0_0123u32));
+        assert!(r.flags().invalid);
+
+        // payloads are carried between formats
+        let r = op(&IEEE754Context::binary64(), &qnan);
+        assert_eq!(r.sign(), Some(true));
+        assert_eq!(r.nan_payload(), Some(Integer::from(0x456) << 29));
+    }
+}
+
+#[test]
+fn convert() {
+    let ctx = IEEE754Context::binary64();
+    let x = RFloat::from(-2.5);
+
+    let r = ctx.convert_to_integer(&x, RoundingMode::NearestTiesToEven, false);
+    assert_eq!(f64_of(&r), -2.0);
+    assert!(!r.flags().inexact && !r.flags().invalid);
+    let r = ctx.convert_to_integer(&x, RoundingMode::NearestTiesAwayZero, true);
+    assert_eq!(f64_of(&r), -3.0);
+    assert!(r.flags().inexact && !r.flags().invalid);
+    let r = ctx.convert_to_integer(&RFloat::from(4.0), RoundingMode::ToZero, true);
+    assert_eq!(f64_of(&r), 4.0);
+    assert!(!r.flags().inexact);
+
+    // non-real values are invalid
+    for x in [RFloat::Nan, RFloat::PosInfinity, RFloat::NegInfinity] {
+        let r = convert_to_integer(&ctx, &x, RoundingMode::ToZero, true);
+        assert!(r.is_nar() && r.flags().invalid && !r.flags().inexact);
+    }
+
+    // out of range values are invalid
+    let ctx = IEEE754Context::new(5, 16);
+    let x = RFloat::from(1e6);
+    let r = ctx.convert_to_integer(&x, RoundingMode::ToZero, false);
+    assert!(r.is_nar() && r.flags().invalid && !r.flags().overflow);
+
+    // unbounded floating-point
+    let ctx = FloatContext::new(8);
+    let r = ctx.convert_to_integer(&RFloat::from(1.5), RoundingMode::ToPositive, true);
+    assert_eq!(f64_of(&r), 2.0);
+    assert!(r.flags().inexact);
+    assert!(
+        ctx.convert_to_integer(&RFloat::Nan, RoundingMode::ToZero, false)
+            .flags()
+            .invalid
+    );
+}
+
+#[test]
+fn fixed() {
+    // integers as 8-bit signed fixed-point numbers
+    let ctx = FixedContext::new(true, 0, 8).with_rounding_mode(RoundingMode::ToNegative);
+    let x = RFloat::from(-2.25);
+    assert_eq!(f64_of(&ctx.floor(&x)), -3.0);
+    assert_eq!(f64_of(&ctx.ceil(&x)), -2.0);
+    assert_eq!(f64_of(&ctx.nearbyint(&x)), -3.0);
+    assert!(!ctx.nearbyint(&x).flags().inexact);
+    assert!(ctx.rint(&x).flags().inexact);
+
+    // wrapping on overflow
+    let x = RFloat::from(200.5);
+    let r = ctx.convert_to_integer(&x, RoundingMode::NearestTiesToEven, false);
+    assert_eq!(f64_of(&r), -56.0);
+    assert!(r.flags().overflow && !r.flags().inexact);
+
+    // saturating on overflow
+    let ctx = ctx.with_overflow(Overflow::Saturate);
+    let r = ctx.convert_to_integer(&x, RoundingMode::NearestTiesToEven, true);
+    assert_eq!(f64_of(&r), 127.0);
+    assert!(r.flags().overflow && r.flags().inexact);
+    let r = ctx.convert_to_integer(&RFloat::NegInfinity, RoundingMode::ToZero, false);
+    assert_eq!(f64_of(&r), -128.0);
+    assert!(r.flags().overflow);
+
+    // NaN is invalid
+    let r = ctx.convert_to_integer(&RFloat::Nan, RoundingMode::ToZero, false);
+    assert!(r.is_zero() && r.flags().invalid);
+}
+
+#[test]
+fn other_contexts() {
+    let x = RFloat::from(-2.5);
+
+    let ctx = PositContext::new(2, 16);
+    assert_eq!(f64_of(&ctx.floor(&x)), -3.0);
+    assert_eq!(f64_of(&ctx.rint(&x)), -2.0);
+    assert!(ctx.ceil(&PositContext::new(2, 16).nar()).is_nar());
+
+    let ctx = TakumContext::new(16);
+    assert_eq!(f64_of(&ctx.round_away(&x)), -3.0);
+
+    let ctx = RealContext::new();
+    assert_eq!(ctx.trunc(&x), RFloat::from(-2.0));
+    assert_eq!(
+        ctx.convert_to_integer(&x, RoundingMode::ToPositive, true),
+        RFloat::from(-2.0)
+    );
+
+    // decimal values are rounded exactly
+    let ctx = DecimalContext::decimal64().with_rounding_mode(RoundingMode::ToZero);
+    let d = ctx.round(&RFloat::from(-2.5));
+    let r = ctx.rint(&d);
+    assert!(r.flags().inexact);
+    assert_eq!(r.to_rational(), Some(Rational::from(-2)));
+    let r = ctx.convert_to_integer(&ctx.inf(false), RoundingMode::ToZero, false);
+    assert!(r.is_nar() && r.flags().invalid);
+
+    let ctx = DecimalFixedContext::new(true, 0, 2);
+    let r = ctx.round_even(&RFloat::from(99.5));
+    assert!(r.flags().overflow);
+    let r = ctx.ceil(&RFloat::from(4.25));
+    assert_eq!(r.to_rational(), Rational::from(5));
+}
+
+#[test]
+fn large_exponents() {
+    // integral values are found without computing `2^(2^32)`
+    let tiny = RFloat::Real(false, -(1 << 32), Integer::from(1));
+    let neg_tiny = RFloat::Real(true, -(1 << 32), Integer::from(1));
+    let big = RFloat::Real(true, 1 << 32, Integer::from(3));
+
+    let ctx = RealContext::new();
+    assert!(ctx.floor(&tiny).is_zero(), "floor(2^-(2^32))");
+    assert_eq!(ctx.ceil(&tiny), RFloat::from(1.0), "ceil(2^-(2^32))");
+    assert!(ctx.round_even(&tiny).is_zero(), "roundeven(2^-(2^32))");
+    assert_eq!(ctx.floor(&neg_tiny), RFloat::from(-1.0));
+    assert_eq!(ctx.ceil(&big), big, "ceil(-3 * 2^(2^32))");
+    let r = ctx.rint(&big);
+    assert_eq!(r, big, "rint(-3 * 2^(2^32))");
+
+    let ctx = IEEE754Context::binary64();
+    let r = ctx.rint(&tiny);
+    assert!(r.is_zero() && r.flags().inexact, "rint(2^-(2^32))");
+    let r = ctx.trunc(&neg_tiny);
+    assert!(r.is_zero() && r.sign() == Some(true), "trunc(-2^-(2^32))");
+    let r = ctx.convert_to_integer(&big, RoundingMode::ToZero, true);
+    assert!(
+        r.is_nar() && r.flags().invalid,
+        "convertToInteger(-3 * 2^(2^32))"
+    );
+}
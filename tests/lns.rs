@@ -0,0 +1,252 @@
+use mpmfnum::decimal::DFloat;
+use mpmfnum::lns::*;
+use mpmfnum::ops::*;
+use mpmfnum::{rfloat::RFloat, Real, RoundingContext, RoundingMode};
+use rug::float::Round;
+use rug::{Float, Integer, Rational};
+
+fn lns(ctx: &LnsContext, b: u64) -> Lns {
+    ctx.bits_to_number(Integer::from(b))
+}
+
+fn value(ctx: &LnsContext, x: f64) -> Lns {
+    ctx.round(&RFloat::from(x))
+}
+
+#[test]
+fn params() {
+    let ctx = LnsContext::new(5, 10);
+    assert_eq!(ctx.ibits(), 5);
+    assert_eq!(ctx.fbits(), 10);
+    assert_eq!(ctx.nbits(), 16);
+
+    // largest logarithm is `16 - 2^-10`
+    let max = ctx.maxval(false);
+    assert_eq!(max.log2(), Some(Rational::from((16383, 1024))));
+    assert_eq!(max.into_bits(), 0x3FFF);
+    let min = ctx.minval(false);
+    assert_eq!(min.log2(), Some(Rational::from((-16383, 1024))));
+    assert_eq!(min.into_bits(), 0x4001);
+}
+
+#[test]
+fn encoding() {
+    let ctx = LnsContext::new(5, 10);
+    let cases = [
+        (0x0000, RFloat::one()),
+        (0x0400, RFloat::from(2.0)),
+        (0x7C00, RFloat::from(0.5)),
+        (0x8400, RFloat::from(-2.0)),
+        (0x8000, RFloat::from(-1.0)),
+        (0x2800, RFloat::Real(false, 10, Integer::from(1))),
+    ];
+
+    for (b, v) in cases {
+        let x = lns(&ctx, b);
+        assert!(x.is_exact(), "exact value {:#x}", b);
+        assert_eq!(RFloat::from(x), v, "decoding {:#x}", b);
+        assert_eq!(ctx.round(&v).into_bits(), b, "encoding {:?}", v);
+    }
+
+    // special values
+    assert!(lns(&ctx, 0x4000).is_zero());
+    assert!(lns(&ctx, 0xC000).is_nan());
+    assert_eq!(ctx.zero().into_bits(), 0x4000);
+    assert_eq!(ctx.nan().into_bits(), 0xC000);
+
+    // irrational values are approximated
+    let x = value(&ctx, 3.0);
+    assert!(!x.is_exact());
+    assert_eq!(x.log2(), Some(Rational::from((1623, 1024))));
+    assert_eq!(x.clone().into_bits(), 1623);
+    assert!((x.to_f64(RoundingMode::NearestTiesToEven) - 3.0).abs() < 3.0 / 1024.0);
+}
+
+#[test]
+fn round_trip() {
+    let ctx = LnsContext::new(4, 6);
+    let mut prev: Option<Lns> = None;
+    for b in 0..(1 << ctx.nbits()) {
+        let x = lns(&ctx, b);
+        assert_eq!(x.clone().into_bits(), b, "bits round trip");
+        let r = ctx.round(&RFloat::from(x.clone()));
+        assert_eq!(r.into_bits(), b, "rounding round trip");
+
+        // positive values are ordered by their logarithms
+        if b > (1 << 9) && b < (1 << 10) {
+            if let Some(p) = prev {
+                assert!(p < x, "{:?} < {:?}", p, x);
+            }
+        }
+        prev = if b < (1 << 10) { Some(x) } else { None };
+    }
+}
+
+#[test]
+fn rounding() {
+    let ctx = LnsContext::new(5, 10);
+
+    // special values
+    assert!(ctx.round(&RFloat::Nan).is_nan());
+    assert!(ctx.round(&RFloat::PosInfinity).is_nan());
+    assert!(ctx.round(&RFloat::NegInfinity).is_nan());
+    assert!(ctx.round(&RFloat::zero()).is_zero());
+
+    // values saturate
+    let huge = RFloat::Real(false, 100, Integer::from(1));
+    assert_eq!(ctx.round(&huge), ctx.maxval(false));
+    let tiny = RFloat::Real(true, -100, Integer::from(1));
+    assert_eq!(ctx.round(&tiny), ctx.minval(true));
+
+    // extreme exponents saturate
+    let ctx = LnsContext::new(8, 8);
+    let huge = RFloat::Real(false, 1 << 40, Integer::from(3));
+    assert_eq!(ctx.round(&huge), ctx.maxval(false));
+    let tiny = RFloat::Real(true, -(1 << 40), Integer::from(3));
+    assert_eq!(ctx.round(&tiny), ctx.minval(true));
+    let huge = DFloat::Real(true, 1 << 40, Integer::from(3));
+    assert_eq!(ctx.round(&huge), ctx.maxval(true));
+    let tiny = DFloat::Real(false, -(1 << 40), Integer::from(3));
+    assert_eq!(ctx.round(&tiny), ctx.minval(false));
+
+    // the largest value does not saturate early
+    let x = RFloat::Real(false, 127, Integer::from(1));
+    assert_eq!(ctx.round(&x).log2(), Some(Rational::from(127)));
+    let x = DFloat::Real(false, -38, Integer::from(1));
+    assert_eq!(ctx.round(&x).log2(), Some(Rational::from((-32316, 256))));
+
+    // rounding to nearest in the log domain
+    let ctx = LnsContext::new(4, 1);
+    let x = RFloat::Real(false, -1, Integer::from(3));
+    // log2(1.5) = 0.58496... rounds to 0.5
+    assert_eq!(ctx.round(&x).log2(), Some(Rational::from((1, 2))));
+    let x = RFloat::Real(false, -2, Integer::from(7));
+    // log2(1.75) = 0.80735... rounds to 1
+    assert_eq!(ctx.round(&x).log2(), Some(Rational::from(1)));
+}
+
+#[test]
+fn exact_ops() {
+    let ctx = LnsContext::new(5, 10);
+    let x = |v: f64| value(&ctx, v);
+
+    // multiplication and division are exact in the log domain
+    assert_eq!(
+        ctx.mul(&x(3.0), &x(3.0)).log2(),
+        Some(Rational::from((3246, 1024)))
+    );
+    assert_eq!(ctx.div(&x(3.0), &x(3.0)), x(1.0));
+    assert_eq!(ctx.mul(&x(-2.0), &x(0.25)), x(-0.5));
+    assert_eq!(ctx.recip(&x(-4.0)), x(-0.25));
+    assert_eq!(ctx.neg(&x(3.0)).log2(), x(3.0).log2());
+    assert_eq!(ctx.abs(&x(-3.0)), x(3.0));
+
+    // square root halves the logarithm with ties to even
+    assert_eq!(ctx.sqrt(&x(16.0)), x(4.0));
+    let y = lns(&ctx, 3);
+    assert_eq!(ctx.sqrt(&y).into_bits(), 2);
+    let y = lns(&ctx, 5);
+    assert_eq!(ctx.sqrt(&y).into_bits(), 2);
+    let y = lns(&ctx, 0x7FFD);
+    assert_eq!(ctx.sqrt(&y).into_bits(), 0x7FFE);
+
+    // overflow saturates
+    assert_eq!(ctx.mul(&ctx.maxval(false), &x(2.0)), ctx.maxval(false));
+    assert_eq!(ctx.div(&ctx.minval(true), &x(2.0)), ctx.minval(true));
+
+    // zeros and invalid operations
+    assert!(ctx.mul(&x(2.0), &x(0.0)).is_zero());
+    assert!(ctx.div(&x(0.0), &x(2.0)).is_zero());
+    assert!(ctx.div(&x(2.0), &x(0.0)).is_nan());
+    assert!(ctx.div(&x(0.0), &x(0.0)).is_nan());
+    assert!(ctx.recip(&x(0.0)).is_nan());
+    assert!(ctx.sqrt(&x(-2.0)).is_nan());
+    assert!(ctx.mul(&ctx.nan(), &x(2.0)).is_nan());
+}
+
+#[test]
+fn single_rounding() {
+    // operations round the exact result of non-LNS inputs
+    let ctx = LnsContext::new(8, 2);
+    let f = |v: f64| RFloat::from(v);
+    assert_eq!(ctx.mul(&f(3.0), &f(5.0)), ctx.round(&f(15.0)));
+    assert_eq!(ctx.mul(&f(3.0), &f(5.0)).log2(), Some(Rational::from(4)));
+    assert_eq!(ctx.div(&f(15.0), &f(5.0)), ctx.round(&f(3.0)));
+    assert_eq!(ctx.add(&f(1.5), &f(1.5)), ctx.round(&f(3.0)));
+    assert_eq!(ctx.sub(&f(7.0), &f(2.0)), ctx.round(&f(5.0)));
+    assert_eq!(ctx.sqrt(&f(10.0)), ctx.round(&f(10.0_f64.sqrt())));
+    assert_eq!(ctx.recip(&f(3.0)), ctx.round(&f(1.0 / 3.0)));
+
+    // conversion between LNS formats rounds the exact logarithm:
+    // `2^(3/4)` is halfway between `2^(1/2)` and `2^1`
+    let src = LnsContext::new(8, 2);
+    let dst = LnsContext::new(8, 1);
+    let x = lns(&src, 3);
+    assert_eq!(x.log2(), Some(Rational::from((3, 4))));
+    assert_eq!(dst.round(&x).log2(), Some(Rational::from(1)));
+    let x = lns(&src, 1);
+    assert_eq!(dst.round(&x).log2(), Some(Rational::from(0)));
+
+    // including the results of operations on LNS values
+    let y = lns(&src, 2);
+    assert_eq!(dst.mul(&x, &y).log2(), Some(Rational::from(1)));
+    assert_eq!(dst.div(&y, &x).log2(), Some(Rational::from(0)));
+    assert_eq!(dst.add(&y, &y).log2(), Some(Rational::from((3, 2))));
+    assert_eq!(dst.sqrt(&lns(&src, 6)).log2(), Some(Rational::from(1)));
+}
+
+#[test]
+fn add_sub() {
+    let ctx = LnsContext::new(5, 10);
+    let x = |v: f64| value(&ctx, v);
+
+    assert_eq!(ctx.add(&x(1.0), &x(1.0)), x(2.0));
+    assert_eq!(ctx.add(&x(1.0), &x(2.0)), x(3.0));
+    assert_eq!(ctx.sub(&x(1.0), &x(4.0)), x(-3.0));
+    assert!(ctx.sub(&x(3.0), &x(3.0)).is_zero());
+    assert_eq!(ctx.add(&x(0.0), &x(3.0)), x(3.0));
+    assert!(ctx.add(&ctx.nan(), &x(1.0)).is_nan());
+
+    // compare against a high-precision reference for all pairs
+    let ctx = LnsContext::new(3, 3);
+    let n = 1 << ctx.nbits();
+    let reference = |a: &Lns, b: &Lns, add: bool| -> Lns {
+        let fa = Float::with_val(256, Float::from(RFloat::from(a.clone())));
+        let fb = Float::with_val(256, Float::from(RFloat::from(b.clone())));
+        let r = if add { fa + fb } else { fa - fb };
+        if r.is_zero() {
+            return ctx.zero();
+        }
+
+        let s = r.is_sign_negative();
+        let (l, _) = Float::with_val_round(256, r.abs().log2_ref(), Round::Nearest);
+        let l = (l << (ctx.fbits() as u32)).round_even();
+        let l = l
+            .to_integer()
+            .unwrap()
+            .clamp(&Integer::from(-31), &Integer::from(31));
+        let b = if s {
+            Integer::from(1) << 6
+        } else {
+            Integer::from(0)
+        };
+        ctx.bits_to_number(b | l.keep_bits(6))
+    };
+
+    for i in 0..n {
+        for j in 0..n {
+            let a = lns(&ctx, i);
+            let b = lns(&ctx, j);
+            if a.is_nan() || b.is_nan() {
+                continue;
+            }
+
+            let r = ctx.add(&a, &b);
+            let e = reference(&a, &b, true);
+            assert_eq!(r.into_bits(), e.into_bits(), "{:#x} + {:#x}", i, j);
+            let r = ctx.sub(&a, &b);
+            let e = reference(&a, &b, false);
+            assert_eq!(r.into_bits(), e.into_bits(), "{:#x} - {:#x}", i, j);
+        }
+    }
+}
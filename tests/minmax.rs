@@ -0,0 +1,183 @@
+use mpmfnum::decimal::DecimalContext;
+use mpmfnum::fixed::FixedContext;
+use mpmfnum::ieee754::{IEEE754Context, IEEE754};
+use mpmfnum::ops::*;
+use mpmfnum::posit::PositContext;
+use mpmfnum::real::RealContext;
+use mpmfnum::rfloat::RFloat;
+use mpmfnum::{Real, RoundingContext, RoundingMode};
+use rug::{Integer, Rational};
+
+fn f64_of(x: &IEEE754) -> f64 {
+    x.to_f64(RoundingMode::NearestTiesToEven)
+}
+
+#[test]
+fn numbers() {
+    let ctx = IEEE754Context::binary64();
+    let x = IEEE754::from(-3.0);
+    let y = IEEE754::from(2.0);
+
+    assert_eq!(f64_of(&ctx.minimum(&x, &y)), -3.0);
+    assert_eq!(f64_of(&ctx.maximum(&x, &y)), 2.0);
+    assert_eq!(f64_of(&ctx.minimum_number(&x, &y)), -3.0);
+    assert_eq!(f64_of(&ctx.maximum_number(&y, &x)), 2.0);
+    assert_eq!(f64_of(&ctx.minimum_magnitude(&x, &y)), 2.0);
+    assert_eq!(f64_of(&ctx.maximum_magnitude(&x, &y)), -3.0);
+    assert_eq!(f64_of(&ctx.min_num(&y, &x)), -3.0);
+    assert_eq!(f64_of(&ctx.max_num(&x, &y)), 2.0);
+
+    // equal magnitudes fall back to minimum and maximum
+    let z = IEEE754::from(3.0);
+    assert_eq!(f64_of(&ctx.minimum_magnitude(&z, &x)), -3.0);
+    assert_eq!(f64_of(&ctx.maximum_magnitude(&x, &z)), 3.0);
+
+    // infinities
+    let inf = ctx.inf(true);
+    assert!(ctx.minimum(&inf, &x).is_infinite());
+    assert_eq!(f64_of(&ctx.maximum(&inf, &x)), -3.0);
+    assert!(ctx.maximum_magnitude(&inf, &x).is_infinite());
+
+    // results are rounded
+    let ctx = IEEE754Context::binary16();
+    let r = ctx.maximum(&IEEE754::from(0.1), &y);
+    assert_eq!(f64_of(&r), 2.0);
+    let r = ctx.minimum(&IEEE754::from(0.1), &y);
+    assert!(r.flags().inexact);
+}
+
+#[test]
+fn signed_zeros() {
+    let ctx = IEEE754Context::binary32();
+    let pz = ctx.zero(false);
+    let nz = ctx.zero(true);
+
+    for (a, b) in [(&pz, &nz), (&nz, &pz)] {
+        assert_eq!(ctx.minimum(a, b).sign(), Some(true));
+        assert_eq!(ctx.maximum(a, b).sign(), Some(false));
+        assert_eq!(ctx.minimum_number(a, b).sign(), Some(true));
+        assert_eq!(ctx.maximum_number(a, b).sign(), Some(false));
+        assert_eq!(ctx.minimum_magnitude(a, b).sign(), Some(true));
+        assert_eq!(ctx.maximum_magnitude(a, b).sign(), Some(false));
+        assert_eq!(ctx.min_num(a, b).sign(), Some(true));
+        assert_eq!(ctx.max_num(a, b).sign(), Some(false));
+    }
+}
+
+#[test]
+fn nans() {
+    let ctx = IEEE754Context::binary64();
+    let x = IEEE754::from(1.5);
+    let qnan = ctx.qnan();
+    let snan = ctx.snan();
+
+    // NaNs propagate
+    for r in [
+        ctx.minimum(&x, &qnan),
+        ctx.maximum(&qnan, &x),
+        ctx.minimum_magnitude(&qnan, &x),
+        ctx.maximum_magnitude(&x, &qnan),
+    ] {
+        assert!(r.is_nan() && !r.flags().invalid);
+    }
+    for r in [ctx.minimum(&x, &snan), ctx.maximum_magnitude(&snan, &x)] {
+        assert!(r.is_nan() && r.flags().invalid);
+    }
+
+    // numbers are preferred
+    for r in [
+        ctx.minimum_number(&x, &qnan),
+        ctx.maximum_number(&qnan, &x),
+        ctx.min_num(&qnan, &x),
+        ctx.max_num(&x, &qnan),
+    ] {
+        assert_eq!(f64_of(&r), 1.5);
+        assert!(!r.flags().invalid);
+    }
+    let r = ctx.minimum_number(&snan, &x);
+    assert_eq!(f64_of(&r), 1.5);
+    assert!(r.flags().invalid);
+    let r = ctx.maximum_number(&qnan, &snan);
+    assert!(r.is_nan() && r.flags().invalid);
+
+    // the 2008 operations produce NaN given a signaling NaN
+    let r = ctx.min_num(&x, &snan);
+    assert!(r.is_nan() && r.flags().invalid);
+    let r = ctx.max_num(&snan, &x);
+    assert!(r.is_nan() && r.flags().invalid);
+    let r = ctx.max_num(&qnan, &qnan);
+    assert!(r.is_nan() && !r.flags().invalid);
+
+    // NaNs keep their sign and payload, and signaling NaNs are quieted
+    let ctx = IEEE754Context::binary32();
+    let x = ctx.round(&RFloat::from(1.5));
+    let qnan = ctx.bits_to_number(Integer::from(0xFFC0_0456u32));
+    let snan = ctx.bits_to_number(Integer::from(0xFF80_0123u32));
+    for r in [ctx.minimum(&x, &qnan), ctx.maximum_magnitude(&qnan, &x)] {
+        assert_eq!(r.into_bits(), Integer::from(0xFFC0_0456u32));
+    }
+    for r in [
+        ctx.maximum(&snan, &x),
+        ctx.min_num(&x, &snan),
+        ctx.maximum_number(&qnan, &snan),
+        ctx.minimum(&snan, &qnan),
+    ] {
+        assert_eq!(r.into_bits(), Integer::from(0xFFC0_0123u32));
+    }
+    let r = ctx.minimum(&qnan, &ctx.qnan());
+    assert_eq!(r.into_bits(), Integer::from(0xFFC0_0456u32));
+}
+
+#[test]
+fn other_contexts() {
+    let x = RFloat::from(-0.75);
+    let y = RFloat::from(0.5);
+
+    let ctx = PositContext::new(2, 16);
+    assert!(ctx.minimum(&x, &ctx.nar()).is_nar());
+    assert_eq!(
+        RFloat::from(ctx.maximum_number(&ctx.nar(), &y)),
+        RFloat::from(0.5)
+    );
+
+    let ctx = FixedContext::new(true, -4, 8);
+    let r = ctx.minimum_magnitude(&x, &y);
+    assert_eq!(RFloat::from(r), RFloat::from(0.5));
+    let r = ctx.maximum(&RFloat::Nan, &y);
+    assert!(r.flags().invalid);
+
+    let ctx = RealContext::new();
+    assert_eq!(ctx.maximum_magnitude(&x, &y), RFloat::from(-0.75));
+    assert_eq!(ctx.min_num(&RFloat::Nan, &y), RFloat::from(0.5));
+
+    // decimal and binary values are compared exactly:
+    // the binary value nearest 0.1 is slightly larger
+    let ctx = DecimalContext::decimal64().with_rounding_mode(RoundingMode::ToPositive);
+    let one = ctx.round(&RFloat::from(1.0));
+    let tenth = ctx.div(&one, &ctx.round(&RFloat::from(10.0)));
+    let r = ctx.minimum(&RFloat::from(0.1), &tenth);
+    assert_eq!(r.to_rational(), Some(Rational::from((1, 10))));
+    let r = ctx.maximum(&RFloat::from(0.1), &tenth);
+    assert!(r.to_rational() > Some(Rational::from((1, 10))));
+    let r = ctx.max_num(&ctx.snan(), &tenth);
+    assert!(r.is_nar() && r.flags().invalid);
+}
+
+#[test]
+fn large_exponents() {
+    // magnitudes are compared without computing `2^(2^32)`
+    let big = RFloat::Real(false, 1 << 32, Integer::from(1));
+    let tiny = RFloat::Real(true, -(1 << 32), Integer::from(3));
+    let two = RFloat::from(2.0);
+
+    let ctx = RealContext::new();
+    assert_eq!(ctx.maximum(&big, &two), big);
+    assert_eq!(ctx.minimum(&big, &two), two);
+    assert_eq!(ctx.maximum_magnitude(&tiny, &two), two);
+    assert_eq!(ctx.minimum_magnitude(&tiny, &two), tiny);
+    assert_eq!(ctx.minimum(&big, &big), big);
+
+    let ctx = DecimalContext::decimal64();
+    let r = ctx.maximum(&big, &ctx.round(&two));
+    assert!(r.is_infinite() && r.flags().overflow);
+}
@@ -0,0 +1,184 @@
+use mpmfnum::ieee754::IEEE754;
+use mpmfnum::multidouble::MultiDoubleContext;
+use mpmfnum::ops::*;
+use mpmfnum::rfloat::RFloat;
+use mpmfnum::{Real, RoundingContext, RoundingMode};
+use rug::Integer;
+
+/// Constructs `2^e` as an [`RFloat`].
+fn pow2(e: isize) -> RFloat {
+    RFloat::Real(false, e, Integer::from(1))
+}
+
+/// Values of the components of a multi-double number.
+fn parts(x: &mpmfnum::multidouble::MultiDouble) -> Vec<RFloat> {
+    x.parts().iter().map(|p| RFloat::from(p.clone())).collect()
+}
+
+#[test]
+fn round() {
+    let dd = MultiDoubleContext::double_double();
+    let qd = MultiDoubleContext::quad_double();
+
+    // 1 + 2^-60 + 2^-120
+    let x = pow2(0) + pow2(-60) + pow2(-120);
+    let y = dd.round(&x);
+    assert_eq!(parts(&y), vec![pow2(0), pow2(-60)], "rounding for dd");
+    assert!(y.flags().inexact, "2^-120 is lost");
+    let y = qd.round(&x);
+    assert_eq!(
+        parts(&y),
+        vec![pow2(0), pow2(-60), pow2(-120), RFloat::zero()],
+        "rounding for qd"
+    );
+    assert!(!y.flags().inexact, "exact for qd");
+    assert_eq!(RFloat::from(y), x, "exact for qd");
+
+    // 2 - 2^-105: leading component rounds up
+    let x = pow2(1) - pow2(-105);
+    let y = dd.round(&x);
+    assert_eq!(
+        parts(&y),
+        vec![pow2(1), RFloat::Real(true, -105, Integer::from(1))],
+        "canonical representation"
+    );
+    assert!(!y.flags().inexact, "2 - 2^-105 is representable");
+
+    // special values
+    let y = dd.round(&pow2(1024));
+    assert!(y.is_infinite(), "2^1024 overflows");
+    assert!(y.flags().overflow, "2^1024 overflows");
+    assert!(dd.round(&RFloat::Nan).is_nan(), "NaN is NaN");
+    let y = dd.round(&IEEE754::from(-0.0));
+    assert!(y.is_zero() && y.sign() == Some(true), "-0 is -0");
+
+    // only tiny values underflow
+    let x = pow2(0) + pow2(-1070) + pow2(-1100);
+    let y = dd.round(&x);
+    assert!(y.flags().inexact, "1 + 2^-1070 + 2^-1100 is inexact");
+    assert!(
+        !y.flags().underflow_pre,
+        "1 + 2^-1070 + 2^-1100 is not tiny"
+    );
+    assert!(
+        !y.flags().underflow_post,
+        "1 + 2^-1070 + 2^-1100 is not tiny"
+    );
+    let x = pow2(-1040) + pow2(-1100);
+    let y = dd.round(&x);
+    assert!(y.flags().underflow_pre, "2^-1040 + 2^-1100 is tiny");
+    assert!(y.flags().underflow_post, "2^-1040 + 2^-1100 is tiny");
+    let y = dd.round(&(pow2(-1040) + pow2(-1070)));
+    assert!(!y.flags().underflow_pre, "2^-1040 + 2^-1070 is exact");
+}
+
+#[test]
+fn bits() {
+    let dd = MultiDoubleContext::double_double();
+    let x = dd.round(&(pow2(0) + pow2(-60)));
+    let expected: Integer =
+        (Integer::from(1.0f64.to_bits()) << 64) | Integer::from(2f64.powi(-60).to_bits());
+    assert_eq!(x.clone().into_bits(), expected, "IBM 128-bit encoding");
+
+    let y = dd.bits_to_number(expected.clone());
+    assert_eq!(x, y, "decoding failed");
+    assert_eq!(y.into_bits(), expected, "round trip failed");
+
+    // negative low component
+    let x = dd.round(&(pow2(1) - pow2(-105)));
+    let b = x.clone().into_bits();
+    assert_eq!(b.clone() >> 64, Integer::from(2.0f64.to_bits()));
+    assert_eq!(dd.bits_to_number(b), x, "round trip failed");
+
+    // quad-double
+    let qd = MultiDoubleContext::quad_double();
+    let x = qd.round(&(pow2(0) + pow2(-60) + pow2(-120) + pow2(-180)));
+    let b = x.clone().into_bits();
+    assert_eq!(qd.bits_to_number(b), x, "round trip failed");
+}
+
+#[test]
+fn ops() {
+    let dd = MultiDoubleContext::double_double();
+
+    // exact results
+    let x = dd.add(&pow2(0), &pow2(-80));
+    assert_eq!(parts(&x), vec![pow2(0), pow2(-80)], "1 + 2^-80");
+    let y = pow2(0) + pow2(-30);
+    let x = dd.mul(&y, &y);
+    assert_eq!(
+        parts(&x),
+        vec![pow2(0) + pow2(-29), pow2(-60)],
+        "(1 + 2^-30)^2"
+    );
+    let x = dd.sub(&x, &pow2(0));
+    assert_eq!(RFloat::from(x), pow2(-29) + pow2(-60), "(1 + 2^-30)^2 - 1");
+
+    // exact results are preserved
+    let y = pow2(0) + pow2(-200);
+    let x = dd.neg(&y);
+    assert_eq!(parts(&x), vec![-pow2(0), -pow2(-200)], "-(1 + 2^-200)");
+    assert!(!x.flags().inexact, "-(1 + 2^-200)");
+    let x = dd.abs(&dd.neg(&y));
+    assert_eq!(parts(&x), vec![pow2(0), pow2(-200)], "|-(1 + 2^-200)|");
+    let x = dd.add(&pow2(0), &pow2(-200));
+    assert_eq!(parts(&x), vec![pow2(0), pow2(-200)], "1 + 2^-200");
+    assert!(!x.flags().inexact, "1 + 2^-200");
+    let x = dd.sub(&y, &pow2(0));
+    assert_eq!(
+        parts(&x),
+        vec![pow2(-200), RFloat::zero()],
+        "(1 + 2^-200) - 1"
+    );
+    let x = dd.mul(&(pow2(0) + pow2(-60)), &(pow2(0) - pow2(-60)));
+    assert_eq!(parts(&x), vec![pow2(0), -pow2(-120)], "1 - 2^-120");
+    assert!(!x.flags().inexact, "1 - 2^-120");
+
+    // inexact results are rounded once, componentwise
+    let x = dd.add(&y, &pow2(-400));
+    assert_eq!(parts(&x), vec![pow2(0), pow2(-200)], "1 + 2^-200 + 2^-400");
+    assert!(x.flags().inexact, "1 + 2^-200 + 2^-400");
+    let x = dd.mul(&y, &y);
+    assert_eq!(parts(&x), vec![pow2(0), pow2(-199)], "(1 + 2^-200)^2");
+    assert!(x.flags().inexact, "(1 + 2^-200)^2");
+    let x = dd.div(&pow2(0), &RFloat::from(3.0));
+    assert!(x.flags().inexact, "1 / 3");
+    let x = dd.fma(&pow2(-60), &pow2(-60), &pow2(0));
+    assert_eq!(parts(&x), vec![pow2(0), pow2(-120)], "fma(2^-60, 2^-60, 1)");
+    assert!(!x.flags().inexact, "fma(2^-60, 2^-60, 1)");
+
+    // exceptions
+    let x = dd.sub(&RFloat::PosInfinity, &RFloat::PosInfinity);
+    assert!(x.is_nan() && x.flags().invalid, "inf - inf");
+    let x = dd.mul(&RFloat::NegInfinity, &RFloat::from(-2.0));
+    assert!(x.is_infinite() && !x.flags().invalid, "-inf * -2");
+    let x = dd.div(&pow2(0), &RFloat::zero());
+    assert!(x.is_infinite() && x.flags().divzero, "1 / 0");
+    let x = dd.sqrt(&RFloat::from(-1.0));
+    assert!(x.is_nan() && x.flags().invalid, "sqrt(-1)");
+
+    // 1/3 is accurate to about 106 bits
+    let third = dd.div(&pow2(0), &RFloat::from(3.0));
+    assert_eq!(
+        third.parts()[0].to_f64(RoundingMode::NearestTiesToEven),
+        1.0 / 3.0
+    );
+    let err = RFloat::from(third) * RFloat::from(3.0) - pow2(0);
+    assert!(err.is_zero() || err.e().unwrap() < -104, "error of 1/3");
+
+    // sqrt(2) is accurate to about 106 bits
+    let x = dd.sqrt(&RFloat::from(2.0));
+    assert_eq!(
+        x.parts()[0].to_f64(RoundingMode::NearestTiesToEven),
+        2f64.sqrt()
+    );
+    let x = RFloat::from(x);
+    let err = x.clone() * x - pow2(1);
+    assert!(err.e().unwrap() < -103, "error of sqrt(2)");
+
+    // quad-double
+    let qd = MultiDoubleContext::quad_double();
+    let third = qd.div(&pow2(0), &RFloat::from(3.0));
+    let err = RFloat::from(third) * RFloat::from(3.0) - pow2(0);
+    assert!(err.is_zero() || err.e().unwrap() < -210, "error of 1/3");
+}
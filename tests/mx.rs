@@ -0,0 +1,143 @@
+use mpmfnum::mx::{E8M0Context, MXContext};
+use mpmfnum::rfloat::RFloat;
+use mpmfnum::{Real, RoundingContext, RoundingMode};
+use rug::Integer;
+
+#[test]
+fn e8m0() {
+    let ctx = E8M0Context::new();
+    for i in 0..256 {
+        let b = Integer::from(i);
+        let x = ctx.bits_to_number(b.clone());
+        if i == 0xFF {
+            assert!(x.is_nan(), "0xFF is NaN");
+        } else {
+            assert_eq!(x.e(), Some(i - 127), "decoding {} failed", i);
+        }
+        assert_eq!(x.into_bits(), b, "round trip failed for {}", i);
+    }
+
+    // rounding to a power of two
+    let x = ctx.round(&RFloat::from(3.0));
+    assert_eq!(RFloat::from(x), RFloat::from(2.0), "round(3) = 2");
+    let ctx_rne = ctx
+        .clone()
+        .with_rounding_mode(RoundingMode::NearestTiesToEven);
+    let x = ctx_rne.round(&RFloat::from(3.0));
+    assert_eq!(RFloat::from(x), RFloat::from(4.0), "round(3) = 4 under RNE");
+
+    // special cases
+    assert!(ctx.round(&RFloat::from(-1.0)).is_nan(), "negative is NaN");
+    assert!(ctx.round(&RFloat::PosInfinity).is_nan(), "+Inf is NaN");
+    assert!(ctx
+        .round(&RFloat::Real(false, 128, Integer::from(1)))
+        .is_nan());
+    let x = ctx.round(&RFloat::Real(false, -200, Integer::from(1)));
+    assert_eq!(x.e(), Some(-127), "small values are clamped");
+    let x = ctx.round(&RFloat::zero());
+    assert_eq!(x.e(), Some(-127), "zero is clamped");
+}
+
+#[test]
+fn quantize_fp() {
+    // FP4: max = 3 => X = 2^(1 - 2), elements are exact
+    let ctx = MXContext::mxfp4_e2m1();
+    let vals: Vec<RFloat> = [1.0, 2.0, -0.5, 3.0].map(RFloat::from).into();
+    let block = ctx.quantize_block(&vals);
+    assert_eq!(block.scale().e(), Some(-1), "shared scale for FP4");
+    assert_eq!(block.dequantize(), vals, "FP4 round trip");
+
+    // FP4: 7 saturates to 6
+    let block = ctx.quantize_block(&[RFloat::from(7.0), RFloat::from(-0.25)]);
+    assert_eq!(block.scale().e(), Some(0), "shared scale for FP4");
+    assert_eq!(
+        block.dequantize(),
+        vec![RFloat::from(6.0), RFloat::zero()],
+        "FP4 saturation"
+    );
+
+    // FP8 (E4M3): 100 => X = 2^(6 - 8), 400 rounds to 384
+    let ctx = MXContext::mxfp8_e4m3();
+    let block = ctx.quantize_block(&[RFloat::from(100.0), RFloat::from(1.0)]);
+    assert_eq!(block.scale().e(), Some(-2), "shared scale for E4M3");
+    assert_eq!(
+        block.dequantize(),
+        vec![RFloat::from(96.0), RFloat::from(1.0)],
+        "E4M3 rounding"
+    );
+
+    // FP6 (E3M2) and FP8 (E5M2)
+    let vals = [RFloat::from(0.75), RFloat::from(-1.5)];
+    let block = MXContext::mxfp6_e3m2().quantize_block(&vals);
+    assert_eq!(block.dequantize(), vals.to_vec(), "E3M2 round trip");
+    let block = MXContext::mxfp8_e5m2().quantize_block(&vals);
+    assert_eq!(block.dequantize(), vals.to_vec(), "E5M2 round trip");
+}
+
+#[test]
+fn quantize_int() {
+    // INT8: max = 1 => X = 1, 0.01 rounds to 2^-6
+    let ctx = MXContext::mxint8();
+    let block = ctx.quantize_block(&[RFloat::from(1.0), RFloat::from(-0.5), RFloat::from(0.01)]);
+    assert_eq!(block.scale().e(), Some(0), "shared scale for INT8");
+    assert_eq!(
+        block.dequantize(),
+        vec![
+            RFloat::from(1.0),
+            RFloat::from(-0.5),
+            RFloat::Real(false, -6, Integer::from(1))
+        ],
+        "INT8 rounding"
+    );
+}
+
+#[test]
+fn quantize_special() {
+    let ctx = MXContext::mxfp8_e4m3();
+
+    // any NaN or infinity poisons the block
+    let block = ctx.quantize_block(&[RFloat::from(1.0), RFloat::Nan]);
+    assert!(block.scale().is_nan(), "NaN scale");
+    assert!(
+        block.dequantize().iter().all(|x| x.is_nar()),
+        "NaN elements"
+    );
+    let block = ctx.quantize_block(&[RFloat::NegInfinity]);
+    assert!(block.scale().is_nan(), "NaN scale");
+
+    // shared exponent too large
+    let block = ctx.quantize_block(&[RFloat::Real(false, 200, Integer::from(1))]);
+    assert!(block.scale().is_nan(), "NaN scale");
+
+    // shared exponent too small
+    let tiny = RFloat::Real(false, -133, Integer::from(1));
+    let block = ctx.quantize_block(std::slice::from_ref(&tiny));
+    assert_eq!(block.scale().e(), Some(-127), "clamped scale");
+    assert_eq!(
+        block.dequantize(),
+        vec![tiny],
+        "2^-133 is still representable"
+    );
+
+    // zeros
+    let block = ctx.quantize_block(&[RFloat::zero(), RFloat::zero()]);
+    assert_eq!(block.scale().e(), Some(-127), "zero block scale");
+    assert!(
+        block.dequantize().iter().all(|x| x.is_zero()),
+        "zero elements"
+    );
+}
+
+#[test]
+fn quantize_blocks() {
+    let ctx = MXContext::mxfp8_e4m3();
+    let vals: Vec<RFloat> = (0..70).map(|i| RFloat::from((i % 8) as f64)).collect();
+    let blocks = ctx.quantize(&vals);
+    assert_eq!(blocks.len(), 3, "70 elements in 3 blocks");
+    assert_eq!(blocks[0].len(), 32);
+    assert_eq!(blocks[2].len(), 6);
+    assert_eq!(ctx.dequantize(&blocks), vals, "small integers are exact");
+
+    let ctx = ctx.with_block_size(16);
+    assert_eq!(ctx.quantize(&vals).len(), 5, "70 elements in 5 blocks");
+}
@@ -0,0 +1,413 @@
+use mpmfnum::fixed::FixedContext;
+use mpmfnum::float::FloatContext;
+use mpmfnum::ieee754::{IEEE754Context, IEEE754};
+use mpmfnum::parse::{ParseRealError, RoundedParse};
+use mpmfnum::posit::PositContext;
+use mpmfnum::rfloat::{RFloat, RFloatContext};
+use mpmfnum::{Real, RoundingMode};
+use rug::Integer;
+
+/// Decimal strings to test against the standard library.
+const DECIMALS: [&str; 16] = [
+    "0",
+    "-0",
+    "0.1",
+    "-2.5",
+    "1e23",
+    "9007199254740993",
+    "16777217",
+    "3.4028235e38",
+    "1e-45",
+    "2.2250738585072011e-308",
+    "4.9e-324",
+    "2.4703282292062327e-324",
+    "2.4703282292062328e-324",
+    "1.7976931348623158e308",
+    "1.000000059604644776257986737988403547205962240695953369140625",
+    "123456789012345678901234567890",
+];
+
+#[test]
+fn parse_native() {
+    let ctx64 = IEEE754Context::new(11, 64);
+    let ctx32 = IEEE754Context::new(8, 32);
+    for s in DECIMALS {
+        let x = ctx64.parse(s).unwrap();
+        let expected = s.parse::<f64>().unwrap();
+        assert_eq!(
+            x.into_bits(),
+            Integer::from(expected.to_bits()),
+            "binary64 parse failed: {}",
+            s
+        );
+
+        let x = ctx32.parse(s).unwrap();
+        let expected = s.parse::<f32>().unwrap();
+        assert_eq!(
+            x.into_bits(),
+            Integer::from(expected.to_bits()),
+            "binary32 parse failed: {}",
+            s
+        );
+    }
+}
+
+#[test]
+fn parse_special() {
+    let ctx = IEEE754Context::new(11, 64);
+    for (s, expected) in [
+        ("inf", f64::INFINITY),
+        ("+Infinity", f64::INFINITY),
+        ("-INF", f64::NEG_INFINITY),
+        ("1e400", f64::INFINITY),
+        ("-1e400", f64::NEG_INFINITY),
+        ("1e-400", 0.0),
+        ("-1e-400", -0.0),
+    ] {
+        let x = ctx.parse(s).unwrap();
+        assert_eq!(
+            x.into_bits(),
+            Integer::from(expected.to_bits()),
+            "parse failed: {}",
+            s
+        );
+    }
+
+    let x = ctx.parse("NaN").unwrap();
+    assert!(x.is_nan(), "parse(NaN) = NaN");
+    let x = ctx.parse("-nan").unwrap();
+    assert!(x.is_nan(), "parse(-NaN) = NaN");
+}
+
+#[test]
+fn parse_flags() {
+    let ctx = IEEE754Context::new(11, 64);
+
+    let x = ctx.parse("0.1").unwrap();
+    assert!(x.flags().inexact, "0.1 is not representable");
+    assert!(!x.flags().overflow, "0.1 does not overflow");
+
+    let x = ctx.parse("0.5").unwrap();
+    assert!(!x.flags().inexact, "0.5 is representable");
+
+    let x = ctx.parse("1e400").unwrap();
+    assert!(x.flags().overflow, "1e400 overflows");
+    assert!(x.flags().inexact, "1e400 is not representable");
+
+    let x = ctx.parse("1e-310").unwrap();
+    assert!(x.is_subnormal(), "1e-310 is subnormal");
+    assert!(x.flags().underflow_post, "1e-310 underflows");
+}
+
+#[test]
+fn parse_rounding_modes() {
+    let tenth = f64::to_bits(0.1);
+    for (rm, expected) in [
+        (RoundingMode::NearestTiesToEven, tenth),
+        (RoundingMode::ToPositive, tenth),
+        (RoundingMode::ToNegative, tenth - 1),
+        (RoundingMode::ToZero, tenth - 1),
+        (RoundingMode::AwayZero, tenth),
+    ] {
+        let ctx = IEEE754Context::new(11, 64).with_rounding_mode(rm);
+        let x = ctx.parse("0.1").unwrap();
+        assert_eq!(
+            x.into_bits(),
+            Integer::from(expected),
+            "parse(0.1) failed for {:?}",
+            rm
+        );
+    }
+
+    let ctx = IEEE754Context::new(11, 64).with_rounding_mode(RoundingMode::ToNegative);
+    let x = ctx.parse("-0.1").unwrap();
+    assert_eq!(
+        x.into_bits(),
+        Integer::from(f64::to_bits(-0.1)),
+        "parse(-0.1) failed for ToNegative"
+    );
+}
+
+#[test]
+fn parse_formats() {
+    // RFloat: 0.1 = 0x1.99999ap-4
+    let ctx = RFloatContext::new().with_max_p(24);
+    let x = ctx.parse("0.1").unwrap();
+    assert_eq!(
+        x,
+        RFloat::Real(false, -27, Integer::from(0xcccccd)),
+        "parse(0.1) for p=24"
+    );
+
+    // Float: 1/3 is not representable
+    let ctx = FloatContext::new(4);
+    let x = ctx.parse("0.33333").unwrap();
+    assert_eq!(
+        RFloat::from(x.clone()),
+        RFloat::Real(false, -5, Integer::from(11)),
+        "parse(0.33333) for p=4"
+    );
+    assert!(x.flags().inexact, "0.33333 is not representable");
+
+    // Fixed: 0.1 * 16 = 1.6
+    let ctx = FixedContext::new(true, -4, 8).with_rounding_mode(RoundingMode::NearestTiesToEven);
+    let x = ctx.parse("0.1").unwrap();
+    assert_eq!(
+        RFloat::from(x),
+        RFloat::Real(false, -3, Integer::from(1)),
+        "parse(0.1) for Fixed<-4, 8>"
+    );
+
+    // Posit: 1.1 is between 1 and 1.125
+    let ctx = PositContext::new(2, 8);
+    let x = ctx.parse("1.1").unwrap();
+    assert_eq!(
+        RFloat::from(x),
+        RFloat::Real(false, -3, Integer::from(9)),
+        "parse(1.1) for Posit<2, 8>"
+    );
+
+    // Posit: infinities are NaR
+    let x = ctx.parse("inf").unwrap();
+    assert!(x.is_nar(), "parse(inf) = NaR");
+}
+
+#[test]
+fn parse_exact() {
+    assert_eq!(
+        "0.375".parse::<RFloat>(),
+        Ok(RFloat::Real(false, -3, Integer::from(3))),
+        "0.375 is representable"
+    );
+    assert_eq!(
+        "-1.5e3".parse::<RFloat>(),
+        Ok(RFloat::Real(true, 0, Integer::from(1500))),
+        "-1.5e3 is representable"
+    );
+    assert_eq!(
+        "0.1".parse::<RFloat>(),
+        Err(ParseRealError::Inexact),
+        "0.1 is not representable"
+    );
+    assert_eq!(
+        "".parse::<RFloat>(),
+        Err(ParseRealError::Empty),
+        "empty string"
+    );
+    for s in [
+        "-", ".", "e5", "1.2.3", "1e", "1e+", "1 ", "infinite", "0x", "0x1g", "0x1p", "0x1e5p",
+        "nan()", "nan(0x)", "nan(12a)", "qnan",
+    ] {
+        assert_eq!(
+            s.parse::<RFloat>(),
+            Err(ParseRealError::Invalid),
+            "invalid: {}",
+            s
+        );
+    }
+
+    let x: RFloat = "-0".parse().unwrap();
+    assert!(x.is_zero() && x.sign().unwrap(), "-0 is negative zero");
+    let x: RFloat = "-0x0p0".parse().unwrap();
+    assert!(x.is_zero() && x.sign().unwrap(), "-0x0p0 is negative zero");
+    let x: RFloat = "0.0".parse().unwrap();
+    assert!(x.is_zero() && !x.sign().unwrap(), "0.0 is positive zero");
+
+    let x: RFloat = "-inf".parse().unwrap();
+    assert!(x.is_infinite() && x.sign().unwrap(), "-inf is -Inf");
+    let x: RFloat = "nan".parse().unwrap();
+    assert!(x.is_nar(), "nan is NaN");
+}
+
+#[test]
+fn parse_generic() {
+    fn parse_all<Ctx: RoundedParse>(ctx: &Ctx) -> Vec<Ctx::Format> {
+        DECIMALS.iter().map(|s| ctx.parse(s).unwrap()).collect()
+    }
+
+    let xs: Vec<IEEE754> = parse_all(&IEEE754Context::new(5, 16));
+    assert_eq!(xs.len(), DECIMALS.len());
+}
+
+#[test]
+fn parse_hex() {
+    for (s, expected) in [
+        ("0x1p0", RFloat::one()),
+        ("-0x1.8p-3", RFloat::Real(true, -4, Integer::from(3))),
+        ("0X1.8P+3", RFloat::Real(false, 2, Integer::from(3))),
+        ("0x.8", RFloat::Real(false, -1, Integer::from(1))),
+        ("0xff", RFloat::Real(false, 0, Integer::from(255))),
+        ("0x1.fffffffffffffp1023", RFloat::from(f64::MAX)),
+        ("0x0p+0", RFloat::zero()),
+    ] {
+        assert_eq!(s.parse::<RFloat>(), Ok(expected), "parse failed: {}", s);
+    }
+
+    // rounded parsing
+    let ctx = IEEE754Context::new(8, 32);
+    let x = ctx.parse("0x1.000001p0").unwrap();
+    assert_eq!(x.clone().into_bits(), Integer::from(1.0f32.to_bits()));
+    assert!(x.flags().inexact, "0x1.000001p0 is not representable");
+    let x = ctx.parse("-0x1p-150").unwrap();
+    assert_eq!(x.clone().into_bits(), Integer::from((-0.0f32).to_bits()));
+    assert!(x.flags().underflow_post, "-0x1p-150 underflows");
+}
+
+#[test]
+fn parse_nan_payload() {
+    let ctx = IEEE754Context::new(11, 64);
+    for (s, sign, quiet, payload) in [
+        ("nan", false, true, 0),
+        ("-NaN", true, true, 0),
+        ("nan(0x1234)", false, true, 0x1234),
+        ("nan(1234)", false, true, 1234),
+        ("snan", false, false, 1),
+        ("-sNaN(0x2a)", true, false, 0x2a),
+    ] {
+        let x = ctx.parse(s).unwrap();
+        assert_eq!(x.nan_quiet(), Some(quiet), "quiet bit: {}", s);
+        assert_eq!(
+            x.nan_payload(),
+            Some(Integer::from(payload)),
+            "payload: {}",
+            s
+        );
+        assert_eq!(x.sign(), Some(sign), "sign: {}", s);
+    }
+
+    // payload must fit below the quiet bit
+    let x = ctx.parse("nan(0x7ffffffffffff)").unwrap();
+    assert_eq!(
+        x.into_bits(),
+        Integer::from(0x7fffffffffffffffu64),
+        "largest payload"
+    );
+    assert_eq!(
+        ctx.parse("nan(0x8000000000000)"),
+        Err(ParseRealError::Invalid)
+    );
+    assert_eq!(ctx.parse("snan(0)"), Err(ParseRealError::Invalid));
+
+    // payloads are dropped by other formats
+    let x: RFloat = "nan(0x1234)".parse().unwrap();
+    assert!(x.is_nar(), "nan(0x1234) is NaN");
+}
+
+#[test]
+fn parse_large_exponent() {
+    let ctx = IEEE754Context::new(11, 64);
+    for s in [
+        "1e4294967295",
+        "1e4294967296",
+        "1e4294967297",
+        "0.001e4294967299",
+        "1e99999999999999999999",
+    ] {
+        let x = ctx.parse(s).unwrap();
+        assert_eq!(
+            x.clone().into_bits(),
+            Integer::from(f64::INFINITY.to_bits())
+        );
+        assert!(x.flags().overflow, "{} overflows", s);
+
+        let x = ctx.parse(&format!("-{}", s)).unwrap();
+        assert_eq!(x.into_bits(), Integer::from(f64::NEG_INFINITY.to_bits()));
+    }
+
+    for s in [
+        "1e-4294967295",
+        "1e-4294967296",
+        "1e-4294967297",
+        "1000e-4294967299",
+        "1e-99999999999999999999",
+    ] {
+        let x = ctx.parse(s).unwrap();
+        assert_eq!(x.clone().into_bits(), Integer::from(0));
+        assert!(x.flags().underflow_post, "{} underflows", s);
+        assert!(x.flags().inexact, "{} is inexact", s);
+    }
+
+    // saturated values still respect the rounding mode
+    let ctx = IEEE754Context::new(11, 64).with_rounding_mode(RoundingMode::ToZero);
+    let x = ctx.parse("1e99999999999999999999").unwrap();
+    assert_eq!(x.into_bits(), Integer::from(f64::MAX.to_bits()));
+    let ctx = IEEE754Context::new(11, 64).with_rounding_mode(RoundingMode::ToPositive);
+    let x = ctx.parse("1e-99999999999999999999").unwrap();
+    assert_eq!(x.into_bits(), Integer::from(1));
+
+    // posits saturate
+    let ctx = PositContext::new(2, 8);
+    let x = ctx.parse("1e4294967296").unwrap();
+    assert_eq!(x.into_bits(), ctx.maxval(false).into_bits());
+    let x = ctx.parse("-1e-99999999999999999999").unwrap();
+    assert_eq!(x.into_bits(), ctx.minval(true).into_bits());
+
+    // zeros are zeros
+    let x = IEEE754Context::new(11, 64)
+        .parse("-0e99999999999999999999")
+        .unwrap();
+    assert_eq!(x.into_bits(), Integer::from((-0.0f64).to_bits()));
+
+    // unbounded exponents cannot be saturated
+    assert_eq!(
+        "1e99999999999999999999".parse::<RFloat>(),
+        Err(ParseRealError::OutOfRange)
+    );
+    assert_eq!(
+        RFloatContext::new().with_max_p(53).parse("1e4294967296"),
+        Err(ParseRealError::OutOfRange)
+    );
+    assert_eq!(
+        "1e-99999999999999999999".parse::<RFloat>(),
+        Err(ParseRealError::Inexact)
+    );
+    assert_eq!(
+        "0e99999999999999999999".parse::<RFloat>(),
+        Ok(RFloat::zero())
+    );
+
+    // unless the smallest digit bounds them
+    let ctx = RFloatContext::new().with_max_p(53).with_min_n(-1075);
+    let x = ctx.parse("-1e-99999999999999999999").unwrap();
+    assert!(x.is_zero(), "rounds to zero");
+}
+
+#[test]
+fn parse_hex_large_exponent() {
+    let ctx = IEEE754Context::new(11, 64);
+    for s in [
+        "0x1p4294967296",
+        "0x1p99999999999999999999",
+        "0x0.01p4294967304",
+    ] {
+        let x = ctx.parse(s).unwrap();
+        assert_eq!(
+            x.clone().into_bits(),
+            Integer::from(f64::INFINITY.to_bits())
+        );
+        assert!(x.flags().overflow, "{} overflows", s);
+    }
+    for s in ["-0x1p-4294967296", "-0x1p-99999999999999999999"] {
+        let x = ctx.parse(s).unwrap();
+        assert_eq!(x.clone().into_bits(), Integer::from((-0.0f64).to_bits()));
+        assert!(x.flags().underflow_post, "{} underflows", s);
+    }
+
+    let ctx = PositContext::new(2, 8);
+    let x = ctx.parse("0x1p4294967296").unwrap();
+    assert_eq!(x.into_bits(), ctx.maxval(false).into_bits());
+
+    // exact parsing only needs the exponent to fit
+    assert_eq!(
+        "0x1.8p4294967296".parse::<RFloat>(),
+        Ok(RFloat::Real(false, 4294967295, Integer::from(3)))
+    );
+    assert_eq!(
+        "0x1p99999999999999999999".parse::<RFloat>(),
+        Err(ParseRealError::OutOfRange)
+    );
+    assert_eq!(
+        "-0x1p-99999999999999999999".parse::<RFloat>(),
+        Err(ParseRealError::OutOfRange)
+    );
+}
@@ -209,3 +209,59 @@ fn round_small() {
         "round(+1.1875) = +1.25"
     );
 }
+
+#[test]
+fn round_exponent_field() {
+    // posit<2, 8> format
+    let ctx = PositContext::new(2, 8);
+    let pow2 = |e: isize| RFloat::Real(false, e, Integer::from(1));
+    let round_bits = |x: &RFloat| ctx.round(x).into_bits();
+
+    // near MAXVAL, the exponent field is truncated: `0x7E` is 2^20
+    // and the midpoint to MAXVAL (`0x7F`) is 2^22, so values above
+    // 2^22 round to MAXVAL even if 2^20 is nearer
+    assert_eq!(bits_to_rfloat(&ctx, 0x7E), pow2(20));
+    assert_eq!(round_bits(&pow2(21)), 0x7E);
+    assert_eq!(round_bits(&pow2(22)), 0x7E, "ties to even");
+    assert_eq!(round_bits(&pow2(23)), 0x7F, "not to the nearest value");
+    assert_eq!(round_bits(&RFloat::Real(false, 20, Integer::from(5))), 0x7F);
+
+    // near MINVAL, `0x02` is 2^-20 and the midpoint is 2^-22
+    assert_eq!(bits_to_rfloat(&ctx, 0x02), pow2(-20));
+    assert_eq!(round_bits(&pow2(-23)), 0x01);
+    assert_eq!(round_bits(&pow2(-22)), 0x02, "ties to even");
+    assert_eq!(round_bits(&pow2(-21)), 0x02);
+
+    // with a partial exponent field: `0x7C` is 2^16, `0x7D` is 2^18,
+    // and the midpoint is 2^17
+    assert_eq!(bits_to_rfloat(&ctx, 0x7C), pow2(16));
+    assert_eq!(bits_to_rfloat(&ctx, 0x7D), pow2(18));
+    assert_eq!(round_bits(&pow2(17)), 0x7C, "ties to even");
+    assert_eq!(round_bits(&RFloat::Real(false, 15, Integer::from(5))), 0x7D);
+}
+
+#[test]
+fn round_min_max() {
+    // posit<2, 8> format
+    let ctx = PositContext::new(2, 8);
+    let maxval = RFloat::from(ctx.maxval(false));
+    let minval = RFloat::from(ctx.minval(false));
+
+    // MAXVAL and MINVAL are exact
+    assert_eq!(ctx.round(&maxval), ctx.maxval(false));
+    assert_eq!(ctx.round(&minval), ctx.minval(false));
+
+    // values beyond MAXVAL saturate
+    let above = RFloat::Real(false, 0, (Integer::from(1) << 24) + 1);
+    assert_eq!(ctx.round(&above), ctx.maxval(false));
+    let above = RFloat::Real(true, 1000, Integer::from(3));
+    assert_eq!(ctx.round(&above), ctx.maxval(true));
+
+    // values just above or below MINVAL round to it
+    let above = RFloat::Real(false, -25, Integer::from(3));
+    assert_eq!(ctx.round(&above), ctx.minval(false));
+    let below = RFloat::Real(false, -26, Integer::from(3));
+    assert_eq!(ctx.round(&below), ctx.minval(false));
+    let below = RFloat::Real(true, -1000, Integer::from(1));
+    assert_eq!(ctx.round(&below), ctx.minval(true));
+}
@@ -0,0 +1,276 @@
+// Compliance tests for the 2022 Posit Standard.
+
+use mpmfnum::ops::*;
+use mpmfnum::{posit::*, rfloat::RFloat, Real, RoundingContext};
+use rug::Integer;
+
+fn posit(ctx: &PositContext, b: u64) -> Posit {
+    ctx.bits_to_number(Integer::from(b))
+}
+
+fn value(ctx: &PositContext, x: f64) -> Posit {
+    ctx.round(&RFloat::from(x))
+}
+
+#[test]
+fn encoding() {
+    let ctx = PositContext::standard(8);
+    assert_eq!(ctx.es(), 2);
+    assert!(ctx.is_standard());
+
+    let cases = [
+        (0x01, RFloat::Real(false, -24, Integer::from(1))),
+        (0x20, RFloat::Real(false, -4, Integer::from(1))),
+        (0x30, RFloat::Real(false, -2, Integer::from(1))),
+        (0x40, RFloat::one()),
+        (0x44, RFloat::from(1.5)),
+        (0x48, RFloat::from(2.0)),
+        (0x50, RFloat::from(4.0)),
+        (0x60, RFloat::from(16.0)),
+        (0x7F, RFloat::Real(false, 24, Integer::from(1))),
+        (0x81, RFloat::Real(true, 24, Integer::from(1))),
+        (0xBC, RFloat::from(-1.5)),
+        (0xC0, RFloat::from(-1.0)),
+        (0xFF, RFloat::Real(true, -24, Integer::from(1))),
+    ];
+
+    for (b, v) in cases {
+        let x = posit(&ctx, b);
+        assert_eq!(RFloat::from(x.clone()), v, "decoding {:#x}", b);
+        assert_eq!(ctx.round(&v).into_bits(), b, "encoding {:?}", v);
+    }
+
+    assert!(posit(&ctx, 0x00).is_zero());
+    assert!(posit(&ctx, 0x80).is_nar());
+    assert_eq!(ctx.nar().into_bits(), 0x80);
+    assert_eq!(ctx.maxval(true).into_bits(), 0x81);
+
+    // larger standard formats
+    let ctx = PositContext::standard(16);
+    assert_eq!(RFloat::from(posit(&ctx, 0x4000)), RFloat::one());
+    assert_eq!(ctx.maxval(false).into_bits(), 0x7FFF);
+    assert_eq!(ctx.minval(true).into_bits(), 0xFFFF);
+    assert_eq!(
+        RFloat::from(ctx.maxval(false)),
+        RFloat::Real(false, 56, Integer::from(1))
+    );
+
+    let ctx = PositContext::standard(32);
+    assert_eq!(value(&ctx, 1.0).into_bits(), 0x4000_0000);
+    assert_eq!(value(&ctx, -1.0).into_bits(), 0xC000_0000_u32);
+    assert_eq!(
+        RFloat::from(ctx.maxval(false)),
+        RFloat::Real(false, 120, Integer::from(1))
+    );
+}
+
+#[test]
+fn round_trip() {
+    for nbits in [8, 16] {
+        let ctx = PositContext::standard(nbits);
+        for b in 0..(1 << nbits) {
+            let x = posit(&ctx, b);
+            assert_eq!(x.clone().into_bits(), b, "bits round trip");
+            let r = ctx.round(&RFloat::from(x));
+            assert_eq!(r.into_bits(), b, "rounding round trip");
+        }
+    }
+}
+
+#[test]
+fn negate() {
+    // negation is two's complement negation of the bit pattern
+    let ctx = PositContext::standard(8);
+    for b in 0..256 {
+        let x = posit(&ctx, b);
+        let expected = (256 - b) % 256;
+        assert_eq!(ctx.neg(&x).into_bits(), expected, "negating {:#x}", b);
+        if b != 0x80 {
+            assert_eq!(ctx.abs(&x), ctx.abs(&ctx.neg(&x)), "abs of {:#x}", b);
+        }
+    }
+}
+
+#[test]
+fn compare() {
+    // posits are ordered as two's complement integers
+    let ctx = PositContext::standard(8);
+    let ordered: Vec<Posit> = (0x80..0x180).map(|b| posit(&ctx, b % 256)).collect();
+    for w in ordered.windows(2) {
+        assert!(w[0] < w[1], "{:?} < {:?}", w[0], w[1]);
+        assert!(w[0] != w[1], "{:?} != {:?}", w[0], w[1]);
+        assert!(w[1] >= w[0], "{:?} >= {:?}", w[1], w[0]);
+    }
+
+    // NaR is equal to itself and less than everything else
+    assert_eq!(ctx.nar(), ctx.nar());
+    assert!(ctx.nar() < ctx.maxval(true));
+    assert_eq!(ctx.nar().max(ctx.zero()), ctx.zero());
+}
+
+#[test]
+fn next_prior() {
+    let ctx = PositContext::standard(8);
+    for b in 0..256 {
+        let x = posit(&ctx, b);
+        assert_eq!(ctx.next(&x).into_bits(), (b + 1) % 256, "next({:#x})", b);
+        assert_eq!(
+            ctx.prior(&x).into_bits(),
+            (b + 255) % 256,
+            "prior({:#x})",
+            b
+        );
+    }
+
+    assert!(ctx.next(&ctx.maxval(false)).is_nar());
+    assert_eq!(ctx.next(&ctx.nar()), ctx.maxval(true));
+    assert_eq!(ctx.prior(&ctx.zero()), ctx.minval(true));
+    assert_eq!(ctx.next(&ctx.zero()), ctx.minval(false));
+
+    // non-standard encodings step through values in the same order
+    let ctx = PositContext::new(2, 8);
+    assert_eq!(ctx.next(&ctx.zero()), ctx.minval(false));
+    assert_eq!(ctx.prior(&ctx.zero()), ctx.minval(true));
+    assert_eq!(ctx.next(&ctx.nar()), ctx.maxval(true));
+    assert_eq!(ctx.next(&value(&ctx, -1.0)), value(&ctx, -0.9375));
+}
+
+#[test]
+fn rounding() {
+    let ctx = PositContext::standard(8);
+
+    // posits never overflow or underflow
+    assert_eq!(value(&ctx, 1e30), ctx.maxval(false));
+    assert_eq!(value(&ctx, -1e30), ctx.maxval(true));
+    assert_eq!(value(&ctx, 1e-30), ctx.minval(false));
+    assert_eq!(value(&ctx, -1e-30), ctx.minval(true));
+    assert!(ctx.round(&RFloat::PosInfinity).is_nar());
+    assert!(ctx.round(&RFloat::Nan).is_nar());
+
+    // ties in the fraction field
+    assert_eq!(value(&ctx, 1.0625).into_bits(), 0x40);
+    assert_eq!(value(&ctx, 1.1875).into_bits(), 0x42);
+
+    // rounding is defined on the bit string: the midpoint between
+    // 2^-24 (0x01) and 2^-20 (0x02) is 2^-22, not their average
+    let x = RFloat::Real(false, -23, Integer::from(3));
+    assert_eq!(ctx.round(&x).into_bits(), 0x02);
+    let x = RFloat::Real(false, -22, Integer::from(1));
+    assert_eq!(ctx.round(&x).into_bits(), 0x02);
+    let x = RFloat::Real(false, -25, Integer::from(7));
+    assert_eq!(ctx.round(&x).into_bits(), 0x01);
+}
+
+#[test]
+fn integer() {
+    let ctx = PositContext::standard(16);
+    let cases = [
+        // (x, nearestInt, ceil, floor, pIntPart)
+        (2.5, 2.0, 3.0, 2.0, 2.0),
+        (3.5, 4.0, 4.0, 3.0, 3.0),
+        (-2.5, -2.0, -2.0, -3.0, -2.0),
+        (0.75, 1.0, 1.0, 0.0, 0.0),
+        (-0.25, 0.0, 0.0, -1.0, 0.0),
+        (7.0, 7.0, 7.0, 7.0, 7.0),
+    ];
+
+    for (x, near, ceil, floor, int) in cases {
+        let x = value(&ctx, x);
+        assert_eq!(
+            ctx.nearest_int(&x),
+            value(&ctx, near),
+            "nearestInt({:?})",
+            x
+        );
+        assert_eq!(ctx.ceil(&x), value(&ctx, ceil), "ceil({:?})", x);
+        assert_eq!(ctx.floor(&x), value(&ctx, floor), "floor({:?})", x);
+        assert_eq!(ctx.int_part(&x), value(&ctx, int), "pIntPart({:?})", x);
+    }
+    assert!(ctx.nearest_int(&ctx.nar()).is_nar());
+    assert!(ctx.floor(&ctx.nar()).is_nar());
+
+    // pToInt
+    assert_eq!(ctx.to_int(&value(&ctx, 2.5), 8), 2);
+    assert_eq!(ctx.to_int(&value(&ctx, -3.5), 8), -4);
+    assert_eq!(ctx.to_int(&value(&ctx, 1000.0), 8), 127);
+    assert_eq!(ctx.to_int(&value(&ctx, -1000.0), 8), -127);
+    assert_eq!(ctx.to_int(&ctx.nar(), 8), -128);
+
+    // minpos and maxpos
+    let minpos = ctx.round(&RFloat::Real(false, -(1 << 32), Integer::from(1)));
+    let maxpos = ctx.round(&RFloat::Real(false, 1 << 32, Integer::from(1)));
+    assert_eq!(ctx.nearest_int(&minpos), ctx.zero());
+    assert_eq!(ctx.ceil(&minpos), value(&ctx, 1.0));
+    assert_eq!(ctx.to_int(&minpos, 8), 0);
+    assert_eq!(ctx.nearest_int(&maxpos), maxpos);
+    assert_eq!(ctx.to_int(&maxpos, 8), 127);
+
+    // intToP
+    let ctx = PositContext::standard(8);
+    assert_eq!(ctx.from_int(&Integer::from(100), 8), value(&ctx, 96.0));
+    assert_eq!(ctx.from_int(&Integer::from(-3), 8), value(&ctx, -3.0));
+    assert!(ctx.from_int(&Integer::from(-128), 8).is_nar());
+    assert!(ctx.from_int(&Integer::from(0), 8).is_zero());
+}
+
+#[test]
+fn convert() {
+    let p8 = PositContext::standard(8);
+    let p16 = PositContext::standard(16);
+    let p32 = PositContext::standard(32);
+
+    for b in 0..256 {
+        // widening is exact
+        let x = posit(&p8, b);
+        let y = p16.round(&x);
+        assert_eq!(x, y, "widening {:#x}", b);
+        let z = p32.round(&y);
+        assert_eq!(p8.round(&z).into_bits(), b, "narrowing {:#x}", b);
+    }
+
+    // narrowing rounds to nearest
+    let x = value(&p16, 1.0 + 1.0 / 64.0);
+    assert_eq!(p8.round(&x).into_bits(), 0x40);
+    let x = value(&p16, 1.0 + 3.0 / 32.0);
+    assert_eq!(p8.round(&x).into_bits(), 0x41);
+    assert!(p8.round(&p16.nar()).is_nar());
+    assert_eq!(p8.round(&p16.maxval(false)), p8.maxval(false));
+}
+
+#[test]
+fn functions() {
+    let ctx = PositContext::standard(8);
+
+    assert_eq!(ctx.signum(&value(&ctx, -3.0)), value(&ctx, -1.0));
+    assert_eq!(ctx.signum(&ctx.minval(false)), value(&ctx, 1.0));
+    assert!(ctx.signum(&ctx.zero()).is_zero());
+    assert!(ctx.signum(&ctx.nar()).is_nar());
+
+    // fused operations round once
+    let big = value(&ctx, 65536.0);
+    let tiny = value(&ctx, 1.0 / 65536.0);
+    assert_eq!(ctx.fmm(&big, &big, &tiny), big, "fMM");
+    assert_eq!(
+        ctx.mul(&ctx.mul(&big, &big), &tiny),
+        value(&ctx, 256.0),
+        "unfused"
+    );
+    assert_eq!(
+        ctx.fms(&value(&ctx, 3.0), &value(&ctx, 3.0), &value(&ctx, 1.0)),
+        value(&ctx, 8.0),
+        "fMS"
+    );
+
+    let max = ctx.maxval(false);
+    let one = value(&ctx, 1.0);
+    assert_eq!(ctx.fmma(&max, &max, &ctx.neg(&max), &max), ctx.zero());
+    assert_eq!(ctx.fmms(&max, &max, &max, &max), ctx.zero());
+    assert_eq!(ctx.fmma(&max, &one, &one, &one), max);
+    assert!(ctx.fmma(&max, &one, &ctx.nar(), &one).is_nar());
+
+    // elementary functions are correctly rounded
+    assert_eq!(ctx.sqrt(&value(&ctx, 4.0)), value(&ctx, 2.0));
+    assert!(ctx.sqrt(&value(&ctx, -4.0)).is_nar());
+    assert!(ctx.div(&one, &ctx.zero()).is_nar());
+    assert_eq!(ctx.quire_bits(), 128);
+}
@@ -0,0 +1,132 @@
+use mpmfnum::ops::*;
+use mpmfnum::{posit::*, rfloat::RFloat, Real, RoundingContext};
+use rug::Integer;
+
+#[test]
+fn bounds() {
+    // standard posits have a quire of `16 * nbits` bits
+    for nbits in [8, 16, 32, 64] {
+        let ctx = PositContext::new(2, nbits);
+        assert_eq!(ctx.quire_bits(), 16 * nbits, "quire for posit{}", nbits);
+    }
+
+    let ctx = PositContext::new(2, 8);
+    let q = ctx.quire();
+    assert!(q.is_zero());
+    assert!(!q.is_nar());
+}
+
+#[test]
+fn encode() {
+    // posit8: quire LSB is `2^-48`
+    let ctx = PositContext::new(2, 8);
+    let one = ctx.round(&RFloat::one());
+
+    let mut q = ctx.quire();
+    q.add_posit(&one);
+    assert_eq!(q.clone().into_bits(), Integer::from(1) << 48, "+1");
+
+    q.negate();
+    let expected = (Integer::from(1) << 128) - (Integer::from(1) << 48);
+    assert_eq!(q.clone().into_bits(), expected, "-1");
+
+    q.clear();
+    q.mul_add(&ctx.minval(false), &ctx.minval(false));
+    assert_eq!(q.clone().into_bits(), Integer::from(1), "minpos^2");
+
+    q.add_posit(&ctx.nar());
+    assert!(q.is_nar());
+    assert_eq!(q.into_bits(), Integer::from(1) << 127, "NAR");
+
+    // decode
+    for b in [
+        Integer::from(0),
+        Integer::from(1),
+        Integer::from(1) << 48,
+        Integer::from(1) << 127,
+        (Integer::from(1) << 128) - 1,
+    ] {
+        let q = ctx.bits_to_quire(b.clone());
+        assert_eq!(q.into_bits(), b, "round trip");
+    }
+
+    let q = ctx.bits_to_quire(expected);
+    assert_eq!(
+        q.to_posit(),
+        ctx.round(&RFloat::Real(true, 0, Integer::from(1)))
+    );
+    assert!(ctx
+        .bits_to_quire(Integer::from(1) << 127)
+        .to_posit()
+        .is_nar());
+}
+
+#[test]
+fn accumulate() {
+    let ctx = PositContext::new(2, 8);
+    let one = ctx.round(&RFloat::one());
+    let maxpos = ctx.maxval(false);
+    let minpos = ctx.minval(false);
+
+    // catastrophic cancellation is exact
+    let mut q = ctx.quire();
+    q.mul_add(&maxpos, &maxpos);
+    q.add_posit(&one);
+    q.mul_sub(&maxpos, &maxpos);
+    assert_eq!(q.to_posit(), one, "maxpos^2 + 1 - maxpos^2");
+
+    let naive = ctx.add(&ctx.mul(&maxpos, &maxpos), &one);
+    let naive = ctx.sub(&naive, &ctx.mul(&maxpos, &maxpos));
+    assert!(naive.is_zero(), "rounded after each operation");
+
+    // products below minpos are not lost
+    let x = ctx.round(&RFloat::Real(false, -15, Integer::from(1)));
+    let mut q = ctx.quire();
+    for _ in 0..(1 << 10) {
+        q.mul_add(&x, &x);
+    }
+    let exact = RFloat::Real(false, -20, Integer::from(1));
+    assert_eq!(q.to_posit(), ctx.round(&exact), "2^10 * 2^-30");
+    assert_eq!(ctx.mul(&x, &x), minpos, "2^-30 rounds to minpos");
+
+    // NAR is sticky
+    let mut q = ctx.quire();
+    q.mul_add(&ctx.nar(), &one);
+    q.add_posit(&one);
+    q.negate();
+    assert!(q.is_nar());
+    assert!(q.to_posit().is_nar());
+    q.clear();
+    assert!(q.is_zero());
+}
+
+#[test]
+fn round() {
+    // posit<2, 8>: 1.0625 is halfway between 1 and 1.125
+    let ctx = PositContext::new(2, 8);
+    let one = ctx.round(&RFloat::one());
+    let tiny = ctx.round(&RFloat::Real(false, -4, Integer::from(1)));
+    let eps = ctx.minval(false);
+
+    let mut q = ctx.quire();
+    q.add_posit(&one);
+    q.add_posit(&tiny);
+    assert_eq!(q.to_posit(), one, "tie rounds to even");
+
+    q.mul_add(&eps, &eps);
+    assert_eq!(
+        RFloat::from(q.to_posit()),
+        RFloat::Real(false, -3, Integer::from(9)),
+        "just above the tie"
+    );
+}
+
+#[test]
+fn fused_dot() {
+    let ctx = PositContext::new(2, 16);
+    let p = |x: f64| ctx.round(&RFloat::from(x));
+    let xs = [p(1e4), p(1.0), p(-1e4)];
+    let ys = [p(1e4), p(1.0), p(1e4)];
+    let r = ctx.fused_dot(&xs, &ys);
+    assert_eq!(r, p(1.0));
+}
@@ -0,0 +1,149 @@
+use mpmfnum::fixed::FixedContext;
+use mpmfnum::float::FloatContext;
+use mpmfnum::ieee754::IEEE754Context;
+use mpmfnum::posit::PositContext;
+use mpmfnum::rational::RoundRational;
+use mpmfnum::rfloat::{RFloat, RFloatContext};
+use mpmfnum::{Real, RoundingMode};
+
+use rug::float::Round;
+use rug::ops::Pow;
+use rug::{Float as MpfrFloat, Integer, Rational};
+
+fn convert_round_mode(rm: RoundingMode) -> Round {
+    match rm {
+        RoundingMode::NearestTiesToEven => Round::Nearest,
+        RoundingMode::ToPositive => Round::Up,
+        RoundingMode::ToNegative => Round::Down,
+        RoundingMode::ToZero => Round::Zero,
+        RoundingMode::AwayZero => Round::AwayZero,
+        _ => panic!("unsupported rounding mode {:?}", rm),
+    }
+}
+
+#[test]
+fn round_native() {
+    let ctx = IEEE754Context::new(11, 64);
+    for (num, den, expected) in [
+        (1, 3, 1.0 / 3.0),
+        (-1, 3, -1.0 / 3.0),
+        (1, 10, 0.1),
+        (22, 7, 22.0 / 7.0),
+        (3, 8, 0.375),
+        (1, 1i64 << 62, 1.0 / (1u64 << 62) as f64),
+    ] {
+        let q = Rational::from((num, den));
+        let x = ctx.round_rational(&q);
+        assert_eq!(
+            x.into_bits(),
+            Integer::from(expected.to_bits()),
+            "rounding {}/{} failed",
+            num,
+            den
+        );
+    }
+}
+
+#[test]
+fn round_flags() {
+    let ctx = IEEE754Context::new(11, 64);
+
+    let x = ctx.round_rational(&Rational::from((1, 3)));
+    assert!(x.flags().inexact, "1/3 is not representable");
+
+    let x = ctx.round_rational(&Rational::from((3, 8)));
+    assert!(!x.flags().inexact, "3/8 is representable");
+
+    let x = ctx.round_rational(&Rational::from((
+        Integer::from(10).pow(400),
+        Integer::from(3),
+    )));
+    assert!(x.is_infinite(), "10^400/3 overflows");
+    assert!(x.flags().overflow, "10^400/3 overflows");
+
+    let x = ctx.round_rational(&Rational::from((
+        Integer::from(1),
+        Integer::from(3) << 1074,
+    )));
+    assert!(x.is_zero(), "1/(3 * 2^1074) underflows to zero");
+    assert!(x.flags().underflow_post, "1/(3 * 2^1074) underflows");
+
+    let x = ctx.round_rational(&Rational::new());
+    assert!(x.is_zero(), "0 is zero");
+    assert!(!x.flags().inexact, "0 is representable");
+}
+
+#[test]
+fn round_mpfr() {
+    // compare against MPFR for many small rationals
+    let rms = [
+        RoundingMode::NearestTiesToEven,
+        RoundingMode::ToPositive,
+        RoundingMode::ToNegative,
+        RoundingMode::ToZero,
+        RoundingMode::AwayZero,
+    ];
+
+    for p in [2, 3, 5, 8] {
+        for rm in rms {
+            let ctx = RFloatContext::new().with_max_p(p).with_rounding_mode(rm);
+            for num in -50..50 {
+                for den in 1..50 {
+                    let q = Rational::from((num, den));
+                    let x = ctx.round_rational(&q);
+                    let (y, _) = MpfrFloat::with_val_round(p as u32, &q, convert_round_mode(rm));
+                    assert_eq!(
+                        x,
+                        RFloat::from(y),
+                        "rounding {}/{} failed for p={}, rm={:?}",
+                        num,
+                        den,
+                        p,
+                        rm
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn round_formats() {
+    let third = Rational::from((1, 3));
+
+    // Float: 1/3 = 0.0101...
+    let ctx = FloatContext::new(4);
+    let x = ctx.round_rational(&third);
+    assert_eq!(
+        RFloat::from(x.clone()),
+        RFloat::Real(false, -5, Integer::from(11)),
+        "rounding 1/3 for p=4"
+    );
+    assert!(x.flags().inexact, "1/3 is not representable");
+
+    // Fixed: truncation by default
+    let ctx = FixedContext::new(true, -4, 8);
+    let x = ctx.round_rational(&third);
+    assert_eq!(
+        RFloat::from(x),
+        RFloat::Real(false, -4, Integer::from(5)),
+        "rounding 1/3 for Fixed<-4, 8>"
+    );
+
+    // Fixed: negative values are still truncated
+    let x = ctx.round_rational(&-third.clone());
+    assert_eq!(
+        RFloat::from(x),
+        RFloat::Real(true, -4, Integer::from(5)),
+        "rounding -1/3 for Fixed<-4, 8>"
+    );
+
+    // Posit: 1/3 is between 0.3125 and 0.34375
+    let ctx = PositContext::new(2, 8);
+    let x = ctx.round_rational(&third);
+    assert_eq!(
+        RFloat::from(x),
+        RFloat::Real(false, -5, Integer::from(11)),
+        "rounding 1/3 for Posit<2, 8>"
+    );
+}
@@ -0,0 +1,153 @@
+use mpmfnum::fixed::FixedContext;
+use mpmfnum::float::FloatContext;
+use mpmfnum::ieee754::IEEE754Context;
+use mpmfnum::ops::*;
+use mpmfnum::posit::PositContext;
+use mpmfnum::rfloat::{RFloat, RFloatContext};
+use mpmfnum::{Real, RoundingContext, RoundingMode};
+use rug::{Complete, Integer};
+
+fn f64_of<N: Real>(x: &N) -> f64 {
+    RFloat::from_number(x).to_f64(RoundingMode::NearestTiesToEven)
+}
+
+#[test]
+fn ieee754() {
+    let ctx = IEEE754Context::binary64();
+    let x = |v: f64| ctx.round(&RFloat::from(v));
+
+    // compare against the host libm where it is correctly rounded
+    assert_eq!(f64_of(&ctx.exp10(&x(3.0))), 1000.0);
+    assert_eq!(f64_of(&ctx.exp10(&x(-1.0))), 0.1);
+    assert_eq!(f64_of(&ctx.pown(&x(1.5), 3)), 3.375);
+    assert_eq!(f64_of(&ctx.pown(&x(2.0), -2)), 0.25);
+    assert_eq!(f64_of(&ctx.rootn(&x(27.0), 3)), 3.0);
+    assert_eq!(f64_of(&ctx.rootn(&x(-32.0), 5)), -2.0);
+    assert_eq!(f64_of(&ctx.compound(&x(1.0), 10)), 1024.0);
+    assert_eq!(f64_of(&ctx.compound(&x(0.5), -1)), 2.0 / 3.0);
+    assert_eq!(f64_of(&ctx.powr(&x(4.0), &x(0.5))), 2.0);
+    assert_eq!(f64_of(&ctx.asin_pi(&x(1.0))), 0.5);
+    assert_eq!(f64_of(&ctx.acos_pi(&x(-1.0))), 1.0);
+    assert_eq!(f64_of(&ctx.atan_pi(&x(1.0))), 0.25);
+    assert_eq!(f64_of(&ctx.atan2_pi(&x(1.0), &x(-1.0))), 0.75);
+    assert_eq!(f64_of(&ctx.fdim(&x(5.0), &x(3.0))), 2.0);
+    assert_eq!(f64_of(&ctx.fdim(&x(3.0), &x(5.0))), 0.0);
+
+    // exceptional cases
+    let r = ctx.rootn(&x(-8.0), 2);
+    assert!(r.is_nar() && r.flags().invalid);
+    let r = ctx.powr(&x(-2.0), &x(2.0));
+    assert!(r.is_nar() && r.flags().invalid);
+    let r = ctx.pown(&x(0.0), -1);
+    assert!(r.is_infinite() && r.flags().divzero);
+    let r = ctx.compound(&x(-2.0), 2);
+    assert!(r.is_nar() && r.flags().invalid);
+    let r = ctx.exp10(&x(400.0));
+    assert!(r.is_infinite() && r.flags().overflow);
+}
+
+#[test]
+fn sin_cos_remquo() {
+    let ctx = IEEE754Context::binary64();
+    let v = 0.75;
+    let (sin, cos) = ctx.sin_cos(&RFloat::from(v));
+    assert_eq!(f64_of(&sin), f64_of(&ctx.sin(&RFloat::from(v))));
+    assert_eq!(f64_of(&cos), f64_of(&ctx.cos(&RFloat::from(v))));
+    assert!(sin.flags().inexact && cos.flags().inexact);
+
+    let (r, q) = ctx.remquo(&RFloat::from(10.0), &RFloat::from(3.0));
+    assert_eq!(f64_of(&r), 1.0);
+    assert_eq!(q & 7, 3);
+    let (r, q) = ctx.remquo(&RFloat::from(-11.0), &RFloat::from(2.0));
+    assert_eq!(f64_of(&r), 1.0);
+    assert_eq!(q, -6);
+    let (r, _) = ctx.remquo(&RFloat::from(1.0), &RFloat::zero());
+    assert!(r.is_nar() && r.flags().invalid);
+}
+
+#[test]
+fn frexp_modf() {
+    let ctx = IEEE754Context::binary32();
+    let (m, e) = ctx.frexp(&RFloat::from(12.0));
+    assert_eq!((f64_of(&m), e), (0.75, 4));
+    let (m, e) = ctx.frexp(&RFloat::from(-0.1875));
+    assert_eq!((f64_of(&m), e), (-0.75, -2));
+    let (m, e) = ctx.frexp(&ctx.zero(true));
+    assert!(m.is_zero() && m.sign() == Some(true) && e == 0);
+    let (m, e) = ctx.frexp(&RFloat::NegInfinity);
+    assert!(m.is_infinite() && e == 0);
+
+    let (frac, int) = ctx.modf(&RFloat::from(-3.625));
+    assert_eq!((f64_of(&frac), f64_of(&int)), (-0.625, -3.0));
+    let (frac, int) = ctx.modf(&RFloat::from(0.5));
+    assert_eq!((f64_of(&frac), f64_of(&int)), (0.5, 0.0));
+    let (frac, int) = ctx.modf(&RFloat::from(-4.0));
+    assert!(frac.is_zero() && frac.sign() == Some(true));
+    assert_eq!(f64_of(&int), -4.0);
+    let (frac, int) = ctx.modf(&RFloat::PosInfinity);
+    assert!(frac.is_zero() && int.is_infinite());
+    let (frac, int) = ctx.modf(&RFloat::Nan);
+    assert!(frac.is_nar() && int.is_nar());
+}
+
+#[test]
+fn other_contexts() {
+    let x = RFloat::from(2.0);
+
+    let ctx = FloatContext::new(24);
+    assert_eq!(f64_of(&ctx.exp10(&x)), 100.0);
+    assert_eq!(f64_of(&ctx.pown(&x, 10)), 1024.0);
+
+    let ctx = PositContext::new(2, 16);
+    assert_eq!(f64_of(&ctx.rootn(&RFloat::from(16.0), 4)), 2.0);
+    assert_eq!(f64_of(&ctx.asin_pi(&RFloat::from(-1.0))), -0.5);
+
+    // results saturate or wrap
+    let ctx = FixedContext::new(true, -4, 8).with_rounding_mode(RoundingMode::NearestTiesToEven);
+    assert_eq!(f64_of(&ctx.atan_pi(&RFloat::from(1.0))), 0.25);
+    assert_eq!(f64_of(&ctx.sin_cos(&RFloat::zero()).1), 1.0);
+    let r = ctx.exp10(&x);
+    assert!(r.flags().overflow);
+    let r = ctx.rootn(&RFloat::from(-1.0), 2);
+    assert!(r.flags().invalid);
+}
+
+#[test]
+fn rfloat() {
+    // floating-point rounding
+    let ctx = RFloatContext::new().with_max_p(53);
+    let r = ctx.exp10(&RFloat::from(-1.0));
+    assert_eq!(f64_of(&r), 0.1);
+    assert_eq!(
+        ctx.add(&RFloat::from(1.0), &RFloat::from(2.0)),
+        RFloat::from(3.0)
+    );
+    assert_eq!(ctx.floor(&RFloat::from(-1.5)), RFloat::from(-2.0));
+
+    // fixed-point rounding requires precision proportional to the
+    // magnitude of the result: 2^100 + 1 needs 101 bits
+    let ctx = RFloatContext::new().with_min_n(-1);
+    let big = RFloat::Real(false, 100, Integer::from(1));
+    let r = ctx.add(&big, &RFloat::one());
+    let expect = RFloat::Real(false, 0, (Integer::from(1) << 100) + 1);
+    assert_eq!(r, expect);
+
+    // 3^100 exactly
+    let r = ctx.pown(&RFloat::from(3.0), 100);
+    assert_eq!(
+        r,
+        RFloat::Real(false, 0, Integer::u_pow_u(3, 100).complete())
+    );
+
+    // small results are rounded at `min_n`
+    let ctx = RFloatContext::new()
+        .with_min_n(-5)
+        .with_rounding_mode(RoundingMode::ToZero);
+    // sin(pi * x) is slightly less than 1/2 since x < 1/6
+    let r = ctx.sin_pi(&RFloat::from(1.0 / 6.0));
+    assert_eq!(f64_of(&r), 0.4375);
+    let r = ctx.exp(&RFloat::from(-10.0));
+    assert!(r.is_zero());
+    let (r, q) = ctx.remquo(&RFloat::from(7.5), &RFloat::from(2.0));
+    assert_eq!((f64_of(&r), q), (-0.5, 4));
+}
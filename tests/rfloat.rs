@@ -380,6 +380,85 @@ fn round_float() {
     assert_eq!(err, one_8, "lost bits is -3/8");
 }
 
+/// Testing tie-breaking rounding modes
+#[test]
+fn round_ties() {
+    let v = |exp: isize, c: u32| RFloat::Real(false, exp, Integer::from(c));
+    let one = RFloat::one();
+    let one_1_8 = v(-3, 9);
+    let one_1_4 = v(-2, 5);
+    let one_1_2 = v(-1, 3);
+    let one_3_4 = v(-2, 7);
+    let two = v(1, 1);
+    let neg = |x: &RFloat| RFloat::Real(true, x.exp().unwrap(), x.c().unwrap());
+
+    // (mode, input, expected)
+    let cases = [
+        // 1.25 is halfway between 1 (even) and 1.5 (odd)
+        (RoundingMode::NearestTiesToZero, &one_1_4, &one),
+        (RoundingMode::NearestTiesToOdd, &one_1_4, &one_1_2),
+        (RoundingMode::NearestTiesToPositive, &one_1_4, &one_1_2),
+        (RoundingMode::NearestTiesToNegative, &one_1_4, &one),
+        (RoundingMode::PrepareShorterPrecision, &one_1_4, &one_1_2),
+        // 1.75 is halfway between 1.5 (odd) and 2 (even)
+        (RoundingMode::NearestTiesToZero, &one_3_4, &one_1_2),
+        (RoundingMode::NearestTiesToOdd, &one_3_4, &one_1_2),
+        (RoundingMode::NearestTiesToPositive, &one_3_4, &two),
+        (RoundingMode::NearestTiesToNegative, &one_3_4, &one_1_2),
+        (RoundingMode::PrepareShorterPrecision, &one_3_4, &one_1_2),
+        // 1.125 is not a tie
+        (RoundingMode::NearestTiesToZero, &one_1_8, &one),
+        (RoundingMode::NearestTiesToOdd, &one_1_8, &one),
+        (RoundingMode::NearestTiesToPositive, &one_1_8, &one),
+        (RoundingMode::NearestTiesToNegative, &one_1_8, &one),
+        (RoundingMode::PrepareShorterPrecision, &one_1_8, &one_1_2),
+        // exact values are unchanged
+        (RoundingMode::PrepareShorterPrecision, &one, &one),
+    ];
+
+    for (rm, input, expected) in cases {
+        let ctx = RFloatContext::new().with_max_p(2).with_rounding_mode(rm);
+        let rounded = ctx.round(input);
+        assert_eq!(&rounded, expected, "rounding {:?} with {:?}", input, rm);
+
+        // sign-symmetric modes round negative values symmetrically
+        let neg_rounded = ctx.round(&neg(input));
+        match rm {
+            RoundingMode::NearestTiesToPositive | RoundingMode::NearestTiesToNegative => (),
+            _ => assert_eq!(
+                neg_rounded,
+                neg(expected),
+                "rounding -{:?} with {:?}",
+                input,
+                rm
+            ),
+        }
+    }
+
+    // ties toward positive and negative infinity are mirrored for negative values
+    let ctx = RFloatContext::new().with_max_p(2);
+    let neg_1_4 = neg(&one_1_4);
+    let rounded = ctx
+        .clone()
+        .with_rounding_mode(RoundingMode::NearestTiesToPositive)
+        .round(&neg_1_4);
+    assert_eq!(rounded, neg(&one));
+    let rounded = ctx
+        .with_rounding_mode(RoundingMode::NearestTiesToNegative)
+        .round(&neg_1_4);
+    assert_eq!(rounded, neg(&one_1_2));
+
+    // round to prepare for shorter precision: double rounding is innocuous
+    let x = RFloat::Real(false, -20, Integer::from(0x12_0001));
+    let ctx = RFloatContext::new().with_max_p(8);
+    let psp = ctx
+        .clone()
+        .with_rounding_mode(RoundingMode::PrepareShorterPrecision)
+        .round(&x);
+    let ctx = RFloatContext::new().with_max_p(3);
+    assert_eq!(ctx.round(&psp), ctx.round(&x));
+}
+
 /// Testing rounding using floating-point rounding using subnormals
 #[test]
 fn round_float_subnorm() {
@@ -729,3 +808,50 @@ fn mpfr_integration() {
         );
     }
 }
+
+#[test]
+fn native_conversions() {
+    // exact conversions from native values
+    let vals = [
+        (0.0, RFloat::zero()),
+        (-0.0, RFloat::Real(true, 0, Integer::from(0))),
+        (1.0, RFloat::one()),
+        (-0.4375, RFloat::Real(true, -4, Integer::from(7))),
+        (
+            f64::from_bits(1),
+            RFloat::Real(false, -1074, Integer::from(1)),
+        ),
+        (f64::INFINITY, RFloat::PosInfinity),
+        (f64::NEG_INFINITY, RFloat::NegInfinity),
+        (f64::NAN, RFloat::Nan),
+    ];
+    for (f, expected) in &vals {
+        let actual = RFloat::from(*f);
+        assert!(
+            is_equal(&actual, expected),
+            "conversion should have been exact: {} != {:?}",
+            f,
+            actual
+        );
+
+        let g = actual.to_f64(RoundingMode::NearestTiesToEven);
+        assert!(
+            f.to_bits() == g.to_bits(),
+            "round trip failed: {} != {}",
+            f,
+            g
+        );
+    }
+
+    let x = RFloat::from(f32::from_bits(1));
+    assert!(is_equal(&x, &RFloat::Real(false, -149, Integer::from(1))));
+
+    // rounded conversions to native values
+    let third = RFloat::Real(false, -64, Integer::from(0x5555_5555_5555_5555_u64));
+    assert_eq!(third.to_f64(RoundingMode::ToNegative), 1.0 / 3.0);
+    assert_eq!(third.to_f32(RoundingMode::NearestTiesToEven), 1.0 / 3.0);
+    assert_eq!(
+        third.to_f32(RoundingMode::ToZero),
+        f32::from_bits((1.0_f32 / 3.0).to_bits() - 1)
+    );
+}
@@ -0,0 +1,201 @@
+use mpmfnum::fixed::FixedContext;
+use mpmfnum::float::FloatContext;
+use mpmfnum::ieee754::IEEE754Context;
+use mpmfnum::ops::*;
+use mpmfnum::rational::RoundRational;
+use mpmfnum::rfloat::{RFloat, RFloatContext};
+use mpmfnum::*;
+use rug::{Integer, Rational};
+
+const TRIALS: usize = 4096;
+
+/// Rounds `x` repeatedly and counts how often it rounds away from zero.
+fn count_away<Ctx: RoundingContext>(ctx: &Ctx, x: &RFloat) -> usize {
+    let mag = |v: &RFloat| match v.exp() {
+        Some(exp) => RFloat::Real(false, exp, v.c().unwrap()),
+        None => RFloat::zero(),
+    };
+
+    let mut count = 0;
+    for _ in 0..TRIALS {
+        let r = RFloat::from_number(&ctx.round(x));
+        if r.is_infinite() || mag(&r) > mag(x) {
+            count += 1;
+        }
+    }
+    count
+}
+
+/// Asserts that an observed frequency is close to a probability.
+fn assert_freq(count: usize, p: f64) {
+    let freq = count as f64 / TRIALS as f64;
+    assert!((freq - p).abs() < 0.03, "expected {}, observed {}", p, freq);
+}
+
+#[test]
+fn splitmix64() {
+    let mut rng = SplitMix64::new(0);
+    assert_eq!(rng.next_u64(), 0xE220A8397B1DCDAF);
+    assert_eq!(rng.next_u64(), 0x6E789E6AA1B965F4);
+
+    let mut rng = SplitMix64::new(1);
+    let x = rng.random_bits(100);
+    assert!(x < (Integer::from(1) << 100));
+}
+
+#[test]
+fn reproducible() {
+    let x = RFloat::Real(false, -10, Integer::from(1100));
+    let run = |seed| {
+        let sr = StochasticRounding::exact(SplitMix64::new(seed));
+        let ctx = RFloatContext::new()
+            .with_max_p(4)
+            .with_stochastic_rounding(sr);
+        (0..64).map(|_| ctx.round(&x)).collect::<Vec<_>>()
+    };
+
+    assert_eq!(run(42), run(42));
+    assert_ne!(run(42), run(43));
+
+    // clones share the same random source
+    let sr = StochasticRounding::exact(SplitMix64::new(42));
+    let ctx1 = RFloatContext::new()
+        .with_max_p(4)
+        .with_stochastic_rounding(sr);
+    let ctx2 = ctx1.clone();
+    let interleaved: Vec<_> = (0..32)
+        .flat_map(|_| [ctx1.round(&x), ctx2.round(&x)])
+        .collect();
+    assert_eq!(interleaved, run(42));
+}
+
+#[test]
+fn exact() {
+    // 1.0001 (binary) lies 1/8 of the way from 1.0 to 1.5
+    let sr = StochasticRounding::exact(SplitMix64::new(1));
+    assert_eq!(sr.mode(), StochasticMode::Exact);
+    let ctx = RFloatContext::new()
+        .with_max_p(2)
+        .with_stochastic_rounding(sr);
+
+    let x = RFloat::Real(false, -4, Integer::from(17));
+    for _ in 0..64 {
+        let r = ctx.round(&x);
+        assert!(r == RFloat::from(1.0) || r == RFloat::from(1.5), "{:?}", r);
+    }
+
+    assert_freq(count_away(&ctx, &x), 0.125);
+    let x = RFloat::Real(true, -4, Integer::from(19));
+    assert_freq(count_away(&ctx, &x), 0.375);
+
+    // representable values are never changed
+    let x = RFloat::from(1.5);
+    assert_eq!(count_away(&ctx, &x), 0);
+}
+
+#[test]
+fn random_bits() {
+    let sr = StochasticRounding::random_bits(2, SplitMix64::new(1));
+    assert_eq!(sr.mode(), StochasticMode::RandomBits(2));
+    let ctx = RFloatContext::new()
+        .with_max_p(2)
+        .with_stochastic_rounding(sr);
+
+    // the lost fraction is truncated to 2 bits
+    let x = RFloat::Real(false, -4, Integer::from(17));
+    assert_eq!(count_away(&ctx, &x), 0);
+    let x = RFloat::Real(false, -4, Integer::from(19));
+    assert_freq(count_away(&ctx, &x), 0.25);
+    let x = RFloat::Real(false, -4, Integer::from(23));
+    assert_freq(count_away(&ctx, &x), 0.75);
+}
+
+#[test]
+fn contexts() {
+    // floating-point with unbounded exponent
+    let ctx = FloatContext::new(2)
+        .with_stochastic_rounding(StochasticRounding::exact(SplitMix64::new(7)));
+    assert!(ctx.stochastic_rounding().is_some());
+    let x = RFloat::Real(false, -3, Integer::from(13));
+    assert_freq(count_away(&ctx, &x), 0.25);
+    assert!(ctx.round(&x).flags().inexact);
+
+    // IEEE 754 floating-point
+    let ctx = IEEE754Context::new(5, 16)
+        .with_stochastic_rounding(StochasticRounding::exact(SplitMix64::new(7)));
+    let x = RFloat::Real(false, 0, Integer::from(2049));
+    assert_freq(count_away(&ctx, &x), 0.5);
+
+    // fixed-point
+    let ctx = FixedContext::new(true, -2, 8)
+        .with_stochastic_rounding(StochasticRounding::exact(SplitMix64::new(7)));
+    let x = RFloat::Real(true, -4, Integer::from(3));
+    assert_freq(count_away(&ctx, &x), 0.75);
+    let r = RFloat::from(ctx.round(&x));
+    assert!(r == RFloat::zero() || r == RFloat::from(-0.25), "{:?}", r);
+}
+
+#[test]
+fn ieee754_edge_cases() {
+    let sr = StochasticRounding::exact(SplitMix64::new(3));
+    let ctx = IEEE754Context::new(5, 16).with_stochastic_rounding(sr);
+    let max = RFloat::from(ctx.max_float(false));
+
+    // halfway between MAX_FLOAT and the next binade:
+    // rounds to MAX_FLOAT or overflows to infinity
+    let x = RFloat::Real(false, 4, Integer::from(4095));
+    let mut overflows = 0;
+    for _ in 0..TRIALS {
+        let r = ctx.round(&x);
+        if r.is_infinite() {
+            assert!(r.flags().overflow);
+            overflows += 1;
+        } else {
+            assert_eq!(RFloat::from(r), max);
+        }
+    }
+    assert_freq(overflows, 0.5);
+
+    // subnormal results
+    let x = RFloat::Real(false, -26, Integer::from(1));
+    let r = ctx.round(&x);
+    let min = RFloat::Real(false, -24, Integer::from(1));
+    assert!(r.is_zero() || RFloat::from(r.clone()) == min);
+    assert!(r.flags().tiny_pre && r.flags().tiny_post && r.flags().underflow_post);
+    assert_freq(count_away(&ctx, &x), 0.25);
+}
+
+#[test]
+fn ops() {
+    // the expected result of rounding is the exact result
+    let sr = StochasticRounding::random_bits(8, SplitMix64::new(11));
+    let ctx = FloatContext::new(4).with_stochastic_rounding(sr);
+    let one = RFloat::one();
+    let y = RFloat::Real(false, -7, Integer::from(1));
+
+    let mut sum = 0.0;
+    for _ in 0..TRIALS {
+        let r = ctx.add(&one, &y);
+        sum += r.to_f64(RoundingMode::NearestTiesToEven);
+    }
+    let mean = sum / TRIALS as f64;
+    assert!((mean - 129.0 / 128.0).abs() < 1.0 / 512.0, "mean {}", mean);
+
+    // rational values use the same precision
+    let sr = StochasticRounding::exact(SplitMix64::new(11));
+    let ctx = IEEE754Context::new(8, 32).with_stochastic_rounding(sr);
+    let third = Rational::from((1, 3));
+    let mut results = Vec::new();
+    for _ in 0..256 {
+        let r = ctx.round_rational(&third);
+        assert!(r.flags().inexact);
+        let v = r.to_f64(RoundingMode::NearestTiesToEven);
+        if !results.contains(&v) {
+            results.push(v);
+        }
+    }
+    results.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    let hi = 1.0f32 / 3.0;
+    let lo = f32::from_bits(hi.to_bits() - 1);
+    assert_eq!(results, vec![lo as f64, hi as f64]);
+}
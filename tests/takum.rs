@@ -0,0 +1,141 @@
+use mpmfnum::ops::*;
+use mpmfnum::{rfloat::RFloat, takum::*, Real, RoundingContext};
+use rug::Integer;
+
+fn takum(ctx: &TakumContext, b: u64) -> Takum {
+    ctx.bits_to_number(Integer::from(b))
+}
+
+fn value(ctx: &TakumContext, x: f64) -> Takum {
+    ctx.round(&RFloat::from(x))
+}
+
+#[test]
+fn encoding() {
+    // takum16
+    let ctx = TakumContext::new(16);
+    let cases = [
+        (0x4000, RFloat::one()),
+        (0x4800, RFloat::from(2.0)),
+        (0x4A00, RFloat::from(3.0)),
+        (0x3800, RFloat::from(0.5)),
+        (0xC000, RFloat::from(-1.0)),
+        (0xB800, RFloat::from(-2.0)),
+        (0x7FFF, RFloat::Real(false, 250, Integer::from(31))),
+        (0x0001, RFloat::Real(false, -259, Integer::from(17))),
+        (0xFFFF, RFloat::Real(true, -259, Integer::from(17))),
+    ];
+
+    for (b, v) in cases {
+        let x = takum(&ctx, b);
+        assert_eq!(RFloat::from(x), v, "decoding {:#x}", b);
+        assert_eq!(ctx.round(&v).into_bits(), b, "encoding {:?}", v);
+    }
+
+    assert!(takum(&ctx, 0x0000).is_zero());
+    assert!(takum(&ctx, 0x8000).is_nar());
+    assert_eq!(ctx.zero().into_bits(), 0x0000);
+    assert_eq!(ctx.nar().into_bits(), 0x8000);
+    assert_eq!(ctx.maxval(false).into_bits(), 0x7FFF);
+    assert_eq!(ctx.maxval(true).into_bits(), 0x8001);
+    assert_eq!(ctx.minval(false).into_bits(), 0x0001);
+
+    // takum12: the smallest value has no fraction bits
+    let ctx = TakumContext::new(12);
+    assert_eq!(
+        RFloat::from(ctx.minval(false)),
+        RFloat::Real(false, -254, Integer::from(1))
+    );
+    assert_eq!(
+        RFloat::from(ctx.maxval(false)),
+        RFloat::Real(false, 254, Integer::from(1))
+    );
+}
+
+#[test]
+fn round_trip() {
+    for nbits in [12, 16] {
+        let ctx = TakumContext::new(nbits);
+        let mut prev: Option<Takum> = None;
+        for b in 0..(1 << nbits) {
+            let x = takum(&ctx, b);
+            assert_eq!(x.clone().into_bits(), b, "bits round trip");
+            let r = ctx.round(&RFloat::from(x.clone()));
+            assert_eq!(r.into_bits(), b, "rounding round trip");
+
+            // ordered as two's complement integers
+            if b != 0 && b != (1 << (nbits - 1)) {
+                if let Some(p) = prev {
+                    assert!(p < x, "{:?} < {:?}", p, x);
+                }
+            }
+            prev = if b == (1 << (nbits - 1)) - 1 {
+                None
+            } else {
+                Some(x)
+            };
+        }
+    }
+}
+
+#[test]
+fn rounding() {
+    let ctx = TakumContext::new(16);
+
+    // special values
+    assert!(ctx.round(&RFloat::Nan).is_nar());
+    assert!(ctx.round(&RFloat::PosInfinity).is_nar());
+    assert!(ctx.round(&RFloat::zero()).is_zero());
+
+    // takums never overflow or underflow
+    let huge = RFloat::Real(false, 300, Integer::from(1));
+    assert_eq!(ctx.round(&huge), ctx.maxval(false));
+    let tiny = RFloat::Real(true, -300, Integer::from(1));
+    assert_eq!(ctx.round(&tiny), ctx.minval(true));
+    let tiny = RFloat::Real(false, -255, Integer::from(1));
+    assert_eq!(ctx.round(&tiny), ctx.minval(false));
+
+    // 12 bits of precision near 1, ties to even
+    let x = RFloat::Real(false, -12, Integer::from(4097));
+    assert_eq!(ctx.round(&x).into_bits(), 0x4000);
+    let x = RFloat::Real(false, -12, Integer::from(4099));
+    assert_eq!(ctx.round(&x).into_bits(), 0x4002);
+    let x = RFloat::Real(false, -13, Integer::from(8195));
+    assert_eq!(ctx.round(&x).into_bits(), 0x4001);
+
+    // precision tapers: 5 bits near 2^250
+    let x = RFloat::Real(false, 246, Integer::from(33));
+    assert_eq!(
+        RFloat::from(ctx.round(&x)),
+        RFloat::Real(false, 246, Integer::from(32))
+    );
+
+    // carry into the next characteristic
+    let x = RFloat::Real(false, -12, Integer::from(8191));
+    assert_eq!(RFloat::from(ctx.round(&x)), RFloat::from(2.0));
+}
+
+#[test]
+fn ops() {
+    let ctx = TakumContext::new(16);
+    let x = |v: f64| value(&ctx, v);
+
+    assert_eq!(ctx.add(&x(1.0), &x(2.0)), x(3.0));
+    assert_eq!(ctx.sub(&x(1.0), &x(3.0)), x(-2.0));
+    assert_eq!(ctx.mul(&x(1.5), &x(1.5)), x(2.25));
+    assert_eq!(ctx.div(&x(1.0), &x(3.0)), x(1.0 / 3.0));
+    assert_eq!(ctx.sqrt(&x(2.0)), x(2f64.sqrt()));
+    assert_eq!(ctx.fma(&x(3.0), &x(3.0), &x(-1.0)), x(8.0));
+    assert_eq!(ctx.exp(&x(0.0)), x(1.0));
+
+    // negation is two's complement negation of the bit pattern
+    for b in [0x0001, 0x4000, 0x7FFF, 0x1234] {
+        let y = ctx.neg(&takum(&ctx, b));
+        assert_eq!(y.into_bits(), 0x10000 - b);
+    }
+
+    // invalid operations produce NAR
+    assert!(ctx.div(&x(1.0), &x(0.0)).is_nar());
+    assert!(ctx.sqrt(&x(-1.0)).is_nar());
+    assert!(ctx.add(&ctx.nar(), &x(1.0)).is_nar());
+}
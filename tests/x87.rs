@@ -0,0 +1,174 @@
+use mpmfnum::rfloat::RFloat;
+use mpmfnum::x87::{PrecisionControl, X87Class, X87Context};
+use mpmfnum::{Real, RoundingContext, RoundingMode};
+use rug::Integer;
+
+/// Composes an 80-bit pattern from the sign-exponent and significand fields.
+fn bits(se: u16, m: u64) -> Integer {
+    (Integer::from(se) << 64) | Integer::from(m)
+}
+
+#[test]
+fn encode() {
+    let ctx = X87Context::new();
+    for (x, se, m) in [
+        (1.0, 0x3FFF, 0x8000000000000000),
+        (-1.5, 0xBFFF, 0xC000000000000000),
+        (0.0, 0x0000, 0x0000000000000000),
+        (f64::MIN_POSITIVE, 0x3C01, 0x8000000000000000),
+        (f64::INFINITY, 0x7FFF, 0x8000000000000000),
+        (f64::NEG_INFINITY, 0xFFFF, 0x8000000000000000),
+    ] {
+        let y = ctx.round(&RFloat::from(x));
+        assert_eq!(y.into_bits(), bits(se, m), "encoding {} failed", x);
+    }
+
+    // smallest denormal
+    let y = ctx.round(&RFloat::Real(false, -16445, Integer::from(1)));
+    assert!(y.is_subnormal(), "2^-16445 is denormal");
+    assert_eq!(y.into_bits(), bits(0x0000, 1), "encoding 2^-16445 failed");
+
+    // canonical patterns round trip
+    for (se, m) in [
+        (0x3FFF, 0x8000000000000000),
+        (0x4000, 0xC90FDAA22168C235),
+        (0x0000, 0x0000000000000001),
+        (0x8000, 0x0000000000000000),
+        (0x7FFE, 0xFFFFFFFFFFFFFFFF),
+        (0x7FFF, 0xC000000000000000),
+        (0xFFFF, 0xC000000000001234),
+        (0x7FFF, 0x8000000000000001),
+    ] {
+        let b = bits(se, m);
+        let y = ctx.bits_to_number(b.clone());
+        assert_eq!(y.into_bits(), b, "round trip failed for {:x}", b);
+    }
+}
+
+#[test]
+fn native() {
+    let ctx = X87Context::new();
+    let mut state = 1u64;
+    for _ in 0..500 {
+        state = state
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        let x = f64::from_bits(state);
+        if x.is_finite() {
+            let y = ctx.round(&RFloat::from(x));
+            assert!(!y.flags().inexact, "{} is representable", x);
+            assert_eq!(
+                y.to_f64(RoundingMode::NearestTiesToEven),
+                x,
+                "round trip failed for {}",
+                x
+            );
+        }
+    }
+}
+
+#[test]
+fn classify() {
+    let ctx = X87Context::new();
+    for (se, m, class) in [
+        (0x0000, 0x0000000000000000, X87Class::Zero),
+        (0x0000, 0x0000000000000001, X87Class::Denormal),
+        (0x0000, 0x8000000000000001, X87Class::PseudoDenormal),
+        (0x3FFF, 0x8000000000000000, X87Class::Normal),
+        (0x3FFF, 0x4000000000000000, X87Class::Unnormal),
+        (0x7FFF, 0x8000000000000000, X87Class::Infinity),
+        (0x7FFF, 0x0000000000000000, X87Class::PseudoInfinity),
+        (0x7FFF, 0xC000000000000000, X87Class::Nan),
+        (0x7FFF, 0x4000000000000000, X87Class::PseudoNan),
+    ] {
+        assert_eq!(ctx.classify_bits(&bits(se, m)), class, "{:x} {:x}", se, m);
+    }
+}
+
+#[test]
+fn decode() {
+    let ctx = X87Context::new();
+
+    // pseudo-denormal: same value as the smallest normal exponent
+    let x = ctx.bits_to_number(bits(0x0000, 0x8000000000000001));
+    assert!(x.flags().denorm, "pseudo-denormals are denormal operands");
+    assert_eq!(
+        RFloat::from(x.clone()),
+        RFloat::Real(false, -16382 - 63, Integer::from(0x8000000000000001u64)),
+        "value of pseudo-denormal"
+    );
+    assert_eq!(
+        x.into_bits(),
+        bits(0x0001, 0x8000000000000001),
+        "pseudo-denormals are canonicalized"
+    );
+
+    // unsupported encodings are invalid
+    let indefinite = bits(0xFFFF, 0xC000000000000000);
+    for (se, m) in [
+        (0x3FFF, 0x4000000000000000),
+        (0x7FFF, 0x0000000000000000),
+        (0xFFFF, 0x4000000000000001),
+    ] {
+        let x = ctx.bits_to_number(bits(se, m));
+        assert!(x.is_nan(), "unsupported encodings are NaN");
+        assert!(x.flags().invalid, "unsupported encodings are invalid");
+        assert_eq!(x.into_bits(), indefinite, "decoded as indefinite");
+    }
+
+    // ordinary values
+    let x = ctx.bits_to_number(bits(0xC000, 0xC000000000000000));
+    assert_eq!(RFloat::from(x), RFloat::from(-3.0), "decoding -3 failed");
+    let x = ctx.bits_to_number(bits(0x7FFF, 0x8000000000000000));
+    assert!(x.is_infinite(), "decoding +Inf failed");
+}
+
+#[test]
+fn precision_control() {
+    let third = RFloat::from(1.0 / 3.0);
+
+    let ctx = X87Context::new().with_precision_control(PrecisionControl::Single);
+    let x = ctx.round(&third);
+    assert_eq!(x.prec(), Some(24), "rounded to 24 bits");
+    assert_eq!(
+        RFloat::from(x.clone()),
+        RFloat::from((1.0f64 / 3.0) as f32),
+        "rounding 1/3 with PC=24"
+    );
+    assert!(x.flags().inexact, "1/3 is inexact with PC=24");
+
+    let ctx = X87Context::new().with_precision_control(PrecisionControl::Double);
+    let x = ctx.round(&third);
+    assert!(!x.flags().inexact, "1/3 is exact with PC=53");
+
+    // 1 + 2^-60 requires 61 bits
+    let y = RFloat::Real(false, -60, (Integer::from(1) << 60) + 1);
+    let x = ctx.round(&y);
+    assert_eq!(
+        RFloat::from(x.clone()),
+        RFloat::one(),
+        "rounding with PC=53"
+    );
+    assert!(x.flags().inexact, "1 + 2^-60 is inexact with PC=53");
+    let x = X87Context::new().round(&y);
+    assert!(!x.flags().inexact, "1 + 2^-60 is exact with PC=64");
+
+    // exponent range is not reduced
+    let y = RFloat::Real(false, 2000, Integer::from(3));
+    let x = X87Context::new()
+        .with_precision_control(PrecisionControl::Single)
+        .round(&y);
+    assert!(!x.flags().overflow, "2^2001 does not overflow");
+    assert_eq!(RFloat::from(x), y, "3 * 2^2000 is exact with PC=24");
+
+    // directed rounding
+    let ctx = X87Context::new()
+        .with_precision_control(PrecisionControl::Single)
+        .with_rounding_mode(RoundingMode::ToPositive);
+    let x = ctx.round(&RFloat::Real(false, -30, (Integer::from(1) << 30) + 1));
+    assert_eq!(
+        RFloat::from(x),
+        RFloat::Real(false, -23, (Integer::from(1) << 23) + 1),
+        "rounding up with PC=24"
+    );
+}